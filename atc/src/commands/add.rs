@@ -0,0 +1,225 @@
+//! ダウンロード済みのコンテストに問題を追加するモジュール
+//!
+//! コンテスト途中で問題が追加された場合や、一部の問題のみを後から取得する場合に利用する。
+//! 問題のサンプル入出力を取得して問題ディレクトリを作成し、`Cargo.toml` の
+//! `[[bin]]` と `[package.metadata.timeout]` を既存の内容を保ったまま追記する。
+//!
+//! コンテストディレクトリ(`Cargo.toml` が存在するディレクトリ)で実行する。
+//! テンプレートは `download` と同様に、コンテストディレクトリの親ディレクトリを基準に探索する。
+use std::{error::Error, fs, path::Path};
+use toml::Value;
+
+use super::config::get_base_url;
+use super::download::{create_main_rs, create_sample_files, get_problem};
+use super::metadata::save_contest_metadata;
+use super::statement::{write_readme, write_statement};
+use super::stats::{record_event, EventKind};
+use super::submit::get_contest_name;
+use super::template::resolve_contest_template_dir;
+
+/// 問題追加処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリ
+/// - `problem_name`: 追加する問題名 (`a`, `ex` など)
+///
+/// # エラーの可能性
+/// - 問題が既に `Cargo.toml` に登録されている、または問題ディレクトリが既に存在する場合
+/// - コンテストの問題一覧に指定した問題が存在しない場合
+pub async fn execute(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let problem_name = problem_name.to_lowercase();
+    let contest_name = get_contest_name(work_dir)?;
+    let cargo_toml_path = work_dir.join("Cargo.toml");
+    if is_registered(&cargo_toml_path, &problem_name)? || work_dir.join(&problem_name).exists() {
+        return Err(format!("問題 {} は既に存在します", problem_name).into());
+    }
+
+    let problem = get_problem(&get_base_url(), &contest_name, &problem_name).await?;
+
+    let root_dir = work_dir
+        .parent()
+        .ok_or("コンテストディレクトリの親ディレクトリを取得できません")?
+        .to_path_buf();
+    let dir_name = work_dir
+        .file_name()
+        .ok_or("コンテストディレクトリ名を取得できません")?
+        .to_string_lossy()
+        .to_string();
+    let template_dir = resolve_contest_template_dir(&root_dir, None, &contest_name)?;
+    create_main_rs(&root_dir, &template_dir, &dir_name, &problem_name)?;
+    create_sample_files(&root_dir, &dir_name, &problem_name, &problem.samples)?;
+    if let Some(statement) = &problem.statement {
+        let problem_dir = work_dir.join(&problem_name);
+        write_statement(&problem_dir, statement)?;
+        write_readme(
+            &problem_dir,
+            &problem,
+            &problem.url(&get_base_url(), &contest_name),
+        )?;
+    }
+    add_problem_to_cargo_toml(&cargo_toml_path, &problem_name, problem.timeout)?;
+    save_contest_metadata(work_dir, &contest_name, std::slice::from_ref(&problem))?;
+
+    record_event(&contest_name, &problem_name, EventKind::Downloaded);
+    println!("Problem added successfully: {}", problem_name);
+    Ok(())
+}
+
+/// `Cargo.toml` に問題の `[[bin]]` が登録されているかを返す
+pub fn is_registered(cargo_toml_path: &Path, problem_name: &str) -> Result<bool, Box<dyn Error>> {
+    let parsed: Value = toml::from_str(&fs::read_to_string(cargo_toml_path)?)?;
+    Ok(parsed
+        .get("bin")
+        .and_then(|b| b.as_array())
+        .map(|bins| {
+            bins.iter()
+                .any(|bin| bin.get("name").and_then(|n| n.as_str()) == Some(problem_name))
+        })
+        .unwrap_or(false))
+}
+
+/// `Cargo.toml` に問題の `[[bin]]` と実行時間制限を追記する
+///
+/// 既存の内容(コメントや依存関係の記述順)を保つため、TOML を再生成せずテキストとして編集する。
+///
+/// # 処理の流れ
+/// 1. `[package.metadata.timeout]` の見出しの直後に `"problem_name" = timeout` を挿入する
+///    (見出しが存在しない場合はセクションごと末尾に追加する)
+/// 2. ファイル末尾に `[[bin]]` を追加する
+pub fn add_problem_to_cargo_toml(
+    cargo_toml_path: &Path,
+    problem_name: &str,
+    timeout: u128,
+) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(cargo_toml_path)?;
+    let mut lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+    set_timeout_entry(&mut lines, problem_name, timeout);
+    lines.push(String::new());
+    lines.push("[[bin]]".to_string());
+    lines.push(format!("name = \"{}\"", problem_name));
+    lines.push(format!("path = \"{}/main.rs\"", problem_name));
+
+    fs::write(cargo_toml_path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// `Cargo.toml` の問題の実行時間制限を更新する (`[[bin]]` は変更しない)
+///
+/// `add_problem_to_cargo_toml` と同様に、テキストとして編集する。
+pub fn update_problem_timeout(
+    cargo_toml_path: &Path,
+    problem_name: &str,
+    timeout: u128,
+) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(cargo_toml_path)?;
+    let mut lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+    set_timeout_entry(&mut lines, problem_name, timeout);
+    fs::write(cargo_toml_path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+/// `[package.metadata.timeout]` の問題の行を置き換える (存在しない場合は見出しの直後に挿入する)
+fn set_timeout_entry(lines: &mut Vec<String>, problem_name: &str, timeout: u128) {
+    let timeout_entry = format!("\"{}\" = {}", problem_name, timeout);
+    let Some(header) = lines
+        .iter()
+        .position(|line| line.trim() == "[package.metadata.timeout]")
+    else {
+        lines.push(String::new());
+        lines.push("[package.metadata.timeout]".to_string());
+        lines.push(timeout_entry);
+        return;
+    };
+    let existing = lines[header + 1..]
+        .iter()
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .position(|line| {
+            line.split_once('=')
+                .is_some_and(|(key, _)| key.trim().trim_matches('"') == problem_name)
+        });
+    match existing {
+        Some(offset) => lines[header + 1 + offset] = timeout_entry,
+        None => lines.insert(header + 1, timeout_entry),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_add_problem_to_cargo_toml() {
+        let work_dir = tempfile::tempdir().expect("");
+        let cargo_toml_path = work_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_toml_path,
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+
+[package.metadata.timeout]
+"a" = 2000
+
+[dependencies]
+proconio = "0.4.5"
+"#,
+        )
+        .unwrap();
+
+        assert!(!is_registered(&cargo_toml_path, "g").unwrap());
+        add_problem_to_cargo_toml(&cargo_toml_path, "g", 3000).unwrap();
+        assert!(is_registered(&cargo_toml_path, "g").unwrap());
+
+        let parsed: Value = toml::from_str(&fs::read_to_string(&cargo_toml_path).unwrap()).unwrap();
+        let timeout = &parsed["package"]["metadata"]["timeout"];
+        assert_eq!(timeout["a"].as_integer(), Some(2000));
+        assert_eq!(timeout["g"].as_integer(), Some(3000));
+        assert_eq!(parsed["bin"].as_array().unwrap().len(), 2);
+        assert_eq!(parsed["dependencies"]["proconio"].as_str(), Some("0.4.5"));
+    }
+
+    #[test]
+    fn test_update_problem_timeout() {
+        let work_dir = tempfile::tempdir().expect("");
+        let cargo_toml_path = work_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_toml_path,
+            "[package]\nname = \"abc388\"\n\n[package.metadata.timeout]\n\"a\" = 2000\n\"b\" = 2000\n\n[dependencies]\n",
+        )
+        .unwrap();
+
+        update_problem_timeout(&cargo_toml_path, "b", 3000).unwrap();
+        update_problem_timeout(&cargo_toml_path, "c", 4000).unwrap();
+        let parsed: Value = toml::from_str(&fs::read_to_string(&cargo_toml_path).unwrap()).unwrap();
+        let timeout = &parsed["package"]["metadata"]["timeout"];
+        assert_eq!(timeout["a"].as_integer(), Some(2000));
+        assert_eq!(timeout["b"].as_integer(), Some(3000));
+        assert_eq!(timeout["c"].as_integer(), Some(4000));
+        assert!(parsed.get("bin").is_none());
+    }
+
+    #[test]
+    fn test_add_problem_to_cargo_toml_without_timeout_section() {
+        let work_dir = tempfile::tempdir().expect("");
+        let cargo_toml_path = work_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_toml_path,
+            "[package]\nname = \"abc388\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        add_problem_to_cargo_toml(&cargo_toml_path, "a", 2000).unwrap();
+        let parsed: Value = toml::from_str(&fs::read_to_string(&cargo_toml_path).unwrap()).unwrap();
+        assert_eq!(
+            parsed["package"]["metadata"]["timeout"]["a"].as_integer(),
+            Some(2000)
+        );
+    }
+}
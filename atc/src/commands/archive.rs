@@ -0,0 +1,146 @@
+//! 終了したコンテストをアーカイブ先に移動するモジュール
+//!
+//! 作業ディレクトリのコンテストディレクトリを `config` の `archive_dir`(既定値は `archive`)へ移動し、
+//! コンテストの最終的な状況(AC した問題)を取り組み記録(`stats`)に保存する。
+//! ログイン済みの場合は、パフォーマンスとレート変動の推定結果も表示して保存する(`performance.rs`)。
+//! `strip` を指定した場合は、移動前にビルド成果物(`target/`)を削除する。
+//!
+//! ```text
+//! .
+//! ├── abc389        # 参加中のコンテスト
+//! └── archive
+//!     └── abc388    # アーカイブしたコンテスト
+//! ```
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::config::{get_archive_dir, get_stats_file};
+use super::performance::estimate_with_saved_session;
+use super::stats::record_archive;
+
+/// アーカイブ処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリを含む作業ディレクトリ
+/// - `contest_name`: コンテスト名 (`abc388` など)
+/// - `strip`: `true` の場合、移動前に `target/` を削除する
+///
+/// # エラーの可能性
+/// - コンテストディレクトリが存在しない、またはアーカイブ先に同名のディレクトリが存在する場合
+/// - ディレクトリの移動に失敗した場合
+pub async fn execute(
+    work_dir: &Path,
+    contest_name: &str,
+    strip: bool,
+) -> Result<(), Box<dyn Error>> {
+    let archive_dir = get_archive_dir(work_dir);
+    let destination = archive_contest(work_dir, &archive_dir, contest_name, strip)?;
+
+    let problems = list_problems(&destination)?;
+    match record_archive(&get_stats_file(), contest_name, &problems) {
+        Ok(archived) => println!(
+            "Solved {} / {} problem(s): {}",
+            archived.solved.len(),
+            archived.problems.len(),
+            archived.solved.join(", ")
+        ),
+        Err(e) => eprintln!("Warning: 取り組み記録の保存に失敗しました: {}", e),
+    }
+    println!("Archived {} to {}", contest_name, destination.display());
+    if let Err(e) = estimate_with_saved_session(contest_name).await {
+        eprintln!("Warning: パフォーマンスを推定できませんでした: {}", e);
+    }
+    Ok(())
+}
+
+/// コンテストディレクトリをアーカイブ先に移動し、移動後のパスを返す
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリを含む作業ディレクトリ
+/// - `archive_dir`: アーカイブ先ディレクトリ
+/// - `contest_name`: コンテスト名
+/// - `strip`: `true` の場合、移動前に `target/` を削除する
+pub fn archive_contest(
+    work_dir: &Path,
+    archive_dir: &Path,
+    contest_name: &str,
+    strip: bool,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let contest_dir = work_dir.join(contest_name);
+    if !contest_dir.join("Cargo.toml").is_file() {
+        return Err(format!(
+            "コンテストディレクトリ {} が存在しません",
+            contest_dir.display()
+        )
+        .into());
+    }
+    let destination = archive_dir.join(contest_name);
+    if destination.exists() {
+        return Err(format!("{} は既に存在します", destination.display()).into());
+    }
+
+    let target_dir = contest_dir.join("target");
+    if strip && target_dir.is_dir() {
+        fs::remove_dir_all(&target_dir)?;
+    }
+    fs::create_dir_all(archive_dir)?;
+    fs::rename(&contest_dir, &destination).map_err(|e| {
+        format!(
+            "{} を {} に移動できません: {}",
+            contest_dir.display(),
+            destination.display(),
+            e
+        )
+    })?;
+    Ok(destination)
+}
+
+/// コンテストディレクトリ内の問題(`main.rs` を持つディレクトリ)の一覧を返す
+fn list_problems(contest_dir: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut problems = Vec::new();
+    for entry in fs::read_dir(contest_dir)? {
+        let path = entry?.path();
+        if path.join("main.rs").is_file() {
+            if let Some(name) = path.file_name() {
+                problems.push(name.to_string_lossy().to_string());
+            }
+        }
+    }
+    problems.sort();
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_archive_contest() {
+        let work_dir = tempfile::tempdir().expect("");
+        let contest_dir = work_dir.path().join("abc388");
+        for dir in ["a", "b", "target/debug", ".atc"] {
+            fs::create_dir_all(contest_dir.join(dir)).unwrap();
+        }
+        fs::write(contest_dir.join("Cargo.toml"), "").unwrap();
+        fs::write(contest_dir.join("a/main.rs"), "").unwrap();
+        fs::write(contest_dir.join("b/main.rs"), "").unwrap();
+
+        let archive_dir = work_dir.path().join("archive");
+        let destination = archive_contest(work_dir.path(), &archive_dir, "abc388", true).unwrap();
+        assert_eq!(destination, archive_dir.join("abc388"));
+        assert!(!contest_dir.exists());
+        assert!(destination.join("a/main.rs").exists());
+        assert!(!destination.join("target").exists());
+        assert_eq!(
+            list_problems(&destination).unwrap(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+
+        // 存在しないコンテストはアーカイブできない
+        assert!(archive_contest(work_dir.path(), &archive_dir, "abc388", false).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! 問題のプログラムの実行時間を計測するモジュール
+//!
+//! リリースビルドしたプログラムに 1 つのテストケースを繰り返し与えて実行し、
+//! 実行時間の最小値・平均値・最大値・95 パーセンタイル・分散を表示する。
+//! 提出前に実行時間制限(`Cargo.toml` の `[package.metadata.timeout]`)との差を確認するために利用する。
+//!
+//! テストケースを指定しない場合は、`tests` ディレクトリ内で最もサイズが大きい `.in` ファイルを利用する。
+use serde::Serialize;
+use std::{
+    error::Error,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use super::test::{cargo_build, find_problem_directory, load_problem_timeout_settings};
+
+/// 実行時間計測処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+/// - `case`: 利用するテストケース名 (`sample_1` など。`None` の場合は最大の入力ファイル)
+/// - `runs`: 実行回数
+///
+/// # エラーの可能性
+/// - 問題ディレクトリ・テストケースが存在しない場合
+/// - リリースビルドに失敗した場合
+/// - プログラムが 0 以外の終了コードで終了した場合
+pub fn execute(
+    work_dir: &Path,
+    problem_name: &str,
+    case: Option<&str>,
+    runs: usize,
+) -> Result<(), Box<dyn Error>> {
+    if runs == 0 {
+        return Err("実行回数には 1 以上を指定してください".into());
+    }
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let input_path = find_test_case(&problem_dir.join("tests"), case)?;
+    let executable = compile_release(work_dir, problem_name)?;
+    let input = fs::read_to_string(&input_path)?;
+
+    println!(
+        "Benchmarking {} with {} x{}",
+        problem_name,
+        input_path.display(),
+        runs
+    );
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        times.push(measure(&executable, &input)?);
+    }
+    let stats = BenchStats::from_durations(&times);
+    println!(
+        "min: {:.1} ms, mean: {:.1} ms, max: {:.1} ms, p95: {:.1} ms, variance: {:.2} ms^2",
+        stats.min, stats.mean, stats.max, stats.p95, stats.variance
+    );
+
+    let timeout = load_problem_timeout_settings(work_dir)
+        .ok()
+        .and_then(|settings| settings.get(problem_name).copied());
+    if let Some(timeout) = timeout {
+        println!(
+            "Time limit: {} ms (max is {:.1}% of the limit)",
+            timeout,
+            stats.max / timeout as f64 * 100.0
+        );
+    }
+    Ok(())
+}
+
+/// 実行時間の統計値 (ms)
+#[derive(Debug, PartialEq, Clone, Copy, Serialize)]
+pub struct BenchStats {
+    pub min: f64,
+    pub mean: f64,
+    pub max: f64,
+    pub p95: f64, // 95 パーセンタイル (最近傍順位法)
+    pub variance: f64,
+}
+
+impl BenchStats {
+    /// 実行時間の一覧から統計値を計算する。`times` は空でないこと
+    pub fn from_durations(times: &[Duration]) -> Self {
+        let millis: Vec<f64> = times.iter().map(|t| t.as_secs_f64() * 1000.0).collect();
+        let n = millis.len() as f64;
+        let mean = millis.iter().sum::<f64>() / n;
+        let mut sorted = millis.clone();
+        sorted.sort_by(f64::total_cmp);
+        let rank = (n * 0.95).ceil() as usize;
+        BenchStats {
+            min: sorted[0],
+            mean,
+            max: sorted[sorted.len() - 1],
+            p95: sorted[rank.max(1) - 1],
+            variance: millis.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / n,
+        }
+    }
+}
+
+/// 計測に利用するテストケースの入力ファイルを返す
+///
+/// # 引数
+/// - `tests_dir`: 問題の `tests` ディレクトリ
+/// - `case`: テストケース名 (拡張子 `.in` は省略可能)。`None` の場合はサイズが最大の入力ファイル
+fn find_test_case(tests_dir: &Path, case: Option<&str>) -> Result<PathBuf, Box<dyn Error>> {
+    if let Some(case) = case {
+        let path = tests_dir.join(case).with_extension("in");
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(format!("テストケース {} が存在しません", path.display()).into())
+        };
+    }
+    let mut largest: Option<(u64, PathBuf)> = None;
+    for entry in fs::read_dir(tests_dir)? {
+        let path = entry?.path();
+        if path.extension().unwrap_or_default() != "in" {
+            continue;
+        }
+        let size = fs::metadata(&path)?.len();
+        if largest.as_ref().is_none_or(|(max, _)| size > *max) {
+            largest = Some((size, path));
+        }
+    }
+    largest
+        .map(|(_, path)| path)
+        .ok_or_else(|| format!("{} に入力ファイルがありません", tests_dir.display()).into())
+}
+
+/// 問題のプログラムをリリースビルドし、実行ファイルのパスを返す
+fn compile_release(work_dir: &Path, problem_name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let mut command = Command::new("cargo");
+    command
+        .args(["build", "--release", "--bin", problem_name])
+        .current_dir(work_dir);
+    let mut executables = cargo_build(&mut command)?;
+    executables
+        .remove(problem_name)
+        .ok_or_else(|| format!("Executable for problem '{}' was not built", problem_name).into())
+}
+
+/// 入力を与えてプログラムを 1 回実行し、実行時間を返す
+fn measure(executable: &Path, input: &str) -> Result<Duration, Box<dyn Error>> {
+    let start_time = Instant::now();
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let status = child.wait()?;
+    let elapsed = start_time.elapsed();
+    if status.success() {
+        Ok(elapsed)
+    } else {
+        Err(format!("Process exited with {}", status).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_bench_stats() {
+        let times = [10, 20, 30].map(Duration::from_millis);
+        let stats = BenchStats::from_durations(&times);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 30.0);
+        assert!((stats.mean - 20.0).abs() < 1e-9);
+        assert!((stats.variance - 200.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats.p95, 30.0);
+
+        let times: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let stats = BenchStats::from_durations(&times);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.p95, 95.0);
+        assert_eq!(stats.max, 100.0);
+    }
+
+    #[test]
+    fn test_find_test_case() {
+        let work_dir = tempfile::tempdir().expect("");
+        let tests_dir = work_dir.path().join("tests");
+        assert!(find_test_case(&tests_dir, None).is_err());
+
+        fs::create_dir_all(&tests_dir).unwrap();
+        fs::write(tests_dir.join("sample_1.in"), "1\n").unwrap();
+        fs::write(tests_dir.join("random_1.in"), "1 2 3 4 5\n").unwrap();
+        fs::write(tests_dir.join("random_1.out"), "15\n15\n15\n15\n").unwrap();
+
+        assert_eq!(
+            find_test_case(&tests_dir, None).unwrap(),
+            tests_dir.join("random_1.in")
+        );
+        assert_eq!(
+            find_test_case(&tests_dir, Some("sample_1")).unwrap(),
+            tests_dir.join("sample_1.in")
+        );
+        assert_eq!(
+            find_test_case(&tests_dir, Some("sample_1.in")).unwrap(),
+            tests_dir.join("sample_1.in")
+        );
+        assert!(find_test_case(&tests_dir, Some("sample_2")).is_err());
+    }
+}
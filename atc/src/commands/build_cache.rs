@@ -0,0 +1,242 @@
+//! ソースが変更されていない場合に再コンパイルを省略するモジュール
+//!
+//! `compile` はビルドに成功するとパッケージ(`Cargo.toml` が存在するコンテストディレクトリ)の
+//! `.atc/build.json` に、ソースのハッシュ値と生成された実行ファイルのパス・更新日時をプロファイルごとに記録する。
+//! 次回のコンパイル時にハッシュ値が一致し、実行ファイルが記録時のまま存在する場合は `cargo build` を実行しない。
+//!
+//! ハッシュ値の対象は、パッケージ内(`target/`・`.atc/`・`.git/` を除く)の `.rs`・`.toml` ファイルと `Cargo.lock`、
+//! ワークスペースの場合はワークスペースの `Cargo.toml`・`Cargo.lock`・`.cargo/config.toml` とする。
+//! ツールチェーンや環境変数(`RUSTFLAGS` など)の変更は検知しないため、その場合は `test --force-build` で再コンパイルする。
+//!
+//! ```text
+//! abc388
+//! ├── Cargo.toml
+//! ├── .atc
+//! │   └── build.json   # { "debug": { "fingerprint": ..., "executables": { "a": ... } } }
+//! └── a
+//!     └── main.rs
+//! ```
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::UNIX_EPOCH,
+};
+
+use super::score::hash_bytes;
+use super::workspace::is_workspace_root;
+
+/// ビルド結果を記録するファイルのパッケージからの相対パス
+const BUILD_CACHE_FILE: &str = ".atc/build.json";
+
+/// ハッシュ値の対象としないディレクトリ
+const IGNORED_DIRS: [&str; 3] = ["target", ".atc", ".git"];
+
+static FORCE_BUILD: AtomicBool = AtomicBool::new(false);
+
+/// 記録に関わらず常に再コンパイルするかどうかを設定する (`--force-build` を指定した場合に有効にする)
+pub fn set_force_build(enabled: bool) {
+    FORCE_BUILD.store(enabled, Ordering::Relaxed);
+}
+
+/// プロファイルごとのビルド結果
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct BuildRecord {
+    fingerprint: String,                            // ソースのハッシュ値
+    executables: BTreeMap<String, BuiltExecutable>, // バイナリ名 → 実行ファイル
+}
+
+/// 生成された実行ファイル
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct BuiltExecutable {
+    path: PathBuf, // 実行ファイルのパス
+    modified: u64, // 記録時の更新日時 (UNIX 時間、ナノ秒)
+}
+
+/// `dir` またはその親ディレクトリのうち、`Cargo.toml` が存在する最も近いディレクトリを返す
+pub fn package_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// パッケージのソースのハッシュ値を計算する
+///
+/// # エラーの可能性
+/// - ファイル・ディレクトリの読み込みに失敗した場合
+pub fn source_fingerprint(package_root: &Path) -> Result<String, Box<dyn Error>> {
+    let mut files = Vec::new();
+    collect_source_files(package_root, &mut files)?;
+    if let Some(parent) = package_root.parent().filter(|dir| is_workspace_root(dir)) {
+        for name in ["Cargo.toml", "Cargo.lock", ".cargo/config.toml"] {
+            let path = parent.join(name);
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+
+    let mut content = Vec::new();
+    for path in files {
+        content.extend_from_slice(path.to_string_lossy().as_bytes());
+        content.push(0);
+        let bytes = fs::read(&path)?;
+        content.extend_from_slice(bytes.len().to_string().as_bytes());
+        content.push(0);
+        content.extend_from_slice(&bytes);
+    }
+    Ok(format!("{:016x}", hash_bytes(&content)))
+}
+
+/// `dir` 以下のハッシュ値の対象となるファイルを再帰的に収集する
+fn collect_source_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.file_type()?.is_dir() {
+            if !IGNORED_DIRS.contains(&name.as_str()) {
+                collect_source_files(&path, files)?;
+            }
+        } else if name == "Cargo.lock"
+            || path
+                .extension()
+                .is_some_and(|extension| extension == "rs" || extension == "toml")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// ソースが前回のビルドから変更されていない場合、前回生成された実行ファイルを返す
+///
+/// `--force-build` が指定された場合、記録がない場合、ハッシュ値が異なる場合、
+/// または実行ファイルが削除・更新されている場合は `None` を返す。
+///
+/// # 引数
+/// - `package_root`: パッケージのディレクトリ
+/// - `profile`: ビルドのプロファイル (`debug` または `release`)
+/// - `fingerprint`: 現在のソースのハッシュ値 (`source_fingerprint`)
+pub fn load_fresh_executables(
+    package_root: &Path,
+    profile: &str,
+    fingerprint: &str,
+) -> Option<BTreeMap<String, PathBuf>> {
+    if FORCE_BUILD.load(Ordering::Relaxed) {
+        return None;
+    }
+    let record = load_records(package_root).remove(profile)?;
+    if record.fingerprint != fingerprint || record.executables.is_empty() {
+        return None;
+    }
+    record
+        .executables
+        .into_iter()
+        .map(|(name, executable)| {
+            (modified_nanos(&executable.path) == Some(executable.modified))
+                .then_some((name, executable.path))
+        })
+        .collect()
+}
+
+/// ビルドに成功したソースのハッシュ値と生成された実行ファイルを記録する
+///
+/// # エラーの可能性
+/// - 記録ファイルの書き込みに失敗した場合
+pub fn save_build(
+    package_root: &Path,
+    profile: &str,
+    fingerprint: &str,
+    executables: &BTreeMap<String, PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let mut records = load_records(package_root);
+    let executables = executables
+        .iter()
+        .filter_map(|(name, path)| {
+            let modified = modified_nanos(path)?;
+            Some((
+                name.clone(),
+                BuiltExecutable {
+                    path: path.clone(),
+                    modified,
+                },
+            ))
+        })
+        .collect();
+    records.insert(
+        profile.to_string(),
+        BuildRecord {
+            fingerprint: fingerprint.to_string(),
+            executables,
+        },
+    );
+    let path = package_root.join(BUILD_CACHE_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(&records)?)?;
+    Ok(())
+}
+
+/// 記録ファイルを読み込む (存在しない、または解析できない場合は空とする)
+fn load_records(package_root: &Path) -> BTreeMap<String, BuildRecord> {
+    fs::read_to_string(package_root.join(BUILD_CACHE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// ファイルの更新日時 (UNIX 時間、ナノ秒) を返す
+fn modified_nanos(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_source_fingerprint() {
+        let package = tempfile::tempdir().expect("");
+        fs::write(package.path().join("Cargo.toml"), "[package]\n").unwrap();
+        fs::create_dir_all(package.path().join("a/tests")).unwrap();
+        fs::create_dir_all(package.path().join("target/debug")).unwrap();
+        fs::write(package.path().join("a/main.rs"), "fn main() {}\n").unwrap();
+        let fingerprint = source_fingerprint(package.path()).unwrap();
+
+        // テストケースやビルド成果物の変更は無視する
+        fs::write(package.path().join("a/tests/sample_1.in"), "1\n").unwrap();
+        fs::write(package.path().join("target/debug/a"), "binary").unwrap();
+        assert_eq!(source_fingerprint(package.path()).unwrap(), fingerprint);
+
+        fs::write(package.path().join("a/main.rs"), "fn main() { }\n").unwrap();
+        assert_ne!(source_fingerprint(package.path()).unwrap(), fingerprint);
+    }
+
+    #[test]
+    fn test_load_fresh_executables() {
+        let package = tempfile::tempdir().expect("");
+        let executable = package.path().join("a");
+        fs::write(&executable, "binary").unwrap();
+        let executables = BTreeMap::from([("a".to_string(), executable.clone())]);
+        assert_eq!(load_fresh_executables(package.path(), "debug", "1"), None);
+
+        save_build(package.path(), "debug", "1", &executables).unwrap();
+        assert_eq!(
+            load_fresh_executables(package.path(), "debug", "1"),
+            Some(executables)
+        );
+        assert_eq!(load_fresh_executables(package.path(), "debug", "2"), None);
+        assert_eq!(load_fresh_executables(package.path(), "release", "1"), None);
+
+        fs::remove_file(&executable).unwrap();
+        assert_eq!(load_fresh_executables(package.path(), "debug", "1"), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! ダウンロード時に取得した HTML をキャッシュするモジュール
+//!
+//! `ETag` または `Last-Modified` を含むレスポンスを、設定ディレクトリの `cache/` に URL ごとに保存する。
+//! 同じ URL を再取得する際は `If-None-Match` / `If-Modified-Since` を付けて問い合わせ、
+//! `304 Not Modified` が返された場合は保存済みの HTML を利用する。
+//!
+//! `download`・`update` で `--no-cache` を指定した場合は、キャッシュを参照・保存しない。
+//!
+//! ```text
+//! <設定ディレクトリ>
+//! └── cache
+//!     └── 1f3a...c9.json  # URL のハッシュ値をファイル名とする
+//! ```
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::hash_map::DefaultHasher,
+    error::Error,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use super::config::get_cache_dir;
+
+static CACHE_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// キャッシュの有効・無効を設定する (`--no-cache` を指定した場合に無効にする)
+pub fn set_enabled(enabled: bool) {
+    CACHE_DISABLED.store(!enabled, Ordering::Relaxed);
+}
+
+/// キャッシュが有効な場合はキャッシュディレクトリを返す
+pub fn active_cache_dir() -> Option<PathBuf> {
+    (!CACHE_DISABLED.load(Ordering::Relaxed)).then(get_cache_dir)
+}
+
+/// キャッシュした HTML
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct CachedPage {
+    pub url: String,                   // 取得した URL
+    pub etag: Option<String>,          // レスポンスの `ETag`
+    pub last_modified: Option<String>, // レスポンスの `Last-Modified`
+    pub body: String,                  // HTML
+}
+
+impl CachedPage {
+    /// URL に対応するキャッシュを読み込む。存在しない、または読み込めない場合は `None` を返す
+    pub fn load(cache_dir: &Path, url: &str) -> Option<Self> {
+        let content = fs::read_to_string(cache_path(cache_dir, url)).ok()?;
+        serde_json::from_str::<CachedPage>(&content)
+            .ok()
+            .filter(|page| page.url == url)
+    }
+
+    /// キャッシュを保存する
+    pub fn save(&self, cache_dir: &Path) -> Result<(), Box<dyn Error>> {
+        fs::create_dir_all(cache_dir)?;
+        fs::write(
+            cache_path(cache_dir, &self.url),
+            serde_json::to_string(self)?,
+        )?;
+        Ok(())
+    }
+
+    /// 再検証に利用できる情報(`ETag` / `Last-Modified`)を持つかどうかを返す
+    pub fn is_revalidatable(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// URL に対応するキャッシュファイルのパスを返す
+fn cache_path(cache_dir: &Path, url: &str) -> PathBuf {
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    cache_dir.join(format!("{:016x}.json", hasher.finish()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_cached_page() {
+        let cache_dir = tempfile::tempdir().expect("");
+        let url = "https://atcoder.jp/contests/abc388/tasks/abc388_a";
+        assert_eq!(CachedPage::load(cache_dir.path(), url), None);
+
+        let page = CachedPage {
+            url: url.to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+            body: "<html></html>".to_string(),
+        };
+        assert!(page.is_revalidatable());
+        page.save(cache_dir.path()).unwrap();
+        assert_eq!(CachedPage::load(cache_dir.path(), url), Some(page));
+        assert_eq!(
+            CachedPage::load(cache_dir.path(), "https://atcoder.jp/contests/abc388/tasks"),
+            None
+        );
+    }
+}
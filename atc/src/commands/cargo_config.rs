@@ -0,0 +1,148 @@
+//! ローカルのビルドを高速化する `.cargo/config.toml` を作成するモジュール
+//!
+//! 設定 `cargo_config` が `true` の場合、`download` でコンテストディレクトリ
+//! (ワークスペースの場合はワークスペースのルート)に `.cargo/config.toml` を作成する。
+//! 作成する内容は以下の設定で切り替える。
+//! - `cargo_linker`: リンカ (`lld` または `mold`)。未設定の場合は既定のリンカを使う
+//! - `cargo_target_dir`: ビルド成果物の格納先。複数のコンテストで共有すると依存クレートのビルドが 1 回で済む
+//!
+//! `[profile.dev]` はデバッグ情報を省き、インクリメンタルコンパイルを有効にして、
+//! 解答を書き換えるたびのビルドを短くする。既に `.cargo/config.toml` がある場合は上書きしない。
+//!
+//! ```toml
+//! [build]
+//! target-dir = "/home/user/.cache/atc/target"
+//!
+//! [target.'cfg(target_os = "linux")']
+//! rustflags = ["-C", "link-arg=-fuse-ld=mold"]
+//!
+//! [profile.dev]
+//! debug = 0
+//! incremental = true
+//! codegen-units = 256
+//! ```
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+/// `.cargo/config.toml` のパス (ディレクトリからの相対パス)
+pub const CARGO_CONFIG_FILE: &str = ".cargo/config.toml";
+
+/// `cargo_linker` に指定できるリンカ
+pub const LINKERS: [&str; 2] = ["lld", "mold"];
+
+/// `.cargo/config.toml` の内容を作成する
+///
+/// # 引数
+/// - `linker`: リンカ (`lld` または `mold`)。`None` の場合はリンカを設定しない
+/// - `target_dir`: ビルド成果物の格納先。`None` の場合は設定しない
+pub fn cargo_config_toml(linker: Option<&str>, target_dir: Option<&Path>) -> String {
+    let mut content = String::new();
+    if let Some(target_dir) = target_dir {
+        content.push_str(&format!(
+            "[build]\ntarget-dir = {}\n\n",
+            Value::String(target_dir.to_string_lossy().to_string())
+        ));
+    }
+    if let Some(linker) = linker {
+        content.push_str(&format!(
+            "[target.'cfg(target_os = \"linux\")']\nrustflags = [\"-C\", \"link-arg=-fuse-ld={}\"]\n\n",
+            linker
+        ));
+    }
+    content.push_str("[profile.dev]\ndebug = 0\nincremental = true\ncodegen-units = 256\n");
+    content
+}
+
+/// `dir` に `.cargo/config.toml` を作成する
+///
+/// # 引数
+/// - `dir`: 作成先のディレクトリ (コンテストディレクトリまたはワークスペースのルート)
+/// - `linker`: リンカ (`lld` または `mold`)
+/// - `target_dir`: ビルド成果物の格納先
+///
+/// # 戻り値
+/// - `Ok(true)`: 作成した場合
+/// - `Ok(false)`: 既に `.cargo/config.toml` が存在するため作成しなかった場合
+///
+/// # エラーの可能性
+/// - ディレクトリの作成、ファイルの書き込みに失敗した場合
+pub fn create_cargo_config(
+    dir: &Path,
+    linker: Option<&str>,
+    target_dir: Option<&Path>,
+) -> Result<bool, Box<dyn Error>> {
+    let path = dir.join(CARGO_CONFIG_FILE);
+    if path.exists() {
+        return Ok(false);
+    }
+    fs::create_dir_all(dir.join(".cargo"))?;
+    fs::write(&path, cargo_config_toml(linker, target_dir))?;
+    Ok(true)
+}
+
+/// `dir` の `.cargo/config.toml` に設定された `build.target-dir` を返す
+///
+/// 相対パスは `dir` を基準とする (Cargo と同じく `.cargo` ディレクトリの親を基準とする)。
+/// `.cargo/config.toml` がない、または `target-dir` が設定されていない場合は `None` を返す。
+pub fn configured_target_dir(dir: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(dir.join(CARGO_CONFIG_FILE)).ok()?;
+    let parsed: Value = toml::from_str(&content).ok()?;
+    let target_dir = parsed.get("build")?.get("target-dir")?.as_str()?;
+    Some(dir.join(target_dir))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_cargo_config_toml() {
+        let content = cargo_config_toml(None, None);
+        assert_eq!(
+            content,
+            "[profile.dev]\ndebug = 0\nincremental = true\ncodegen-units = 256\n"
+        );
+
+        let content = cargo_config_toml(Some("mold"), Some(Path::new("/tmp/atc-target")));
+        let parsed: Value = toml::from_str(&content).unwrap();
+        assert_eq!(
+            parsed["build"]["target-dir"].as_str(),
+            Some("/tmp/atc-target")
+        );
+        assert_eq!(
+            parsed["target"]["cfg(target_os = \"linux\")"]["rustflags"][1].as_str(),
+            Some("link-arg=-fuse-ld=mold")
+        );
+        assert_eq!(parsed["profile"]["dev"]["debug"].as_integer(), Some(0));
+    }
+
+    #[test]
+    fn test_create_cargo_config() {
+        let contest_dir = tempfile::tempdir().expect("");
+        assert_eq!(configured_target_dir(contest_dir.path()), None);
+
+        assert!(create_cargo_config(
+            contest_dir.path(),
+            Some("lld"),
+            Some(Path::new("../target"))
+        )
+        .unwrap());
+        assert_eq!(
+            configured_target_dir(contest_dir.path()),
+            Some(contest_dir.path().join("../target"))
+        );
+
+        // 既存の `.cargo/config.toml` は上書きしない
+        assert!(!create_cargo_config(contest_dir.path(), None, None).unwrap());
+        assert!(
+            fs::read_to_string(contest_dir.path().join(CARGO_CONFIG_FILE))
+                .unwrap()
+                .contains("fuse-ld=lld")
+        );
+    }
+}
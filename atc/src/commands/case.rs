@@ -0,0 +1,199 @@
+//! ユーザが作成するテストケース(カスタムケース)を扱うモジュール
+//!
+//! `tests/` 直下の `.in`・`.out` の組はサンプル以外もテストケースとして実行される。
+//! `case add` は `custom_<番号>.in`・`custom_<番号>.out` の組を作成する。
+//! 入力と期待する出力は以下のいずれかで与える。
+//! - 標準入力が端末の場合: エディタ(環境変数 `VISUAL`・`EDITOR`、未設定の場合は `vi`)で入力ファイル・出力ファイルを順に編集する
+//! - 標準入力がパイプの場合: `---` のみの行で区切った入力と期待する出力を読み込む
+//!
+//! ```text
+//! a
+//! └── tests
+//!     ├── sample_1.in
+//!     ├── sample_1.out
+//!     ├── custom_1.in   # case add で作成したテストケース
+//!     └── custom_1.out
+//! ```
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, IsTerminal, Read},
+    path::Path,
+    process::Command,
+};
+
+use super::output::is_json;
+use super::test::find_problem_directory;
+
+/// カスタムケースのファイル名の接頭辞
+pub const CUSTOM_CASE_PREFIX: &str = "custom_";
+
+/// 標準入力から読み込む場合の、入力と期待する出力の区切り
+const STDIN_SEPARATOR: &str = "---";
+
+/// テストケース作成処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリのパス
+/// - `problem_name`: 問題名 (`a` など)
+/// - `name`: テストケース名。`None` の場合は `custom_<番号>` とする
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない場合
+/// - 同名のテストケースが既に存在する場合
+/// - エディタの起動に失敗した、または入力が空の場合
+pub fn execute_add(
+    work_dir: &Path,
+    problem_name: &str,
+    name: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let tests_dir = find_problem_directory(work_dir, problem_name)?.join("tests");
+    fs::create_dir_all(&tests_dir)?;
+    let name = match name {
+        Some(name) => name.strip_suffix(".in").unwrap_or(name).to_string(),
+        None => next_custom_case_name(&tests_dir)?,
+    };
+    let input_path = tests_dir.join(format!("{}.in", name));
+    let output_path = tests_dir.join(format!("{}.out", name));
+    if input_path.exists() || output_path.exists() {
+        return Err(format!("テストケース '{}' は既に存在します", name).into());
+    }
+
+    if io::stdin().is_terminal() {
+        fs::write(&input_path, "")?;
+        fs::write(&output_path, "")?;
+        let edited = edit_in_editor(&input_path).and_then(|_| edit_in_editor(&output_path));
+        let is_empty = !fs::read_to_string(&input_path).is_ok_and(|input| !input.trim().is_empty());
+        if edited.is_err() || is_empty {
+            let _ = fs::remove_file(&input_path);
+            let _ = fs::remove_file(&output_path);
+            edited?;
+            return Err("入力が空のため、テストケースを作成しませんでした".into());
+        }
+    } else {
+        let mut content = String::new();
+        io::stdin().read_to_string(&mut content)?;
+        let (input, output) = split_case(&content)?;
+        fs::write(&input_path, input)?;
+        fs::write(&output_path, output)?;
+    }
+    if !is_json() {
+        println!(
+            "Created {} and {}",
+            input_path.display(),
+            output_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// `tests_dir` で使われていない `custom_<番号>` のうち、最小の番号のものを返す
+fn next_custom_case_name(tests_dir: &Path) -> Result<String, Box<dyn Error>> {
+    let mut used = Vec::new();
+    for entry in fs::read_dir(tests_dir)? {
+        let path = entry?.path();
+        let Some(stem) = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        if let Some(number) = stem
+            .strip_prefix(CUSTOM_CASE_PREFIX)
+            .and_then(|number| number.parse::<usize>().ok())
+        {
+            used.push(number);
+        }
+    }
+    let number = (1..).find(|number| !used.contains(number)).unwrap();
+    Ok(format!("{}{}", CUSTOM_CASE_PREFIX, number))
+}
+
+/// 標準入力の内容を、`---` のみの行で入力と期待する出力に分ける
+///
+/// # エラーの可能性
+/// - 区切りの行が含まれない場合
+fn split_case(content: &str) -> Result<(String, String), Box<dyn Error>> {
+    let mut input = Vec::new();
+    let mut lines = content.lines();
+    for line in lines.by_ref() {
+        if line.trim_end() == STDIN_SEPARATOR {
+            let output: Vec<&str> = lines.collect();
+            return Ok((
+                format!("{}\n", input.join("\n")),
+                format!("{}\n", output.join("\n")),
+            ));
+        }
+        input.push(line);
+    }
+    Err(format!(
+        "入力と期待する出力を `{}` のみの行で区切ってください",
+        STDIN_SEPARATOR
+    )
+    .into())
+}
+
+/// ファイルをエディタで開き、エディタが終了するまで待機する
+///
+/// # エラーの可能性
+/// - エディタを起動できない、または異常終了した場合
+fn edit_in_editor(path: &Path) -> Result<(), Box<dyn Error>> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    // `code --wait` のように引数を含む指定を許可する
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map_err(|e| format!("エディタ {} を起動できませんでした: {}", editor, e))?;
+    if !status.success() {
+        return Err(format!("エディタが異常終了しました: {}", status).into());
+    }
+    Ok(())
+}
+
+/// テストケース名(`sample_1.in` など)がカスタムケースかどうかを返す
+///
+/// AtCoder から取得したサンプル(`sample_` で始まるもの)以外をカスタムケースとする。
+pub fn is_custom_case(test_case_name: &str) -> bool {
+    !test_case_name.starts_with("sample_")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_next_custom_case_name() {
+        let tests_dir = tempfile::tempdir().expect("");
+        assert_eq!(next_custom_case_name(tests_dir.path()).unwrap(), "custom_1");
+        fs::write(tests_dir.path().join("sample_1.in"), "").unwrap();
+        fs::write(tests_dir.path().join("custom_1.in"), "").unwrap();
+        fs::write(tests_dir.path().join("custom_1.out"), "").unwrap();
+        fs::write(tests_dir.path().join("custom_3.in"), "").unwrap();
+        assert_eq!(next_custom_case_name(tests_dir.path()).unwrap(), "custom_2");
+    }
+
+    #[test]
+    fn test_split_case() {
+        assert_eq!(
+            split_case("3\n1 2 3\n---\n6\n").unwrap(),
+            ("3\n1 2 3\n".to_string(), "6\n".to_string())
+        );
+        assert!(split_case("3\n1 2 3\n").is_err());
+    }
+
+    #[test]
+    fn test_is_custom_case() {
+        assert!(!is_custom_case("sample_1.in"));
+        assert!(is_custom_case("custom_1.in"));
+        assert!(is_custom_case("max_n.in"));
+    }
+}
@@ -0,0 +1,244 @@
+//! コンテストの質問(Clarifications)を表示するモジュール
+//!
+//! 質問ページ(`clarifications`)を認証済みのセッションで取得し、未読の質問と回答を表示する。
+//! 表示した質問はコンテストディレクトリの `.atc/clarifications.json` に既読として記録し、
+//! 次回以降は新しい質問と回答が更新された質問のみを表示する。
+//! `watch` に更新間隔(秒)を指定した場合は、指定した間隔で質問ページを再取得する。
+//!
+//! ```text
+//! .
+//! ├── Cargo.toml
+//! └── .atc
+//!     └── clarifications.json  # 既読の質問
+//! ```
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path, time::Duration};
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+use super::submit::get_contest_name;
+
+/// 既読の質問を記録するファイル (コンテストディレクトリからの相対パス)
+const SEEN_CLARIFICATIONS_FILE: &str = ".atc/clarifications.json";
+
+/// 質問表示処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `all`: `true` の場合、既読の質問も含めてすべて表示する
+/// - `watch`: 指定された場合、その間隔(秒)で質問ページを再取得して新しい質問を表示する
+pub async fn execute(work_dir: &Path, all: bool, watch: Option<u64>) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+    let base_url = get_base_url();
+    let contest_name = get_contest_name(work_dir)?;
+    let seen_path = work_dir.join(SEEN_CLARIFICATIONS_FILE);
+
+    let mut show_all = all;
+    loop {
+        let url = format!("{}/contests/{}/clarifications", base_url, contest_name);
+        let html = fetch_with_session(&client, &url, &session).await?;
+        let clarifications = parse_clarifications(&Html::parse_document(&html));
+
+        let mut seen = SeenClarifications::load(&seen_path)?;
+        let new_count = clarifications
+            .iter()
+            .filter(|clarification| !seen.contains(clarification))
+            .count();
+        let shown: Vec<&Clarification> = clarifications
+            .iter()
+            .filter(|clarification| show_all || !seen.contains(clarification))
+            .collect();
+        if watch.is_none() || !shown.is_empty() {
+            println!(
+                "=== Clarifications: {} ({} new / {} total) ===",
+                contest_name,
+                new_count,
+                clarifications.len()
+            );
+        }
+        for clarification in shown {
+            print_clarification(clarification);
+        }
+        if new_count > 0 {
+            seen.mark_all(&clarifications);
+            seen.save(&seen_path)?;
+        }
+        show_all = false;
+
+        match watch {
+            Some(interval) => tokio::time::sleep(Duration::from_secs(interval.max(1))).await,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// 質問ページの各質問
+#[derive(Debug, PartialEq)]
+pub struct Clarification {
+    pub task: String,       // 対象の問題 (全体への質問の場合は空)
+    pub question: String,   // 質問
+    pub answer: String,     // 回答 (未回答の場合は空)
+    pub updated_at: String, // 更新日時
+}
+
+impl Clarification {
+    /// 既読判定に利用するキー。回答が更新された場合は別の質問として扱う
+    fn key(&self) -> String {
+        format!("{}\n{}\n{}", self.task, self.question, self.answer)
+    }
+}
+
+/// 既読の質問の一覧
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SeenClarifications {
+    pub seen: Vec<String>, // 既読の質問のキー
+}
+
+impl SeenClarifications {
+    /// 既読の質問を読み込む。ファイルが存在しない場合は空の一覧を返す
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(SeenClarifications::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// 既読の質問を保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn contains(&self, clarification: &Clarification) -> bool {
+        self.seen.contains(&clarification.key())
+    }
+
+    /// 現在の質問をすべて既読にする
+    fn mark_all(&mut self, clarifications: &[Clarification]) {
+        self.seen = clarifications.iter().map(Clarification::key).collect();
+    }
+}
+
+/// 質問ページの HTML から質問の一覧を抽出する
+///
+/// 質問の表は「問題・ユーザ・質問・回答・全体公開・更新日時」の順に並ぶ。
+pub fn parse_clarifications(document: &Html) -> Vec<Clarification> {
+    let row_selector = Selector::parse("table tbody tr").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+
+    let text = |cell: &ElementRef| cell.text().collect::<String>().trim().to_string();
+    document
+        .select(&row_selector)
+        .filter_map(|row| {
+            let cells: Vec<ElementRef> = row.select(&cell_selector).collect();
+            if cells.len() < 6 {
+                return None;
+            }
+            Some(Clarification {
+                task: text(&cells[0]),
+                question: text(&cells[2]),
+                answer: text(&cells[3]),
+                updated_at: text(&cells[5]),
+            })
+        })
+        .collect()
+}
+
+/// 質問と回答を表示する
+fn print_clarification(clarification: &Clarification) {
+    let task = if clarification.task.is_empty() {
+        "(all)"
+    } else {
+        &clarification.task
+    };
+    println!("\n[{}] {}", clarification.updated_at, task);
+    println!("  Q: {}", clarification.question);
+    if clarification.answer.is_empty() {
+        println!("  A: (not answered yet)");
+    } else {
+        println!("  A: {}", clarification.answer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    const CLARIFICATIONS_HTML: &str = r#"
+        <div class="table-responsive">
+          <table class="table table-bordered table-striped">
+            <thead>
+              <tr><th>Task</th><th>User</th><th>Clarification</th><th>Response</th><th>Public</th><th>Time</th></tr>
+            </thead>
+            <tbody>
+              <tr>
+                <td><a href="/contests/abc388/tasks/abc388_c">C - Various Kagamimochi</a></td>
+                <td></td>
+                <td>Can N be 1?</td>
+                <td>Yes.</td>
+                <td>Yes</td>
+                <td><time class="fixtime">2025-01-11 21:30:00+0900</time></td>
+              </tr>
+              <tr>
+                <td></td>
+                <td></td>
+                <td>Is the judge slow?</td>
+                <td></td>
+                <td>Yes</td>
+                <td><time class="fixtime">2025-01-11 21:10:00+0900</time></td>
+              </tr>
+            </tbody>
+          </table>
+        </div>
+    "#;
+
+    #[test]
+    fn test_parse_clarifications() {
+        let clarifications = parse_clarifications(&Html::parse_document(CLARIFICATIONS_HTML));
+        assert_eq!(
+            clarifications,
+            vec![
+                Clarification {
+                    task: "C - Various Kagamimochi".to_string(),
+                    question: "Can N be 1?".to_string(),
+                    answer: "Yes.".to_string(),
+                    updated_at: "2025-01-11 21:30:00+0900".to_string(),
+                },
+                Clarification {
+                    task: "".to_string(),
+                    question: "Is the judge slow?".to_string(),
+                    answer: "".to_string(),
+                    updated_at: "2025-01-11 21:10:00+0900".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_seen_clarifications() {
+        let work_dir = tempfile::tempdir().expect("");
+        let path = work_dir.path().join(SEEN_CLARIFICATIONS_FILE);
+        let mut clarifications = parse_clarifications(&Html::parse_document(CLARIFICATIONS_HTML));
+
+        let mut seen = SeenClarifications::load(&path).unwrap();
+        assert!(!seen.contains(&clarifications[1]));
+        seen.mark_all(&clarifications);
+        seen.save(&path).unwrap();
+
+        let seen = SeenClarifications::load(&path).unwrap();
+        assert!(seen.contains(&clarifications[1]));
+        // 回答が更新された質問は未読として扱う
+        clarifications[1].answer = "No.".to_string();
+        assert!(!seen.contains(&clarifications[1]));
+    }
+}
@@ -0,0 +1,193 @@
+//! コンテストのビルド成果物を一括で削除するモジュール
+//!
+//! 作業ディレクトリ直下のコンテストディレクトリ(`Cargo.toml` と `target/` を持つディレクトリ)を探索し、
+//! 最後に更新されてから指定日数以上経過したコンテストの `target/` を削除する。
+//! 更新日時は `target/` を除くファイルの最終更新日時のうち最も新しいものとする。
+//!
+//! `dry_run` を指定した場合は削除を行わず、対象のコンテストと削除される容量のみを表示する。
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// 1 日の秒数
+const SECS_PER_DAY: u64 = 86400;
+
+/// 削除対象のコンテスト
+#[derive(Debug, PartialEq)]
+pub struct CleanTarget {
+    pub contest_dir: PathBuf, // コンテストディレクトリ
+    pub size: u64,            // `target/` のサイズ (byte)
+}
+
+/// 一括削除処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリを含む作業ディレクトリ
+/// - `days`: 最終更新から経過した日数がこの値以上のコンテストを対象とする
+/// - `dry_run`: `true` の場合は削除せずに対象と容量のみを表示する
+pub fn execute(work_dir: &Path, days: u64, dry_run: bool) -> Result<(), Box<dyn Error>> {
+    let threshold = SystemTime::now() - Duration::from_secs(days * SECS_PER_DAY);
+    let targets = find_clean_targets(work_dir, threshold)?;
+    if targets.is_empty() {
+        println!("No contests to clean");
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for target in &targets {
+        println!(
+            "{} {} ({})",
+            if dry_run { "Would remove" } else { "Removing" },
+            target.contest_dir.join("target").display(),
+            format_size(target.size)
+        );
+        if !dry_run {
+            fs::remove_dir_all(target.contest_dir.join("target"))?;
+        }
+        total += target.size;
+    }
+    println!(
+        "{} {} from {} contest(s)",
+        if dry_run {
+            "Would reclaim"
+        } else {
+            "Reclaimed"
+        },
+        format_size(total),
+        targets.len()
+    );
+    Ok(())
+}
+
+/// 作業ディレクトリ直下から削除対象のコンテストを探索する
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリを含む作業ディレクトリ
+/// - `threshold`: この日時より後に更新されたコンテストは対象外とする
+pub fn find_clean_targets(
+    work_dir: &Path,
+    threshold: SystemTime,
+) -> Result<Vec<CleanTarget>, Box<dyn Error>> {
+    let mut targets = Vec::new();
+    for entry in fs::read_dir(work_dir)? {
+        let contest_dir = entry?.path();
+        if !contest_dir.join("Cargo.toml").is_file() || !contest_dir.join("target").is_dir() {
+            continue;
+        }
+        let last_modified = latest_modified(&contest_dir)?;
+        if last_modified.is_none_or(|modified| modified <= threshold) {
+            targets.push(CleanTarget {
+                size: dir_size(&contest_dir.join("target"))?,
+                contest_dir,
+            });
+        }
+    }
+    targets.sort_by(|a, b| a.contest_dir.cmp(&b.contest_dir));
+    Ok(targets)
+}
+
+/// `target/` を除くファイルの最終更新日時のうち最も新しいものを返す
+fn latest_modified(dir: &Path) -> Result<Option<SystemTime>, Box<dyn Error>> {
+    let mut latest = None;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let modified = if path.is_dir() {
+            if entry.file_name() == "target" {
+                continue;
+            }
+            latest_modified(&path)?
+        } else {
+            Some(entry.metadata()?.modified()?)
+        };
+        latest = latest.max(modified);
+    }
+    Ok(latest)
+}
+
+/// ディレクトリ配下のファイルサイズの合計を返す
+fn dir_size(dir: &Path) -> Result<u64, Box<dyn Error>> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
+
+/// バイト数を読みやすい単位に変換する
+fn format_size(size: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = size as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", size, UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    fn create_contest(work_dir: &Path, contest_name: &str) {
+        let contest_dir = work_dir.join(contest_name);
+        fs::create_dir_all(contest_dir.join("a")).unwrap();
+        fs::create_dir_all(contest_dir.join("target/debug")).unwrap();
+        fs::write(contest_dir.join("Cargo.toml"), "").unwrap();
+        fs::write(contest_dir.join("a/main.rs"), "fn main() {}").unwrap();
+        fs::write(contest_dir.join("target/debug/a"), vec![0u8; 2048]).unwrap();
+    }
+
+    #[test]
+    fn test_find_clean_targets() {
+        let work_dir = tempfile::tempdir().expect("");
+        create_contest(work_dir.path(), "abc387");
+        create_contest(work_dir.path(), "abc388");
+        fs::create_dir_all(work_dir.path().join("templates")).unwrap();
+
+        // 全コンテストが閾値より前に更新されている
+        let future = SystemTime::now() + Duration::from_secs(SECS_PER_DAY);
+        let targets = find_clean_targets(work_dir.path(), future).unwrap();
+        assert_eq!(
+            targets,
+            vec![
+                CleanTarget {
+                    contest_dir: work_dir.path().join("abc387"),
+                    size: 2048,
+                },
+                CleanTarget {
+                    contest_dir: work_dir.path().join("abc388"),
+                    size: 2048,
+                },
+            ]
+        );
+
+        // 閾値より後に更新されたコンテストは対象外
+        let past = SystemTime::now() - Duration::from_secs(SECS_PER_DAY);
+        assert!(find_clean_targets(work_dir.path(), past)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
@@ -0,0 +1,285 @@
+//! 解答の出力と期待する出力を比較する方法を切り替えるモジュール
+//!
+//! 比較方法は `Judge` トレイトとして実装し、`CompareMode` で選択する。
+//! - `exact`: 完全に一致する場合のみ正解とする
+//! - `trim` (既定): 各行の末尾の空白と、末尾の空行を無視して比較する
+//! - `token`: 空白・改行で区切ったトークンの列として比較する
+//!
+//! `trim`・`token` は改行コードの違い(`\r\n` と `\n`)と末尾の改行の有無を無視するため、
+//! Windows で `\r\n` を出力する解答も正解と判定する。改行コードまで比較する場合は `exact` を使う。
+//!
+//! 比較方法は `test --compare <方法>` (`exact` は `test --exact` でもよい)で指定する。指定しない場合は `Cargo.toml` の
+//! `[package.metadata.compare]` に問題ごとに設定した方法を使う。
+//!
+//! WA の場合は `first_mismatch` で最初に異なる行と列を求め、結果の一覧に表示する。
+//!
+//! ```toml
+//! [package.metadata.compare]
+//! c = "token"
+//! ```
+use std::{error::Error, fmt::Display, path::Path, str::FromStr};
+use toml::Value;
+
+/// `--compare` に指定できる比較方法
+pub const COMPARE_MODES: [&str; 3] = ["exact", "trim", "token"];
+
+/// 解答の出力が正解かどうかを判定する
+pub trait Judge {
+    /// 期待する出力 `expected` に対して、解答の出力 `actual` が正解であれば `true` を返す
+    fn judge(&self, expected: &str, actual: &str) -> bool;
+}
+
+/// 完全に一致する場合のみ正解とする
+pub struct ExactJudge;
+
+impl Judge for ExactJudge {
+    fn judge(&self, expected: &str, actual: &str) -> bool {
+        expected == actual
+    }
+}
+
+/// 各行の末尾の空白と、末尾の空行を無視して比較する
+pub struct TrimJudge;
+
+impl TrimJudge {
+    /// 行ごとに分割し、末尾の空白(`\r` を含む)と末尾の空行を取り除く
+    fn normalize(text: &str) -> Vec<&str> {
+        let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+}
+
+impl Judge for TrimJudge {
+    fn judge(&self, expected: &str, actual: &str) -> bool {
+        Self::normalize(expected) == Self::normalize(actual)
+    }
+}
+
+/// 空白・改行で区切ったトークンの列として比較する
+pub struct TokenJudge;
+
+impl Judge for TokenJudge {
+    fn judge(&self, expected: &str, actual: &str) -> bool {
+        expected.split_whitespace().eq(actual.split_whitespace())
+    }
+}
+
+/// 出力の比較方法
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum CompareMode {
+    Exact,
+    #[default]
+    Trim,
+    Token,
+}
+
+impl CompareMode {
+    /// 比較方法に対応する `Judge` を返す
+    pub fn judge(&self) -> Box<dyn Judge> {
+        match self {
+            CompareMode::Exact => Box::new(ExactJudge),
+            CompareMode::Trim => Box::new(TrimJudge),
+            CompareMode::Token => Box::new(TokenJudge),
+        }
+    }
+}
+
+impl FromStr for CompareMode {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "exact" => Ok(CompareMode::Exact),
+            "trim" => Ok(CompareMode::Trim),
+            "token" => Ok(CompareMode::Token),
+            _ => Err(format!(
+                "比較方法 {} は指定できません ({} のいずれかを指定してください)",
+                s,
+                COMPARE_MODES.join(", ")
+            )
+            .into()),
+        }
+    }
+}
+
+impl Display for CompareMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self {
+            CompareMode::Exact => "exact",
+            CompareMode::Trim => "trim",
+            CompareMode::Token => "token",
+        };
+        f.pad(mode)
+    }
+}
+
+/// 期待する出力と解答の出力で最初に異なる位置を説明するメッセージを返す
+///
+/// 行ごとに末尾の空白を除いて比較し、異なる行の最初に異なる文字の列(1 始まり)と、その位置のトークンを示す。
+/// 空白・改行のみが異なる場合(`exact` で WA となった場合)はその旨を返す。
+pub fn first_mismatch(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    for line_number in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (Some(expected), Some(actual)) if expected.trim_end() == actual.trim_end() => {}
+            (Some(expected), Some(actual)) => {
+                let column = expected
+                    .chars()
+                    .zip(actual.chars())
+                    .position(|(e, a)| e != a)
+                    .unwrap_or_else(|| expected.chars().count().min(actual.chars().count()));
+                return format!(
+                    "first mismatch at line {}, column {}: expected {}, got {}",
+                    line_number,
+                    column + 1,
+                    describe_token(expected, column),
+                    describe_token(actual, column)
+                );
+            }
+            (Some(line), None) | (None, Some(line)) if line.trim().is_empty() => {}
+            (Some(expected), None) => {
+                return format!(
+                    "first mismatch at line {}: expected `{}`, got end of output",
+                    line_number,
+                    expected.trim_end()
+                );
+            }
+            (None, Some(actual)) => {
+                return format!(
+                    "first mismatch at line {}: expected end of output, got `{}`",
+                    line_number,
+                    actual.trim_end()
+                );
+            }
+        }
+    }
+    "outputs differ only in whitespace or line endings".to_string()
+}
+
+/// 行の `column` 文字目(0 始まり)を含むトークンを表示用に返す
+fn describe_token(line: &str, column: usize) -> String {
+    let chars: Vec<char> = line.trim_end().chars().collect();
+    // 空白の位置で異なる場合は、続くトークンを示す
+    let column = (column..chars.len())
+        .find(|&index| !chars[index].is_whitespace())
+        .unwrap_or(chars.len());
+    if column >= chars.len() {
+        return "end of line".to_string();
+    }
+    let start = chars[..column]
+        .iter()
+        .rposition(|c| c.is_whitespace())
+        .map_or(0, |index| index + 1);
+    let end = chars[column..]
+        .iter()
+        .position(|c| c.is_whitespace())
+        .map_or(chars.len(), |index| column + index);
+    format!("`{}`", chars[start..end].iter().collect::<String>())
+}
+
+/// `Cargo.toml` の `[package.metadata.compare]` から問題の比較方法を読み込む
+///
+/// # 戻り値
+/// - `Ok(Some(mode))`: 問題の比較方法が設定されている場合
+/// - `Ok(None)`: `Cargo.toml` が存在しない、または設定されていない場合
+///
+/// # エラーの可能性
+/// - `Cargo.toml` を解析できない、または比較方法が不正な場合
+pub fn load_compare_mode(
+    work_dir: &Path,
+    problem_name: &str,
+) -> Result<Option<CompareMode>, Box<dyn Error>> {
+    let Ok(content) = std::fs::read_to_string(work_dir.join("Cargo.toml")) else {
+        return Ok(None);
+    };
+    let parsed: Value = toml::from_str(&content)?;
+    parsed
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("compare"))
+        .and_then(|compare| compare.get(problem_name))
+        .and_then(|mode| mode.as_str())
+        .map(str::parse)
+        .transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use tempfile;
+
+    #[test]
+    fn test_judges() {
+        assert!(ExactJudge.judge("1 2\n", "1 2\n"));
+        assert!(!ExactJudge.judge("1 2\n", "1 2 \n"));
+
+        assert!(TrimJudge.judge("1 2\n3\n", "1 2  \n3\n\n"));
+        assert!(!TrimJudge.judge("1 2\n3\n", "1 2 3\n"));
+
+        assert!(TokenJudge.judge("1 2\n3\n", "1  2 3"));
+        assert!(!TokenJudge.judge("1 2 3\n", "1 2\n"));
+    }
+
+    #[test]
+    fn test_judges_line_endings() {
+        // Windows の改行コードと末尾の改行の有無は `exact` 以外では無視する
+        assert!(TrimJudge.judge("1 2\n3\n", "1 2\r\n3\r\n"));
+        assert!(TrimJudge.judge("1 2\n3\n", "1 2\n3"));
+        assert!(TrimJudge.judge("Yes\n", "Yes\r\n\r\n"));
+        assert!(TokenJudge.judge("1 2\n3\n", "1 2\r\n3"));
+        assert!(!ExactJudge.judge("1 2\n3\n", "1 2\r\n3\r\n"));
+        assert!(!ExactJudge.judge("1 2\n3\n", "1 2\n3"));
+    }
+
+    #[test]
+    fn test_first_mismatch() {
+        assert_eq!(
+            first_mismatch("1\n2\n12 5\n", "1\n2\n13 5\n"),
+            "first mismatch at line 3, column 2: expected `12`, got `13`"
+        );
+        assert_eq!(
+            first_mismatch("#..\n.#.\n", "#..\n..#\n"),
+            "first mismatch at line 2, column 2: expected `.#.`, got `..#`"
+        );
+        assert_eq!(
+            first_mismatch("1 2 3\n", "1 2\n"),
+            "first mismatch at line 1, column 4: expected `3`, got end of line"
+        );
+        assert_eq!(
+            first_mismatch("1\n2\n", "1\n"),
+            "first mismatch at line 2: expected `2`, got end of output"
+        );
+        assert_eq!(
+            first_mismatch("1\n", "1\n\n2\n"),
+            "first mismatch at line 3: expected end of output, got `2`"
+        );
+        assert_eq!(
+            first_mismatch("1\n", "1\r\n"),
+            "outputs differ only in whitespace or line endings"
+        );
+    }
+
+    #[test]
+    fn test_load_compare_mode() {
+        let work_dir = tempfile::tempdir().expect("");
+        assert_eq!(load_compare_mode(work_dir.path(), "a").unwrap(), None);
+
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\n\n[package.metadata.compare]\na = \"token\"\nb = \"float\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_compare_mode(work_dir.path(), "a").unwrap(),
+            Some(CompareMode::Token)
+        );
+        assert!(load_compare_mode(work_dir.path(), "b").is_err());
+        assert_eq!(load_compare_mode(work_dir.path(), "c").unwrap(), None);
+    }
+}
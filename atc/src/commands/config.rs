@@ -1,27 +1,567 @@
 /// AtCodeのURL情報
 pub const BASE_URL: &str = "https://atcoder.jp";
 
+use serde::{Deserialize, Serialize};
 use std::env;
-use std::path::PathBuf;
-/// セッションファイルの保存先を取得
-pub fn get_session_file() -> PathBuf {
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::cargo_config::LINKERS;
+use super::testcases::TESTCASE_ARCHIVE_URL;
+/// atc の設定・キャッシュファイルを保存するディレクトリを取得
+pub fn get_config_dir() -> PathBuf {
     if cfg!(target_os = "windows") {
-        dirs::config_dir().unwrap().join("atc/session.json")
+        dirs::config_dir().unwrap().join("atc")
     } else if cfg!(target_os = "macos") {
-        dirs::data_local_dir().unwrap().join("atc/session.json")
+        dirs::data_local_dir().unwrap().join("atc")
     } else {
         if let Some(xdg_cache) = env::var_os("XDG_CACHE_HOME") {
-            return PathBuf::from(xdg_cache).join("atc/session.json");
+            return PathBuf::from(xdg_cache).join("atc");
+        }
+        PathBuf::from(env::var_os("HOME").unwrap()).join(".cache/atc")
+    }
+}
+
+/// セッションファイルの保存先を取得
+pub fn get_session_file() -> PathBuf {
+    get_config_dir().join("session.json")
+}
+
+/// ジャッジ環境情報(提出言語IDなど)の保存先を取得
+pub fn get_environment_file() -> PathBuf {
+    get_config_dir().join("environment.json")
+}
+
+/// 提出可能な言語一覧のキャッシュの保存先を取得
+pub fn get_languages_file() -> PathBuf {
+    get_config_dir().join("languages.json")
+}
+
+/// HTML のキャッシュの保存先を取得
+pub fn get_cache_dir() -> PathBuf {
+    get_config_dir().join("cache")
+}
+
+/// 問題ごとの取り組み記録の保存先を取得
+pub fn get_stats_file() -> PathBuf {
+    get_config_dir().join("stats.json")
+}
+
+/// ユーザー設定ファイルの保存先を取得
+///
+/// キャッシュとは分けて OS の設定ディレクトリ(Linux では `$XDG_CONFIG_HOME/atc` または `~/.config/atc`)に保存する。
+/// 以前の保存先(`get_config_dir`)にのみ設定ファイルが存在する場合は、そのファイルを引き続き利用する。
+pub fn get_user_config_file() -> PathBuf {
+    let legacy = get_config_dir().join("config.toml");
+    match dirs::config_dir() {
+        Some(dir) => {
+            let path = dir.join("atc").join("config.toml");
+            if !path.exists() && legacy.exists() {
+                legacy
+            } else {
+                path
+            }
         }
-        PathBuf::from(env::var_os("HOME").unwrap()).join(".cache/atc/session.json")
+        None => legacy,
+    }
+}
+
+/// AtCoder へのリクエストの最小間隔の既定値 (ms)
+pub const DEFAULT_REQUEST_INTERVAL: u64 = 200;
+
+/// `config` コマンドで読み書きできる設定項目
+///
+/// コンテストごとの設定(`[package.metadata.format]`、`[package.metadata.remote]` など)は
+/// コンテストの `Cargo.toml` に記述するため含まない。
+/// `remote_host`・`remote_dir` は `[package.metadata.remote]` が未設定の場合に利用する。
+pub const CONFIG_KEYS: [&str; 22] = [
+    "archive_dir",
+    "base_url",
+    "cargo_config",
+    "cargo_linker",
+    "cargo_target_dir",
+    "contests_dir",
+    "fail_fast",
+    "fetch_retries",
+    "git_init",
+    "language_id",
+    "proxy",
+    "rating",
+    "remote_dir",
+    "remote_host",
+    "request_interval",
+    "rust_toolchain",
+    "sample_lang",
+    "template",
+    "template_dir",
+    "testcase_url",
+    "timeout_margin",
+    "timeout_multiplier",
+];
+
+/// ユーザー設定 (`config.toml`)
+///
+/// 未設定の項目は `None` となり、各コマンドの既定値が利用される。
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct UserConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_dir: Option<String>, // アーカイブ先ディレクトリ (相対パスは作業ディレクトリ基準)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>, // AtCoder のベース URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cargo_config: Option<bool>, // `download` で `.cargo/config.toml` を作成するか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cargo_linker: Option<String>, // `.cargo/config.toml` に設定するリンカ (`lld` または `mold`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cargo_target_dir: Option<String>, // `.cargo/config.toml` に設定するビルド成果物の格納先 (`~/` はホームディレクトリ基準)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contests_dir: Option<String>, // `download` でコンテストを作成するディレクトリ (`~/` はホームディレクトリ基準)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fail_fast: Option<bool>, // `test` で AC 以外の結果が出た時点で残りのテストケースを実行しないか (`--keep-going` で無効にできる)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_retries: Option<u32>, // ページ取得の最大試行回数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_init: Option<bool>, // `download` でコンテストディレクトリを Git リポジトリとして初期化するか
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language_id: Option<String>, // 提出言語 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>, // HTTP リクエストに利用するプロキシの URL (未設定の場合は環境変数 `HTTP_PROXY`・`HTTPS_PROXY` に従う)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u64>, // 自分のレート (`contests --next` の対象判定に利用)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_dir: Option<String>, // `--remote` でコンテストを同期するリモート側のディレクトリ (コンテストディレクトリ名を付け加えて利用する)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_host: Option<String>, // `--remote` の ssh の接続先
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_interval: Option<u64>, // AtCoder へのリクエストの最小間隔 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rust_toolchain: Option<String>, // `download` で作成する rust-toolchain.toml の channel (`none` で作成しない)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_lang: Option<String>, // サンプルを取得する問題文の言語 (`en` または `ja`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>, // 既定のテンプレートプロファイル (`template use` で切り替える)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_dir: Option<String>, // テンプレートディレクトリ (相対パスは作業ディレクトリ基準、`~/` はホームディレクトリ基準)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub testcase_url: Option<String>, // 公式のテストケースを公開している共有フォルダの URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_margin: Option<u64>, // 実行時間制限に加算する猶予 (ms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_multiplier: Option<f64>, // 実行時間制限に掛ける倍率 (`timeout_margin` の加算前に適用する)
+}
+
+impl UserConfig {
+    /// 設定ファイルを読み込む。ファイルが存在しない場合は既定値を返す
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(UserConfig::default());
+        }
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// 設定ファイルを保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// 設定項目の値を文字列で取得する
+    ///
+    /// # エラーの可能性
+    /// - `key` が `CONFIG_KEYS` に含まれない場合
+    pub fn get(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(match key {
+            "archive_dir" => self.archive_dir.clone(),
+            "base_url" => self.base_url.clone(),
+            "cargo_config" => self.cargo_config.map(|enabled| enabled.to_string()),
+            "cargo_linker" => self.cargo_linker.clone(),
+            "cargo_target_dir" => self.cargo_target_dir.clone(),
+            "contests_dir" => self.contests_dir.clone(),
+            "fail_fast" => self.fail_fast.map(|enabled| enabled.to_string()),
+            "fetch_retries" => self.fetch_retries.map(|retries| retries.to_string()),
+            "git_init" => self.git_init.map(|enabled| enabled.to_string()),
+            "language_id" => self.language_id.clone(),
+            "proxy" => self.proxy.clone(),
+            "rating" => self.rating.map(|rating| rating.to_string()),
+            "remote_dir" => self.remote_dir.clone(),
+            "remote_host" => self.remote_host.clone(),
+            "request_interval" => self.request_interval.map(|interval| interval.to_string()),
+            "rust_toolchain" => self.rust_toolchain.clone(),
+            "sample_lang" => self.sample_lang.clone(),
+            "template" => self.template.clone(),
+            "template_dir" => self.template_dir.clone(),
+            "testcase_url" => self.testcase_url.clone(),
+            "timeout_margin" => self.timeout_margin.map(|margin| margin.to_string()),
+            "timeout_multiplier" => self
+                .timeout_multiplier
+                .map(|multiplier| multiplier.to_string()),
+            _ => return Err(unknown_key(key)),
+        })
+    }
+
+    /// 設定項目に値を設定する。空文字列を指定した場合は設定を削除する
+    ///
+    /// # エラーの可能性
+    /// - `key` が `CONFIG_KEYS` に含まれない場合
+    /// - `fetch_retries`、`rating`、`request_interval`、`timeout_margin` に整数以外を指定した場合
+    /// - `timeout_multiplier` に正の数以外を指定した場合
+    /// - `cargo_config`・`fail_fast`・`git_init` に `true`・`false` 以外を指定した場合
+    /// - `cargo_linker` に `lld`・`mold` 以外を指定した場合
+    /// - `sample_lang` に `en`・`ja` 以外を指定した場合
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        let value = Some(value.to_string()).filter(|v| !v.is_empty());
+        match key {
+            "archive_dir" => self.archive_dir = value,
+            "base_url" => self.base_url = value.map(|v| v.trim_end_matches('/').to_string()),
+            "cargo_config" => {
+                self.cargo_config = value
+                    .map(|v| v.parse::<bool>())
+                    .transpose()
+                    .map_err(|_| "cargo_config には true または false を指定してください")?
+            }
+            "cargo_linker" => {
+                if value.as_deref().is_some_and(|v| !LINKERS.contains(&v)) {
+                    return Err(format!(
+                        "cargo_linker には {} のいずれかを指定してください",
+                        LINKERS.join(", ")
+                    )
+                    .into());
+                }
+                self.cargo_linker = value
+            }
+            "cargo_target_dir" => self.cargo_target_dir = value,
+            "contests_dir" => self.contests_dir = value,
+            "fail_fast" => {
+                self.fail_fast = value
+                    .map(|v| v.parse::<bool>())
+                    .transpose()
+                    .map_err(|_| "fail_fast には true または false を指定してください")?
+            }
+            "fetch_retries" => {
+                self.fetch_retries = value
+                    .map(|v| v.parse::<u32>())
+                    .transpose()
+                    .map_err(|_| "fetch_retries には整数を指定してください")?
+            }
+            "git_init" => {
+                self.git_init = value
+                    .map(|v| v.parse::<bool>())
+                    .transpose()
+                    .map_err(|_| "git_init には true または false を指定してください")?
+            }
+            "language_id" => self.language_id = value,
+            "proxy" => self.proxy = value,
+            "rating" => {
+                self.rating = value
+                    .map(|v| v.parse::<u64>())
+                    .transpose()
+                    .map_err(|_| "rating には整数を指定してください")?
+            }
+            "remote_dir" => self.remote_dir = value.map(|v| v.trim_end_matches('/').to_string()),
+            "remote_host" => self.remote_host = value,
+            "request_interval" => {
+                self.request_interval = value
+                    .map(|v| v.parse::<u64>())
+                    .transpose()
+                    .map_err(|_| "request_interval にはミリ秒単位の整数を指定してください")?
+            }
+            "rust_toolchain" => self.rust_toolchain = value,
+            "sample_lang" => {
+                if value.as_deref().is_some_and(|v| v != "en" && v != "ja") {
+                    return Err("sample_lang には en または ja を指定してください".into());
+                }
+                self.sample_lang = value
+            }
+            "template" => self.template = value,
+            "template_dir" => self.template_dir = value,
+            "testcase_url" => {
+                self.testcase_url = value.map(|v| v.trim_end_matches('/').to_string())
+            }
+            "timeout_margin" => {
+                self.timeout_margin = value
+                    .map(|v| v.parse::<u64>())
+                    .transpose()
+                    .map_err(|_| "timeout_margin にはミリ秒単位の整数を指定してください")?
+            }
+            "timeout_multiplier" => {
+                let invalid = "timeout_multiplier には正の数 (2.0 など) を指定してください";
+                let multiplier = value
+                    .map(|v| v.parse::<f64>())
+                    .transpose()
+                    .map_err(|_| invalid)?;
+                if multiplier.is_some_and(|m| !m.is_finite() || m <= 0.0) {
+                    return Err(invalid.into());
+                }
+                self.timeout_multiplier = multiplier
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+}
+
+fn unknown_key(key: &str) -> Box<dyn Error> {
+    format!(
+        "不明な設定項目です: {} (設定可能な項目: {})",
+        key,
+        CONFIG_KEYS.join(", ")
+    )
+    .into()
+}
+
+/// 読み込んだユーザー設定
+static USER_CONFIG: OnceLock<UserConfig> = OnceLock::new();
+
+/// ユーザー設定を返す。読み込みに失敗した場合は警告を表示して既定値を返す
+///
+/// 設定ファイルは最初の呼び出し時に 1 度だけ読み込み、以降は読み込んだ設定を返す。
+/// そのため、警告も 1 回の実行につき 1 度だけ表示する。
+pub fn load_user_config() -> &'static UserConfig {
+    USER_CONFIG.get_or_init(|| {
+        let path = get_user_config_file();
+        UserConfig::load(&path).unwrap_or_else(|e| {
+            eprintln!(
+                "Warning: 設定ファイル {} を読み込めないため、既定値を利用します: {}",
+                path.display(),
+                e
+            );
+            UserConfig::default()
+        })
+    })
+}
+
+/// AtCoder のベース URL を取得 (`base_url` が未設定の場合は `BASE_URL`)
+pub fn get_base_url() -> String {
+    load_user_config()
+        .base_url
+        .clone()
+        .unwrap_or_else(|| BASE_URL.to_string())
+}
+
+/// テンプレートディレクトリを取得 (`template_dir` が未設定の場合は `work_dir/templates`)
+///
+/// `template_dir` が `~/` で始まる場合はホームディレクトリからの相対パスとして扱う。
+pub fn get_template_dir(work_dir: &Path) -> PathBuf {
+    match &load_user_config().template_dir {
+        Some(dir) => expand_home(work_dir, dir),
+        None => work_dir.join("templates"),
+    }
+}
+
+/// `~/` で始まるパスをホームディレクトリ基準、それ以外を `base_dir` 基準のパスに変換する
+fn expand_home(base_dir: &Path, path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => base_dir.join(path),
+    }
+}
+
+/// `download` でコンテストを作成するディレクトリを取得 (`contests_dir` が未設定の場合は `work_dir`)
+///
+/// `contests_dir` が `~/` で始まる場合はホームディレクトリからの相対パスとして扱う。
+pub fn get_contests_dir(work_dir: &Path) -> PathBuf {
+    match &load_user_config().contests_dir {
+        Some(dir) => expand_home(work_dir, dir),
+        None => work_dir.to_path_buf(),
+    }
+}
+
+/// アーカイブ先ディレクトリを取得 (`archive_dir` が未設定の場合は `work_dir/archive`)
+pub fn get_archive_dir(work_dir: &Path) -> PathBuf {
+    match &load_user_config().archive_dir {
+        Some(dir) => work_dir.join(dir),
+        None => work_dir.join("archive"),
     }
 }
 
+/// 自分のレートを取得 (未設定の場合は `None`)
+pub fn get_rating() -> Option<u64> {
+    load_user_config().rating
+}
+
+/// ページ取得の最大試行回数を取得 (`fetch_retries` が未設定の場合は 3、0 の場合は 1)
+pub fn get_fetch_retries() -> u32 {
+    load_user_config().fetch_retries.unwrap_or(3).max(1)
+}
+
+/// AtCoder へのリクエストの最小間隔を取得 (`request_interval` が未設定の場合は `DEFAULT_REQUEST_INTERVAL`)
+pub fn get_request_interval() -> Duration {
+    Duration::from_millis(
+        load_user_config()
+            .request_interval
+            .unwrap_or(DEFAULT_REQUEST_INTERVAL),
+    )
+}
+
+/// `test` で最初の失敗で打ち切るかを取得 (`fail_fast` が未設定の場合は `false`)
+pub fn get_fail_fast() -> bool {
+    load_user_config().fail_fast.unwrap_or(false)
+}
+
+/// `download` で Git リポジトリを初期化するかを取得 (`git_init` が未設定の場合は `true`)
+pub fn get_git_init() -> bool {
+    load_user_config().git_init.unwrap_or(true)
+}
+
+/// `download` で `.cargo/config.toml` を作成するかを取得 (`cargo_config` が未設定の場合は `false`)
+pub fn get_cargo_config() -> bool {
+    load_user_config().cargo_config.unwrap_or(false)
+}
+
+/// `.cargo/config.toml` に設定するビルド成果物の格納先を取得 (`cargo_target_dir` が未設定の場合は `None`)
+///
+/// `cargo_target_dir` が `~/` で始まる場合はホームディレクトリからの相対パスとして扱う。
+pub fn get_cargo_target_dir(work_dir: &Path) -> Option<PathBuf> {
+    load_user_config()
+        .cargo_target_dir
+        .as_ref()
+        .map(|dir| expand_home(work_dir, dir))
+}
+
+/// サンプルを優先して取得する問題文の言語を取得 (`sample_lang` が未設定の場合は `en`)
+pub fn get_sample_lang() -> String {
+    load_user_config()
+        .sample_lang
+        .clone()
+        .unwrap_or_else(|| "en".to_string())
+}
+
+/// 公式のテストケースを公開している共有フォルダの URL を取得 (`testcase_url` が未設定の場合は `TESTCASE_ARCHIVE_URL`)
+pub fn get_testcase_url() -> String {
+    load_user_config()
+        .testcase_url
+        .clone()
+        .unwrap_or_else(|| TESTCASE_ARCHIVE_URL.to_string())
+}
+
+/// 実行時間制限に加算する猶予 (ms) を取得
+pub fn get_timeout_margin() -> u64 {
+    load_user_config().timeout_margin.unwrap_or(0)
+}
+
+/// 実行時間制限に掛ける倍率を取得 (`timeout_multiplier` が未設定の場合は `1.0`)
+pub fn get_timeout_multiplier() -> f64 {
+    load_user_config().timeout_multiplier.unwrap_or(1.0)
+}
+
+/// `config get` のエントリーポイント
+pub fn execute_get(key: &str) -> Result<(), Box<dyn Error>> {
+    match UserConfig::load(&get_user_config_file())?.get(key)? {
+        Some(value) => println!("{}", value),
+        None => println!("{} is not set", key),
+    }
+    Ok(())
+}
+
+/// `config set` のエントリーポイント
+pub fn execute_set(key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    let path = get_user_config_file();
+    let mut config = UserConfig::load(&path)?;
+    config.set(key, value)?;
+    config.save(&path)?;
+    Ok(())
+}
+
+/// `config list` のエントリーポイント
+pub fn execute_list() -> Result<(), Box<dyn Error>> {
+    let config = UserConfig::load(&get_user_config_file())?;
+    for key in CONFIG_KEYS {
+        println!(
+            "{} = {}",
+            key,
+            config.get(key)?.unwrap_or_else(|| "(not set)".to_string())
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use serial_test::serial;
 
+    #[test]
+    fn test_expand_home() {
+        let base_dir = Path::new("/work");
+        assert_eq!(
+            expand_home(base_dir, "templates"),
+            PathBuf::from("/work/templates")
+        );
+        assert_eq!(
+            expand_home(base_dir, "/opt/templates"),
+            PathBuf::from("/opt/templates")
+        );
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(
+                expand_home(base_dir, "~/.config/atc/templates"),
+                home.join(".config/atc/templates")
+            );
+        }
+    }
+
+    #[test]
+    fn test_user_config_get_set() {
+        let config_dir = tempfile::tempdir().expect("");
+        let config_path = config_dir.path().join("config.toml");
+        let mut config = UserConfig::load(&config_path).unwrap();
+        assert_eq!(config, UserConfig::default());
+
+        config.set("base_url", "http://localhost:8080/").unwrap();
+        config.set("timeout_margin", "500").unwrap();
+        assert!(config.set("timeout_margin", "fast").is_err());
+        config.set("timeout_multiplier", "2.5").unwrap();
+        assert!(config.set("timeout_multiplier", "0").is_err());
+        assert!(config.set("timeout_multiplier", "slow").is_err());
+        config.set("sample_lang", "ja").unwrap();
+        assert!(config.set("sample_lang", "fr").is_err());
+        config.set("cargo_config", "true").unwrap();
+        config.set("fail_fast", "true").unwrap();
+        assert!(config.set("fail_fast", "yes").is_err());
+        config.set("cargo_linker", "mold").unwrap();
+        assert!(config.set("cargo_linker", "gold").is_err());
+        config.set("remote_dir", "~/atcoder/").unwrap();
+        assert!(config.set("unknown", "value").is_err());
+        config.save(&config_path).unwrap();
+
+        let config = UserConfig::load(&config_path).unwrap();
+        assert_eq!(
+            config.get("base_url").unwrap(),
+            Some("http://localhost:8080".to_string())
+        );
+        assert_eq!(
+            config.get("timeout_margin").unwrap(),
+            Some("500".to_string())
+        );
+        assert_eq!(
+            config.get("timeout_multiplier").unwrap(),
+            Some("2.5".to_string())
+        );
+        assert_eq!(config.get("language_id").unwrap(), None);
+        assert_eq!(
+            config.get("cargo_config").unwrap(),
+            Some("true".to_string())
+        );
+        assert_eq!(
+            config.get("cargo_linker").unwrap(),
+            Some("mold".to_string())
+        );
+        assert_eq!(config.get("fail_fast").unwrap(), Some("true".to_string()));
+        assert_eq!(
+            config.get("remote_dir").unwrap(),
+            Some("~/atcoder".to_string())
+        );
+
+        let mut config = config;
+        config.set("timeout_margin", "").unwrap();
+        assert_eq!(config.timeout_margin, None);
+    }
+
     #[test]
     fn test_get_session_file_default() {
         let session_path = get_session_file();
@@ -79,4 +619,30 @@ mod test {
             }
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_get_user_config_file_with_xdg_config_home() {
+        if cfg!(target_os = "linux") {
+            let temp_dir = tempfile::tempdir().expect("");
+            let xdg_config = temp_dir.path().join("config");
+            let xdg_cache = temp_dir.path().join("cache");
+            env::set_var("XDG_CONFIG_HOME", &xdg_config);
+            env::set_var("XDG_CACHE_HOME", &xdg_cache);
+            assert_eq!(get_user_config_file(), xdg_config.join("atc/config.toml"));
+
+            // 以前の保存先にのみ存在する場合はそのファイルを利用する
+            fs::create_dir_all(xdg_cache.join("atc")).unwrap();
+            fs::write(xdg_cache.join("atc/config.toml"), "").unwrap();
+            assert_eq!(get_user_config_file(), xdg_cache.join("atc/config.toml"));
+
+            fs::create_dir_all(xdg_config.join("atc")).unwrap();
+            fs::write(xdg_config.join("atc/config.toml"), "").unwrap();
+            assert_eq!(get_user_config_file(), xdg_config.join("atc/config.toml"));
+
+            // 環境変数をリセット
+            env::remove_var("XDG_CONFIG_HOME");
+            env::remove_var("XDG_CACHE_HOME");
+        }
+    }
 }
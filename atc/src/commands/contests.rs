@@ -0,0 +1,333 @@
+//! 予定されたコンテストの一覧を表示するモジュール
+//!
+//! コンテスト一覧ページ(`/contests/`)の「予定されたコンテスト」の表から、
+//! 開始時刻(ローカルタイムゾーンに変換)・コンテスト時間・Rated 対象範囲を取得して表示する。
+//!
+//! `next` を指定した場合は、`config` の `rating` に設定したレートが Rated 対象範囲に含まれる
+//! 直近のコンテストのみを表示する。`rating` が未設定の場合は直近の Rated コンテストを表示する。
+use chrono::{DateTime, FixedOffset, Local};
+use scraper::{ElementRef, Html, Selector};
+use std::error::Error;
+
+use super::config::{get_base_url, get_rating};
+use super::http::{client, throttle};
+
+/// 予定されたコンテスト一覧表示処理のエントリーポイント
+///
+/// # 引数
+/// - `next`: `true` の場合、自分が Rated 対象となる直近のコンテストのみを表示する
+pub async fn execute(next: bool) -> Result<(), Box<dyn Error>> {
+    let url = format!("{}/contests/", get_base_url());
+    throttle(&url).await;
+    let html = client()?
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let contests = parse_upcoming_contests(&Html::parse_document(&html));
+
+    if next {
+        let rating = get_rating();
+        let contest = find_next_rated(&contests, rating).ok_or_else(|| match rating {
+            Some(rating) => format!(
+                "レート {} が Rated 対象となる予定のコンテストはありません",
+                rating
+            ),
+            None => "予定された Rated コンテストはありません".to_string(),
+        })?;
+        print_contest(contest);
+        return Ok(());
+    }
+
+    if contests.is_empty() {
+        println!("No upcoming contests");
+    }
+    for contest in &contests {
+        print_contest(contest);
+    }
+    Ok(())
+}
+
+/// 予定されたコンテスト
+#[derive(Debug, PartialEq)]
+pub struct UpcomingContest {
+    pub name: String,                      // コンテスト名 (`abc389` など)
+    pub title: String,                     // コンテストの表示名
+    pub start_time: DateTime<FixedOffset>, // 開始時刻
+    pub duration: String,                  // コンテスト時間 (`01:40` など)
+    pub rated_range: RatedRange,           // Rated 対象範囲
+}
+
+/// Rated 対象範囲
+#[derive(Debug, PartialEq)]
+pub enum RatedRange {
+    /// Unrated
+    Unrated,
+    /// 下限・上限を含む範囲 (`None` は制限なし)
+    Range(Option<u64>, Option<u64>),
+}
+
+impl RatedRange {
+    /// 一覧ページの表記 (`- 1999`、`1200 - 2799`、`All`、`-`) から Rated 対象範囲を生成する
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        if text == "All" {
+            return RatedRange::Range(None, None);
+        }
+        let Some((lower, upper)) = text.split_once('-') else {
+            return RatedRange::Unrated;
+        };
+        let bound = |s: &str| s.trim().parse::<u64>().ok();
+        match (bound(lower), bound(upper)) {
+            (None, None) => RatedRange::Unrated,
+            (lower, upper) => RatedRange::Range(lower, upper),
+        }
+    }
+
+    /// 指定したレートが Rated 対象範囲に含まれるかを返す
+    ///
+    /// `rating` が `None` の場合は Rated コンテストであるかのみを判定する。
+    pub fn includes(&self, rating: Option<u64>) -> bool {
+        match (self, rating) {
+            (RatedRange::Unrated, _) => false,
+            (RatedRange::Range(..), None) => true,
+            (RatedRange::Range(lower, upper), Some(rating)) => {
+                lower.is_none_or(|lower| lower <= rating)
+                    && upper.is_none_or(|upper| rating <= upper)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RatedRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let range = match self {
+            RatedRange::Unrated => "Unrated".to_string(),
+            RatedRange::Range(None, None) => "All".to_string(),
+            RatedRange::Range(lower, upper) => format!(
+                "{} - {}",
+                lower.map(|l| l.to_string()).unwrap_or_default(),
+                upper.map(|u| u.to_string()).unwrap_or_default()
+            ),
+        };
+        // 一覧表示で幅を揃えられるように `pad` で出力する
+        f.pad(&range)
+    }
+}
+
+/// コンテスト一覧ページから予定されたコンテストを取得する
+pub fn parse_upcoming_contests(document: &Html) -> Vec<UpcomingContest> {
+    let row_selector = Selector::parse("#contest-table-upcoming tbody tr").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+    let link_selector = Selector::parse("a[href^=\"/contests/\"]").unwrap();
+
+    let text = |cell: &ElementRef| cell.text().collect::<String>().trim().to_string();
+    document
+        .select(&row_selector)
+        .filter_map(|row| {
+            let cells: Vec<ElementRef> = row.select(&cell_selector).collect();
+            if cells.len() < 4 {
+                return None;
+            }
+            let start_time =
+                DateTime::parse_from_str(&text(&cells[0]), "%Y-%m-%d %H:%M:%S%z").ok()?;
+            let link = cells[1].select(&link_selector).next()?;
+            Some(UpcomingContest {
+                name: link
+                    .value()
+                    .attr("href")?
+                    .trim_start_matches("/contests/")
+                    .to_string(),
+                title: text(&link),
+                start_time,
+                duration: text(&cells[2]),
+                rated_range: RatedRange::parse(&text(&cells[3])),
+            })
+        })
+        .collect()
+}
+
+/// 指定したレートが Rated 対象となる直近のコンテストを返す
+pub fn find_next_rated(
+    contests: &[UpcomingContest],
+    rating: Option<u64>,
+) -> Option<&UpcomingContest> {
+    contests
+        .iter()
+        .filter(|contest| contest.rated_range.includes(rating))
+        .min_by_key(|contest| contest.start_time)
+}
+
+/// コンテスト一覧ページに含まれるすべてのコンテスト名を返す (重複は除く)
+///
+/// 予定・開催中・最近のコンテストの表のリンク(`/contests/<コンテスト名>`)から取得する。
+pub fn parse_contest_names(document: &Html) -> Vec<String> {
+    let link_selector = Selector::parse("a[href^=\"/contests/\"]").unwrap();
+    let mut names: Vec<String> = Vec::new();
+    for link in document.select(&link_selector) {
+        let Some(name) = link.value().attr("href").and_then(|href| {
+            href.trim_start_matches("/contests/")
+                .split(['/', '?'])
+                .next()
+        }) else {
+            continue;
+        };
+        if !name.is_empty() && name != "archive" && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// `target` に近いコンテスト名を、近い順に最大 3 件返す
+///
+/// 編集距離が 2 以下のものを近いコンテスト名とする。
+pub fn similar_contest_names<'a>(names: &'a [String], target: &str) -> Vec<&'a str> {
+    let mut candidates: Vec<(usize, &str)> = names
+        .iter()
+        .map(|name| (edit_distance(name, target), name.as_str()))
+        .filter(|(distance, _)| *distance <= 2)
+        .collect();
+    candidates.sort();
+    candidates
+        .into_iter()
+        .take(3)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+/// 2 つの文字列の編集距離 (レーベンシュタイン距離) を返す
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// コンテストの情報を 1 行で表示する
+fn print_contest(contest: &UpcomingContest) {
+    println!(
+        "{}  {:<8} {:>5}  {:<12} {}",
+        contest
+            .start_time
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M"),
+        contest.name,
+        contest.duration,
+        contest.rated_range,
+        contest.title
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CONTESTS_HTML: &str = r##"
+        <div id="contest-table-upcoming">
+          <table><tbody>
+            <tr>
+              <td><a href="#"><time class="fixtime fixtime-full">2025-01-25 21:00:00+0900</time></a></td>
+              <td><span>Ⓐ</span> <a href="/contests/arc191">AtCoder Regular Contest 191</a></td>
+              <td>02:00</td>
+              <td>1200 - 2799</td>
+            </tr>
+            <tr>
+              <td><a href="#"><time class="fixtime fixtime-full">2025-01-18 21:00:00+0900</time></a></td>
+              <td><span>Ⓐ</span> <a href="/contests/abc389">AtCoder Beginner Contest 389</a></td>
+              <td>01:40</td>
+              <td> - 1999</td>
+            </tr>
+            <tr>
+              <td><a href="#"><time class="fixtime fixtime-full">2025-01-12 13:00:00+0900</time></a></td>
+              <td><a href="/contests/practice2">Practice Contest</a></td>
+              <td>120:00</td>
+              <td> - </td>
+            </tr>
+          </tbody></table>
+        </div>
+        <div id="contest-table-recent">
+          <table><tbody>
+            <tr>
+              <td><time>2025-01-11 21:00:00+0900</time></td>
+              <td><a href="/contests/abc388">AtCoder Beginner Contest 388</a></td>
+              <td>01:40</td>
+              <td> - 1999</td>
+            </tr>
+          </tbody></table>
+        </div>
+    "##;
+
+    #[test]
+    fn test_parse_upcoming_contests() {
+        let contests = parse_upcoming_contests(&Html::parse_document(CONTESTS_HTML));
+        assert_eq!(contests.len(), 3);
+        assert_eq!(contests[1].name, "abc389");
+        assert_eq!(contests[1].title, "AtCoder Beginner Contest 389");
+        assert_eq!(contests[1].duration, "01:40");
+        assert_eq!(contests[1].rated_range, RatedRange::Range(None, Some(1999)));
+        assert_eq!(
+            contests[1].start_time.to_rfc3339(),
+            "2025-01-18T21:00:00+09:00"
+        );
+        assert_eq!(contests[2].rated_range, RatedRange::Unrated);
+    }
+
+    #[test]
+    fn test_parse_contest_names() {
+        let names = parse_contest_names(&Html::parse_document(CONTESTS_HTML));
+        assert_eq!(names, vec!["arc191", "abc389", "practice2", "abc388"]);
+    }
+
+    #[test]
+    fn test_similar_contest_names() {
+        let names: Vec<String> = ["abc388", "abc389", "arc191", "practice2"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        assert_eq!(
+            similar_contest_names(&names, "abc387"),
+            vec!["abc388", "abc389"]
+        );
+        assert_eq!(similar_contest_names(&names, "arc19"), vec!["arc191"]);
+        assert!(similar_contest_names(&names, "abc9999").is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_rated_range() {
+        assert_eq!(RatedRange::parse("All"), RatedRange::Range(None, None));
+        assert_eq!(
+            RatedRange::parse("1200 - "),
+            RatedRange::Range(Some(1200), None)
+        );
+        assert!(RatedRange::parse(" - 1999").includes(Some(1999)));
+        assert!(!RatedRange::parse(" - 1999").includes(Some(2000)));
+        assert!(!RatedRange::parse("-").includes(None));
+        assert_eq!(RatedRange::parse("1200 - 2799").to_string(), "1200 - 2799");
+    }
+
+    #[test]
+    fn test_find_next_rated() {
+        let contests = parse_upcoming_contests(&Html::parse_document(CONTESTS_HTML));
+        assert_eq!(find_next_rated(&contests, None).unwrap().name, "abc389");
+        assert_eq!(
+            find_next_rated(&contests, Some(2400)).unwrap().name,
+            "arc191"
+        );
+        assert!(find_next_rated(&contests, Some(3000)).is_none());
+    }
+}
@@ -0,0 +1,223 @@
+//! 問題の `main.rs` とテンプレートの差分を表示するモジュール
+//!
+//! コンテストディレクトリで実行し、`<problem>/main.rs` と `templates/main.rs` の差分を
+//! unified diff 形式(前後 3 行を表示)で出力する。
+//! 標準出力が端末の場合は、削除行を赤、追加行を緑で表示する(環境変数 `NO_COLOR` が設定されている場合を除く)。
+//!
+//! テンプレートはコンテストディレクトリの親ディレクトリ(`download` を実行したディレクトリ)から探し、
+//! 既定のテンプレートプロファイル(`template use` で切り替える)の `main.rs` と比較する。
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+use super::download::read_template_main_rs;
+use super::template::resolve_contest_template_dir;
+use super::test::find_problem_directory;
+
+/// 差分の前後に表示する行数
+const CONTEXT_LINES: usize = 3;
+
+/// 差分表示処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリ
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+///
+/// # エラーの可能性
+/// - 問題ディレクトリ、`main.rs` が存在しない場合
+///   (テンプレートの `main.rs` が存在しない場合は組み込みのテンプレートと比較する)
+pub fn execute(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let main_rs_path = find_problem_directory(work_dir, problem_name)?.join("main.rs");
+    let root_dir = work_dir
+        .parent()
+        .ok_or("コンテストディレクトリの親ディレクトリを取得できません")?;
+    let contest_name = work_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let template_dir = resolve_contest_template_dir(root_dir, None, &contest_name)?;
+    let template_path = template_dir.join("main.rs");
+
+    let read = |path: &PathBuf| {
+        fs::read_to_string(path).map_err(|e| format!("{} を読み込めません: {}", path.display(), e))
+    };
+    let template = read_template_main_rs(&template_dir)?;
+    let current = read(&main_rs_path)?;
+
+    let lines = unified_diff(&diff_lines(&template, &current), CONTEXT_LINES);
+    if lines.is_empty() {
+        println!("No changes from the template");
+        return Ok(());
+    }
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    println!("--- {}", template_path.display());
+    println!("+++ {}", main_rs_path.display());
+    for line in lines {
+        match (color, line.chars().next()) {
+            (true, Some('-')) => println!("\x1b[31m{}\x1b[0m", line),
+            (true, Some('+')) => println!("\x1b[32m{}\x1b[0m", line),
+            (true, Some('@')) => println!("\x1b[36m{}\x1b[0m", line),
+            _ => println!("{}", line),
+        }
+    }
+    Ok(())
+}
+
+/// 行単位の差分
+#[derive(Debug, PartialEq)]
+pub enum DiffLine<'a> {
+    /// 両方に存在する行
+    Same(&'a str),
+    /// テンプレートにのみ存在する行
+    Removed(&'a str),
+    /// 問題の `main.rs` にのみ存在する行
+    Added(&'a str),
+}
+
+/// 最長共通部分列を基に 2 つの文字列の行単位の差分を計算する
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j]: old[i..] と new[j..] の最長共通部分列の長さ
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+/// 差分を unified diff 形式の行に変換する。差分がない場合は空を返す
+///
+/// # 引数
+/// - `diff`: `diff_lines` の結果
+/// - `context`: 変更行の前後に表示する行数
+pub fn unified_diff(diff: &[DiffLine], context: usize) -> Vec<String> {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Same(_)))
+        .map(|(k, _)| k)
+        .collect();
+    let mut output = Vec::new();
+    let mut k = 0;
+    while k < changed.len() {
+        // 前後の表示範囲が重なる変更は 1 つのハンクにまとめる
+        let start = changed[k].saturating_sub(context);
+        let mut last = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] <= last + 2 * context + 1 {
+            k += 1;
+            last = changed[k];
+        }
+        let end = (last + context + 1).min(diff.len());
+        k += 1;
+
+        // ハンクの開始行番号 (1 始まり) は、ハンクより前の行数から求める
+        let count_old = |range: &[DiffLine]| {
+            range
+                .iter()
+                .filter(|line| !matches!(line, DiffLine::Added(_)))
+                .count()
+        };
+        let count_new = |range: &[DiffLine]| {
+            range
+                .iter()
+                .filter(|line| !matches!(line, DiffLine::Removed(_)))
+                .count()
+        };
+        let hunk = &diff[start..end];
+        output.push(format!(
+            "@@ -{},{} +{},{} @@",
+            count_old(&diff[..start]) + 1,
+            count_old(hunk),
+            count_new(&diff[..start]) + 1,
+            count_new(hunk)
+        ));
+        output.extend(hunk.iter().map(|line| match line {
+            DiffLine::Same(text) => format!(" {}", text),
+            DiffLine::Removed(text) => format!("-{}", text),
+            DiffLine::Added(text) => format!("+{}", text),
+        }));
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("x"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ]
+        );
+        assert!(unified_diff(&diff_lines("a\nb\n", "a\nb\n"), CONTEXT_LINES).is_empty());
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+        let new = "1\n2\n3\n4\n5\nfive\n6\n7\n8\n9\n10\n11\n";
+        let lines = unified_diff(&diff_lines(old, new), 1);
+        assert_eq!(
+            lines,
+            vec![
+                "@@ -5,2 +5,3 @@",
+                " 5",
+                "+five",
+                " 6",
+                "@@ -11,2 +12,1 @@",
+                " 11",
+                "-12",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute() {
+        let root_dir = tempfile::tempdir().expect("");
+        let work_dir = root_dir.path().join("abc388");
+        fs::create_dir_all(work_dir.join("a")).unwrap();
+        assert!(execute(&work_dir, "a").is_err());
+
+        fs::create_dir_all(root_dir.path().join("templates")).unwrap();
+        fs::write(root_dir.path().join("templates/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(work_dir.join("a/main.rs"), "fn main() {\n}\n").unwrap();
+        assert!(execute(&work_dir, "a").is_ok());
+    }
+}
@@ -0,0 +1,343 @@
+//! 実行環境を診断するモジュール
+//!
+//! 以下の項目を順に確認し、問題がある項目には対処方法を表示する。
+//! - 設定ディレクトリへの書き込み可否
+//! - テンプレート(`main.rs` / `Cargo.toml`)の有無
+//! - ローカルの `rustc` / `cargo` のバージョンとジャッジの `rustc` のバージョンの差異
+//! - AtCoder への接続可否
+//! - 保存されているセッションの有効性
+//!
+//! ジャッジの `rustc` のバージョンは `migrate-env` で検出した値(`environment.json`)を利用し、
+//! 未検出の場合は `migrate::DEFAULT_JUDGE_RUSTC_VERSION` とする。
+use reqwest::Client;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    path::Path,
+    process::Command,
+};
+
+use super::config::{get_base_url, get_config_dir, get_session_file};
+use super::http::client;
+use super::login::Session;
+use super::migrate::get_judge_rustc_version;
+use super::template::resolve_template_dir;
+use super::whoami::fetch_username;
+
+/// 環境診断処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `templates` ディレクトリが存在する作業ディレクトリ
+///
+/// # エラーの可能性
+/// - 失敗した診断項目が 1 つ以上存在する場合
+pub async fn execute(work_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let base_url = get_base_url();
+    let client = client()?;
+    let judge_version = get_judge_rustc_version();
+
+    let mut results = vec![
+        check_writable(&get_config_dir()),
+        match resolve_template_dir(work_dir, None) {
+            Ok(template_dir) => check_templates(&template_dir),
+            Err(e) => CheckResult::fail(
+                "templates",
+                e.to_string(),
+                "`cargo atc template use default` で既定のプロファイルに戻してください".to_string(),
+            ),
+        },
+    ];
+    for tool in ["rustc", "cargo"] {
+        results.push(check_toolchain(tool, tool_version(tool), &judge_version));
+    }
+    let reachable = check_network(&client, &base_url).await;
+    let network_ok = reachable.status != CheckStatus::Fail;
+    results.push(reachable);
+    if network_ok {
+        results.push(check_session(&client, &base_url).await);
+    }
+
+    for result in &results {
+        println!("{}", result);
+    }
+    let failed = results
+        .iter()
+        .filter(|result| result.status == CheckStatus::Fail)
+        .count();
+    if failed == 0 {
+        println!("\nNo problems found");
+        Ok(())
+    } else {
+        Err(format!("{} 件の診断項目で問題が見つかりました", failed).into())
+    }
+}
+
+/// 診断結果の状態
+#[derive(Debug, PartialEq)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// 1 つの診断項目の結果
+#[derive(Debug)]
+pub struct CheckResult {
+    pub name: &'static str,  // 診断項目名
+    pub status: CheckStatus, // 状態
+    pub message: String,     // 診断内容
+    pub fix: Option<String>, // 対処方法
+}
+
+impl CheckResult {
+    fn ok(name: &'static str, message: String) -> Self {
+        CheckResult {
+            name,
+            status: CheckStatus::Ok,
+            message,
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, message: String, fix: String) -> Self {
+        CheckResult {
+            name,
+            status: CheckStatus::Warn,
+            message,
+            fix: Some(fix),
+        }
+    }
+
+    fn fail(name: &'static str, message: String, fix: String) -> Self {
+        CheckResult {
+            name,
+            status: CheckStatus::Fail,
+            message,
+            fix: Some(fix),
+        }
+    }
+}
+
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mark = match self.status {
+            CheckStatus::Ok => "[ OK ]",
+            CheckStatus::Warn => "[WARN]",
+            CheckStatus::Fail => "[FAIL]",
+        };
+        write!(f, "{} {:<10} {}", mark, self.name, self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, "\n       -> {}", fix)?;
+        }
+        Ok(())
+    }
+}
+
+/// 設定ディレクトリに書き込めるかを確認する
+fn check_writable(config_dir: &Path) -> CheckResult {
+    let probe = config_dir.join(".doctor");
+    let result = fs::create_dir_all(config_dir)
+        .and_then(|_| fs::write(&probe, ""))
+        .and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => CheckResult::ok("config", format!("{} is writable", config_dir.display())),
+        Err(e) => CheckResult::fail(
+            "config",
+            format!("{} is not writable: {}", config_dir.display(), e),
+            "ディレクトリの権限を確認するか、XDG_CACHE_HOME で別のディレクトリを指定してください"
+                .to_string(),
+        ),
+    }
+}
+
+/// テンプレートの有無を確認する
+fn check_templates(template_dir: &Path) -> CheckResult {
+    let missing: Vec<&str> = ["main.rs", "Cargo.toml"]
+        .into_iter()
+        .filter(|file| !template_dir.join(file).is_file())
+        .collect();
+    if missing.is_empty() {
+        CheckResult::ok("templates", format!("found in {}", template_dir.display()))
+    } else {
+        CheckResult::fail(
+            "templates",
+            format!(
+                "{} not found in {}",
+                missing.join(", "),
+                template_dir.display()
+            ),
+            "`cargo atc init` でテンプレートを作成するか、`cargo atc config set template_dir <dir>` で場所を指定してください"
+                .to_string(),
+        )
+    }
+}
+
+/// `<tool> --version` の出力を取得する
+fn tool_version(tool: &str) -> Option<String> {
+    let output = Command::new(tool).arg("--version").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// ローカルのツールのバージョンとジャッジの rustc のバージョンを比較する
+///
+/// # 引数
+/// - `tool`: ツール名 (`rustc` / `cargo`)
+/// - `version_output`: `<tool> --version` の出力 (`None` の場合はツールが見つからない)
+/// - `judge_version`: ジャッジの rustc のバージョン
+fn check_toolchain(
+    tool: &'static str,
+    version_output: Option<String>,
+    judge_version: &str,
+) -> CheckResult {
+    let Some(output) = version_output else {
+        return CheckResult::fail(
+            tool,
+            "not found".to_string(),
+            "rustup (https://rustup.rs) で Rust をインストールしてください".to_string(),
+        );
+    };
+    let (Some(local), Some(judge)) = (parse_version(&output), parse_version(judge_version)) else {
+        return CheckResult::warn(
+            tool,
+            format!("unknown version: {}", output),
+            "バージョンを手動で確認してください".to_string(),
+        );
+    };
+    let message = format!("{} (judge: {})", output, judge_version);
+    if (local.0, local.1) == (judge.0, judge.1) {
+        CheckResult::ok(tool, message)
+    } else {
+        CheckResult::warn(
+            tool,
+            message,
+            format!(
+                "ジャッジと異なるバージョンの API を利用しないよう、`rustup toolchain install {}` を実行して rust-toolchain.toml で固定してください",
+                judge_version
+            ),
+        )
+    }
+}
+
+/// `rustc 1.70.0 (90c541806 2023-05-31)` などの文字列からバージョンを取り出す
+fn parse_version(text: &str) -> Option<(u32, u32, u32)> {
+    let version = text
+        .split_whitespace()
+        .find(|word| word.chars().next().is_some_and(|c| c.is_ascii_digit()))?;
+    let mut parts = version
+        .split(['.', '-'])
+        .map(|part| part.parse::<u32>().ok());
+    Some((
+        parts.next()??,
+        parts.next()??,
+        parts.next().flatten().unwrap_or(0),
+    ))
+}
+
+/// AtCoder に接続できるかを確認する
+async fn check_network(client: &Client, base_url: &str) -> CheckResult {
+    match client.get(base_url).send().await {
+        Ok(response) if response.status().is_success() => {
+            CheckResult::ok("network", format!("{} is reachable", base_url))
+        }
+        Ok(response) => CheckResult::fail(
+            "network",
+            format!("{} returned {}", base_url, response.status()),
+            "時間をおいて再度実行するか、`cargo atc config get base_url` の設定を確認してください"
+                .to_string(),
+        ),
+        Err(e) => CheckResult::fail(
+            "network",
+            format!("{} is not reachable: {}", base_url, e),
+            "ネットワーク接続とプロキシの設定を確認してください".to_string(),
+        ),
+    }
+}
+
+/// 保存されているセッションが有効かを確認する
+async fn check_session(client: &Client, base_url: &str) -> CheckResult {
+    let session = match Session::load(&get_session_file()) {
+        Ok(Some(session)) => session,
+        _ => {
+            return CheckResult::fail(
+                "session",
+                "not logged in".to_string(),
+                "`cargo atc login` を実行してください".to_string(),
+            )
+        }
+    };
+    match fetch_username(base_url, client, &session).await {
+        Ok(Some(username)) => CheckResult::ok("session", format!("logged in as {}", username)),
+        _ => CheckResult::fail(
+            "session",
+            format!("session for {} is no longer valid", session.username),
+            "`cargo atc login` を実行して再ログインしてください".to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::Server;
+    use tempfile;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version("rustc 1.70.0 (90c541806 2023-05-31)"),
+            Some((1, 70, 0))
+        );
+        assert_eq!(
+            parse_version("cargo 1.84.0-nightly (abc 2024-11-01)"),
+            Some((1, 84, 0))
+        );
+        assert_eq!(parse_version("1.70"), Some((1, 70, 0)));
+        assert_eq!(parse_version("rustc unknown"), None);
+    }
+
+    #[test]
+    fn test_check_toolchain() {
+        let result = check_toolchain("rustc", Some("rustc 1.70.0 (abc)".to_string()), "1.70.0");
+        assert_eq!(result.status, CheckStatus::Ok);
+        let result = check_toolchain("rustc", Some("rustc 1.84.0 (abc)".to_string()), "1.70.0");
+        assert_eq!(result.status, CheckStatus::Warn);
+        let result = check_toolchain("cargo", None, "1.70.0");
+        assert_eq!(result.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_check_templates_and_writable() {
+        let work_dir = tempfile::tempdir().expect("");
+        let template_dir = work_dir.path().join("templates");
+        assert_eq!(check_templates(&template_dir).status, CheckStatus::Fail);
+
+        fs::create_dir_all(&template_dir).unwrap();
+        fs::write(template_dir.join("main.rs"), "").unwrap();
+        let result = check_templates(&template_dir);
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(result.message.starts_with("Cargo.toml not found"));
+
+        fs::write(template_dir.join("Cargo.toml"), "").unwrap();
+        assert_eq!(check_templates(&template_dir).status, CheckStatus::Ok);
+
+        let config_dir = work_dir.path().join("config");
+        assert_eq!(check_writable(&config_dir).status, CheckStatus::Ok);
+        assert!(!config_dir.join(".doctor").exists());
+    }
+
+    #[tokio::test]
+    async fn test_check_network() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/")
+            .with_status(503)
+            .create_async()
+            .await;
+        let result = check_network(&Client::new(), &server.url()).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+    }
+}
@@ -1,11 +1,19 @@
 //! AtCoder のコンテスト情報をダウンロードし、問題ごとのディレクトリ構造を作成するモジュール
 //!
 //! このモジュールには以下の機能が含まれる。
-//! - AtCoder の問題一覧を取得 (`get_problem_list`)
+//! - AtCoder の問題一覧を取得 (`get_problem_list`、印刷用ページ `tasks_print` を優先して利用)
+//! - AtCoder の問題一覧から指定した問題のみを取得 (`get_problem`)
+//! - 問題ページを解析できない場合は AtCoder Problems の API から問題一覧のみを取得 (`problems_api`)
 //! - 各問題のディレクトリを作成 (`create_contest_directory`)
 //! - `Cargo.toml` の生成 (`generate_cargo_toml`)
 //! - `main.rs` のテンプレートコピー (`create_main_rs`)
 //! - サンプル入出力ファイルの作成 (`create_sample_files`)
+//! - 実行時間制限・メモリ制限・配点の保存 (`metadata::save_contest_metadata`)
+//! - 問題文の保存 (`statement::write_statement`)
+//! - 制約などをまとめた `README.md` の保存 (`statement::write_readme`)
+//! - `rustfmt.toml` のテンプレートコピー (`copy_rustfmt_toml`)
+//! - `rust-toolchain.toml` の作成 (`create_rust_toolchain`)
+//! - 問題ページの取得状況の表示 (`problem_progress_bar`)
 //!
 //! ## ディレクトリ構造
 //! このモジュールが処理対象とするディレクトリ構造は以下の通り。
@@ -14,12 +22,20 @@
 //! .
 //! ├── templates               # テンプレートフォルダ (プログラム生成に利用)
 //! │   ├── main.rs             # main.rs のテンプレート
-//! │   └── Cargo.toml          # Cargo.toml の依存関係テンプレート
+//! │   ├── Cargo.toml          # Cargo.toml の依存関係テンプレート
+//! │   └── rustfmt.toml        # rustfmt の設定テンプレート (任意)
 //! └── contest_name            # コンテスト名 (例: abc388)
 //!     ├── Cargo.toml
 //!     ├── Cargo.lock
+//!     ├── contest.json        # 各問題の実行時間制限・メモリ制限・配点・URL とダウンロード日時
+//!     ├── rustfmt.toml        # templates/rustfmt.toml が存在する場合のみ作成
+//!     ├── rust-toolchain.toml # ジャッジの rustc のバージョンに固定 (設定 `rust_toolchain` で変更)
+//!     ├── .cargo/config.toml  # 設定 `cargo_config` が `true` の場合のみ作成 (`cargo_config.rs`)
 //!     ├── a                   # 問題ごとのディレクトリ
 //!     │   ├── main.rs         # 問題に回答するロジックを実装するファイル
+//!     │   ├── problem.html    # 問題文 (HTML)
+//!     │   ├── problem.md      # 問題文 (Markdown)
+//!     │   ├── README.md       # 見出し・URL・実行時間制限・メモリ制限・制約の概要
 //!     │   └── tests           # AtCoder より取得したサンプル入出力を記録したディレクトリ
 //!     │       ├── sample_1.in
 //!     │       ├── sample_1.out
@@ -37,6 +53,7 @@
 //! 4. **`generate_cargo_toml`**: `Cargo.toml` を生成し、問題ごとのバイナリ定義を追加
 //! 5. **`create_main_rs`**: `templates/main.rs` をコピーし、各問題の `main.rs` を作成
 //! 6. **`create_sample_files`**: AtCoder から取得したサンプル入出力ファイル (`tests/`) を作成
+//! 7. **`copy_rustfmt_toml`**: `templates/rustfmt.toml` が存在する場合はコピーし、各問題の `main.rs` を整形
 //!
 //! ## エラーハンドリング
 //! - **ネットワークエラー**: `fetch_html` で HTTP ステータスコードが `200-299` 以外の場合はエラーを返す
@@ -46,24 +63,258 @@
 //!
 //! このモジュールを利用することで、AtCoder のコンテスト環境を迅速にセットアップし、スムーズなコーディング環境を提供する。
 
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use futures::stream::{self, StreamExt};
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::{
+    header::{HeaderName, COOKIE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode,
+};
 use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
 use std::{
     error::Error,
     fs::{self, File},
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use super::config::BASE_URL;
+use super::add::{add_problem_to_cargo_toml, is_registered, update_problem_timeout};
+use super::cache::{active_cache_dir, CachedPage};
+use super::cargo_config::{create_cargo_config, CARGO_CONFIG_FILE};
+use super::config::{
+    get_base_url, get_cargo_config, get_cargo_target_dir, get_contests_dir, get_fetch_retries,
+    get_sample_lang, get_session_file, load_user_config,
+};
+use super::contests::{parse_contest_names, similar_contest_names};
+use super::format::format_file;
+use super::git::init_repository;
+use super::http::{client_builder, throttle};
+use super::init::{DEFAULT_CARGO_TOML, DEFAULT_MAIN_RS};
+use super::login::Session;
+use super::metadata::save_contest_metadata;
+use super::migrate::{get_judge_rustc_version, rust_toolchain_toml};
+use super::new::DEFAULT_TIMEOUT;
+use super::output::{is_json, print_json};
+use super::problems_api::{fetch_contest_problems, MERGED_PROBLEMS_URL};
+use super::statement::{extract_statement, write_readme, write_statement};
+use super::stats::{record_event, EventKind};
+use super::template::resolve_contest_template_dir;
+use super::workspace::{add_workspace_member, is_workspace_root};
+
+/// 問題ページを並行して取得する最大数
+const FETCH_CONCURRENCY: usize = 4;
+
+/// `--wait` で開始時刻を過ぎた後に問題一覧ページを再取得する間隔
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// `--wait` で問題一覧ページの公開を待つ最大回数 (`WAIT_POLL_INTERVAL` との積で約 5 分)
+const WAIT_MAX_POLLS: u32 = 150;
+
+/// `download` のオプション
+#[derive(Debug, Default)]
+pub struct DownloadOptions {
+    pub problem: Option<String>,  // 取得する問題 (`--problem`)
+    pub template: Option<String>, // テンプレートのプロファイル (`--template`)
+    pub wait: bool,               // コンテストの開始まで待機する (`--wait`)
+    pub workspace: bool,          // ワークスペースのメンバーに追加する (`--workspace`)
+    pub git: bool,                // Git リポジトリとして初期化する (設定 `git_init`、`--no-git`)
+    pub force: bool,              // 既存の `main.rs` をテンプレートで上書きする (`--force`)
+    pub output: Option<PathBuf>,  // コンテストを作成するディレクトリ (`--output`)
+    pub dry_run: bool,            // 取得・解析のみ行い、作成する内容を表示する (`--dry-run`)
+}
 
 /// ダウンロード処理のエントリーポイント
-pub async fn execute(work_dir: &PathBuf, contest_name: &str) -> Result<(), Box<dyn Error>> {
-    let contest_info = get_problem_list(BASE_URL, &contest_name).await?;
+///
+/// `template` を指定した場合は、既定のテンプレートプロファイルの代わりに指定したプロファイルを利用する。
+///
+/// 問題を指定した場合(`abc388/c` または `--problem c`)は、その問題のみを取得する。
+/// コンテスト名の代わりにコンテスト・問題の URL (`https://atcoder.jp/contests/abc388/tasks/abc388_c`) も指定できる。
+///
+/// コンテストディレクトリが既に存在する場合は、`main.rs` とサンプルが揃っている問題を取得せず、
+/// 残りの問題のみを取得して `Cargo.toml` に `[[bin]]`・実行時間制限を追記する。
+/// 一部の問題の取得に失敗した場合も取得できた問題は作成するため、再度実行すると残りの問題のみを取得できる。
+/// `force` を指定した場合はすべての問題を取得し直し、既存の `main.rs` も上書きする。
+///
+/// `dry_run` を指定した場合は、問題の取得・解析のみを行い、作成するディレクトリ構成・`Cargo.toml`・サンプル数を表示する。
+///
+/// `wait` を指定した場合は、コンテストの開始まで待機し、問題一覧が公開されてからダウンロードする。
+///
+/// `workspace` を指定した場合、または作業ディレクトリが既にワークスペースの場合は、
+/// コンテストディレクトリを作業ディレクトリのワークスペースのメンバーに追加する。
+///
+/// `git` を指定した場合は、新しく作成したコンテストディレクトリを Git リポジトリとして初期化し、最初のコミットを作成する。
+///
+/// コンテストは `output`(未指定の場合は設定 `contests_dir`、どちらもなければ `work_dir`)に作成する。
+/// テンプレート・ワークスペースも作成先のディレクトリを基準に解決する。
+pub async fn execute(
+    work_dir: &Path,
+    target: &str,
+    options: &DownloadOptions,
+) -> Result<(), Box<dyn Error>> {
+    let work_dir = &if options.dry_run {
+        download_dir(work_dir, options.output.as_deref())
+    } else {
+        resolve_download_dir(work_dir, options.output.as_deref())?
+    };
+    if options.workspace && work_dir.join("Cargo.toml").exists() && !is_workspace_root(work_dir) {
+        return Err(format!(
+            "{} はワークスペースではありません ([workspace] がありません)",
+            work_dir.join("Cargo.toml").display()
+        )
+        .into());
+    }
+    let (contest_name, problem_name) = parse_target(target, options.problem.as_deref())?;
+    let contest_name = contest_name.as_str();
+    let template_dir =
+        resolve_contest_template_dir(work_dir, options.template.as_deref(), contest_name)?;
+    if !template_dir.join("main.rs").exists() && !is_json() {
+        println!(
+            "Note: {} にテンプレートがないため、組み込みのテンプレートを利用します (`cargo atc init` で作成できます)",
+            template_dir.display()
+        );
+    }
+    let contest_dir = work_dir.join(contest_name);
+    let cargo_toml_path = contest_dir.join("Cargo.toml");
+    let existing = cargo_toml_path.exists();
+
+    if options.wait {
+        wait_for_contest_start(
+            &get_base_url(),
+            contest_name,
+            WAIT_POLL_INTERVAL,
+            WAIT_MAX_POLLS,
+        )
+        .await?;
+    }
+
+    let fetched = match &problem_name {
+        Some(problem_name) => get_problem(&get_base_url(), contest_name, problem_name)
+            .await
+            .map(|problem| FetchedProblems {
+                contest_info: ContestInfo {
+                    contest_name: contest_name.to_string(),
+                    problems: vec![problem],
+                },
+                skipped: Vec::new(),
+                failed: Vec::new(),
+            }),
+        // 取得済みの問題は取得しない (`force` を指定した場合はすべて取得し直す)
+        None => {
+            get_missing_problems(
+                &get_base_url(),
+                contest_name,
+                &contest_dir,
+                existing && !options.force,
+            )
+            .await
+        }
+    };
+    let FetchedProblems {
+        contest_info,
+        skipped,
+        failed,
+    } = match fetched {
+        Ok(fetched) if !fetched.contest_info.problems.is_empty() || !fetched.skipped.is_empty() => {
+            fetched
+        }
+        Err(e) if e.is::<PageNotFound>() => return Err(e),
+        // 問題ページを解析できない場合は AtCoder Problems の API から問題一覧のみを取得する
+        fetched => match fetch_from_problems_api(contest_name, problem_name.as_deref()).await {
+            Some(contest_info) => FetchedProblems {
+                contest_info,
+                skipped: Vec::new(),
+                failed: Vec::new(),
+            },
+            None => fetched?,
+        },
+    };
+    if contest_info.problems.is_empty() {
+        if !failed.is_empty() {
+            return Err(failed_problems_message(contest_name, &failed).into());
+        }
+        if !skipped.is_empty() {
+            if !is_json() {
+                println!(
+                    "All problems are already downloaded: {} (use --force to download again)",
+                    skipped.join(", ")
+                );
+            }
+            return Ok(());
+        }
+        return Err(format!(
+            "コンテスト {} の問題一覧が空です。開始前のコンテストの場合は `--wait` を指定してください",
+            contest_name
+        )
+        .into());
+    }
+    if !skipped.is_empty() && !is_json() {
+        println!("Skipping downloaded problems: {}", skipped.join(", "));
+    }
+    if options.dry_run {
+        let cargo_toml = if existing {
+            None
+        } else {
+            Some(cargo_toml_content(
+                &template_dir,
+                contest_name,
+                &contest_info.problems,
+            )?)
+        };
+        print!(
+            "{}",
+            format_download_plan(
+                &contest_dir,
+                cargo_toml.as_deref(),
+                &contest_info,
+                options.force
+            )
+        );
+        return Ok(());
+    }
     create_contest_directory(&work_dir, &contest_info)?;
-    generate_cargo_toml(&work_dir, contest_name, &contest_info.problems)?;
+    if existing {
+        for problem in &contest_info.problems {
+            if is_registered(&cargo_toml_path, &problem.problem_name)? {
+                update_problem_timeout(&cargo_toml_path, &problem.problem_name, problem.timeout)?;
+            } else {
+                add_problem_to_cargo_toml(
+                    &cargo_toml_path,
+                    &problem.problem_name,
+                    problem.timeout,
+                )?;
+            }
+        }
+    } else {
+        generate_cargo_toml(
+            work_dir,
+            &template_dir,
+            contest_name,
+            &contest_info.problems,
+        )?;
+        if options.workspace || is_workspace_root(work_dir) {
+            add_workspace_member(work_dir, contest_name)?;
+        }
+    }
+
+    save_contest_metadata(&contest_dir, contest_name, &contest_info.problems)?;
 
+    // 既存の `main.rs` は解答を失わないよう、`force` を指定しない限り上書きしない
+    let mut created_main_rs = Vec::new();
     for problem in &contest_info.problems {
-        create_main_rs(&work_dir, contest_name, &problem.problem_name)?;
+        let main_rs_path = contest_dir.join(&problem.problem_name).join("main.rs");
+        if main_rs_path.exists() && !options.force {
+            if !is_json() {
+                println!(
+                    "Keeping existing main.rs: {:?} (use --force to overwrite)",
+                    main_rs_path
+                );
+            }
+            continue;
+        }
+        create_main_rs(work_dir, &template_dir, contest_name, &problem.problem_name)?;
+        created_main_rs.push(main_rs_path);
     }
 
     for problem in &contest_info.problems {
@@ -73,12 +324,378 @@ pub async fn execute(work_dir: &PathBuf, contest_name: &str) -> Result<(), Box<d
             &problem.problem_name,
             &problem.samples,
         )?;
+        if !is_json() {
+            println!(
+                "[{}] {} samples written",
+                problem.problem_name,
+                problem.samples.len()
+            );
+        }
+        if let Some(statement) = &problem.statement {
+            let problem_dir = work_dir.join(contest_name).join(&problem.problem_name);
+            write_statement(&problem_dir, statement)?;
+            write_readme(
+                &problem_dir,
+                problem,
+                &problem.url(&get_base_url(), contest_name),
+            )?;
+        }
+    }
+
+    if !existing {
+        create_rust_toolchain(
+            work_dir,
+            &template_dir,
+            contest_name,
+            load_user_config().rust_toolchain.as_deref(),
+        )?;
+        if get_cargo_config() {
+            // ワークスペースの場合は全コンテストで共有するよう、ワークスペースのルートに作成する
+            let config_dir = if is_workspace_root(work_dir) {
+                work_dir.as_path()
+            } else {
+                contest_dir.as_path()
+            };
+            if create_cargo_config(
+                config_dir,
+                load_user_config().cargo_linker.as_deref(),
+                get_cargo_target_dir(work_dir).as_deref(),
+            )? && !is_json()
+            {
+                println!("Created {:?}", config_dir.join(CARGO_CONFIG_FILE));
+            }
+        }
+    }
+
+    // 既存のコンテストディレクトリの `rustfmt.toml` は上書きしない
+    let has_rustfmt_toml = if existing {
+        contest_dir.join("rustfmt.toml").exists()
+    } else {
+        copy_rustfmt_toml(work_dir, &template_dir, contest_name)?
+    };
+    if has_rustfmt_toml {
+        for main_rs_path in &created_main_rs {
+            if let Err(e) = format_file(main_rs_path) {
+                eprintln!("Warning: {}", e);
+            }
+        }
+    }
+
+    if options.git && !existing {
+        match init_repository(&contest_dir, contest_name) {
+            Ok(true) if !is_json() => println!("Initialized git repository: {:?}", contest_dir),
+            Ok(_) => {}
+            Err(e) => eprintln!("Warning: {}", e),
+        }
     }
 
-    println!("Contest setup completed successfully: {}", contest_name);
+    for problem in &contest_info.problems {
+        record_event(contest_name, &problem.problem_name, EventKind::Downloaded);
+    }
+    if is_json() {
+        print_json(&DownloadSummary {
+            contest: contest_name,
+            problems: contest_info
+                .problems
+                .iter()
+                .map(|problem| DownloadedProblem {
+                    problem: &problem.problem_name,
+                    timeout: problem.timeout,
+                    samples: problem.samples.len(),
+                })
+                .collect(),
+        })?;
+    } else if failed.is_empty() {
+        println!("Contest setup completed successfully: {}", contest_name);
+    }
+    if !failed.is_empty() {
+        return Err(failed_problems_message(contest_name, &failed).into());
+    }
     Ok(())
 }
 
+/// 取得に失敗した問題を示すエラーメッセージを返す
+fn failed_problems_message(contest_name: &str, failed: &[(String, String)]) -> String {
+    let details: Vec<String> = failed
+        .iter()
+        .map(|(problem_name, e)| format!("  {}: {}", problem_name, e))
+        .collect();
+    format!(
+        "{} 問の取得に失敗しました。`cargo atc download {}` を再度実行すると、取得していない問題のみを取得します\n{}",
+        failed.len(),
+        contest_name,
+        details.join("\n")
+    )
+}
+
+/// コンテストの開始まで待機し、問題一覧ページが公開されるまでポーリングする
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `contest_name`: コンテスト名 (例: `"abc400"`)
+/// - `poll_interval`: 問題一覧ページを再取得する間隔
+/// - `max_polls`: 問題一覧ページを取得する最大回数
+///
+/// # 処理の流れ
+/// 1. コンテストのトップページから開始時刻を取得する
+/// 2. 開始時刻までリクエストを送らずに待機する
+/// 3. 問題一覧ページを `poll_interval` ごとに取得し、問題が 1 つ以上あれば終了する
+///
+/// # エラーの可能性
+/// - コンテストのトップページを取得できない場合
+/// - `max_polls` 回取得しても問題一覧が公開されない場合
+pub async fn wait_for_contest_start(
+    base_url: &str,
+    contest_name: &str,
+    poll_interval: Duration,
+    max_polls: u32,
+) -> Result<(), Box<dyn Error>> {
+    let html = fetch_html(&format!("{}/contests/{}", base_url, contest_name)).await?;
+    if let Some(start_time) = parse_start_time(&Html::parse_document(&html)) {
+        let remaining = start_time
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO);
+        if !remaining.is_zero() {
+            if !is_json() {
+                println!(
+                    "Waiting for {} to start at {} ({} seconds left)",
+                    contest_name,
+                    start_time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"),
+                    remaining.as_secs()
+                );
+            }
+            tokio::time::sleep(remaining).await;
+        }
+    }
+
+    let mut last_error = String::new();
+    for poll in 0..max_polls.max(1) {
+        if poll > 0 {
+            tokio::time::sleep(poll_interval).await;
+        }
+        match fetch_task_list(base_url, contest_name).await {
+            Ok(tasks) if !tasks.is_empty() => return Ok(()),
+            Ok(_) => last_error = "問題が見つかりません".to_string(),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(format!(
+        "{} の問題一覧が公開されませんでした ({} 回取得): {}",
+        contest_name,
+        max_polls.max(1),
+        last_error
+    )
+    .into())
+}
+
+/// コンテストのトップページから開始時刻(`time.fixtime-full` の 1 つ目)を取得する
+fn parse_start_time(document: &Html) -> Option<DateTime<FixedOffset>> {
+    let time_selector = Selector::parse("time.fixtime-full").unwrap();
+    let text = document
+        .select(&time_selector)
+        .next()?
+        .text()
+        .collect::<String>();
+    DateTime::parse_from_str(text.trim(), "%Y-%m-%d %H:%M:%S%z").ok()
+}
+
+/// AtCoder Problems の API からコンテストの問題一覧を取得する
+///
+/// 問題ページを解析できない場合の代替として利用する。サンプルは取得できないため、
+/// `update` で取得するよう警告を表示する。取得できない場合や該当する問題がない場合は `None` を返す。
+async fn fetch_from_problems_api(
+    contest_name: &str,
+    problem_name: Option<&str>,
+) -> Option<ContestInfo> {
+    let problems: Vec<ProblemInfo> = fetch_contest_problems(MERGED_PROBLEMS_URL, contest_name)
+        .await
+        .ok()?
+        .into_iter()
+        .filter(|problem| problem_name.is_none_or(|name| problem.problem_name == name))
+        .collect();
+    if problems.is_empty() {
+        return None;
+    }
+    eprintln!(
+        "Warning: 問題ページを解析できないため、AtCoder Problems から問題一覧のみを取得しました。\
+         実行時間制限は既定値 ({} ms) です。サンプルは `cargo atc update {}` で取得してください",
+        DEFAULT_TIMEOUT, contest_name
+    );
+    Some(ContestInfo {
+        contest_name: contest_name.to_string(),
+        problems,
+    })
+}
+
+/// コンテストを作成するディレクトリを返す。存在しない場合は作成する
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ (`output`・`contests_dir` の相対パスの基準)
+/// - `output`: `--output` で指定したディレクトリ。未指定の場合は設定 `contests_dir`(未設定の場合は `work_dir`)
+fn resolve_download_dir(work_dir: &Path, output: Option<&Path>) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = download_dir(work_dir, output);
+    fs::create_dir_all(&dir).map_err(|e| format!("{} を作成できません: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// コンテストを作成するディレクトリを返す (`resolve_download_dir` と異なり、ディレクトリは作成しない)
+fn download_dir(work_dir: &Path, output: Option<&Path>) -> PathBuf {
+    match output {
+        Some(output) => work_dir.join(output),
+        None => get_contests_dir(work_dir),
+    }
+}
+
+/// ダウンロード対象のコンテスト名と問題名 (問題名は指定された場合のみ)
+type DownloadTarget = (String, Option<String>);
+
+/// ダウンロード対象の指定をコンテスト名と問題名に分割する
+///
+/// # 引数
+/// - `target`: コンテスト名 (`abc388`)、コンテスト名と問題名 (`abc388/c`)、
+///   またはコンテスト・問題の URL (`https://atcoder.jp/contests/abc388/tasks/abc388_c`)
+/// - `problem`: `--problem` で指定した問題名
+///
+/// # エラーの可能性
+/// - `abc388/c` の形式(または問題の URL)と `--problem` の両方で問題を指定した場合
+/// - コンテスト名・問題名が空の場合
+/// - URL からコンテスト名を取り出せない場合
+fn parse_target(target: &str, problem: Option<&str>) -> Result<DownloadTarget, Box<dyn Error>> {
+    let (contest_name, problem_name) = match parse_contest_url(target) {
+        Some(Ok(parsed)) => parsed,
+        Some(Err(e)) => return Err(e),
+        None => match target.split_once('/') {
+            Some((contest_name, problem_name)) => {
+                (contest_name.to_string(), Some(problem_name.to_string()))
+            }
+            None => (target.to_string(), None),
+        },
+    };
+    let problem_name = match (problem_name, problem) {
+        (Some(_), Some(_)) => {
+            return Err(
+                "問題は `abc388/c` の形式と `--problem` のどちらか一方で指定してください".into(),
+            )
+        }
+        (Some(problem_name), None) => Some(problem_name),
+        (None, problem_name) => problem_name.map(str::to_string),
+    };
+    if contest_name.is_empty() || problem_name.as_ref().is_some_and(|name| name.is_empty()) {
+        return Err(format!("無効なダウンロード対象が指定されました: {}", target).into());
+    }
+    Ok((contest_name, problem_name.map(|name| name.to_lowercase())))
+}
+
+/// コンテスト・問題の URL からコンテスト名と問題名を取り出す
+///
+/// `/contests/<コンテスト名>` 以降のパスを解析する。問題名は問題 ID の最後の `_` 以降 (`abc388_c` → `c`) とする。
+///
+/// # 戻り値
+/// - `None`: `target` が URL でない場合
+/// - `Some(Err)`: URL に `/contests/<コンテスト名>` が含まれない場合
+fn parse_contest_url(target: &str) -> Option<Result<DownloadTarget, Box<dyn Error>>> {
+    let (_, rest) = target.split_once("://")?;
+    let path = rest.split(['?', '#']).next().unwrap_or("");
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let Some(index) = segments.iter().position(|&s| s == "contests") else {
+        return Some(Err(format!(
+            "URL からコンテスト名を取得できません: {}",
+            target
+        )
+        .into()));
+    };
+    let Some(contest_name) = segments.get(index + 1) else {
+        return Some(Err(format!(
+            "URL からコンテスト名を取得できません: {}",
+            target
+        )
+        .into()));
+    };
+    let problem_name = match (segments.get(index + 2), segments.get(index + 3)) {
+        (Some(&"tasks"), Some(task_id)) => Some(
+            task_id
+                .rsplit_once('_')
+                .map_or(*task_id, |(_, problem_name)| problem_name)
+                .to_string(),
+        ),
+        _ => None,
+    };
+    Some(Ok((contest_name.to_string(), problem_name)))
+}
+
+/// `--dry-run` で表示する、作成するディレクトリ構成・`Cargo.toml`・サンプル数を返す
+///
+/// # 引数
+/// - `contest_dir`: 作成するコンテストディレクトリ
+/// - `cargo_toml`: 生成する `Cargo.toml` の内容 (既存のコンテストディレクトリの場合は `None`)
+/// - `contest_info`: 取得したコンテスト情報
+/// - `force`: 既存の `main.rs` を上書きするかどうか
+fn format_download_plan(
+    contest_dir: &Path,
+    cargo_toml: Option<&str>,
+    contest_info: &ContestInfo,
+    force: bool,
+) -> String {
+    let mut plan = format!("Dry run: nothing is written\n{}\n", contest_dir.display());
+    plan.push_str(match cargo_toml {
+        Some(_) => "├── Cargo.toml\n",
+        None => "├── Cargo.toml  (update [[bin]] and timeouts)\n",
+    });
+    plan.push_str("├── contest.json\n");
+    for (i, problem) in contest_info.problems.iter().enumerate() {
+        let last = i + 1 == contest_info.problems.len();
+        let (branch, indent) = if last {
+            ("└──", "    ")
+        } else {
+            ("├──", "│   ")
+        };
+        let main_rs_exists = contest_dir
+            .join(&problem.problem_name)
+            .join("main.rs")
+            .exists();
+        plan.push_str(&format!("{} {}\n", branch, problem.problem_name));
+        plan.push_str(&format!(
+            "{}├── main.rs{}\n",
+            indent,
+            if main_rs_exists && !force {
+                "  (keep)"
+            } else {
+                ""
+            }
+        ));
+        if problem.statement.is_some() {
+            plan.push_str(&format!(
+                "{}├── README.md\n{}├── problem.html\n{}├── problem.md\n",
+                indent, indent, indent
+            ));
+        }
+        plan.push_str(&format!(
+            "{}└── tests  ({} samples)\n",
+            indent,
+            problem.samples.len()
+        ));
+    }
+    if let Some(cargo_toml) = cargo_toml {
+        plan.push_str(&format!("\n--- Cargo.toml ---\n{}\n", cargo_toml.trim()));
+    }
+    plan
+}
+
+/// `--json` を指定した場合に出力するダウンロード結果
+#[derive(Serialize)]
+struct DownloadSummary<'a> {
+    contest: &'a str,
+    problems: Vec<DownloadedProblem<'a>>,
+}
+
+#[derive(Serialize)]
+struct DownloadedProblem<'a> {
+    problem: &'a str,
+    timeout: u128,
+    samples: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct ContestInfo {
     pub contest_name: String,
@@ -89,10 +706,29 @@ pub struct ContestInfo {
 pub struct ProblemInfo {
     pub problem_name: String,
     pub timeout: u128,
+    pub memory_limit: Option<u64>, // メモリ制限 (MB)
+    pub score: Option<u64>,        // 配点
     pub samples: Vec<Sample>,
+    pub statement: Option<String>, // 問題文の HTML (`statement::extract_statement` で取り出したもの)
+    pub task_screen_name: Option<String>, // AtCoder 上の問題 ID (`abc388_a` など)。問題名と一致しない場合がある
+    pub title: Option<String>,            // 問題の見出し (`A - ?UPC` など)
 }
 
-#[derive(Debug, Clone)]
+impl ProblemInfo {
+    /// 問題ページの URL を返す (問題 ID が不明な場合は `<コンテスト名>_<問題名>` とする)
+    pub fn url(&self, base_url: &str, contest_name: &str) -> String {
+        format!(
+            "{}/contests/{}/tasks/{}",
+            base_url,
+            contest_name,
+            self.task_screen_name
+                .clone()
+                .unwrap_or_else(|| format!("{}_{}", contest_name, self.problem_name))
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Sample {
     pub input: String,
     pub output: String,
@@ -100,6 +736,11 @@ pub struct Sample {
 
 /// 指定されたURLからHTMLを取得する
 ///
+/// 一時的な障害(5xx・タイムアウト・接続失敗)の場合は、設定 `fetch_retries` の回数まで
+/// 指数バックオフ(`RETRY_BASE_DELAY` の 2 の累乗倍 + ジッター)で待機して再試行する。
+/// キャッシュが有効な場合は、保存済みの HTML を `cache` モジュールの方法で再検証して利用する。
+/// ログイン済みの場合はセッションの Cookie を付与し、開催中のコンテストなど閲覧に権限が必要なページも取得する。
+///
 /// # 引数
 /// - `url`: 取得するページのURL
 ///
@@ -110,30 +751,565 @@ pub struct Sample {
 /// # エラーハンドリング
 /// - HTTPリクエストが失敗した場合、エラーを返す
 /// - ステータスコードが 200-299 以外の場合はエラーを返す
+/// - エラーメッセージには取得しようとした URL と試行回数を含める
+/// - 閲覧権限がない(401・403・404、またはログインページへのリダイレクト)場合は、ログインを促すエラーを返す
 pub async fn fetch_html(url: &str) -> Result<String, Box<dyn std::error::Error>> {
-    let response = reqwest::get(url).await?;
+    let cookie = Session::load(&get_session_file())
+        .ok()
+        .flatten()
+        .map(|session| session.cookie_header());
+    fetch_html_with_retry(
+        url,
+        get_fetch_retries(),
+        RETRY_BASE_DELAY,
+        active_cache_dir().as_deref(),
+        cookie.as_deref(),
+    )
+    .await
+}
 
-    // ステータスコードが 200-299 の範囲であることを確認
-    if !response.status().is_success() {
-        return Err(format!("HTTP request failed with status: {}", response.status()).into());
+/// 1 回目の再試行までの待機時間 (2 回目以降は倍になる)
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// 1 回のリクエストのタイムアウト
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 1 回のリクエストの失敗
+enum FetchError {
+    /// 再試行しても成功する見込みのない失敗 (4xx など)
+    Fatal(String),
+    /// 一時的な失敗 (5xx・タイムアウト・接続失敗)
+    Transient(String),
+    /// ページが存在しない (404)
+    NotFound(String),
+}
+
+/// ページが存在しない(404)場合のエラー
+///
+/// コンテスト名の誤りを検出するため、他の取得の失敗と区別して返す。
+#[derive(Debug)]
+pub struct PageNotFound(String);
+
+impl std::fmt::Display for PageNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for PageNotFound {}
+
+/// `fetch_html` の本体。試行回数と待機時間を指定して HTML を取得する
+///
+/// # 引数
+/// - `url`: 取得するページの URL
+/// - `attempts`: 最大試行回数 (1 以上)
+/// - `base_delay`: 1 回目の再試行までの待機時間
+/// - `cache_dir`: キャッシュディレクトリ (`None` の場合はキャッシュを利用しない)
+/// - `cookie`: リクエストに付与する `Cookie` ヘッダーの値 (`None` の場合は未ログインとして取得する)
+async fn fetch_html_with_retry(
+    url: &str,
+    attempts: u32,
+    base_delay: Duration,
+    cache_dir: Option<&Path>,
+    cookie: Option<&str>,
+) -> Result<String, Box<dyn Error>> {
+    let client = client_builder()?.timeout(REQUEST_TIMEOUT).build()?;
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        let message = match fetch_once(&client, url, cache_dir, cookie).await {
+            Ok(body) => return Ok(body),
+            Err(FetchError::Fatal(message)) => message,
+            Err(FetchError::Transient(message)) if attempt < attempts => {
+                let delay = backoff_delay(base_delay, attempt);
+                eprintln!(
+                    "Warning: {} ({}) - retrying in {} ms ({}/{})",
+                    message,
+                    url,
+                    delay.as_millis(),
+                    attempt,
+                    attempts
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+                continue;
+            }
+            Err(FetchError::Transient(message)) => message,
+            Err(FetchError::NotFound(message)) => {
+                return Err(Box::new(PageNotFound(format!(
+                    "{} の取得に失敗しました ({} 回試行): {}",
+                    url, attempt, message
+                ))))
+            }
+        };
+        return Err(format!(
+            "{} の取得に失敗しました ({} 回試行): {}",
+            url, attempt, message
+        )
+        .into());
+    }
+}
+
+/// HTML を 1 回取得する
+///
+/// `cache_dir` を指定した場合は、キャッシュがあれば条件付きリクエストを送信し、
+/// `304 Not Modified` の場合はキャッシュした HTML を返す。
+/// `ETag` または `Last-Modified` を含むレスポンスはキャッシュに保存する。
+/// `cookie` を指定した場合は `Cookie` ヘッダーとして付与する。
+async fn fetch_once(
+    client: &reqwest::Client,
+    url: &str,
+    cache_dir: Option<&Path>,
+    cookie: Option<&str>,
+) -> Result<String, FetchError> {
+    let classify = |e: reqwest::Error| {
+        if e.is_timeout() || e.is_connect() {
+            FetchError::Transient(e.to_string())
+        } else {
+            FetchError::Fatal(e.to_string())
+        }
+    };
+    let cached = cache_dir.and_then(|dir| CachedPage::load(dir, url));
+    let mut request = client.get(url);
+    if let Some(cookie) = cookie {
+        request = request.header(COOKIE, cookie);
     }
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    throttle(url).await;
+    let response = request.send().await.map_err(classify)?;
 
-    let body = response.text().await?;
+    let status = response.status();
+    if status == StatusCode::NOT_MODIFIED {
+        if let Some(cached) = cached {
+            return Ok(cached.body);
+        }
+    }
+    // 閲覧権限がない場合は 401・403・404 が返る、またはログインページにリダイレクトされる
+    if response.url().path().starts_with("/login")
+        || matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+    {
+        return Err(FetchError::Fatal(permission_denied_message(
+            status,
+            cookie.is_some(),
+        )));
+    }
+    if status == StatusCode::NOT_FOUND {
+        return Err(FetchError::NotFound(permission_denied_message(
+            status,
+            cookie.is_some(),
+        )));
+    }
+    // ステータスコードが 200-299 の範囲であることを確認
+    if status.is_server_error() {
+        return Err(FetchError::Transient(format!(
+            "HTTP request failed with status: {}",
+            status
+        )));
+    }
+    if !status.is_success() {
+        return Err(FetchError::Fatal(format!(
+            "HTTP request failed with status: {}",
+            status
+        )));
+    }
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    };
+    let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+    let body = response.text().await.map_err(classify)?;
+
+    if let Some(cache_dir) = cache_dir {
+        let page = CachedPage {
+            url: url.to_string(),
+            etag,
+            last_modified,
+            body,
+        };
+        if page.is_revalidatable() {
+            if let Err(e) = page.save(cache_dir) {
+                eprintln!("Warning: キャッシュを保存できません: {}", e);
+            }
+        }
+        return Ok(page.body);
+    }
     Ok(body)
 }
 
+/// 閲覧権限がないページを取得した場合のエラーメッセージを返す
+///
+/// 未ログインの場合は `login` を、ログイン済みの場合はセッションの確認とコンテストへの参加登録を促す。
+fn permission_denied_message(status: StatusCode, authenticated: bool) -> String {
+    let hint = if authenticated {
+        "セッションの有効期限が切れていないか `cargo atc login --check` で確認し、コンテストへの参加登録を確認してください"
+    } else {
+        "開催中のコンテストなどログインが必要なページは `cargo atc login` でログインしてから再度実行してください"
+    };
+    format!(
+        "ページが存在しないか、閲覧する権限がありません (status: {})。{}",
+        status, hint
+    )
+}
+
+/// `attempt` 回目の失敗後の待機時間を返す
+///
+/// `base_delay * 2^(attempt - 1)` に、その 1/4 までのジッターを加える。
+fn backoff_delay(base_delay: Duration, attempt: u32) -> Duration {
+    let delay = base_delay * 2u32.saturating_pow(attempt.saturating_sub(1)).min(64);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.subsec_nanos())
+        .unwrap_or(0);
+    let jitter_range = (delay.as_millis() / 4) as u64 + 1;
+    delay + Duration::from_millis(nanos as u64 % jitter_range)
+}
+
 /// コンテストの問題一覧を取得する関数
 ///
+/// 問題一覧ページと印刷用ページ(`tasks_print`)の 2 回の取得で、すべての問題の情報を取得する。
+/// 印刷用ページを取得・解析できない場合は、各問題のページを最大 `FETCH_CONCURRENCY` 件まで並行して取得する。
+/// 取得の完了順によらず、問題の並びは問題一覧ページの順序のまま返す。
+///
 /// # 引数
 /// - `contest_name`: AtCoderのコンテスト名 (例: `"abc388"`)
 ///
 /// # 戻り値
 /// - `Ok(ContestInfo)`: コンテスト情報
-/// - `Err(Box<dyn Error>)`: エラー時
+/// - `Err(Box<dyn Error>)`: エラー時 (1 問でも取得に失敗した場合を含む)
 pub async fn get_problem_list(
     base_url: &str,
     contest_name: &str,
 ) -> Result<ContestInfo, Box<dyn Error>> {
+    let tasks = fetch_contest_tasks(base_url, contest_name).await?;
+    let (problems, failures) = fetch_problems(base_url, contest_name, tasks).await;
+    if let Some((_, e)) = failures.into_iter().next() {
+        return Err(e);
+    }
+    Ok(ContestInfo {
+        contest_name: contest_name.to_string(),
+        problems,
+    })
+}
+
+/// `download` で取得した問題
+pub struct FetchedProblems {
+    pub contest_info: ContestInfo,     // 取得した問題
+    pub skipped: Vec<String>,          // 取得済みのため取得しなかった問題
+    pub failed: Vec<(String, String)>, // 取得に失敗した問題とエラーメッセージ
+}
+
+/// コンテストの問題のうち、まだ取得していない問題のみを取得する
+///
+/// `skip_downloaded` を指定した場合は、`main.rs` とサンプルが揃っている問題(`is_downloaded`)を取得しない。
+/// 一部の問題の取得に失敗しても、取得できた問題を返す。
+///
+/// # エラーの可能性
+/// - 問題一覧ページの取得・解析に失敗した場合
+pub async fn get_missing_problems(
+    base_url: &str,
+    contest_name: &str,
+    contest_dir: &Path,
+    skip_downloaded: bool,
+) -> Result<FetchedProblems, Box<dyn Error>> {
+    let (skipped, tasks): (Vec<TaskEntry>, Vec<TaskEntry>) =
+        fetch_contest_tasks(base_url, contest_name)
+            .await?
+            .into_iter()
+            .partition(|task| skip_downloaded && is_downloaded(contest_dir, &task.problem_name));
+    let (problems, failures) = if tasks.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        fetch_problems(base_url, contest_name, tasks).await
+    };
+    Ok(FetchedProblems {
+        contest_info: ContestInfo {
+            contest_name: contest_name.to_string(),
+            problems,
+        },
+        skipped: skipped.into_iter().map(|task| task.problem_name).collect(),
+        failed: failures
+            .into_iter()
+            .map(|(problem_name, e)| (problem_name, e.to_string()))
+            .collect(),
+    })
+}
+
+/// 問題の `main.rs` とサンプル(`tests/sample_1.in`)が既に存在するかどうかを返す
+fn is_downloaded(contest_dir: &Path, problem_name: &str) -> bool {
+    let problem_dir = contest_dir.join(problem_name);
+    problem_dir.join("main.rs").is_file() && problem_dir.join("tests/sample_1.in").is_file()
+}
+
+/// 問題ページの取得結果 (問題一覧での順番, 問題名, 取得結果)
+type FetchResult = (usize, String, Result<ProblemInfo, Box<dyn Error>>);
+
+/// 問題一覧ページの各問題の情報を取得し、取得できた問題と失敗した問題(問題名とエラー)を返す
+///
+/// 印刷用ページ(`tasks_print`)を優先し、利用できない場合は各問題のページを並行して取得する。
+async fn fetch_problems(
+    base_url: &str,
+    contest_name: &str,
+    tasks: Vec<TaskEntry>,
+) -> (Vec<ProblemInfo>, Vec<(String, Box<dyn Error>)>) {
+    match fetch_tasks_print(base_url, contest_name, &tasks).await {
+        Ok(problems) => return (problems, Vec::new()),
+        Err(e) if !is_json() => eprintln!(
+            "Warning: 印刷用ページから取得できないため、問題ページを個別に取得します: {}",
+            e
+        ),
+        Err(_) => {}
+    }
+    let progress = problem_progress_bar(tasks.len());
+    let mut results: Vec<FetchResult> = stream::iter(tasks.into_iter().enumerate())
+        .map(|(index, task)| {
+            let progress = progress.clone();
+            async move {
+                report_progress(
+                    &progress,
+                    &format!("[{}] fetching {}", task.problem_name, task.url),
+                );
+                let problem_name = task.problem_name.clone();
+                let result = fetch_html(&task.url).await.and_then(|html| {
+                    let document = Html::parse_document(&html);
+                    Ok(ProblemInfo {
+                        task_screen_name: task.task_screen_name(),
+                        problem_name: task.problem_name,
+                        timeout: task.timeout,
+                        memory_limit: task.memory_limit,
+                        score: parse_score(&document),
+                        samples: parse_samples(&document)?,
+                        statement: extract_statement(&document, base_url),
+                        title: parse_title(&document),
+                    })
+                });
+                match &result {
+                    Ok(problem) => report_progress(
+                        &progress,
+                        &format!(
+                            "[{}] parsed {} samples",
+                            problem_name,
+                            problem.samples.len()
+                        ),
+                    ),
+                    Err(e) => {
+                        report_progress(&progress, &format!("[{}] failed: {}", problem_name, e))
+                    }
+                }
+                progress.inc(1);
+                (index, problem_name, result)
+            }
+        })
+        .buffer_unordered(FETCH_CONCURRENCY)
+        .collect()
+        .await;
+    progress.finish_and_clear();
+    results.sort_by_key(|(index, _, _)| *index);
+    let mut problems = Vec::new();
+    let mut failures = Vec::new();
+    for (_, problem_name, result) in results {
+        match result {
+            Ok(problem) => problems.push(problem),
+            Err(e) => failures.push((problem_name, e)),
+        }
+    }
+    (problems, failures)
+}
+
+/// 印刷用ページ(`tasks_print`)から問題の情報を取得する
+///
+/// 印刷用ページには全問題の問題文が並んでいるため、問題一覧ページの各問題と問題のラベルで対応付ける。
+///
+/// # エラーの可能性
+/// - 印刷用ページの取得に失敗した場合
+/// - 印刷用ページに問題一覧ページの問題が含まれない場合
+/// - サンプルの解析に失敗した場合
+async fn fetch_tasks_print(
+    base_url: &str,
+    contest_name: &str,
+    tasks: &[TaskEntry],
+) -> Result<Vec<ProblemInfo>, Box<dyn Error>> {
+    let url = format!("{}/contests/{}/tasks_print", base_url, contest_name);
+    if !is_json() {
+        eprintln!("[{}] fetching {}", contest_name, url);
+    }
+    let blocks = split_tasks_print(&Html::parse_document(&fetch_html(&url).await?));
+    let mut problems = Vec::new();
+    for task in tasks {
+        let (_, document) = blocks
+            .iter()
+            .find(|(label, _)| *label == task.problem_name)
+            .ok_or_else(|| format!("印刷用ページに問題 {} がありません", task.problem_name))?;
+        let problem = ProblemInfo {
+            problem_name: task.problem_name.clone(),
+            timeout: task.timeout,
+            memory_limit: task.memory_limit,
+            score: parse_score(document),
+            samples: parse_samples(document)?,
+            statement: extract_statement(document, base_url),
+            title: parse_title(document),
+            task_screen_name: task.task_screen_name(),
+        };
+        if !is_json() {
+            eprintln!(
+                "[{}] parsed {} samples",
+                problem.problem_name,
+                problem.samples.len()
+            );
+        }
+        problems.push(problem);
+    }
+    Ok(problems)
+}
+
+/// 印刷用ページを問題ごとに分割し、問題のラベル(小文字)と問題文の HTML の組を返す
+///
+/// 各問題は見出し(`span.h2`、`A - ?UPC` など)を含むブロックとして並んでいる。
+fn split_tasks_print(document: &Html) -> Vec<(String, Html)> {
+    let title_selector = Selector::parse("span.h2").unwrap();
+    document
+        .select(&title_selector)
+        .filter_map(|title| {
+            let block = title.parent().and_then(ElementRef::wrap)?;
+            let text = title.text().collect::<String>();
+            let label = text.split(" - ").next()?.trim().to_lowercase();
+            Some((label, Html::parse_document(&block.html())))
+        })
+        .collect()
+}
+
+/// コンテストの問題一覧から指定した問題のみを取得する
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `contest_name`: AtCoderのコンテスト名 (例: `"abc388"`)
+/// - `problem_name`: 問題名 (例: `"a"`)
+///
+/// # 戻り値
+/// - `Ok(ProblemInfo)`: 問題情報
+/// - `Err(Box<dyn Error>)`: 問題一覧に指定した問題が存在しない場合、またはページの取得・解析に失敗した場合
+pub async fn get_problem(
+    base_url: &str,
+    contest_name: &str,
+    problem_name: &str,
+) -> Result<ProblemInfo, Box<dyn Error>> {
+    let task = fetch_contest_tasks(base_url, contest_name)
+        .await?
+        .into_iter()
+        .find(|task| task.problem_name == problem_name)
+        .ok_or_else(|| format!("{} に問題 {} が見つかりません", contest_name, problem_name))?;
+    if !is_json() {
+        eprintln!("[{}] fetching {}", task.problem_name, task.url);
+    }
+    let problem_document = Html::parse_document(&fetch_html(&task.url).await?);
+    let problem = ProblemInfo {
+        task_screen_name: task.task_screen_name(),
+        problem_name: task.problem_name,
+        timeout: task.timeout,
+        memory_limit: task.memory_limit,
+        score: parse_score(&problem_document),
+        samples: parse_samples(&problem_document)?,
+        statement: extract_statement(&problem_document, base_url),
+        title: parse_title(&problem_document),
+    };
+    if !is_json() {
+        eprintln!(
+            "[{}] parsed {} samples",
+            problem.problem_name,
+            problem.samples.len()
+        );
+    }
+    Ok(problem)
+}
+
+/// 問題ページの取得状況を表示するプログレスバーを作成する
+///
+/// `--json` を指定した場合は表示しない。標準エラー出力が端末でない場合は、indicatif がバーの描画を省略する。
+fn problem_progress_bar(len: usize) -> ProgressBar {
+    if is_json() {
+        return ProgressBar::hidden();
+    }
+    let progress = ProgressBar::new(len as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} [{bar:30}] {pos}/{len} problems")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    progress
+}
+
+/// プログレスバーの上に状況を 1 行表示する
+///
+/// `ProgressBar::println` は端末以外では出力しないため、バーを一時的に消して標準エラー出力に書き込む。
+fn report_progress(progress: &ProgressBar, message: &str) {
+    if !is_json() {
+        progress.suspend(|| eprintln!("{}", message));
+    }
+}
+
+/// 問題一覧ページの各問題の情報
+struct TaskEntry {
+    problem_name: String,
+    timeout: u128,
+    memory_limit: Option<u64>,
+    url: String,
+}
+
+impl TaskEntry {
+    /// 問題ページの URL (`/contests/abc388/tasks/abc388_a`) から問題 ID を返す
+    fn task_screen_name(&self) -> Option<String> {
+        self.url
+            .rsplit_once("/tasks/")
+            .map(|(_, id)| id.trim_end_matches('/').to_string())
+            .filter(|id| !id.is_empty())
+    }
+}
+
+/// 問題一覧ページを取得する。ページが存在しない場合はコンテスト名の誤りとしてエラーを返す
+///
+/// コンテスト一覧ページを取得できた場合は、近いコンテスト名を候補として提示する。
+async fn fetch_contest_tasks(
+    base_url: &str,
+    contest_name: &str,
+) -> Result<Vec<TaskEntry>, Box<dyn Error>> {
+    match fetch_task_list(base_url, contest_name).await {
+        Err(e) if e.is::<PageNotFound>() => {
+            let mut message = format!(
+                "コンテスト {} が見つかりません。コンテスト名を確認してください",
+                contest_name
+            );
+            let names = match fetch_html(&format!("{}/contests/", base_url)).await {
+                Ok(html) => parse_contest_names(&Html::parse_document(&html)),
+                Err(_) => Vec::new(),
+            };
+            let candidates = similar_contest_names(&names, contest_name);
+            if !candidates.is_empty() {
+                message.push_str(&format!(" (候補: {})", candidates.join(", ")));
+            }
+            message.push_str(&format!(
+                "\n開催前・開催中のコンテストの場合は `--wait` を指定するか、`cargo atc login` でログインしてください ({})",
+                e
+            ));
+            Err(Box::new(PageNotFound(message)))
+        }
+        result => result,
+    }
+}
+
+/// 問題一覧ページを取得し、各問題の問題名・実行時間制限・メモリ制限・URL を返す
+async fn fetch_task_list(
+    base_url: &str,
+    contest_name: &str,
+) -> Result<Vec<TaskEntry>, Box<dyn Error>> {
     let url = format!("{}/contests/{}/tasks", base_url, contest_name);
     let html = fetch_html(&url).await?;
     let document = Html::parse_document(&html);
@@ -143,7 +1319,7 @@ pub async fn get_problem_list(
     let timeout_selector = Selector::parse("td.text-right").unwrap();
     let link_selector = Selector::parse("td a").unwrap();
 
-    let mut problems = Vec::new();
+    let mut tasks = Vec::new();
 
     for row in document.select(&row_selector) {
         let problem_name = row
@@ -163,6 +1339,11 @@ pub async fn get_problem_list(
             .parse::<f64>()
             .map(|sec| (sec * 1000.0) as u128)
             .unwrap_or(0);
+        // 2 つ目の `td.text-right` はメモリ制限 (`1024 MB` など)
+        let memory_limit = row
+            .select(&timeout_selector)
+            .nth(1)
+            .and_then(|el| parse_memory_limit(&el.text().collect::<String>()));
         let problem_url = row
             .select(&link_selector)
             .next()
@@ -177,32 +1358,104 @@ pub async fn get_problem_list(
         if problem_url.is_empty() {
             continue;
         }
-        let problme_html = fetch_html(&problem_url).await.unwrap();
-        let problem_document = Html::parse_document(&problme_html);
-        let samples = parse_samples(&problem_document).unwrap();
-        problems.push(ProblemInfo {
+        tasks.push(TaskEntry {
             problem_name,
             timeout,
-            samples,
+            memory_limit,
+            url: problem_url,
         });
     }
-    Ok(ContestInfo {
-        contest_name: contest_name.to_string(),
-        problems,
+    Ok(tasks)
+}
+
+/// メモリ制限の表記(`1024 MB`・`256 MiB` など)を MB 単位の値に変換する
+fn parse_memory_limit(text: &str) -> Option<u64> {
+    let mut parts = text.split_whitespace();
+    let value = parts.next()?.parse::<f64>().ok()?;
+    let mb = match parts.next().unwrap_or("MB").to_uppercase().chars().next() {
+        Some('K') => value / 1024.0,
+        Some('G') => value * 1024.0,
+        _ => value,
+    };
+    Some(mb as u64)
+}
+
+/// 問題ページの問題文から配点(`配点 : 100 点`・`Score : 100 points`)を取得する
+///
+/// 配点が記載されていない場合は `None` を返す。
+fn parse_score(document: &Html) -> Option<u64> {
+    let p_selector = Selector::parse("#task-statement p").unwrap();
+    document.select(&p_selector).find_map(|element| {
+        let text = element.text().collect::<String>();
+        let (label, value) = text.split_once(':')?;
+        if !matches!(label.trim(), "Score" | "配点") {
+            return None;
+        }
+        value.split_whitespace().next()?.parse::<u64>().ok()
     })
 }
 
+/// 問題ページの見出し(`span.h2`)から問題の見出し(`A - ?UPC` など)を取得する
+///
+/// 見出しに含まれる解説へのリンクなどの子要素は含めない。見出しがない場合は `None` を返す。
+fn parse_title(document: &Html) -> Option<String> {
+    let title_selector = Selector::parse("span.h2").unwrap();
+    let title = document.select(&title_selector).next()?;
+    let text = title
+        .children()
+        .filter_map(|child| child.value().as_text())
+        .map(|text| text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
 /// AtCoderの問題ページのHTMLからサンプル入出力データを抽出する
 ///
+/// 設定 `sample_lang` の言語の問題文(`span.lang-en` または `span.lang-ja`)からサンプルを取得する。
+/// その言語の問題文にサンプルがない場合はもう一方の言語、どちらもない場合はページ全体から取得する。
+///
 /// # 引数
 /// - `document`: AtCoderの問題ページの `Html` オブジェクト
 ///
 /// # 戻り値
 /// - `Ok(Vec<Sample>)`: 抽出されたサンプル入出力のリスト
 /// - `Err(Box<dyn Error>)`: エラーが発生した場合
+fn parse_samples(document: &Html) -> Result<Vec<Sample>, Box<dyn Error>> {
+    parse_samples_with_lang(document, &get_sample_lang())
+}
+
+/// `lang` の問題文を優先してサンプル入出力データを抽出する
+///
+/// 1 つの言語の問題文の中だけを解析するため、日本語と英語の両方に含まれるサンプルが重複しない。
+fn parse_samples_with_lang(document: &Html, lang: &str) -> Result<Vec<Sample>, Box<dyn Error>> {
+    let other = if lang == "ja" { "en" } else { "ja" };
+    for lang in [lang, other] {
+        let selector = Selector::parse(&format!("span.lang-{}", lang)).unwrap();
+        let samples = document.select(&selector).find_map(|container| {
+            parse_samples_in(container)
+                .ok()
+                .filter(|samples| !samples.is_empty())
+        });
+        if let Some(samples) = samples {
+            return Ok(samples);
+        }
+    }
+    // 言語ごとの問題文に分かれていないページでは、日本語と英語の両方に含まれる同じサンプルを 1 つにまとめる
+    let mut samples = Vec::new();
+    for sample in parse_samples_in(document.root_element())? {
+        if !samples.contains(&sample) {
+            samples.push(sample);
+        }
+    }
+    Ok(samples)
+}
+
+/// 要素の中からサンプル入出力データを抽出する
 ///
 /// # 処理の流れ
-/// 1. `h3` タグを解析し、"Sample Input" または "Sample Output" を検索する
+/// 1. `h3` タグを解析し、"Sample Input"・"Sample Output"(日本語の問題文では「入力例」・「出力例」)を検索する
 /// 2. `pre` タグの内容を対応する入力または出力として格納する
 /// 3. 入力と出力がペアになっているかを検証し、ペアが崩れている場合はエラーを返す
 ///
@@ -210,7 +1463,7 @@ pub async fn get_problem_list(
 /// - `h3` タグが見つからない場合 → `"入力データが見つかりません (h3タグが存在しません)"`
 /// - `pre` タグが見つからない場合 → `"入力データが見つかりません (preタグが存在しません)"`
 /// - 入力と出力の数が一致しない場合 → `"入出力のペアが揃っていません"`
-fn parse_samples(document: &Html) -> Result<Vec<Sample>, Box<dyn Error>> {
+fn parse_samples_in(root: ElementRef) -> Result<Vec<Sample>, Box<dyn Error>> {
     let h3_selector = Selector::parse("h3").unwrap();
     let mut samples = Vec::new();
     let mut inputs = Vec::new();
@@ -219,13 +1472,13 @@ fn parse_samples(document: &Html) -> Result<Vec<Sample>, Box<dyn Error>> {
     let mut found_h3 = false;
     let mut found_pre = false;
 
-    for element in document.select(&h3_selector) {
+    for element in root.select(&h3_selector) {
         let text = element.text().collect::<String>().trim().to_string();
         found_h3 = true;
 
-        if text.contains("Sample Input") {
+        if text.contains("Sample Input") || text.contains("入力例") {
             current_mode = Some("input");
-        } else if text.contains("Sample Output") {
+        } else if text.contains("Sample Output") || text.contains("出力例") {
             current_mode = Some("output");
         } else {
             current_mode = None;
@@ -288,8 +1541,8 @@ fn parse_samples(document: &Html) -> Result<Vec<Sample>, Box<dyn Error>> {
 /// # エラーの可能性
 /// - `contest_name` や `problem_name` に無効な文字（`?`, `/`, `\` など）が含まれている場合
 /// - ディレクトリの作成に失敗した場合（権限不足など）
-fn create_contest_directory(
-    work_dir: &PathBuf,
+pub fn create_contest_directory(
+    work_dir: &Path,
     contest_info: &ContestInfo,
 ) -> Result<(), Box<dyn Error>> {
     fn is_valid_directory_name(name: &str) -> bool {
@@ -315,6 +1568,7 @@ fn create_contest_directory(
 ///
 /// # 引数
 /// - `work_dir`: 作業ディレクトリの `PathBuf`
+/// - `template_dir`: テンプレートディレクトリ (`template` のプロファイルを解決したもの)
 /// - `contest_name`: コンテスト名 (`abc388` など)
 /// - `problems`: コンテスト内の問題リスト (`Vec<ProblemInfo>`)
 ///
@@ -324,28 +1578,46 @@ fn create_contest_directory(
 ///
 /// # 処理の流れ
 /// 1. `Cargo.toml` のパスを決定
-/// 2. `template/Cargo.toml` の [dependencies] セクションを読み込み（存在する場合）
+/// 2. `templates/Cargo.toml` の [dependencies] セクションを読み込み（存在する場合。テンプレートが存在しない場合は組み込みのもの）
 /// 3. `Cargo.toml` の [package] セクションを作成
 /// 4. 各問題ごとの `[[bin]]` セクションを追加
 /// 5. 各問題のタイムアウト設定 `[package.metadata.timeout]` を追加
-/// 6. `Cargo.toml` を作成し、書き込み
+/// 6. `Cargo.toml` を作成し、書き込み (1. 以外は `cargo_toml_content`)
 ///
 /// # エラーの可能性
 /// - `Cargo.toml` の作成に失敗した場合（権限不足など）
-/// - `template/Cargo.toml` の読み取りに失敗した場合（ファイルが破損しているなど）
-fn generate_cargo_toml(
-    work_dir: &PathBuf,
+/// - `templates/Cargo.toml` の読み取りに失敗した場合（ファイルが破損しているなど）
+pub fn generate_cargo_toml(
+    work_dir: &Path,
+    template_dir: &Path,
     contest_name: &str,
     problems: &[ProblemInfo],
 ) -> Result<(), Box<dyn Error>> {
     let cargo_toml_path = work_dir.join(format!("{}/Cargo.toml", contest_name));
-    let template_path = work_dir.join("template/Cargo.toml");
+    let content = cargo_toml_content(template_dir, contest_name, problems)?;
+    let mut file = File::create(&cargo_toml_path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// コンテスト用の `Cargo.toml` の内容を返す (`generate_cargo_toml` で書き込む内容)
+///
+/// # エラーの可能性
+/// - `templates/Cargo.toml` の読み取りに失敗した場合
+pub fn cargo_toml_content(
+    template_dir: &Path,
+    contest_name: &str,
+    problems: &[ProblemInfo],
+) -> Result<String, Box<dyn Error>> {
+    let template_path = template_dir.join("Cargo.toml");
     let mut cargo_toml_content = String::new();
-    // templateの[dependencies]を読み込む
+    // templateの[dependencies]を読み込む (テンプレート自体が存在しない場合は組み込みのテンプレート)
     let mut dependencies_content = String::new();
     if template_path.exists() {
         let mut template_file = File::open(template_path)?;
         template_file.read_to_string(&mut dependencies_content)?;
+    } else if !template_dir.join("main.rs").exists() {
+        dependencies_content.push_str(DEFAULT_CARGO_TOML);
     }
 
     // [package]
@@ -385,16 +1657,16 @@ path = "{}/main.rs"
     cargo_toml_content.push_str(&timeout_content);
     cargo_toml_content.push_str("\n");
     cargo_toml_content.push_str(&dependencies_content);
-
-    let mut file = File::create(&cargo_toml_path)?;
-    file.write_all(cargo_toml_content.as_bytes())?;
-    Ok(())
+    Ok(cargo_toml_content)
 }
 
 /// `main.rs` を問題ごとのディレクトリにコピーする
 ///
+/// テンプレートの `main.rs` が存在しない場合は、組み込みのテンプレート(`init` で作成するものと同じ)を書き込む。
+///
 /// # 引数
 /// - `work_dir`: 作業ディレクトリの `PathBuf`
+/// - `template_dir`: テンプレートディレクトリ (`template` のプロファイルを解決したもの)
 /// - `contest_name`: コンテスト名 (`abc388` など)
 /// - `problem_name`: 問題名 (`a`, `b`, `c` など)
 ///
@@ -403,31 +1675,30 @@ path = "{}/main.rs"
 /// - `Err(Box<dyn Error>)`: エラー発生時
 ///
 /// # 処理の流れ
-/// 1. `templates/main.rs` を読み込む
+/// 1. `templates/main.rs` を読み込む (存在しない場合は組み込みのテンプレート)
 /// 2. コンテストディレクトリ内に `problem_name` のディレクトリを作成
-/// 3. `main.rs` をコピー
+/// 3. `main.rs` を書き込む
 ///
 /// # エラーの可能性
-/// - `templates/main.rs` が存在しない場合
 /// - ディレクトリの作成に失敗した場合
-/// - ファイルのコピーに失敗した場合
-fn create_main_rs(
-    work_dir: &PathBuf,
+/// - ファイルの読み込み・書き込みに失敗した場合
+pub fn create_main_rs(
+    work_dir: &Path,
+    template_dir: &Path,
     contest_name: &str,
     problem_name: &str,
 ) -> Result<(), Box<dyn Error>> {
-    let template_path = work_dir.join("templates/main.rs");
     let problem_dir = work_dir.join(contest_name).join(problem_name);
     let main_rs_path = problem_dir.join("main.rs");
 
-    if !template_path.exists() {
-        return Err("テンプレート main.rs が見つかりません".into());
-    }
+    let template = read_template_main_rs(template_dir)?;
     if !problem_dir.exists() {
-        println!("Creating problem directory: {:?}", problem_dir);
+        if !is_json() {
+            println!("Creating problem directory: {:?}", problem_dir);
+        }
         fs::create_dir_all(&problem_dir)?;
     }
-    match fs::copy(&template_path, &main_rs_path) {
+    match fs::write(&main_rs_path, template) {
         Ok(_) => Ok(()),
         Err(e) => {
             eprintln!("Error: Failed to copy main.rs - {}", e);
@@ -436,6 +1707,15 @@ fn create_main_rs(
     }
 }
 
+/// テンプレートの `main.rs` を読み込む。存在しない場合は組み込みのテンプレートを返す
+pub fn read_template_main_rs(template_dir: &Path) -> Result<String, Box<dyn Error>> {
+    let template_path = template_dir.join("main.rs");
+    if !template_path.exists() {
+        return Ok(DEFAULT_MAIN_RS.to_string());
+    }
+    Ok(fs::read_to_string(&template_path)?)
+}
+
 /// サンプル入出力ファイル (`tests/`) を作成する
 ///
 /// # 引数
@@ -456,15 +1736,17 @@ fn create_main_rs(
 /// # エラーの可能性
 /// - `tests/` ディレクトリの作成に失敗した場合
 /// - ファイルの作成や書き込みに失敗した場合
-fn create_sample_files(
-    work_dir: &PathBuf,
+pub fn create_sample_files(
+    work_dir: &Path,
     contest_name: &str,
     problem_name: &str,
     samples: &[Sample],
 ) -> Result<(), Box<dyn Error>> {
     let tests_dir = work_dir.join(contest_name).join(problem_name).join("tests");
     if !tests_dir.exists() {
-        println!("Creating tests directory: {:?}", tests_dir);
+        if !is_json() {
+            println!("Creating tests directory: {:?}", tests_dir);
+        }
         fs::create_dir_all(&tests_dir)?;
     }
 
@@ -473,18 +1755,86 @@ fn create_sample_files(
         let output_file_path = tests_dir.join(format!("sample_{}.out", i + 1));
 
         // サンプル入力ファイルを作成
-        println!("Creating input sample file: {:?}", input_file_path);
+        if !is_json() {
+            println!("Creating input sample file: {:?}", input_file_path);
+        }
         let mut input_file = File::create(&input_file_path)?;
         input_file.write_all(sample.input.as_bytes())?;
 
         // サンプル出力ファイルを作成
-        println!("Creating output sample file: {:?}", output_file_path);
+        if !is_json() {
+            println!("Creating output sample file: {:?}", output_file_path);
+        }
         let mut output_file = File::create(&output_file_path)?;
         output_file.write_all(sample.output.as_bytes())?;
     }
     Ok(())
 }
 
+/// `templates/rustfmt.toml` をコンテストディレクトリにコピーする
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリの `PathBuf`
+/// - `template_dir`: テンプレートディレクトリ (`template` のプロファイルを解決したもの)
+/// - `contest_name`: コンテスト名 (`abc388` など)
+///
+/// # 戻り値
+/// - `Ok(true)`: コピーした場合
+/// - `Ok(false)`: テンプレートが存在しないためコピーしなかった場合
+/// - `Err(Box<dyn Error>)`: ファイルのコピーに失敗した場合
+pub fn copy_rustfmt_toml(
+    work_dir: &Path,
+    template_dir: &Path,
+    contest_name: &str,
+) -> Result<bool, Box<dyn Error>> {
+    let template_path = template_dir.join("rustfmt.toml");
+    if !template_path.exists() {
+        return Ok(false);
+    }
+    fs::copy(
+        &template_path,
+        work_dir.join(contest_name).join("rustfmt.toml"),
+    )?;
+    Ok(true)
+}
+
+/// コンテストディレクトリにジャッジの rustc に固定する `rust-toolchain.toml` を作成する
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリの `PathBuf`
+/// - `template_dir`: テンプレートディレクトリ (`template` のプロファイルを解決したもの)
+/// - `contest_name`: コンテスト名 (`abc388` など)
+/// - `channel`: 設定 `rust_toolchain` の値
+///
+/// # 処理の流れ
+/// 1. `channel` が `none` の場合は作成しない
+/// 2. `channel` を指定した場合はそのバージョンに固定する
+/// 3. 未指定の場合は `templates/rust-toolchain.toml` をコピーし、存在しなければジャッジの rustc のバージョンに固定する
+///
+/// # 戻り値
+/// - `Ok(true)`: 作成した場合
+/// - `Ok(false)`: `none` のため作成しなかった場合
+pub fn create_rust_toolchain(
+    work_dir: &Path,
+    template_dir: &Path,
+    contest_name: &str,
+    channel: Option<&str>,
+) -> Result<bool, Box<dyn Error>> {
+    let content = match channel {
+        Some("none") => return Ok(false),
+        Some(channel) => rust_toolchain_toml(channel),
+        None => match fs::read_to_string(template_dir.join("rust-toolchain.toml")) {
+            Ok(content) => content,
+            Err(_) => rust_toolchain_toml(&get_judge_rustc_version()),
+        },
+    };
+    fs::write(
+        work_dir.join(contest_name).join("rust-toolchain.toml"),
+        content,
+    )?;
+    Ok(true)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -518,10 +1868,63 @@ mod test {
         assert!(result.is_err());
     }
 
-    #[test]
-    fn test_parse_samples_success() {
+    #[test]
+    fn test_parse_samples_success() {
+        let html = Html::parse_document(
+            r#"
+<h3>Sample Input 1</h3><pre>Kyoto
+</pre>
+<h3>Sample Output 1</h3><pre>KUPC
+</pre>
+<h3>Sample Input 2</h3><pre>Tohoku
+</pre>
+<h3>Sample Output 2</h3><pre>TUPC
+</pre>
+        "#,
+        );
+
+        let result = parse_samples(&html);
+        assert!(result.is_ok());
+        let result = result.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].input, "Kyoto\n");
+        assert_eq!(result[0].output, "KUPC\n");
+        assert_eq!(result[1].input, "Tohoku\n");
+        assert_eq!(result[1].output, "TUPC\n");
+    }
+
+    #[test]
+    fn test_parse_samples_japanese() {
+        // 英語の問題文がない場合は「入力例」・「出力例」から取得する
+        let html = Html::parse_document(
+            r#"
+<span class="lang-ja">
+<h3>入力例 1</h3><pre>Kyoto
+</pre>
+<h3>出力例 1</h3><pre>KUPC
+</pre>
+</span>
+        "#,
+        );
+        let result = parse_samples(&html).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].input, "Kyoto\n");
+        assert_eq!(result[0].output, "KUPC\n");
+
+        // 日本語と英語の両方に含まれるサンプルは重複させない
         let html = Html::parse_document(
             r#"
+<span class="lang-ja">
+<h3>入力例 1</h3><pre>Kyoto
+</pre>
+<h3>出力例 1</h3><pre>KUPC
+</pre>
+<h3>入力例 2</h3><pre>Tohoku
+</pre>
+<h3>出力例 2</h3><pre>TUPC
+</pre>
+</span>
+<span class="lang-en">
 <h3>Sample Input 1</h3><pre>Kyoto
 </pre>
 <h3>Sample Output 1</h3><pre>KUPC
@@ -530,19 +1933,64 @@ mod test {
 </pre>
 <h3>Sample Output 2</h3><pre>TUPC
 </pre>
+</span>
         "#,
         );
-
-        let result = parse_samples(&html);
-        assert!(result.is_ok());
-        let result = result.unwrap();
+        let result = parse_samples(&html).unwrap();
         assert_eq!(result.len(), 2);
-        assert_eq!(result[0].input, "Kyoto\n");
-        assert_eq!(result[0].output, "KUPC\n");
         assert_eq!(result[1].input, "Tohoku\n");
         assert_eq!(result[1].output, "TUPC\n");
     }
 
+    #[test]
+    fn test_parse_samples_with_lang() {
+        let html = Html::parse_document(
+            r#"
+<span class="lang">
+<span class="lang-ja">
+<h3>入力例 1</h3><pre>1 1
+</pre>
+<h3>出力例 1</h3><pre>はい
+</pre>
+</span>
+<span class="lang-en">
+<h3>Sample Input 1</h3><pre>1 1
+</pre>
+<h3>Sample Output 1</h3><pre>Yes
+</pre>
+<h3>Sample Input 2</h3><pre>1 1
+</pre>
+<h3>Sample Output 2</h3><pre>Yes
+</pre>
+</span>
+</span>
+        "#,
+        );
+        // 指定した言語の問題文のサンプルのみを取得し、同じ内容のサンプルもそのまま残す
+        let result = parse_samples_with_lang(&html, "en").unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[1].output, "Yes\n");
+        let result = parse_samples_with_lang(&html, "ja").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].output, "はい\n");
+
+        // 指定した言語の問題文にサンプルがない場合はもう一方の言語から取得する
+        let html = Html::parse_document(
+            r#"
+<span class="lang-ja"><h3>問題文</h3><p>日本語のみ</p></span>
+<span class="lang-en">
+<h3>Sample Input 1</h3><pre>1
+</pre>
+<h3>Sample Output 1</h3><pre>2
+</pre>
+</span>
+        "#,
+        );
+        let result = parse_samples_with_lang(&html, "ja").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].input, "1\n");
+    }
+
     #[test]
     fn test_parse_samples_missing_output() {
         let html = Html::parse_document(
@@ -727,6 +2175,248 @@ mod test {
         assert_eq!(problem_b.samples[1].output, "10100\n10200\n10300\n10400\n");
     }
 
+    #[test]
+    fn test_parse_start_time() {
+        let html = Html::parse_document(
+            r##"<small class="contest-duration">Contest Duration:
+            <a href="#"><time class="fixtime fixtime-full">2025-04-05 21:00:00+0900</time></a> -
+            <a href="#"><time class="fixtime fixtime-full">2025-04-05 22:40:00+0900</time></a></small>"##,
+        );
+        assert_eq!(
+            parse_start_time(&html).unwrap().to_rfc3339(),
+            "2025-04-05T21:00:00+09:00"
+        );
+        assert!(parse_start_time(&Html::parse_document("<div></div>")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_contest_start() {
+        let mut server = Server::new_async().await;
+        let base_url = server.url();
+        let _contest = server
+            .mock("GET", "/contests/started")
+            .with_status(200)
+            .with_body(r#"<time class="fixtime fixtime-full">2025-04-05 21:00:00+0900</time>"#)
+            .create();
+        let tasks = server
+            .mock("GET", "/contests/started/tasks")
+            .with_status(200)
+            .with_body(
+                r#"<table><tbody><tr>
+                <td class="text-center no-break"><a href="/contests/started/tasks/started_a">A</a></td>
+                <td class="text-right">2 sec</td>
+                </tr></tbody></table>"#,
+            )
+            .expect(1)
+            .create();
+        wait_for_contest_start(&base_url, "started", Duration::from_millis(1), 3)
+            .await
+            .unwrap();
+        tasks.assert();
+
+        // 問題一覧が公開されない場合は指定した回数で諦める
+        let _contest = server
+            .mock("GET", "/contests/hidden")
+            .with_status(200)
+            .with_body("<html></html>")
+            .create();
+        let hidden_tasks = server
+            .mock("GET", "/contests/hidden/tasks")
+            .with_status(404)
+            .expect(3)
+            .create();
+        let error = wait_for_contest_start(&base_url, "hidden", Duration::from_millis(1), 3)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("3 回取得"));
+        hidden_tasks.assert();
+    }
+
+    #[test]
+    fn test_parse_memory_limit() {
+        assert_eq!(parse_memory_limit("1024 MB"), Some(1024));
+        assert_eq!(parse_memory_limit(" 256 MiB "), Some(256));
+        assert_eq!(parse_memory_limit("2 GB"), Some(2048));
+        assert_eq!(parse_memory_limit("-"), None);
+    }
+
+    #[test]
+    fn test_parse_score() {
+        let html = Html::parse_document(
+            r#"<div id="task-statement"><span class="lang-ja"><p>配点 : <var>100</var> 点</p></span></div>"#,
+        );
+        assert_eq!(parse_score(&html), Some(100));
+        let html = Html::parse_document(r#"<div id="task-statement"><p>Print N.</p></div>"#);
+        assert_eq!(parse_score(&html), None);
+    }
+
+    #[test]
+    fn test_parse_title() {
+        let html = Html::parse_document(
+            r#"<span class="h2">
+                A - ?UPC
+                <a class="btn btn-default btn-sm" href="/contests/abc388/editorial">Editorial</a>
+            </span>"#,
+        );
+        assert_eq!(parse_title(&html), Some("A - ?UPC".to_string()));
+        assert_eq!(parse_title(&Html::parse_document("<div></div>")), None);
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(
+            parse_target("abc388", None).unwrap(),
+            ("abc388".to_string(), None)
+        );
+        assert_eq!(
+            parse_target("abc388/C", None).unwrap(),
+            ("abc388".to_string(), Some("c".to_string()))
+        );
+        assert_eq!(
+            parse_target("abc388", Some("ex")).unwrap(),
+            ("abc388".to_string(), Some("ex".to_string()))
+        );
+        assert!(parse_target("abc388/c", Some("d")).is_err());
+        assert!(parse_target("abc388/", None).is_err());
+        assert!(parse_target("/c", None).is_err());
+    }
+
+    #[test]
+    fn test_resolve_download_dir() {
+        let work_dir = tempfile::tempdir().expect("");
+        let dir = resolve_download_dir(work_dir.path(), Some(Path::new("contests"))).unwrap();
+        assert_eq!(dir, work_dir.path().join("contests"));
+        assert!(dir.is_dir());
+
+        let output_dir = tempfile::tempdir().expect("");
+        assert_eq!(
+            resolve_download_dir(work_dir.path(), Some(output_dir.path())).unwrap(),
+            output_dir.path()
+        );
+    }
+
+    #[test]
+    fn test_format_download_plan() {
+        let work_dir = tempfile::tempdir().expect("");
+        let contest_dir = work_dir.path().join("abc388");
+        let problem = |name: &str, samples: usize| ProblemInfo {
+            problem_name: name.to_string(),
+            timeout: 2000,
+            memory_limit: None,
+            score: None,
+            samples: vec![
+                Sample {
+                    input: "1\n".to_string(),
+                    output: "2\n".to_string(),
+                };
+                samples
+            ],
+            statement: None,
+            title: None,
+            task_screen_name: None,
+        };
+        let contest_info = ContestInfo {
+            contest_name: "abc388".to_string(),
+            problems: vec![problem("a", 2), problem("b", 3)],
+        };
+        let cargo_toml = cargo_toml_content(
+            &work_dir.path().join("templates"),
+            "abc388",
+            &contest_info.problems,
+        )
+        .unwrap();
+        assert!(cargo_toml.contains("\"b\" = 2000"));
+
+        let plan = format_download_plan(&contest_dir, Some(&cargo_toml), &contest_info, false);
+        assert!(plan.contains("├── a\n│   ├── main.rs\n│   └── tests  (2 samples)\n"));
+        assert!(plan.contains("└── b\n    ├── main.rs\n    └── tests  (3 samples)\n"));
+        assert!(plan.contains("--- Cargo.toml ---\n[package]"));
+        // 何も書き込まない
+        assert!(!contest_dir.exists());
+
+        fs::create_dir_all(contest_dir.join("a")).unwrap();
+        fs::write(contest_dir.join("a/main.rs"), "").unwrap();
+        let plan = format_download_plan(&contest_dir, None, &contest_info, false);
+        assert!(plan.contains("│   ├── main.rs  (keep)\n"));
+        assert!(plan.contains("(update [[bin]] and timeouts)"));
+        assert!(!plan.contains("--- Cargo.toml ---"));
+    }
+
+    #[test]
+    fn test_parse_target_url() {
+        assert_eq!(
+            parse_target("https://atcoder.jp/contests/abc388", None).unwrap(),
+            ("abc388".to_string(), None)
+        );
+        assert_eq!(
+            parse_target("https://atcoder.jp/contests/abc388/tasks?lang=en", None).unwrap(),
+            ("abc388".to_string(), None)
+        );
+        assert_eq!(
+            parse_target("https://atcoder.jp/contests/abc388/tasks/abc388_c", None).unwrap(),
+            ("abc388".to_string(), Some("c".to_string()))
+        );
+        assert!(parse_target(
+            "https://atcoder.jp/contests/abc388/tasks/abc388_c",
+            Some("d")
+        )
+        .is_err());
+        assert!(parse_target("https://atcoder.jp/home", None).is_err());
+        assert!(parse_target("https://atcoder.jp/contests/", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_problem() {
+        let mut server = Server::new_async().await;
+        let base_url = server.url();
+        let mock_problem_list_html = r#"
+        <table>
+            <tbody>
+                <tr>
+                    <td class="text-center no-break"><a href="/contests/test/tasks/test_a">A</a></td>
+                    <td class="text-right">1 sec</td>
+                </tr>
+                <tr>
+                    <td class="text-center no-break"><a href="/contests/test/tasks/test_ex">Ex</a></td>
+                    <td class="text-right">3 sec</td>
+                    <td class="text-right">1024 MB</td>
+                </tr>
+            </tbody>
+        </table>
+    "#;
+        let _mock_problem_list = server
+            .mock("GET", "/contests/test/tasks")
+            .with_status(200)
+            .with_body(mock_problem_list_html)
+            .create();
+        let mock_problem_a = server
+            .mock("GET", "/contests/test/tasks/test_a")
+            .with_status(200)
+            .expect(0)
+            .create();
+        let _mock_problem_ex = server
+            .mock("GET", "/contests/test/tasks/test_ex")
+            .with_status(200)
+            .with_body(
+                "<div id=\"task-statement\"><p>Score : <var>600</var> points</p></div>\
+                 <h3>Sample Input 1</h3><pre>1\n</pre><h3>Sample Output 1</h3><pre>2\n</pre>",
+            )
+            .create();
+
+        let problem = get_problem(&base_url, "test", "ex").await.unwrap();
+        assert_eq!(problem.problem_name, "ex");
+        assert_eq!(problem.timeout, 3000);
+        assert_eq!(problem.memory_limit, Some(1024));
+        assert_eq!(problem.score, Some(600));
+        assert_eq!(problem.task_screen_name.as_deref(), Some("test_ex"));
+        assert_eq!(problem.samples[0].input, "1\n");
+        assert_eq!(problem.samples[0].output, "2\n");
+        // 指定していない問題のページは取得しない
+        mock_problem_a.assert();
+
+        assert!(get_problem(&base_url, "test", "z").await.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_problem_list_no_problems() {
         let mut server = Server::new_async().await;
@@ -756,6 +2446,38 @@ mod test {
         assert!(contest_info.problems.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_problem_list_problem_page_error() {
+        let mut server = Server::new_async().await;
+        let base_url = server.url();
+        let contest_name = "test";
+        let mock_problem_list_html = r#"
+            <table><tbody>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_a">A</a></td><td class="text-right">2 sec</td></tr>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_b">B</a></td><td class="text-right">2 sec</td></tr>
+            </tbody></table>
+        "#;
+        let _mock_problem_list = server
+            .mock("GET", "/contests/test/tasks")
+            .with_status(200)
+            .with_body(mock_problem_list_html)
+            .create();
+        let _mock_problem_a = server
+            .mock("GET", "/contests/test/tasks/test_a")
+            .with_status(200)
+            .with_body(
+                "<h3>Sample Input 1</h3><pre>1\n</pre><h3>Sample Output 1</h3><pre>2\n</pre>",
+            )
+            .create();
+        let _mock_problem_b = server
+            .mock("GET", "/contests/test/tasks/test_b")
+            .with_status(404)
+            .create();
+
+        // 一部の問題ページの取得に失敗した場合はパニックせずエラーを返す
+        assert!(get_problem_list(&base_url, contest_name).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_problem_list_invalid_html() {
         let mut server = Server::new_async().await;
@@ -784,6 +2506,146 @@ mod test {
         assert!(contest_info.problems.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_get_problem_list_tasks_print() {
+        let mut server = Server::new_async().await;
+        let base_url = server.url();
+
+        let _mock_problem_list = server
+            .mock("GET", "/contests/test/tasks")
+            .with_status(200)
+            .with_body(
+                r#"<table><tbody>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_a">A</a></td><td class="text-right">2 sec</td></tr>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_b">B</a></td><td class="text-right">3 sec</td></tr>
+                </tbody></table>"#,
+            )
+            .create();
+        let _mock_tasks_print = server
+            .mock("GET", "/contests/test/tasks_print")
+            .with_status(200)
+            .with_body(
+                r#"<div class="row">
+                <div class="col-sm-12">
+                  <span class="h2">A - First</span>
+                  <div id="task-statement"><p>Score : <var>100</var> points</p>
+                    <h3>Sample Input 1</h3><pre>1
+</pre><h3>Sample Output 1</h3><pre>2
+</pre></div>
+                </div>
+                <div class="col-sm-12">
+                  <span class="h2">B - Second</span>
+                  <div id="task-statement">
+                    <h3>Sample Input 1</h3><pre>3
+</pre><h3>Sample Output 1</h3><pre>4
+</pre><h3>Sample Input 2</h3><pre>5
+</pre><h3>Sample Output 2</h3><pre>6
+</pre></div>
+                </div>
+                </div>"#,
+            )
+            .create();
+        // 印刷用ページを取得できた場合は問題ページを個別に取得しない
+        let mock_problem_a = server
+            .mock("GET", "/contests/test/tasks/test_a")
+            .expect(0)
+            .create();
+
+        let contest_info = get_problem_list(&base_url, "test").await.unwrap();
+        assert_eq!(contest_info.problems.len(), 2);
+        let a = &contest_info.problems[0];
+        assert_eq!((a.timeout, a.score), (2000, Some(100)));
+        assert_eq!(a.samples[0].output, "2\n");
+        let b = &contest_info.problems[1];
+        assert_eq!(b.problem_name, "b");
+        assert_eq!(b.samples.len(), 2);
+        assert_eq!(b.samples[1].input, "5\n");
+        assert_eq!(b.task_screen_name.as_deref(), Some("test_b"));
+        mock_problem_a.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_problems() {
+        let mut server = Server::new_async().await;
+        let base_url = server.url();
+        let contest_dir = tempfile::tempdir().expect("");
+        fs::create_dir_all(contest_dir.path().join("a/tests")).unwrap();
+        fs::write(contest_dir.path().join("a/main.rs"), "").unwrap();
+        fs::write(contest_dir.path().join("a/tests/sample_1.in"), "1\n").unwrap();
+
+        let _mock_problem_list = server
+            .mock("GET", "/contests/test/tasks")
+            .with_status(200)
+            .with_body(
+                r#"<table><tbody>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_a">A</a></td><td class="text-right">2 sec</td></tr>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_b">B</a></td><td class="text-right">2 sec</td></tr>
+                <tr><td class="text-center no-break"><a href="/contests/test/tasks/test_c">C</a></td><td class="text-right">2 sec</td></tr>
+                </tbody></table>"#,
+            )
+            .create();
+        // 取得済みの問題のページは取得しない
+        let mock_problem_a = server
+            .mock("GET", "/contests/test/tasks/test_a")
+            .expect(0)
+            .create();
+        let _mock_problem_b = server
+            .mock("GET", "/contests/test/tasks/test_b")
+            .with_status(200)
+            .with_body(
+                "<h3>Sample Input 1</h3><pre>1\n</pre><h3>Sample Output 1</h3><pre>2\n</pre>",
+            )
+            .create();
+        let _mock_problem_c = server
+            .mock("GET", "/contests/test/tasks/test_c")
+            .with_status(503)
+            .create();
+
+        // 一部の問題の取得に失敗しても、取得できた問題を返す
+        let fetched = get_missing_problems(&base_url, "test", contest_dir.path(), true)
+            .await
+            .unwrap();
+        assert_eq!(fetched.skipped, vec!["a"]);
+        let names: Vec<&str> = fetched
+            .contest_info
+            .problems
+            .iter()
+            .map(|problem| problem.problem_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["b"]);
+        assert_eq!(fetched.failed.len(), 1);
+        assert_eq!(fetched.failed[0].0, "c");
+        mock_problem_a.assert();
+    }
+
+    #[tokio::test]
+    async fn test_get_problem_list_contest_not_found() {
+        let mut server = Server::new_async().await;
+        let base_url = server.url();
+
+        let _mock_problem_list = server
+            .mock("GET", "/contests/abc387x/tasks")
+            .with_status(404)
+            .create();
+        let _mock_contests = server
+            .mock("GET", "/contests/")
+            .with_status(200)
+            .with_body(
+                r#"<table><tbody>
+                <tr><td><a href="/contests/abc387">AtCoder Beginner Contest 387</a></td></tr>
+                <tr><td><a href="/contests/arc191">AtCoder Regular Contest 191</a></td></tr>
+                </tbody></table>"#,
+            )
+            .create();
+
+        // 問題一覧ページが存在しない場合はコンテスト名の誤りとして、近いコンテスト名を提示する
+        let error = get_problem_list(&base_url, "abc387x").await.unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("コンテスト abc387x が見つかりません"));
+        assert!(error.to_string().contains("候補: abc387)"));
+    }
+
     #[tokio::test]
     async fn test_get_problem_list_network_error() {
         let mut server = Server::new_async().await;
@@ -799,6 +2661,136 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_fetch_html_with_retry() {
+        let mut server = Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/unavailable")
+            .with_status(503)
+            .expect(3)
+            .create();
+        let not_found = server
+            .mock("GET", "/not_found")
+            .with_status(404)
+            .expect(1)
+            .create();
+
+        // 5xx は指定した回数まで再試行する
+        let url = format!("{}/unavailable", server.url());
+        let error = fetch_html_with_retry(&url, 3, Duration::from_millis(1), None, None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains(&url));
+        assert!(error.to_string().contains("3 回試行"));
+        unavailable.assert();
+
+        // 4xx は再試行しない
+        let url = format!("{}/not_found", server.url());
+        assert!(
+            fetch_html_with_retry(&url, 3, Duration::from_millis(1), None, None)
+                .await
+                .is_err()
+        );
+        not_found.assert();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_html_with_session() {
+        let mut server = Server::new_async().await;
+        let _authenticated = server
+            .mock("GET", "/contests/live/tasks")
+            .match_header("cookie", "REVEL_SESSION=abc;")
+            .with_status(200)
+            .with_body("<html>tasks</html>")
+            .create();
+        let _anonymous = server
+            .mock("GET", "/contests/live/tasks")
+            .match_header("cookie", mockito::Matcher::Missing)
+            .with_status(404)
+            .create();
+        let _redirect = server
+            .mock("GET", "/contests/private/tasks")
+            .with_status(302)
+            .with_header("location", "/login?continue=%2Fcontests%2Fprivate%2Ftasks")
+            .create();
+        let _login = server
+            .mock("GET", "/login")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_body("<html>login</html>")
+            .create();
+
+        let url = format!("{}/contests/live/tasks", server.url());
+        let body = fetch_html_with_retry(
+            &url,
+            1,
+            Duration::from_millis(1),
+            None,
+            Some("REVEL_SESSION=abc;"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(body, "<html>tasks</html>");
+
+        // 未ログインで閲覧権限がない場合はログインを促す
+        let error = fetch_html_with_retry(&url, 3, Duration::from_millis(1), None, None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("cargo atc login"));
+
+        // ログインページにリダイレクトされた場合も権限がないものとして扱う
+        let url = format!("{}/contests/private/tasks", server.url());
+        let error = fetch_html_with_retry(&url, 1, Duration::from_millis(1), None, None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("閲覧する権限がありません"));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_html_with_cache() {
+        let mut server = Server::new_async().await;
+        let cache_dir = tempfile::tempdir().expect("");
+        let fresh = server
+            .mock("GET", "/page")
+            .match_header("if-none-match", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_header("etag", "\"v1\"")
+            .with_body("<html>cached</html>")
+            .expect(1)
+            .create();
+        let not_modified = server
+            .mock("GET", "/page")
+            .match_header("if-none-match", "\"v1\"")
+            .with_status(304)
+            .expect(1)
+            .create();
+
+        let url = format!("{}/page", server.url());
+        let fetch = || {
+            fetch_html_with_retry(
+                &url,
+                1,
+                Duration::from_millis(1),
+                Some(cache_dir.path()),
+                None,
+            )
+        };
+        assert_eq!(fetch().await.unwrap(), "<html>cached</html>");
+        // 2 回目は条件付きリクエストを送信し、304 の場合はキャッシュを返す
+        assert_eq!(fetch().await.unwrap(), "<html>cached</html>");
+        fresh.assert();
+        not_modified.assert();
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        let base_delay = Duration::from_millis(100);
+        for (attempt, expected) in [(1, 100), (2, 200), (3, 400)] {
+            let delay = backoff_delay(base_delay, attempt).as_millis();
+            assert!(expected <= delay && delay <= expected + expected / 4);
+        }
+    }
+
     #[test]
     fn test_create_contest_directory_success() {
         let work_dir = tempfile::tempdir().expect("");
@@ -820,16 +2812,26 @@ mod test {
                     problem_name: "test_1".to_string(),
                     timeout: 1000,
                     samples: sample.clone(),
+                    memory_limit: None,
+                    score: None,
+                    statement: None,
+                    title: None,
+                    task_screen_name: None,
                 },
                 ProblemInfo {
                     problem_name: "test_2".to_string(),
                     timeout: 2000,
                     samples: sample.clone(),
+                    memory_limit: None,
+                    score: None,
+                    statement: None,
+                    title: None,
+                    task_screen_name: None,
                 },
             ],
         };
 
-        let result = create_contest_directory(&work_dir.path().to_path_buf(), &contest_info);
+        let result = create_contest_directory(work_dir.path(), &contest_info);
         assert!(result.is_ok());
 
         // 結果の確認
@@ -854,9 +2856,14 @@ mod test {
                 problem_name: "a".to_string(),
                 timeout: 1000,
                 samples: vec![],
+                memory_limit: None,
+                score: None,
+                statement: None,
+                title: None,
+                task_screen_name: None,
             }],
         };
-        let result = create_contest_directory(&work_dir.path().to_path_buf(), &contest_info);
+        let result = create_contest_directory(work_dir.path(), &contest_info);
         assert!(result.is_err());
     }
 
@@ -869,9 +2876,14 @@ mod test {
                 problem_name: "b/c".to_string(), // 不正な文字を含む
                 timeout: 2000,
                 samples: vec![],
+                memory_limit: None,
+                score: None,
+                statement: None,
+                title: None,
+                task_screen_name: None,
             }],
         };
-        let result = create_contest_directory(&work_dir.path().to_path_buf(), &contest_info);
+        let result = create_contest_directory(work_dir.path(), &contest_info);
         assert!(result.is_err());
     }
 
@@ -886,16 +2898,31 @@ mod test {
                 problem_name: "a".to_string(),
                 timeout: 2000,
                 samples: vec![],
+                memory_limit: None,
+                score: None,
+                statement: None,
+                title: None,
+                task_screen_name: None,
             },
             ProblemInfo {
                 problem_name: "b".to_string(),
                 timeout: 2500,
                 samples: vec![],
+                memory_limit: None,
+                score: None,
+                statement: None,
+                title: None,
+                task_screen_name: None,
             },
         ];
         let contest_dir = work_dir.path().join(contest_name);
         let _ = fs::create_dir_all(contest_dir);
-        let result = generate_cargo_toml(&work_dir.path().to_path_buf(), contest_name, &problems);
+        let result = generate_cargo_toml(
+            work_dir.path(),
+            &work_dir.path().join("templates"),
+            contest_name,
+            &problems,
+        );
         assert!(result.is_ok());
         assert!(cargo_toml_path.exists());
         let cargo_content = fs::read_to_string(&cargo_toml_path).unwrap();
@@ -925,7 +2952,12 @@ mod test {
         fs::write(template_path, "fn main() { println!(\"Hello, world!\"); }").unwrap();
 
         // 実行
-        let result = create_main_rs(&work_dir.path().to_path_buf(), contest_name, problem_name);
+        let result = create_main_rs(
+            work_dir.path(),
+            &work_dir.path().join("templates"),
+            contest_name,
+            problem_name,
+        );
         assert!(result.is_ok());
 
         // `main.rs` が作成されているか確認
@@ -936,15 +2968,68 @@ mod test {
         assert_eq!(content, "fn main() { println!(\"Hello, world!\"); }");
     }
 
+    #[test]
+    fn test_create_rust_toolchain() {
+        let work_dir = tempfile::tempdir().expect("");
+        let work_dir_path = work_dir.path().to_path_buf();
+        let template_dir = work_dir.path().join("templates");
+        let toolchain_path = work_dir.path().join("abc388/rust-toolchain.toml");
+        fs::create_dir_all(work_dir.path().join("abc388")).unwrap();
+        fs::create_dir_all(&template_dir).unwrap();
+
+        assert!(
+            !create_rust_toolchain(&work_dir_path, &template_dir, "abc388", Some("none")).unwrap()
+        );
+        assert!(!toolchain_path.exists());
+
+        create_rust_toolchain(&work_dir_path, &template_dir, "abc388", Some("1.89.0")).unwrap();
+        assert_eq!(
+            fs::read_to_string(&toolchain_path).unwrap(),
+            "[toolchain]\nchannel = \"1.89.0\"\n"
+        );
+
+        fs::write(
+            template_dir.join("rust-toolchain.toml"),
+            "[toolchain]\nchannel = \"1.70.0\"\ncomponents = [\"clippy\"]\n",
+        )
+        .unwrap();
+        create_rust_toolchain(&work_dir_path, &template_dir, "abc388", None).unwrap();
+        assert!(fs::read_to_string(&toolchain_path)
+            .unwrap()
+            .contains("components = [\"clippy\"]"));
+    }
+
     #[test]
     fn test_create_main_rs_missing_template() {
         let work_dir = tempfile::tempdir().expect("");
         let contest_name = "test_contest";
         let problem_name = "test_problem";
-        let result = create_main_rs(&work_dir.path().to_path_buf(), contest_name, problem_name);
+        let result = create_main_rs(
+            work_dir.path(),
+            &work_dir.path().join("templates"),
+            contest_name,
+            problem_name,
+        );
 
-        // `templates/main.rs` が存在しない場合、エラーになることを確認
-        assert!(result.is_err());
+        // `templates/main.rs` が存在しない場合、組み込みのテンプレートを利用することを確認
+        assert!(result.is_ok());
+        let main_rs_path = work_dir
+            .path()
+            .join(contest_name)
+            .join(problem_name)
+            .join("main.rs");
+        assert_eq!(fs::read_to_string(main_rs_path).unwrap(), DEFAULT_MAIN_RS);
+
+        let result = generate_cargo_toml(
+            work_dir.path(),
+            &work_dir.path().join("templates"),
+            contest_name,
+            &[],
+        );
+        assert!(result.is_ok());
+        let cargo_toml =
+            fs::read_to_string(work_dir.path().join(contest_name).join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains(DEFAULT_CARGO_TOML));
     }
 
     #[test]
@@ -969,12 +3054,7 @@ mod test {
             },
         ];
 
-        let result = create_sample_files(
-            &work_dir.path().to_path_buf(),
-            contest_name,
-            problem_name,
-            &samples,
-        );
+        let result = create_sample_files(work_dir.path(), contest_name, problem_name, &samples);
         assert!(result.is_ok());
         for (i, sample) in samples.iter().enumerate() {
             let input_file_path = tests_path.join(format!("sample_{}.in", i + 1));
@@ -990,4 +3070,35 @@ mod test {
             assert_eq!(output_content, sample.output);
         }
     }
+
+    #[test]
+    fn test_copy_rustfmt_toml() {
+        let work_dir = tempfile::tempdir().expect("");
+        let contest_name = "test_contest";
+        fs::create_dir_all(work_dir.path().join(contest_name)).unwrap();
+
+        // テンプレートが存在しない場合はコピーしない
+        let result = copy_rustfmt_toml(
+            work_dir.path(),
+            &work_dir.path().join("templates"),
+            contest_name,
+        );
+        assert!(!result.unwrap());
+
+        fs::create_dir_all(work_dir.path().join("templates")).unwrap();
+        fs::write(
+            work_dir.path().join("templates/rustfmt.toml"),
+            "max_width = 80\n",
+        )
+        .unwrap();
+        let result = copy_rustfmt_toml(
+            work_dir.path(),
+            &work_dir.path().join("templates"),
+            contest_name,
+        );
+        assert!(result.unwrap());
+        let content =
+            fs::read_to_string(work_dir.path().join(contest_name).join("rustfmt.toml")).unwrap();
+        assert_eq!(content, "max_width = 80\n");
+    }
 }
@@ -0,0 +1,228 @@
+//! 問題の解説ページへのリンクを取得するモジュール
+//!
+//! コンテスト終了後に公開される解説を、問題ごとの解説一覧ページ(`tasks/<問題 ID>/editorial`)から取得し、
+//! `contest.json` の `editorials` と問題ディレクトリの `README.md` に記録する。
+//! 解説が公開されていない問題は記録しないため、公開後に再度実行すればよい。
+//!
+//! `contest.json` を参照するため、コンテストディレクトリで実行する。
+//!
+//! ```text
+//! abc388
+//! ├── contest.json  # problems[].editorials に解説ページへのリンクを保存
+//! └── a
+//!     └── README.md # 末尾の `## Editorials` に解説ページへのリンクを記載
+//! ```
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use std::{error::Error, fs, path::Path};
+
+use super::config::get_base_url;
+use super::download::fetch_html;
+use super::metadata::{load_contest_metadata, write_contest_metadata, EditorialLink};
+use super::output::{is_json, print_json};
+
+/// `README.md` の解説ページへのリンクを記載する節の見出し
+const README_EDITORIALS_HEADING: &str = "## Editorials";
+
+/// `--json` を指定した場合に出力する問題ごとの解説ページへのリンク
+#[derive(Serialize)]
+struct ProblemEditorials<'a> {
+    problem: &'a str,
+    editorials: &'a [EditorialLink],
+}
+
+/// 解説取得処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `contest.json` が存在するコンテストディレクトリのパス
+/// - `problem_name`: 対象の問題名。`None` の場合はコンテストの全問題を対象とする
+///
+/// # エラーの可能性
+/// - `contest.json` が存在しない、または指定した問題が `contest.json` に含まれない場合
+/// - 解説一覧ページの取得に失敗した場合
+/// - `contest.json`・`README.md` の書き込みに失敗した場合
+pub async fn execute(work_dir: &Path, problem_name: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut metadata = load_contest_metadata(work_dir)?.ok_or(
+        "contest.json が見つかりません。コンテストディレクトリで実行してください (`cargo atc download` で作成されます)",
+    )?;
+    if let Some(problem_name) = problem_name {
+        if metadata.problem(problem_name).is_none() {
+            return Err(format!(
+                "{} に問題 {} が見つかりません",
+                metadata.contest, problem_name
+            )
+            .into());
+        }
+    }
+
+    let base_url = get_base_url();
+    let contest_name = metadata.contest.clone();
+    let mut fetched = Vec::new();
+    for problem in metadata
+        .problems
+        .iter_mut()
+        .filter(|problem| problem_name.is_none_or(|name| problem.problem == name))
+    {
+        let task_screen_name = problem
+            .task_screen_name
+            .clone()
+            .unwrap_or_else(|| format!("{}_{}", contest_name, problem.problem));
+        let url = format!(
+            "{}/contests/{}/tasks/{}/editorial",
+            base_url, contest_name, task_screen_name
+        );
+        let editorials =
+            parse_editorials(&Html::parse_document(&fetch_html(&url).await?), &base_url);
+        if editorials.is_empty() {
+            if !is_json() {
+                println!("[{}] No editorials published yet", problem.problem);
+            }
+            continue;
+        }
+        let readme_path = work_dir.join(&problem.problem).join("README.md");
+        if let Ok(readme) = fs::read_to_string(&readme_path) {
+            fs::write(&readme_path, set_readme_editorials(&readme, &editorials))?;
+        }
+        if !is_json() {
+            for editorial in &editorials {
+                println!(
+                    "[{}] {}: {}",
+                    problem.problem, editorial.title, editorial.url
+                );
+            }
+        }
+        problem.editorials = editorials;
+        fetched.push(problem.problem.clone());
+    }
+    write_contest_metadata(work_dir, &metadata)?;
+
+    if is_json() {
+        let problems: Vec<ProblemEditorials> = metadata
+            .problems
+            .iter()
+            .filter(|problem| fetched.contains(&problem.problem))
+            .map(|problem| ProblemEditorials {
+                problem: &problem.problem,
+                editorials: &problem.editorials,
+            })
+            .collect();
+        print_json(&problems)?;
+    }
+    Ok(())
+}
+
+/// 解説一覧ページから解説ページへのリンクを取り出す
+///
+/// 解説ページ(`/contests/<コンテスト名>/editorial/<解説 ID>`)へのリンクのみを対象とし、
+/// リンクを含む項目(`li`)のテキスト(`公式 解説 by en_translator` など)を見出しとする。
+///
+/// # 引数
+/// - `document`: 解説一覧ページの HTML
+/// - `base_url`: 相対 URL の基準 (`https://atcoder.jp` など)
+fn parse_editorials(document: &Html, base_url: &str) -> Vec<EditorialLink> {
+    let link_selector = Selector::parse("a[href]").unwrap();
+    let collapse = |text: String| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut editorials: Vec<EditorialLink> = Vec::new();
+    for link in document.select(&link_selector) {
+        let href = link.value().attr("href").unwrap_or("");
+        let is_editorial = href
+            .rsplit_once("/editorial/")
+            .is_some_and(|(_, id)| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()));
+        if !is_editorial {
+            continue;
+        }
+        let url = if href.starts_with('/') {
+            format!("{}{}", base_url.trim_end_matches('/'), href)
+        } else {
+            href.to_string()
+        };
+        if editorials.iter().any(|editorial| editorial.url == url) {
+            continue;
+        }
+        let title = link
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .find(|element| element.value().name() == "li")
+            .map(|item| collapse(item.text().collect()))
+            .unwrap_or_else(|| collapse(link.text().collect()));
+        editorials.push(EditorialLink { title, url });
+    }
+    editorials
+}
+
+/// `README.md` の末尾の `## Editorials` 節を解説ページへのリンクで置き換える (節がない場合は追加する)
+fn set_readme_editorials(readme: &str, editorials: &[EditorialLink]) -> String {
+    let body = match readme.find(&format!("\n{}\n", README_EDITORIALS_HEADING)) {
+        Some(index) => &readme[..index],
+        None => readme,
+    };
+    let links: Vec<String> = editorials
+        .iter()
+        .map(|editorial| format!("- [{}]({})", editorial.title, editorial.url))
+        .collect();
+    format!(
+        "{}\n\n{}\n\n{}\n",
+        body.trim_end(),
+        README_EDITORIALS_HEADING,
+        links.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EDITORIAL_HTML: &str = r#"
+<div class="col-sm-12">
+  <h2>Editorial</h2>
+  <a href="/contests/abc388/editorial">Editorial List</a>
+  <h3>Overall Editorial</h3>
+  <ul>
+    <li><span class="label label-primary">Official</span> <a href="/contests/abc388/editorial/11850">Editorial</a> by <a href="/users/en_translator" class="username">en_translator</a></li>
+  </ul>
+  <h3>A - ?UPC</h3>
+  <ul>
+    <li><a href="/contests/abc388/editorial/11851">Editorial</a> by <a href="/users/mock_user" class="username">mock_user</a></li>
+    <li><a href="https://www.youtube.com/watch?v=mock">Video</a></li>
+  </ul>
+  <a href="/contests/abc388/editorial/11850">Editorial</a>
+</div>
+"#;
+
+    #[test]
+    fn test_parse_editorials() {
+        let editorials =
+            parse_editorials(&Html::parse_document(EDITORIAL_HTML), "https://atcoder.jp/");
+        assert_eq!(
+            editorials,
+            vec![
+                EditorialLink {
+                    title: "Official Editorial by en_translator".to_string(),
+                    url: "https://atcoder.jp/contests/abc388/editorial/11850".to_string(),
+                },
+                EditorialLink {
+                    title: "Editorial by mock_user".to_string(),
+                    url: "https://atcoder.jp/contests/abc388/editorial/11851".to_string(),
+                },
+            ]
+        );
+        assert!(parse_editorials(&Html::parse_document("<ul></ul>"), "").is_empty());
+    }
+
+    #[test]
+    fn test_set_readme_editorials() {
+        let editorials = vec![EditorialLink {
+            title: "Official Editorial by en_translator".to_string(),
+            url: "https://atcoder.jp/contests/abc388/editorial/11850".to_string(),
+        }];
+        let readme = set_readme_editorials("# A - ?UPC\n\n- Time Limit: 2 sec\n", &editorials);
+        assert_eq!(
+            readme,
+            "# A - ?UPC\n\n- Time Limit: 2 sec\n\n\
+             ## Editorials\n\n\
+             - [Official Editorial by en_translator](https://atcoder.jp/contests/abc388/editorial/11850)\n"
+        );
+        // 既存の節は置き換える
+        assert_eq!(set_readme_editorials(&readme, &editorials), readme);
+    }
+}
@@ -0,0 +1,289 @@
+//! ローカルのモジュールやライブラリクレートを 1 つのソースコードに展開するモジュール
+//!
+//! AtCoder には 1 ファイルしか提出できないため、以下を `main.rs` にインライン化する。
+//! - `mod foo;` で宣言されたモジュール(`foo.rs` または `foo/mod.rs`)
+//! - `Cargo.toml` の `[dependencies]` に `path` で指定されたローカルのライブラリクレート
+//!
+//! ライブラリクレートは `mod <crate_name> { ... }` としてファイル末尾に追加し、
+//! 参照側の `<crate_name>::` を `crate::<crate_name>::` に、ライブラリ内の `crate::` を
+//! `crate::<crate_name>::` に書き換える。ライブラリクレート間の依存関係は展開しない。
+//!
+//! 展開結果は `<problem_name>/.atc/expanded.rs` に出力され、`submit` でも同じ展開が行われる。
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+use super::submit::get_contest_info;
+
+/// 展開結果を出力するファイルの問題ディレクトリからの相対パス
+const EXPANDED_FILE: &str = ".atc/expanded.rs";
+
+/// 展開処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+pub fn execute(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let (_, source_path) = get_contest_info(work_dir, problem_name)?;
+    let source_path = PathBuf::from(source_path);
+    let source = fs::read_to_string(&source_path)?;
+    let expanded = expand_source(work_dir, &source_path, &source)?;
+
+    let output_path = source_path
+        .parent()
+        .ok_or("問題ディレクトリを取得できません")?
+        .join(EXPANDED_FILE);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&output_path, expanded)?;
+    println!("Expanded source written to {}", output_path.display());
+    Ok(())
+}
+
+/// ソースコード内のモジュールとローカルのライブラリクレートを展開する
+///
+/// 展開対象が存在しない場合は、ソースコードをそのまま返す。
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `source_path`: ソースコードのパス (モジュールのファイルの探索に利用)
+/// - `source`: ソースコードの内容
+///
+/// # エラーの可能性
+/// - 宣言されたモジュールのファイルが存在しない場合
+/// - `Cargo.toml` やライブラリクレートの読み込みに失敗した場合
+pub fn expand_source(
+    work_dir: &Path,
+    source_path: &Path,
+    source: &str,
+) -> Result<String, Box<dyn Error>> {
+    let source_dir = source_path.parent().unwrap_or(Path::new("."));
+    let mut expanded = expand_modules(source, source_dir)?;
+
+    for (crate_name, lib_path) in local_crates(work_dir)? {
+        if !contains_path(&expanded, &crate_name) {
+            continue;
+        }
+        let lib_source = fs::read_to_string(&lib_path)?;
+        let lib_dir = lib_path.parent().unwrap_or(Path::new("."));
+        let lib_expanded = expand_modules(&lib_source, lib_dir)?
+            .replace("crate::", &format!("crate::{}::", crate_name));
+        expanded = qualify_paths(&expanded, &crate_name)
+            .lines()
+            .filter(|line| line.trim() != format!("extern crate {};", crate_name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        expanded.push_str(&format!(
+            "\n\n#[allow(dead_code)]\nmod {} {{\n{}\n}}\n",
+            crate_name, lib_expanded
+        ));
+    }
+    Ok(expanded)
+}
+
+/// `mod foo;` の宣言を再帰的にモジュールのファイルの内容で置き換える
+///
+/// # 引数
+/// - `source`: ソースコードの内容
+/// - `child_dir`: 子モジュールのファイルを探索するディレクトリ
+fn expand_modules(source: &str, child_dir: &Path) -> Result<String, Box<dyn Error>> {
+    let mut lines = Vec::new();
+    for line in source.lines() {
+        let Some((prefix, name)) = parse_mod_declaration(line) else {
+            lines.push(line.to_string());
+            continue;
+        };
+        let candidates = [
+            child_dir.join(format!("{}.rs", name)),
+            child_dir.join(name).join("mod.rs"),
+        ];
+        let module_path = candidates
+            .iter()
+            .find(|path| path.is_file())
+            .ok_or_else(|| format!("モジュール {} のファイルが見つかりません", name))?;
+        let module_source = fs::read_to_string(module_path)?;
+        let module_expanded = expand_modules(&module_source, &child_dir.join(name))?;
+        lines.push(format!("{}mod {} {{", prefix, name));
+        lines.push(module_expanded);
+        lines.push(format!("{}}}", leading_whitespace(line)));
+    }
+    let mut expanded = lines.join("\n");
+    if source.ends_with('\n') {
+        expanded.push('\n');
+    }
+    Ok(expanded)
+}
+
+/// `mod foo;` / `pub mod foo;` の形式の行から、可視性を含む接頭辞とモジュール名を取り出す
+fn parse_mod_declaration(line: &str) -> Option<(String, &str)> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_suffix(';')?;
+    let (visibility, rest) = match rest.find("mod ") {
+        Some(index) => (&rest[..index], &rest[index + 4..]),
+        None => return None,
+    };
+    if !(visibility.is_empty() || visibility.starts_with("pub")) {
+        return None;
+    }
+    let name = rest.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    Some((format!("{}{}", leading_whitespace(line), visibility), name))
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// `Cargo.toml` の `[dependencies]` から `path` 指定のクレートを取得する
+///
+/// # 戻り値
+/// クレート名(`-` は `_` に置換)と `src/lib.rs` のパスの組
+fn local_crates(work_dir: &Path) -> Result<Vec<(String, PathBuf)>, Box<dyn Error>> {
+    let cargo_toml_path = work_dir.join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        return Ok(Vec::new());
+    }
+    let parsed: Value = toml::from_str(&fs::read_to_string(cargo_toml_path)?)?;
+    let Some(dependencies) = parsed.get("dependencies").and_then(|d| d.as_table()) else {
+        return Ok(Vec::new());
+    };
+    Ok(dependencies
+        .iter()
+        .filter_map(|(name, spec)| {
+            let path = spec.get("path")?.as_str()?;
+            Some((
+                name.replace('-', "_"),
+                work_dir.join(path).join("src/lib.rs"),
+            ))
+        })
+        .collect())
+}
+
+/// `name::` がパスの先頭として出現する位置を返す
+fn path_positions(source: &str, name: &str) -> Vec<usize> {
+    let pattern = format!("{}::", name);
+    source
+        .match_indices(&pattern)
+        .filter(|(index, _)| {
+            source[..*index]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// ソースコードが `name::` で始まるパスを含むかを返す
+fn contains_path(source: &str, name: &str) -> bool {
+    !path_positions(source, name).is_empty()
+}
+
+/// `name::` で始まるパスを `crate::name::` に書き換える
+fn qualify_paths(source: &str, name: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut last = 0;
+    for index in path_positions(source, name) {
+        result.push_str(&source[last..index]);
+        result.push_str("crate::");
+        last = index;
+    }
+    result.push_str(&source[last..]);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_parse_mod_declaration() {
+        assert_eq!(
+            parse_mod_declaration("mod graph;"),
+            Some(("".to_string(), "graph"))
+        );
+        assert_eq!(
+            parse_mod_declaration("    pub(crate) mod util;"),
+            Some(("    pub(crate) ".to_string(), "util"))
+        );
+        assert_eq!(parse_mod_declaration("mod graph {"), None);
+        assert_eq!(parse_mod_declaration("use graph::dfs;"), None);
+    }
+
+    #[test]
+    fn test_expand_modules() {
+        let work_dir = tempfile::tempdir().expect("");
+        let problem_dir = work_dir.path().join("a");
+        fs::create_dir_all(problem_dir.join("graph")).unwrap();
+        fs::write(
+            problem_dir.join("graph.rs"),
+            "pub mod dfs;\npub fn size() -> usize { 0 }",
+        )
+        .unwrap();
+        fs::write(problem_dir.join("graph/dfs.rs"), "pub fn dfs() {}").unwrap();
+
+        let source = "mod graph;\nfn main() { graph::dfs::dfs(); }";
+        let expanded =
+            expand_source(work_dir.path(), &problem_dir.join("main.rs"), source).unwrap();
+        assert_eq!(
+            expanded,
+            "mod graph {\npub mod dfs {\npub fn dfs() {}\n}\npub fn size() -> usize { 0 }\n}\nfn main() { graph::dfs::dfs(); }"
+        );
+
+        assert!(expand_source(
+            work_dir.path(),
+            &problem_dir.join("main.rs"),
+            "mod missing;"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_expand_local_crate() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+
+[dependencies]
+proconio = "0.4.5"
+my-lib = { path = "lib/my-lib" }
+"#,
+        )
+        .unwrap();
+        let lib_dir = work_dir.path().join("lib/my-lib/src");
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(
+            lib_dir.join("lib.rs"),
+            "pub mod math;\npub use crate::math::gcd;",
+        )
+        .unwrap();
+        fs::write(lib_dir.join("math.rs"), "pub fn gcd() {}").unwrap();
+
+        let source = "use my_lib::gcd;\nfn main() { gcd(); }";
+        let expanded =
+            expand_source(work_dir.path(), &work_dir.path().join("a/main.rs"), source).unwrap();
+        assert!(expanded.starts_with("use crate::my_lib::gcd;"));
+        assert!(expanded.contains("mod my_lib {\npub mod math {\npub fn gcd() {}\n}"));
+        assert!(expanded.contains("pub use crate::my_lib::math::gcd;"));
+
+        // 参照されていないクレートは展開しない
+        let expanded = expand_source(
+            work_dir.path(),
+            &work_dir.path().join("a/main.rs"),
+            "fn main() {}",
+        )
+        .unwrap();
+        assert_eq!(expanded, "fn main() {}");
+    }
+}
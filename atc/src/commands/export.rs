@@ -0,0 +1,105 @@
+//! 問題のテストケースを online-judge-tools(oj) の形式で書き出すモジュール
+//!
+//! `tests/sample_1.in` の形式のテストケースを、oj の既定の形式(`test/sample-1.in`)に変換して書き出す。
+//! サンプル以外のテストケースは名前を変えずに書き出す。
+//! oj の形式から取り込む場合は `import --oj` を利用する。
+//!
+//! ```text
+//! a
+//! ├── main.rs
+//! ├── tests             # このツールの形式
+//! │   ├── sample_1.in
+//! │   └── sample_1.out
+//! └── test              # oj の形式
+//!     ├── sample-1.in
+//!     └── sample-1.out
+//! ```
+use std::{error::Error, fs, path::Path};
+
+use super::import::read_test_case_pairs;
+use super::test::find_problem_directory;
+
+/// oj のテストケースディレクトリ (問題ディレクトリからの相対パス)
+pub const OJ_TEST_DIR: &str = "test";
+
+/// 書き出し処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリ
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない場合
+/// - テストケースが 1 つも存在しない場合
+pub fn execute(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let tests_dir = problem_dir.join("tests");
+    let test_cases = read_test_case_pairs(&tests_dir, &tests_dir, "in", "out")?;
+    if test_cases.is_empty() {
+        return Err(format!("{} にテストケースがありません", tests_dir.display()).into());
+    }
+
+    let oj_dir = problem_dir.join(OJ_TEST_DIR);
+    fs::create_dir_all(&oj_dir)?;
+    for (name, case) in &test_cases {
+        let name = to_oj_name(name);
+        fs::write(oj_dir.join(format!("{}.in", name)), &case.input)?;
+        fs::write(oj_dir.join(format!("{}.out", name)), &case.output)?;
+    }
+    println!(
+        "Exported {} test case(s) to {}",
+        test_cases.len(),
+        oj_dir.display()
+    );
+    Ok(())
+}
+
+/// このツールの形式のテストケース名(`sample_1`)を oj 形式(`sample-1`)に変換する
+///
+/// サンプル以外のテストケース名はそのまま返す。
+pub fn to_oj_name(name: &str) -> String {
+    match name.strip_prefix("sample_") {
+        Some(number) if number.parse::<usize>().is_ok() => format!("sample-{}", number),
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::import::from_oj_name;
+    use tempfile;
+
+    #[test]
+    fn test_to_oj_name() {
+        assert_eq!(to_oj_name("sample_12"), "sample-12");
+        assert_eq!(to_oj_name("sample_ex"), "sample_ex");
+        assert_eq!(to_oj_name("random_01"), "random_01");
+        assert_eq!(from_oj_name(&to_oj_name("sample_3")), "sample_3");
+    }
+
+    #[test]
+    fn test_execute() {
+        let work_dir = tempfile::tempdir().expect("");
+        let work_dir_path = work_dir.path().to_path_buf();
+        let tests_dir = work_dir.path().join("a/tests");
+        fs::create_dir_all(&tests_dir).unwrap();
+        assert!(execute(&work_dir_path, "a").is_err());
+
+        fs::write(tests_dir.join("sample_1.in"), "3\n").unwrap();
+        fs::write(tests_dir.join("sample_1.out"), "6\n").unwrap();
+        fs::write(tests_dir.join("random_01.in"), "5\n").unwrap();
+        fs::write(tests_dir.join("random_01.out"), "10\n").unwrap();
+        execute(&work_dir_path, "a").unwrap();
+
+        let oj_dir = work_dir.path().join("a").join(OJ_TEST_DIR);
+        assert_eq!(
+            fs::read_to_string(oj_dir.join("sample-1.in")).unwrap(),
+            "3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(oj_dir.join("random_01.out")).unwrap(),
+            "10\n"
+        );
+    }
+}
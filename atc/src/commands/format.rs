@@ -0,0 +1,230 @@
+//! `rustfmt` によるソースコードのフォーマットを行うモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - `Cargo.toml` からフォーマット設定を取得(`load_format_settings`)
+//! - ソースコードのフォーマット(`format_file`)
+//! - ソースコードがフォーマット済みかの判定(`is_formatted`)
+//! - 提出前のフォーマット処理・検査(`prepare_submission`)
+//!
+//! フォーマット設定はコンテストの `Cargo.toml` に以下の形式で記述する。
+//! ```toml
+//! [package.metadata.format]
+//! auto = true    # 提出前に rustfmt でソースコードを整形する
+//! check = true   # フォーマットされていないソースコードの提出を拒否する
+//! ```
+//!
+//! これらはコンテストごとの設定のため、ユーザー設定(`config.toml`、`config` コマンド)ではなくコンテストの `Cargo.toml` に記述する。
+//!
+//! コンテストディレクトリに `rustfmt.toml` が存在する場合、`rustfmt` はその設定に従って整形を行う。
+//! `check` が無効で `rustfmt.toml` のみが存在する場合は検査結果を警告として表示するのみとし、
+//! `rustfmt` が存在しない・実行に失敗した場合も提出は中断しない。
+use std::{error::Error, fs, path::Path, process::Command};
+use toml::Value;
+
+/// `rustfmt` に渡すエディション
+const RUSTFMT_EDITION: &str = "2021";
+
+/// フォーマット設定
+#[derive(Debug, Default, PartialEq)]
+pub struct FormatSettings {
+    pub auto: bool,  // 提出前に整形する
+    pub check: bool, // 未整形のソースコードの提出を拒否する
+}
+
+/// `Cargo.toml` の `[package.metadata.format]` からフォーマット設定を取得する
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+///
+/// # 戻り値
+/// - `Ok(FormatSettings)`: フォーマット設定(セクションが存在しない場合はすべて `false`)
+/// - `Err(Box<dyn Error>)`: `Cargo.toml` の読み込みや解析に失敗した場合
+pub fn load_format_settings(work_dir: &Path) -> Result<FormatSettings, Box<dyn Error>> {
+    let cargo_toml_content = fs::read_to_string(work_dir.join("Cargo.toml"))?;
+    let parsed: Value = toml::from_str(&cargo_toml_content)?;
+    let format = parsed
+        .get("package")
+        .and_then(|pkg| pkg.get("metadata"))
+        .and_then(|meta| meta.get("format"));
+    let flag = |key: &str| {
+        format
+            .and_then(|f| f.get(key))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    };
+    Ok(FormatSettings {
+        auto: flag("auto"),
+        check: flag("check"),
+    })
+}
+
+/// `rustfmt` でソースコードを整形する
+pub fn format_file(source_path: &Path) -> Result<(), Box<dyn Error>> {
+    let status = Command::new("rustfmt")
+        .arg("--edition")
+        .arg(RUSTFMT_EDITION)
+        .arg(source_path)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("rustfmt に失敗しました: {}", source_path.display()).into())
+    }
+}
+
+/// ソースコードが `rustfmt` で整形済みかを判定する
+///
+/// # 戻り値
+/// - `Ok(true)`: 整形済みの場合
+/// - `Ok(false)`: 整形が必要な場合
+/// - `Err(Box<dyn Error>)`: `rustfmt` の実行に失敗した場合(構文エラーなど)
+pub fn is_formatted(source_path: &Path) -> Result<bool, Box<dyn Error>> {
+    let output = Command::new("rustfmt")
+        .arg("--check")
+        .arg("--edition")
+        .arg(RUSTFMT_EDITION)
+        .arg(source_path)
+        .output()?;
+    match output.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => Err(format!(
+            "rustfmt の実行に失敗しました: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into()),
+    }
+}
+
+/// 提出前にフォーマット設定に従ってソースコードの整形・検査を行う
+///
+/// # 処理の流れ
+/// 1. `auto` が有効な場合、ソースコードを整形する
+/// 2. ソースコードが整形済みかを判定する
+/// 3. 未整形の場合、`check` が有効であればエラーを返し、`rustfmt.toml` が存在すれば警告を表示する
+///
+/// `rustfmt` による判定に失敗した場合も、`check` が無効であれば警告の表示のみとする。
+pub fn prepare_submission(work_dir: &Path, source_path: &Path) -> Result<(), Box<dyn Error>> {
+    let settings = load_format_settings(work_dir)?;
+    let has_rustfmt_toml = work_dir.join("rustfmt.toml").exists();
+    if !settings.auto && !settings.check && !has_rustfmt_toml {
+        return Ok(());
+    }
+    if settings.auto {
+        format_file(source_path)?;
+    }
+    match is_formatted(source_path) {
+        Ok(true) => {}
+        Ok(false) if settings.check => {
+            return Err(format!(
+                "ソースコードがフォーマットされていません: {}",
+                source_path.display()
+            )
+            .into());
+        }
+        Ok(false) => eprintln!(
+            "Warning: ソースコードがフォーマットされていません: {}",
+            source_path.display()
+        ),
+        Err(e) if settings.check => return Err(e),
+        Err(e) => eprintln!("Warning: フォーマットを確認できませんでした: {}", e),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_load_format_settings_success() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+
+[package.metadata.format]
+check = true
+"#,
+        )
+        .unwrap();
+        let settings = load_format_settings(work_dir.path()).unwrap();
+        assert_eq!(
+            settings,
+            FormatSettings {
+                auto: false,
+                check: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_format_settings_default() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+"#,
+        )
+        .unwrap();
+        let settings = load_format_settings(work_dir.path()).unwrap();
+        assert_eq!(settings, FormatSettings::default());
+    }
+
+    #[test]
+    fn test_is_formatted_and_format_file() {
+        let work_dir = tempfile::tempdir().expect("");
+        let source_path = work_dir.path().join("main.rs");
+        fs::write(&source_path, "fn main(){println!(\"Hello\");}").unwrap();
+        assert!(!is_formatted(&source_path).unwrap());
+
+        format_file(&source_path).unwrap();
+        assert!(is_formatted(&source_path).unwrap());
+    }
+
+    #[test]
+    fn test_prepare_submission_check_rejects_unformatted() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+
+[package.metadata.format]
+check = true
+"#,
+        )
+        .unwrap();
+        let source_path = work_dir.path().join("main.rs");
+        fs::write(&source_path, "fn main(){println!(\"Hello\");}").unwrap();
+        let result = prepare_submission(work_dir.path(), &source_path);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_prepare_submission_warn_only_on_rustfmt_failure() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        )
+        .unwrap();
+        fs::write(work_dir.path().join("rustfmt.toml"), "").unwrap();
+        // 構文エラーで rustfmt が失敗しても、`check` が無効であれば提出を中断しない
+        let source_path = work_dir.path().join("main.rs");
+        fs::write(&source_path, "fn main() {").unwrap();
+        assert!(prepare_submission(work_dir.path(), &source_path).is_ok());
+    }
+}
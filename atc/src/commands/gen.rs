@@ -0,0 +1,374 @@
+//! ランダムなテストケースを生成するモジュール
+//!
+//! 問題ディレクトリの `gen.rs` をコンパイルして繰り返し実行し、生成された入力を
+//! `tests/random_{i}.in` として保存する。`gen.rs` はコマンドライン引数の 1 つ目に
+//! シード値を受け取り、入力を標準出力に書き出すプログラムとして実装する。
+//!
+//! `gen.rs` の代わりに、変数の範囲などを記述した `gen.toml` から入力を生成することもできる(`gen_spec` を参照)。
+//! 両方が存在する場合は `gen.rs` を利用する。
+//!
+//! `naive.rs`(愚直解)が存在する場合は、愚直解の出力を `tests/random_{i}.out` として保存するため、
+//! 生成したテストケースはそのまま `test` で利用できる。
+//! `validator.rs` が存在する場合は、生成した入力を検証する。
+//!
+//! ```text
+//! .
+//! ├── Cargo.toml            # `{problem_name}-gen`、`{problem_name}-naive` のバイナリ定義が自動で追加される
+//! └── problem_name
+//!     ├── main.rs
+//!     ├── gen.rs            # 入力の生成プログラム
+//!     ├── gen.toml          # 入力の宣言的な記述(gen.rs がない場合に利用)
+//!     ├── naive.rs          # 愚直解(任意)
+//!     └── tests
+//!         ├── random_1.in
+//!         └── random_1.out  # naive.rs が存在する場合のみ作成
+//! ```
+use std::{
+    error::Error,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::gen_spec::{GenSpec, GEN_SPEC_FILE};
+use super::test::{compile, find_problem_directory, get_execution_path};
+use super::validator::{ensure_problem_bin, ensure_validator_bin, validate_inputs};
+
+/// 生成プログラムのファイル名
+pub const GENERATOR_FILE: &str = "gen.rs";
+
+/// 愚直解のファイル名
+pub const NAIVE_FILE: &str = "naive.rs";
+
+/// 入力の生成方法
+pub enum Generator {
+    Program(PathBuf), // gen.rs の実行ファイル
+    Spec(GenSpec),    // gen.toml の記述
+}
+
+impl Generator {
+    /// シード値を指定して入力を生成する
+    pub fn generate(&self, seed: u64) -> Result<String, Box<dyn Error>> {
+        match self {
+            Generator::Program(path) => generate_input(path, seed),
+            Generator::Spec(spec) => spec
+                .generate(seed)
+                .map_err(|e| format!("{} (seed = {})", e, seed).into()),
+        }
+    }
+}
+
+/// 生成プログラムのバイナリ名を返す
+pub fn generator_bin_name(problem_name: &str) -> String {
+    format!("{}-gen", problem_name)
+}
+
+/// 愚直解のバイナリ名を返す
+pub fn naive_bin_name(problem_name: &str) -> String {
+    format!("{}-naive", problem_name)
+}
+
+/// テストケース生成処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `count`: 生成するテストケースの数
+/// - `seed`: 最初のテストケースのシード値 (`None` の場合は現在時刻から決定する)
+///
+/// # エラーの可能性
+/// - `gen.rs` と `gen.toml` のどちらも存在しない場合
+/// - コンパイル、生成プログラム・愚直解の実行に失敗した場合
+/// - 生成した入力が `validator.rs` の検証に失敗した場合
+pub fn execute(
+    work_dir: &Path,
+    problem_name: &str,
+    count: usize,
+    seed: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let spec = prepare_generator(work_dir, problem_name, &problem_dir)?;
+    let naive_bin = naive_bin_name(problem_name);
+    let has_naive = ensure_problem_bin(work_dir, problem_name, NAIVE_FILE, &naive_bin)?;
+    let has_validator = ensure_validator_bin(work_dir, problem_name)?;
+    compile(&problem_dir)?;
+
+    let generator = load_generator(work_dir, problem_name, spec)?;
+    let naive = if has_naive {
+        Some(get_execution_path(work_dir, &naive_bin)?)
+    } else {
+        None
+    };
+    let tests_dir = problem_dir.join("tests");
+    fs::create_dir_all(&tests_dir)?;
+
+    let first_seed = seed.unwrap_or_else(default_seed);
+    let first_index = next_random_index(&tests_dir)?;
+    let mut generated = Vec::new();
+    for (index, seed) in (first_index..).zip(case_seeds(first_seed, count)) {
+        let input = generator.generate(seed)?;
+        let input_path = tests_dir.join(format!("random_{}.in", index));
+        fs::write(&input_path, &input)?;
+        if let Some(naive) = &naive {
+            fs::write(
+                input_path.with_extension("out"),
+                run_solution(naive, &input)?,
+            )?;
+        }
+        println!("Generated {} (seed = {})", input_path.display(), seed);
+        generated.push(input_path);
+    }
+
+    if has_validator {
+        validate_inputs(work_dir, problem_name, &generated)?;
+    }
+    if !has_naive {
+        println!(
+            "Note: {} が存在しないため、期待出力 (.out) は生成されていません",
+            NAIVE_FILE
+        );
+    }
+    Ok(())
+}
+
+/// 入力の生成方法を決定し、コンパイルの前に必要な準備を行う
+///
+/// `gen.rs` が存在する場合はバイナリ定義を `Cargo.toml` に追加して `None` を返し、
+/// 存在しない場合は `gen.toml` を読み込んで返す。
+///
+/// # エラーの可能性
+/// - `gen.rs` と `gen.toml` のどちらも存在しない場合
+/// - `gen.toml` の記述が不正な場合
+pub fn prepare_generator(
+    work_dir: &Path,
+    problem_name: &str,
+    problem_dir: &Path,
+) -> Result<Option<GenSpec>, Box<dyn Error>> {
+    let generator_bin = generator_bin_name(problem_name);
+    if ensure_problem_bin(work_dir, problem_name, GENERATOR_FILE, &generator_bin)? {
+        return Ok(None);
+    }
+    let spec_path = problem_dir.join(GEN_SPEC_FILE);
+    if !spec_path.is_file() {
+        return Err(format!(
+            "{}/{} または {}/{} が見つかりません",
+            problem_name, GENERATOR_FILE, problem_name, GEN_SPEC_FILE
+        )
+        .into());
+    }
+    Ok(Some(GenSpec::load(&spec_path)?))
+}
+
+/// コンパイル後に入力の生成方法を返す (`spec` は `prepare_generator` の戻り値)
+pub fn load_generator(
+    work_dir: &Path,
+    problem_name: &str,
+    spec: Option<GenSpec>,
+) -> Result<Generator, Box<dyn Error>> {
+    match spec {
+        Some(spec) => Ok(Generator::Spec(spec)),
+        None => Ok(Generator::Program(get_execution_path(
+            work_dir,
+            &generator_bin_name(problem_name),
+        )?)),
+    }
+}
+
+/// シード値が指定されない場合に利用するシード値 (現在時刻) を返す
+pub fn default_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// `first_seed` から始まる `count` 個のテストケースのシード値を返す
+///
+/// テストケースごとのシード値は `first_seed` に番号を加えた値とするため、
+/// 表示されたシード値を `--seed` に指定すると同じ入力から再現できる。
+/// `u64` の最大値を超える場合は 0 に戻る。
+pub fn case_seeds(first_seed: u64, count: usize) -> impl Iterator<Item = u64> {
+    (0..count as u64).map(move |offset| first_seed.wrapping_add(offset))
+}
+
+/// 生成プログラムをシード値を指定して実行し、生成された入力を返す
+pub fn generate_input(generator: &Path, seed: u64) -> Result<String, Box<dyn Error>> {
+    let output = Command::new(generator)
+        .arg(seed.to_string())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(format!("生成プログラムが異常終了しました (seed = {})", seed).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// プログラムに入力を与えて実行し、標準出力を返す
+pub fn run_solution(executable: &Path, input: &str) -> Result<String, Box<dyn Error>> {
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // 出力がパイプの容量を超えても停止しないよう、入力は別スレッドで書き込みながら出力を読み込む
+    // (プログラムが入力を読み切らずに終了した場合の書き込みエラーは無視する)
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_string();
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        })
+    });
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if !output.status.success() {
+        return Err(format!("{} が異常終了しました", executable.display()).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 既存の `random_{i}.in` と重複しない次の番号を返す
+pub fn next_random_index(tests_dir: &Path) -> Result<usize, Box<dyn Error>> {
+    let mut max_index = 0;
+    for entry in fs::read_dir(tests_dir)? {
+        let file_name = entry?.file_name().to_string_lossy().to_string();
+        if let Some(index) = file_name
+            .strip_prefix("random_")
+            .and_then(|rest| rest.strip_suffix(".in"))
+            .and_then(|index| index.parse::<usize>().ok())
+        {
+            max_index = max_index.max(index);
+        }
+    }
+    Ok(max_index + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_case_seeds() {
+        assert_eq!(case_seeds(10, 3).collect::<Vec<_>>(), vec![10, 11, 12]);
+        assert_eq!(
+            case_seeds(u64::MAX, 2).collect::<Vec<_>>(),
+            vec![u64::MAX, 0]
+        );
+        assert_eq!(case_seeds(5, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_next_random_index() {
+        let tests_dir = tempfile::tempdir().expect("");
+        assert_eq!(next_random_index(tests_dir.path()).unwrap(), 1);
+
+        fs::write(tests_dir.path().join("sample_1.in"), "").unwrap();
+        fs::write(tests_dir.path().join("random_3.in"), "").unwrap();
+        fs::write(tests_dir.path().join("random_3.out"), "").unwrap();
+        assert_eq!(next_random_index(tests_dir.path()).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_execute() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "gen_test"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+"#,
+        )
+        .unwrap();
+        let problem_dir = work_dir.path().join("a");
+        fs::create_dir_all(problem_dir.join("tests")).unwrap();
+        fs::write(problem_dir.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(
+            problem_dir.join(GENERATOR_FILE),
+            r#"
+fn main() {
+    let seed: u64 = std::env::args().nth(1).unwrap().parse().unwrap();
+    println!("{}", seed % 10);
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            problem_dir.join(NAIVE_FILE),
+            r#"
+use std::io::Read;
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let n: u64 = input.trim().parse().unwrap();
+    println!("{}", n * 2);
+}
+"#,
+        )
+        .unwrap();
+
+        execute(work_dir.path(), "a", 2, Some(12)).unwrap();
+
+        let tests_dir = problem_dir.join("tests");
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("random_1.in")).unwrap(),
+            "2\n"
+        );
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("random_1.out")).unwrap(),
+            "4\n"
+        );
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("random_2.in")).unwrap(),
+            "3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("random_2.out")).unwrap(),
+            "6\n"
+        );
+    }
+
+    #[test]
+    fn test_execute_with_spec() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "gen_spec_test"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+"#,
+        )
+        .unwrap();
+        let problem_dir = work_dir.path().join("a");
+        fs::create_dir_all(&problem_dir).unwrap();
+        fs::write(problem_dir.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(
+            problem_dir.join(GEN_SPEC_FILE),
+            "n = \"int 3..=3\"\na = \"array n int 5..=5\"\n",
+        )
+        .unwrap();
+
+        execute(work_dir.path(), "a", 1, Some(1)).unwrap();
+        assert_eq!(
+            fs::read_to_string(problem_dir.join("tests/random_1.in")).unwrap(),
+            "3\n5 5 5\n"
+        );
+        assert!(!problem_dir.join("tests/random_1.out").exists());
+    }
+}
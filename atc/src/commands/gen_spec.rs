@@ -0,0 +1,461 @@
+//! 宣言的な記述(`gen.toml`)からランダムな入力を生成するモジュール
+//!
+//! 問題ディレクトリに `gen.rs` がなく `gen.toml` がある場合、`gen`・`stress` は `gen.toml` の記述に従って入力を生成する。
+//! 変数は記述した順に生成し、後の変数の範囲や長さには前に定義した `int` の変数を式(`+`、`-`、`*`)で利用できる。
+//! 数値は `100000` のほか `1e5` のように指数表記でも指定できる。
+//!
+//! | 記述                        | 生成する値                                   |
+//! | --------------------------- | -------------------------------------------- |
+//! | `int L..=R`                 | `L` 以上 `R` 以下の整数 (`L..R` は `R` を含まない) |
+//! | `array LEN int L..=R`       | `LEN` 個の整数を空白区切りで 1 行に出力         |
+//! | `string LEN CHARS`          | `CHARS` (`a-z`、`#.` など)の文字からなる長さ `LEN` の文字列 |
+//! | `perm LEN`                  | `1` から `LEN` の順列を空白区切りで 1 行に出力   |
+//! | `grid ROWS COLS CHARS`      | `CHARS` の文字からなる `ROWS` 行 `COLS` 列のグリッド |
+//!
+//! 既定では変数ごとに 1 行ずつ出力する。`output` に各行の変数を空白区切りで指定すると、複数の変数を 1 行に出力できる。
+//!
+//! ```toml
+//! output = ["n m", "a", "s"]
+//! n = "int 1..=1e5"
+//! m = "int 1..=n"
+//! a = "array n int 1..=1e9"
+//! s = "string m a-z"
+//! ```
+use std::{collections::BTreeMap, collections::HashMap, error::Error, fs, path::Path};
+use toml::{Spanned, Value};
+
+/// 宣言的な生成の記述のファイル名
+pub const GEN_SPEC_FILE: &str = "gen.toml";
+
+/// 出力する行を指定するキー
+const OUTPUT_KEY: &str = "output";
+
+/// `gen.toml` の記述
+#[derive(Debug, PartialEq)]
+pub struct GenSpec {
+    vars: Vec<(String, VarKind)>,     // 記述した順の変数
+    output: Option<Vec<Vec<String>>>, // 各行に出力する変数 (`None` の場合は 1 変数 1 行)
+}
+
+/// 変数の種類
+#[derive(Debug, PartialEq)]
+enum VarKind {
+    Int(IntRange),
+    Array {
+        len: String,
+        range: IntRange,
+    },
+    Str {
+        len: String,
+        chars: Vec<char>,
+    },
+    Perm {
+        len: String,
+    },
+    Grid {
+        rows: String,
+        cols: String,
+        chars: Vec<char>,
+    },
+}
+
+/// 整数の範囲 (`L..=R` または `L..R`)
+#[derive(Debug, PartialEq)]
+struct IntRange {
+    low: String,     // 下限の式
+    high: String,    // 上限の式
+    inclusive: bool, // 上限を含むかどうか
+}
+
+impl GenSpec {
+    /// `gen.toml` を読み込む
+    ///
+    /// # エラーの可能性
+    /// - ファイルの読み込み、または記述の解析に失敗した場合
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        Self::parse(&fs::read_to_string(path)?)
+            .map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// `gen.toml` の内容を解析する
+    ///
+    /// TOML のテーブルはキーの順序を保持しないため、各値の記述位置で並べ替えて記述した順とする。
+    pub fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let table: BTreeMap<String, Spanned<Value>> = toml::from_str(content)?;
+        let mut entries: Vec<(String, Spanned<Value>)> = table.into_iter().collect();
+        entries.sort_by_key(|(_, value)| value.span().start);
+
+        let mut vars = Vec::new();
+        let mut output = None;
+        for (name, value) in entries {
+            let value = value.into_inner();
+            if name == OUTPUT_KEY {
+                let lines = value
+                    .as_array()
+                    .and_then(|lines| lines.iter().map(Value::as_str).collect::<Option<Vec<_>>>())
+                    .ok_or("output には各行の変数名を文字列の配列で指定してください")?;
+                output = Some(
+                    lines
+                        .iter()
+                        .map(|line| line.split_whitespace().map(str::to_string).collect())
+                        .collect(),
+                );
+                continue;
+            }
+            let kind = value
+                .as_str()
+                .ok_or_else(|| format!("{} の記述は文字列で指定してください", name))
+                .and_then(|spec| {
+                    parse_var_kind(spec).map_err(|e| format!("{} = \"{}\": {}", name, spec, e))
+                })?;
+            vars.push((name, kind));
+        }
+        let spec = GenSpec { vars, output };
+        if let Some(lines) = &spec.output {
+            for name in lines.iter().flatten() {
+                if !spec.vars.iter().any(|(var, _)| var == name) {
+                    return Err(format!("output の変数 {} が定義されていません", name).into());
+                }
+            }
+        }
+        Ok(spec)
+    }
+
+    /// シード値から入力を生成する
+    ///
+    /// # エラーの可能性
+    /// - 式に未定義の変数が含まれる場合
+    /// - 範囲が空、または長さが負の場合
+    pub fn generate(&self, seed: u64) -> Result<String, Box<dyn Error>> {
+        let mut rng = SplitMix64(seed);
+        let mut ints: HashMap<&str, i64> = HashMap::new();
+        let mut rendered: HashMap<&str, String> = HashMap::new();
+        for (name, kind) in &self.vars {
+            let text = match kind {
+                VarKind::Int(range) => {
+                    let value = range.sample(&mut rng, &ints)?;
+                    ints.insert(name, value);
+                    value.to_string()
+                }
+                VarKind::Array { len, range } => {
+                    let values = (0..eval_len(len, &ints)?)
+                        .map(|_| range.sample(&mut rng, &ints).map(|value| value.to_string()))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    values.join(" ")
+                }
+                VarKind::Str { len, chars } => (0..eval_len(len, &ints)?)
+                    .map(|_| rng.choose(chars))
+                    .collect(),
+                VarKind::Perm { len } => {
+                    let mut values: Vec<usize> = (1..=eval_len(len, &ints)?).collect();
+                    for i in (1..values.len()).rev() {
+                        let j = rng.range(0, i as i64) as usize;
+                        values.swap(i, j);
+                    }
+                    let values: Vec<String> =
+                        values.iter().map(|value| value.to_string()).collect();
+                    values.join(" ")
+                }
+                VarKind::Grid { rows, cols, chars } => {
+                    let cols = eval_len(cols, &ints)?;
+                    let lines: Vec<String> = (0..eval_len(rows, &ints)?)
+                        .map(|_| (0..cols).map(|_| rng.choose(chars)).collect())
+                        .collect();
+                    lines.join("\n")
+                }
+            };
+            rendered.insert(name, text);
+        }
+
+        let lines: Vec<String> = match &self.output {
+            Some(lines) => lines
+                .iter()
+                .map(|names| {
+                    let values: Vec<&str> = names
+                        .iter()
+                        .map(|name| rendered[name.as_str()].as_str())
+                        .collect();
+                    values.join(" ")
+                })
+                .collect(),
+            None => self
+                .vars
+                .iter()
+                .map(|(name, _)| rendered[name.as_str()].clone())
+                .collect(),
+        };
+        Ok(format!("{}\n", lines.join("\n")))
+    }
+}
+
+/// 変数の記述(`array n int 1..=1e9` など)を解析する
+fn parse_var_kind(spec: &str) -> Result<VarKind, String> {
+    let words: Vec<&str> = spec.split_whitespace().collect();
+    match words.as_slice() {
+        ["int", range] => Ok(VarKind::Int(parse_range(range)?)),
+        ["array", len, "int", range] | ["array", len, range] => Ok(VarKind::Array {
+            len: len.to_string(),
+            range: parse_range(range)?,
+        }),
+        ["string", len, chars] => Ok(VarKind::Str {
+            len: len.to_string(),
+            chars: parse_chars(chars)?,
+        }),
+        ["perm", len] => Ok(VarKind::Perm {
+            len: len.to_string(),
+        }),
+        ["grid", rows, cols, chars] => Ok(VarKind::Grid {
+            rows: rows.to_string(),
+            cols: cols.to_string(),
+            chars: parse_chars(chars)?,
+        }),
+        _ => Err(
+            "int L..=R、array LEN int L..=R、string LEN CHARS、perm LEN、grid ROWS COLS CHARS のいずれかで指定してください"
+                .to_string(),
+        ),
+    }
+}
+
+/// 範囲の記述(`1..=1e5`、`0..n` など)を解析する
+fn parse_range(range: &str) -> Result<IntRange, String> {
+    let (low, high, inclusive) = match range.split_once("..=") {
+        Some((low, high)) => (low, high, true),
+        None => match range.split_once("..") {
+            Some((low, high)) => (low, high, false),
+            None => return Err(format!("範囲 {} は L..=R の形式で指定してください", range)),
+        },
+    };
+    if low.is_empty() || high.is_empty() {
+        return Err(format!("範囲 {} の上限と下限を指定してください", range));
+    }
+    Ok(IntRange {
+        low: low.to_string(),
+        high: high.to_string(),
+        inclusive,
+    })
+}
+
+/// 文字の集合の記述(`a-z`、`a-zA-Z0-9`、`#.` など)を解析する
+fn parse_chars(spec: &str) -> Result<Vec<char>, String> {
+    let source: Vec<char> = spec.chars().collect();
+    let mut chars = Vec::new();
+    let mut i = 0;
+    while i < source.len() {
+        if i + 2 < source.len() && source[i + 1] == '-' {
+            let (start, end) = (source[i], source[i + 2]);
+            if start > end {
+                return Err(format!("文字の範囲 {}-{} が不正です", start, end));
+            }
+            chars.extend(start..=end);
+            i += 3;
+        } else {
+            chars.push(source[i]);
+            i += 1;
+        }
+    }
+    chars.sort_unstable();
+    chars.dedup();
+    Ok(chars)
+}
+
+impl IntRange {
+    /// 範囲から整数を 1 つ選ぶ
+    fn sample(&self, rng: &mut SplitMix64, ints: &HashMap<&str, i64>) -> Result<i64, String> {
+        let low = eval(&self.low, ints)?;
+        let high = eval(&self.high, ints)? - if self.inclusive { 0 } else { 1 };
+        if low > high {
+            return Err(format!(
+                "範囲 {}..{}{} が空です ({} > {})",
+                self.low,
+                if self.inclusive { "=" } else { "" },
+                self.high,
+                low,
+                high
+            ));
+        }
+        Ok(rng.range(low, high))
+    }
+}
+
+/// 長さの式を評価する
+fn eval_len(expr: &str, ints: &HashMap<&str, i64>) -> Result<usize, String> {
+    let len = eval(expr, ints)?;
+    usize::try_from(len).map_err(|_| format!("長さ {} が負の値 ({}) です", expr, len))
+}
+
+/// 式(`n-1`、`2*n`、`1e9` など)を評価する
+///
+/// `+`・`-`・`*` と、`1e5` のような指数表記の数値、定義済みの `int` の変数を利用できる。
+fn eval(expr: &str, ints: &HashMap<&str, i64>) -> Result<i64, String> {
+    let mut total = 0i64;
+    let mut sign = 1i64;
+    let mut product: Option<i64> = None;
+    let mut term = String::new();
+    let mut chars = expr.chars();
+    loop {
+        let c = chars.next();
+        match c {
+            // 単項の符号
+            Some(op @ ('+' | '-')) if term.is_empty() => {
+                if op == '-' {
+                    sign = -sign;
+                }
+            }
+            // 指数表記の `e` の直後の符号は演算子としない
+            Some(op @ ('+' | '-'))
+                if term.ends_with('e') && term.starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                term.push(op)
+            }
+            Some(c @ ('+' | '-' | '*')) => {
+                let value = eval_term(&term, ints)?;
+                product = Some(product.map_or(value, |p| p.saturating_mul(value)));
+                term.clear();
+                if c != '*' {
+                    total = total.saturating_add(sign * product.take().unwrap());
+                    sign = if c == '-' { -1 } else { 1 };
+                }
+            }
+            Some(c) => term.push(c),
+            None => {
+                let value = eval_term(&term, ints)?;
+                let value = product.map_or(value, |p| p.saturating_mul(value));
+                return Ok(total.saturating_add(sign * value));
+            }
+        }
+    }
+}
+
+/// 式の項(数値または変数)を評価する
+fn eval_term(term: &str, ints: &HashMap<&str, i64>) -> Result<i64, String> {
+    if term.is_empty() {
+        return Err("式が不正です".to_string());
+    }
+    if term.starts_with(|c: char| c.is_ascii_digit()) {
+        if let Some((mantissa, exponent)) = term.split_once('e') {
+            let mantissa: i64 = mantissa
+                .parse()
+                .map_err(|_| format!("数値 {} が不正です", term))?;
+            let exponent: u32 = exponent
+                .parse()
+                .map_err(|_| format!("数値 {} が不正です", term))?;
+            return 10i64
+                .checked_pow(exponent)
+                .and_then(|power| mantissa.checked_mul(power))
+                .ok_or_else(|| format!("数値 {} が大きすぎます", term));
+        }
+        return term
+            .parse()
+            .map_err(|_| format!("数値 {} が不正です", term));
+    }
+    ints.get(term).copied().ok_or_else(|| {
+        format!(
+            "変数 {} が定義されていません (int の変数のみ利用できます)",
+            term
+        )
+    })
+}
+
+/// シード値から決まる疑似乱数生成器 (SplitMix64)
+///
+/// 同じシード値からは環境によらず同じ入力を生成する。
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// `low` 以上 `high` 以下の整数を返す (`low <= high` であること)
+    fn range(&mut self, low: i64, high: i64) -> i64 {
+        let span = (high as i128 - low as i128 + 1) as u128;
+        (low as i128 + ((self.next() as u128 * span) >> 64) as i128) as i64
+    }
+
+    /// 文字の集合から 1 文字選ぶ (空の場合は空白)
+    fn choose(&mut self, chars: &[char]) -> char {
+        if chars.is_empty() {
+            return ' ';
+        }
+        chars[self.range(0, chars.len() as i64 - 1) as usize]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eval() {
+        let ints = HashMap::from([("n", 10)]);
+        assert_eq!(eval("1e5", &ints), Ok(100000));
+        assert_eq!(eval("2e5+1", &ints), Ok(200001));
+        assert_eq!(eval("-1e9", &ints), Ok(-1000000000));
+        assert_eq!(eval("n-1", &ints), Ok(9));
+        assert_eq!(eval("2*n+3", &ints), Ok(23));
+        assert!(eval("m", &ints).is_err());
+        assert!(eval("1+", &ints).is_err());
+    }
+
+    #[test]
+    fn test_parse_chars() {
+        assert_eq!(parse_chars("a-e").unwrap(), vec!['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(parse_chars("#.").unwrap(), vec!['#', '.']);
+        assert_eq!(parse_chars("0-1a").unwrap(), vec!['0', '1', 'a']);
+        assert!(parse_chars("z-a").is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let spec = GenSpec::parse(
+            r#"
+output = ["n m", "a", "s", "p"]
+n = "int 1..=5"
+m = "int n..=n"
+a = "array n int -3..3"
+s = "string m a-c"
+p = "perm n"
+"#,
+        )
+        .unwrap();
+        for seed in 0..100 {
+            let input = spec.generate(seed).unwrap();
+            assert_eq!(input, spec.generate(seed).unwrap());
+            let lines: Vec<&str> = input.lines().collect();
+            assert_eq!(lines.len(), 4);
+            let header: Vec<usize> = lines[0].split(' ').map(|v| v.parse().unwrap()).collect();
+            let (n, m) = (header[0], header[1]);
+            assert!((1..=5).contains(&n));
+            assert_eq!(n, m);
+            let a: Vec<i64> = lines[1].split(' ').map(|v| v.parse().unwrap()).collect();
+            assert_eq!(a.len(), n);
+            assert!(a.iter().all(|v| (-3..3).contains(v)));
+            assert_eq!(lines[2].len(), m);
+            assert!(lines[2].chars().all(|c| ('a'..='c').contains(&c)));
+            let mut p: Vec<usize> = lines[3].split(' ').map(|v| v.parse().unwrap()).collect();
+            p.sort();
+            assert_eq!(p, (1..=n).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_generate_in_definition_order() {
+        let spec =
+            GenSpec::parse("w = \"int 3..=3\"\nh = \"int 2..=2\"\ng = \"grid h w #\"\n").unwrap();
+        assert_eq!(spec.generate(0).unwrap(), "3\n2\n###\n###\n");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(GenSpec::parse("n = \"float 1..=2\"\n").is_err());
+        assert!(GenSpec::parse("n = \"int 1-2\"\n").is_err());
+        assert!(GenSpec::parse("n = 3\n").is_err());
+        assert!(GenSpec::parse("output = [\"n m\"]\nn = \"int 1..=2\"\n").is_err());
+        let spec = GenSpec::parse("n = \"int 5..=1\"\n").unwrap();
+        assert!(spec.generate(0).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! ダウンロードしたコンテストディレクトリを Git で管理するモジュール
+//!
+//! `download` の完了後、コンテストディレクトリで `git init` を実行し、`.gitignore` を作成して
+//! 雛形一式を最初のコミットとして記録する。解答の履歴をコンテスト開始時点から残すために利用する。
+//!
+//! 設定 `git_init` を `false` にするか、`download --no-git` を指定した場合は実行しない。
+//! コンテストディレクトリが既に Git の管理下にある場合(作業ディレクトリ全体を Git で管理している場合など)も実行しない。
+//!
+//! ```text
+//! abc388
+//! ├── .git
+//! ├── .gitignore   # target/ とセッション情報を除外
+//! ├── Cargo.toml
+//! └── a
+//! ```
+use std::{error::Error, fs, path::Path, process::Command};
+
+/// コンテストディレクトリに作成する `.gitignore`
+pub const GITIGNORE: &str = "/target\n**/*.rs.bk\nsession.json\n*.session\n";
+
+/// コンテストディレクトリを Git リポジトリとして初期化し、最初のコミットを作成する
+///
+/// # 引数
+/// - `contest_dir`: コンテストディレクトリ
+/// - `contest_name`: コンテスト名 (コミットメッセージに利用)
+///
+/// # 戻り値
+/// - `Ok(true)`: リポジトリを初期化した場合
+/// - `Ok(false)`: 既に Git の管理下にあるため初期化しなかった場合
+///
+/// # エラーの可能性
+/// - `git` コマンドが存在しない場合
+/// - `git init`・`git add`・`git commit` が失敗した場合 (`user.name` が未設定など)
+pub fn init_repository(contest_dir: &Path, contest_name: &str) -> Result<bool, Box<dyn Error>> {
+    if is_inside_work_tree(contest_dir) {
+        return Ok(false);
+    }
+    let gitignore_path = contest_dir.join(".gitignore");
+    if !gitignore_path.exists() {
+        fs::write(&gitignore_path, GITIGNORE)?;
+    }
+    run_git(contest_dir, &["init", "--quiet"])?;
+    run_git(contest_dir, &["add", "--all"])?;
+    run_git(
+        contest_dir,
+        &[
+            "commit",
+            "--quiet",
+            "--message",
+            &format!("Initial commit for {}", contest_name),
+        ],
+    )?;
+    Ok(true)
+}
+
+/// ディレクトリが Git の作業ツリーの中にあるかどうかを返す
+fn is_inside_work_tree(dir: &Path) -> bool {
+    Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(dir)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// `git` コマンドを実行し、失敗した場合は標準エラー出力をエラーとして返す
+fn run_git(dir: &Path, args: &[&str]) -> Result<(), Box<dyn Error>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("git を実行できません: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} が失敗しました: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use tempfile;
+
+    #[test]
+    fn test_init_repository() {
+        let contest_dir = tempfile::tempdir().expect("");
+        // git が利用できない環境や、一時ディレクトリ自体が Git の管理下にある環境では確認しない
+        if Command::new("git").arg("--version").output().is_err()
+            || is_inside_work_tree(contest_dir.path())
+        {
+            return;
+        }
+        for (key, value) in [
+            ("GIT_AUTHOR_NAME", "atc"),
+            ("GIT_AUTHOR_EMAIL", "atc@example.com"),
+            ("GIT_COMMITTER_NAME", "atc"),
+            ("GIT_COMMITTER_EMAIL", "atc@example.com"),
+        ] {
+            env::set_var(key, value);
+        }
+        fs::write(contest_dir.path().join("Cargo.toml"), "[package]\n").unwrap();
+
+        assert!(init_repository(contest_dir.path(), "abc388").unwrap());
+        assert_eq!(
+            fs::read_to_string(contest_dir.path().join(".gitignore")).unwrap(),
+            GITIGNORE
+        );
+        let log = Command::new("git")
+            .args(["log", "--format=%s"])
+            .current_dir(contest_dir.path())
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&log.stdout).trim(),
+            "Initial commit for abc388"
+        );
+        // 既に Git の管理下にある場合は初期化しない
+        assert!(!init_repository(contest_dir.path(), "abc388").unwrap());
+    }
+}
@@ -0,0 +1,306 @@
+//! `test` の実行結果の履歴を記録・表示するモジュール
+//!
+//! `test` を実行するたびに、テストケースごとの結果と `main.rs` のハッシュ値を問題ディレクトリの
+//! `.atc/results.json` に追記する(最新の `MAX_HISTORY_RUNS` 件まで)。
+//! 前回の実行で AC だったテストケースが AC 以外になった場合はリグレッションとして結果の一覧に表示する。
+//!
+//! `test --history` は記録された実行結果の一覧を表示し、リグレッションを強調する。
+//!
+//! ```text
+//! a
+//! ├── main.rs            # 記録時にハッシュ値を取得するソースコード
+//! ├── .atc
+//! │   └── results.json   # テストの実行結果の履歴
+//! └── tests
+//! ```
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::output::{is_json, print_json};
+use super::score::hash_bytes;
+use super::test::{find_problem_directory, worst_status, TestCaseResult, TestStatus};
+
+/// 実行結果の履歴ファイルの問題ディレクトリからの相対パス
+const RESULTS_HISTORY_FILE: &str = ".atc/results.json";
+
+/// 履歴に保持する実行結果の最大件数
+const MAX_HISTORY_RUNS: usize = 100;
+
+/// テストケースごとの結果の記録
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CaseRecord {
+    pub name: String,         // テストケース名(例: "sample_1.in")
+    pub status: TestStatus,   // 実行結果
+    pub execution_time: u128, // 実行時間(ミリ秒)
+}
+
+/// 1 回分の `test` の実行結果の記録
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunRecord {
+    pub timestamp: u64,           // 記録時刻(UNIX時間)
+    pub code_hash: String,        // 記録時点の main.rs のハッシュ値
+    pub system: bool,             // 公式のテストケースで実行したかどうか
+    pub results: Vec<CaseRecord>, // テストケースごとの結果
+}
+
+/// 実行結果の履歴
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RunHistory {
+    pub runs: Vec<RunRecord>,
+}
+
+impl RunHistory {
+    /// 履歴をロードする。ファイルが存在しない場合は空の履歴を返す。
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(RunHistory::default());
+        }
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// 履歴を保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// `index` 番目の実行結果より前で、同じ種類のテストケース(サンプル・公式)を実行した直近の記録を返す
+    fn previous(&self, index: usize) -> Option<&RunRecord> {
+        let system = self.runs[index].system;
+        self.runs[..index]
+            .iter()
+            .rev()
+            .find(|run| run.system == system)
+    }
+}
+
+/// 前回 AC だったテストケースが AC 以外になったもの
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub name: String,       // テストケース名
+    pub status: TestStatus, // 今回の実行結果
+}
+
+/// 前回の実行結果と比較し、リグレッションを返す
+///
+/// 両方の実行に含まれるテストケースのみを比較する。
+pub fn find_regressions(previous: &RunRecord, current: &RunRecord) -> Vec<Regression> {
+    current
+        .results
+        .iter()
+        .filter(|case| case.status != TestStatus::AC)
+        .filter(|case| {
+            previous
+                .results
+                .iter()
+                .any(|before| before.name == case.name && before.status == TestStatus::AC)
+        })
+        .map(|case| Regression {
+            name: case.name.clone(),
+            status: case.status,
+        })
+        .collect()
+}
+
+/// 実行結果を履歴に追記し、前回の実行結果からのリグレッションを返す
+///
+/// # 引数
+/// - `problem_dir`: 問題ディレクトリのパス
+/// - `results`: テストケースごとの実行結果
+/// - `system`: 公式のテストケースで実行した場合は `true`
+///
+/// # エラーの可能性
+/// - 履歴ファイルの読み書きに失敗した場合
+pub fn record_run(
+    problem_dir: &Path,
+    results: &[TestCaseResult],
+    system: bool,
+) -> Result<Vec<Regression>, Box<dyn Error>> {
+    let history_path = problem_dir.join(RESULTS_HISTORY_FILE);
+    let mut history = RunHistory::load(&history_path)?;
+    let code_hash = match fs::read(problem_dir.join("main.rs")) {
+        Ok(content) => format!("{:016x}", hash_bytes(&content)),
+        Err(_) => "-".to_string(),
+    };
+    history.runs.push(RunRecord {
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        code_hash,
+        system,
+        results: results
+            .iter()
+            .map(|result| CaseRecord {
+                name: result.test_case_name.clone(),
+                status: result.status,
+                execution_time: result.execution_time,
+            })
+            .collect(),
+    });
+    let current = history.runs.len() - 1;
+    let regressions = history
+        .previous(current)
+        .map(|previous| find_regressions(previous, &history.runs[current]))
+        .unwrap_or_default();
+    if history.runs.len() > MAX_HISTORY_RUNS {
+        history.runs.drain(..history.runs.len() - MAX_HISTORY_RUNS);
+    }
+    history.save(&history_path)?;
+    Ok(regressions)
+}
+
+/// 実行結果の履歴の表示処理のエントリーポイント (`test --history`)
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `problem_name`: 問題名 (`a` など)
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない、または履歴がない場合
+pub fn execute(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let history = RunHistory::load(&problem_dir.join(RESULTS_HISTORY_FILE))?;
+    if history.runs.is_empty() {
+        return Err(format!("'{}' のテスト結果の履歴がありません", problem_name).into());
+    }
+    if is_json() {
+        return print_json(&history.runs);
+    }
+
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    println!("=== Test History: {} ===", problem_name);
+    println!(
+        "{:<19}  {:<8}  {:<7}  {:>6}  Cases",
+        "Date", "Code", "Verdict", "Passed"
+    );
+    for (index, run) in history.runs.iter().enumerate() {
+        let date = Local
+            .timestamp_opt(run.timestamp as i64, 0)
+            .single()
+            .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| run.timestamp.to_string());
+        let passed = run
+            .results
+            .iter()
+            .filter(|case| case.status == TestStatus::AC)
+            .count();
+        let passed = format!("{}/{}", passed, run.results.len());
+        let cases: Vec<String> = run
+            .results
+            .iter()
+            .map(|case| case.status.to_string())
+            .collect();
+        let label = if run.system { " [system]" } else { "" };
+        println!(
+            "{:<19}  {:<8}  {:<7}  {:>6}  {}{}",
+            date,
+            run.code_hash.get(..8).unwrap_or(&run.code_hash),
+            worst_status(run.results.iter().map(|case| case.status)),
+            passed,
+            cases.join(" "),
+            label
+        );
+        let regressions = history
+            .previous(index)
+            .map(|previous| find_regressions(previous, run))
+            .unwrap_or_default();
+        for line in regressions.iter().map(describe_regression) {
+            if color {
+                println!("  \x1b[31m{}\x1b[0m", line);
+            } else {
+                println!("  {}", line);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// リグレッションを表示用の文字列に変換する (例: `regression: sample_2.in (AC -> WA)`)
+pub fn describe_regression(regression: &Regression) -> String {
+    format!(
+        "regression: {} (AC -> {})",
+        regression.name, regression.status
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    fn result(name: &str, status: TestStatus) -> TestCaseResult {
+        TestCaseResult {
+            test_case_name: name.to_string(),
+            status,
+            execution_time: 10,
+            error_message: None,
+            stderr: None,
+            timing: None,
+        }
+    }
+
+    #[test]
+    fn test_record_run_regressions() {
+        let problem_dir = tempfile::tempdir().expect("");
+        fs::write(problem_dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+
+        let regressions = record_run(
+            problem_dir.path(),
+            &[
+                result("sample_1.in", TestStatus::AC),
+                result("sample_2.in", TestStatus::AC),
+                result("sample_3.in", TestStatus::WA),
+            ],
+            false,
+        )
+        .unwrap();
+        assert!(regressions.is_empty());
+
+        // 公式のテストケースの結果とは比較しない
+        let regressions = record_run(
+            problem_dir.path(),
+            &[result("sample_1.in", TestStatus::WA)],
+            true,
+        )
+        .unwrap();
+        assert!(regressions.is_empty());
+
+        let regressions = record_run(
+            problem_dir.path(),
+            &[
+                result("sample_1.in", TestStatus::AC),
+                result("sample_2.in", TestStatus::TLE),
+                result("sample_3.in", TestStatus::WA),
+                result("custom_1.in", TestStatus::RE),
+            ],
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            regressions,
+            vec![Regression {
+                name: "sample_2.in".to_string(),
+                status: TestStatus::TLE
+            }]
+        );
+        assert_eq!(
+            describe_regression(&regressions[0]),
+            "regression: sample_2.in (AC -> TLE)"
+        );
+
+        let history = RunHistory::load(&problem_dir.path().join(RESULTS_HISTORY_FILE)).unwrap();
+        assert_eq!(history.runs.len(), 3);
+        assert_eq!(history.runs[2].results[1].status, TestStatus::TLE);
+        assert_eq!(history.runs[0].code_hash.len(), 16);
+    }
+}
@@ -0,0 +1,166 @@
+//! 各コマンドで利用する HTTP クライアントを作成するモジュール
+//!
+//! 設定 `proxy` が設定されている場合は、すべてのリクエストをそのプロキシ経由で送信する。
+//! 未設定の場合は reqwest の既定の動作として、環境変数 `HTTP_PROXY`・`HTTPS_PROXY`・`ALL_PROXY`・`NO_PROXY` に従う。
+//! 設定 `proxy` を指定した場合も、`NO_PROXY` に含まれるホストへのリクエストはプロキシを経由しない。
+//!
+//! AtCoder (設定 `base_url` のホスト) へのリクエストは、送信前に `throttle` を呼び出して
+//! 設定 `request_interval` の間隔を空ける。`download` の並列取得や `status --watch` のポーリングを含め、
+//! 1 つのプロセスから送信するすべてのリクエストで間隔を共有する。
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy, Url};
+use std::{
+    error::Error,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::config::{get_base_url, get_request_interval, load_user_config};
+
+/// 次に AtCoder へリクエストを送信できる時刻
+static NEXT_REQUEST_AT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// プロキシの設定を反映した `ClientBuilder` を返す
+///
+/// タイムアウトやクッキーなど、コマンドごとの設定はこの `ClientBuilder` に追加する。
+///
+/// # エラーの可能性
+/// - 設定 `proxy` が URL として解釈できない場合
+pub fn client_builder() -> Result<ClientBuilder, Box<dyn Error>> {
+    with_proxy(Client::builder(), load_user_config().proxy.as_deref())
+}
+
+/// プロキシの設定を反映した `Client` を返す
+///
+/// # エラーの可能性
+/// - 設定 `proxy` が URL として解釈できない場合
+pub fn client() -> Result<Client, Box<dyn Error>> {
+    Ok(client_builder()?.build()?)
+}
+
+/// `builder` にプロキシを設定する (`proxy` が `None` の場合は環境変数に従う)
+fn with_proxy(
+    builder: ClientBuilder,
+    proxy: Option<&str>,
+) -> Result<ClientBuilder, Box<dyn Error>> {
+    let Some(proxy) = proxy else {
+        return Ok(builder);
+    };
+    let proxy = Proxy::all(proxy)
+        .map_err(|e| format!("proxy の設定が不正です ({}): {}", proxy, e))?
+        .no_proxy(NoProxy::from_env());
+    Ok(builder.proxy(proxy))
+}
+
+/// AtCoder へのリクエストの前に呼び出し、前回のリクエストから `request_interval` が経過するまで待機する
+///
+/// AtCoder 以外のホスト (AtCoder Problems の API など) への URL の場合は待機しない。
+///
+/// # 引数
+/// - `url`: 送信するリクエストの URL
+pub async fn throttle(url: &str) {
+    if !is_same_host(url, &get_base_url()) {
+        return;
+    }
+    let wait = reserve_request_slot(&NEXT_REQUEST_AT, Instant::now(), get_request_interval());
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// 送信時刻を予約し、予約した時刻までの待機時間を返す
+///
+/// 予約した時刻の `interval` 後を次に送信できる時刻とする。
+fn reserve_request_slot(
+    next: &Mutex<Option<Instant>>,
+    now: Instant,
+    interval: Duration,
+) -> Duration {
+    let mut next = next.lock().unwrap_or_else(|e| e.into_inner());
+    let slot = next.map_or(now, |next| next.max(now));
+    *next = Some(slot + interval);
+    slot - now
+}
+
+/// 2 つの URL のホストとポートが一致するかを返す
+fn is_same_host(url: &str, base_url: &str) -> bool {
+    match (Url::parse(url), Url::parse(base_url)) {
+        (Ok(url), Ok(base_url)) => {
+            url.host_str() == base_url.host_str()
+                && url.port_or_known_default() == base_url.port_or_known_default()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn test_with_proxy() {
+        // 存在しないホストへのリクエストが、プロキシとして振る舞うモックサーバーに届くことを確認する
+        let mut proxy = Server::new_async().await;
+        let _mock = proxy
+            .mock("GET", Matcher::Any)
+            .with_status(200)
+            .with_body("via proxy")
+            .create_async()
+            .await;
+
+        let client = with_proxy(Client::builder(), Some(&proxy.url()))
+            .unwrap()
+            .build()
+            .unwrap();
+        let body = client
+            .get("http://atcoder.invalid/contests/")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "via proxy");
+
+        assert!(with_proxy(Client::builder(), Some("not a url")).is_err());
+        assert!(with_proxy(Client::builder(), None).is_ok());
+    }
+
+    #[test]
+    fn test_reserve_request_slot() {
+        let next = Mutex::new(None);
+        let now = Instant::now();
+        let interval = Duration::from_millis(200);
+        assert_eq!(reserve_request_slot(&next, now, interval), Duration::ZERO);
+        assert_eq!(
+            reserve_request_slot(&next, now, interval),
+            Duration::from_millis(200)
+        );
+        assert_eq!(
+            reserve_request_slot(&next, now + Duration::from_millis(100), interval),
+            Duration::from_millis(300)
+        );
+        // 間隔より長く空いた場合は待機しない
+        assert_eq!(
+            reserve_request_slot(&next, now + Duration::from_secs(1), interval),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_is_same_host() {
+        assert!(is_same_host(
+            "https://atcoder.jp/contests/abc388/tasks",
+            "https://atcoder.jp"
+        ));
+        assert!(!is_same_host(
+            "https://kenkoooo.com/atcoder/resources/merged-problems.json",
+            "https://atcoder.jp"
+        ));
+        assert!(!is_same_host(
+            "http://127.0.0.1:1234/",
+            "http://127.0.0.1:5678"
+        ));
+        assert!(!is_same_host("not a url", "https://atcoder.jp"));
+    }
+}
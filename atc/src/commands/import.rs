@@ -0,0 +1,609 @@
+//! cargo-compete・atcoder-cli(acc) のプロジェクトを取り込むモジュール
+//!
+//! 既存のコンテストディレクトリの構成を判定し、`download` と同じディレクトリ構造に変換して
+//! 作業ディレクトリに新しいコンテストディレクトリを作成する。取り込み元のファイルは変更しない。
+//!
+//! | 取り込み元 | 判定方法 | 解答 | テストケース | 実行時間制限 |
+//! | --- | --- | --- | --- | --- |
+//! | cargo-compete | `Cargo.toml` の `[package.metadata.cargo-compete]` | `[[bin]]` の `path` | `testcases/<問題>.yml`、`testcases/<問題>/{in,out}/*.txt` | YAML の `timelimit` |
+//! | atcoder-cli | `contest.acc.json` | `<問題>/<submit>` | `<問題>/<testdir>/*.{in,out}` (oj 形式) | 既定値 (`DEFAULT_TIMEOUT`) |
+//!
+//! テストケースは `tests/<名前>.in`・`tests/<名前>.out` として保存する。
+//! サンプルは `sample_1` の形式の名前に変換する。
+//! 取り込み元の `Cargo.toml` の依存クレートのうち、テンプレートに含まれないものは `[dependencies]` に追記する。
+//!
+//! `--oj` を指定した場合は、問題ディレクトリの oj 形式のテストケース(`test/sample-1.in`)を
+//! `tests/` に取り込む (`export` の逆の変換)。
+use serde::Deserialize;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+use super::download::{
+    copy_rustfmt_toml, create_contest_directory, create_main_rs, generate_cargo_toml, ContestInfo,
+    ProblemInfo, Sample,
+};
+use super::export::OJ_TEST_DIR;
+use super::metadata::save_contest_metadata;
+use super::new::DEFAULT_TIMEOUT;
+use super::template::resolve_template_dir;
+use super::test::find_problem_directory;
+use super::workspace::{add_workspace_member, is_workspace_root};
+
+/// 取り込み元のプロジェクトの種類
+#[derive(Debug, PartialEq)]
+pub enum Layout {
+    CargoCompete,
+    AtCoderCli,
+}
+
+/// 取り込む問題
+#[derive(Debug)]
+pub struct ImportedProblem {
+    pub info: ProblemInfo,                 // 問題名と実行時間制限
+    pub source: Option<PathBuf>,           // 解答のファイル (存在しない場合はテンプレートを利用)
+    pub test_cases: Vec<(String, Sample)>, // テストケース名と入出力
+}
+
+/// 取り込み処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ (コンテストディレクトリを作成する場所)
+/// - `source_dir`: 取り込み元のコンテストディレクトリ
+///
+/// # エラーの可能性
+/// - 取り込み元が cargo-compete・atcoder-cli のプロジェクトではない場合
+/// - 同名のコンテストディレクトリが既に存在する場合
+pub fn execute(work_dir: &Path, source_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let layout = detect_layout(source_dir).ok_or_else(|| {
+        format!(
+            "{} は cargo-compete・atcoder-cli のプロジェクトではありません",
+            source_dir.display()
+        )
+    })?;
+    let (contest_name, problems) = match layout {
+        Layout::CargoCompete => read_cargo_compete(source_dir)?,
+        Layout::AtCoderCli => read_atcoder_cli(source_dir)?,
+    };
+    if work_dir.join(&contest_name).exists() {
+        return Err(format!("ディレクトリが既に存在します: {}", contest_name).into());
+    }
+
+    let template_dir = resolve_template_dir(work_dir, None)?;
+    let contest_info = ContestInfo {
+        contest_name: contest_name.clone(),
+        problems: problems
+            .iter()
+            .map(|problem| problem.info.clone())
+            .collect(),
+    };
+    create_contest_directory(work_dir, &contest_info)?;
+    generate_cargo_toml(
+        work_dir,
+        &template_dir,
+        &contest_name,
+        &contest_info.problems,
+    )?;
+    let contest_dir = work_dir.join(&contest_name);
+    save_contest_metadata(&contest_dir, &contest_name, &contest_info.problems)?;
+    let added = merge_dependencies(
+        &contest_dir.join("Cargo.toml"),
+        &read_dependencies(&source_dir.join("Cargo.toml"))?,
+    )?;
+    if !added.is_empty() {
+        println!("Added dependencies: {}", added.join(", "));
+    }
+
+    for problem in &problems {
+        let problem_name = &problem.info.problem_name;
+        match &problem.source {
+            Some(source) => {
+                fs::copy(source, contest_dir.join(problem_name).join("main.rs"))?;
+            }
+            None => create_main_rs(work_dir, &template_dir, &contest_name, problem_name)?,
+        }
+        let tests_dir = contest_dir.join(problem_name).join("tests");
+        for (name, case) in &problem.test_cases {
+            fs::write(tests_dir.join(format!("{}.in", name)), &case.input)?;
+            fs::write(tests_dir.join(format!("{}.out", name)), &case.output)?;
+        }
+        println!(
+            "Imported {}: {} test case(s)",
+            problem_name,
+            problem.test_cases.len()
+        );
+    }
+    copy_rustfmt_toml(work_dir, &template_dir, &contest_name)?;
+    if is_workspace_root(work_dir) {
+        add_workspace_member(work_dir, &contest_name)?;
+    }
+
+    println!(
+        "Contest imported successfully: {} (from {:?})",
+        contest_name, layout
+    );
+    Ok(())
+}
+
+/// oj 形式のテストケースの取り込み処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリ
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない場合
+/// - oj 形式のテストケースが 1 つも存在しない場合
+pub fn execute_oj(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let oj_dir = problem_dir.join(OJ_TEST_DIR);
+    let test_cases = read_test_case_pairs(&oj_dir, &oj_dir, "in", "out")?;
+    if test_cases.is_empty() {
+        return Err(format!("{} にテストケースがありません", oj_dir.display()).into());
+    }
+
+    let tests_dir = problem_dir.join("tests");
+    fs::create_dir_all(&tests_dir)?;
+    for (name, case) in &test_cases {
+        let name = from_oj_name(name);
+        fs::write(tests_dir.join(format!("{}.in", name)), &case.input)?;
+        fs::write(tests_dir.join(format!("{}.out", name)), &case.output)?;
+    }
+    println!(
+        "Imported {} test case(s) from {}",
+        test_cases.len(),
+        oj_dir.display()
+    );
+    Ok(())
+}
+
+/// 取り込み元のプロジェクトの種類を判定する
+///
+/// # 戻り値
+/// - `Some(Layout)`: cargo-compete・atcoder-cli のプロジェクトの場合
+/// - `None`: いずれでもない場合
+pub fn detect_layout(source_dir: &Path) -> Option<Layout> {
+    if source_dir.join("contest.acc.json").is_file() {
+        return Some(Layout::AtCoderCli);
+    }
+    let cargo_toml: Value =
+        toml::from_str(&fs::read_to_string(source_dir.join("Cargo.toml")).ok()?).ok()?;
+    cargo_toml
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("cargo-compete"))
+        .map(|_| Layout::CargoCompete)
+}
+
+/// cargo-compete のテストケースファイル (`testcases/<問題>.yml`)
+#[derive(Deserialize, Debug)]
+struct CompeteTestSuite {
+    timelimit: Option<String>,
+    #[serde(default)]
+    cases: Vec<CompeteTestCase>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CompeteTestCase {
+    #[serde(rename = "in")]
+    input: String,
+    #[serde(default)]
+    out: Option<String>,
+}
+
+/// cargo-compete のプロジェクトからコンテスト名と問題の一覧を読み込む
+///
+/// # 処理の流れ
+/// 1. `Cargo.toml` の `[package]` の `name` をコンテスト名とする
+/// 2. `[[bin]]` の `path` (`src/bin/a.rs` など) のファイル名を問題名とする
+/// 3. `testcases/<問題>.yml` から実行時間制限とサンプルを読み込む
+/// 4. `testcases/<問題>/in/*.txt` と `out/*.txt` の組をテストケースとして読み込む
+fn read_cargo_compete(source_dir: &Path) -> Result<(String, Vec<ImportedProblem>), Box<dyn Error>> {
+    let cargo_toml: Value = toml::from_str(&fs::read_to_string(source_dir.join("Cargo.toml"))?)?;
+    let contest_name = cargo_toml
+        .get("package")
+        .and_then(|package| package.get("name"))
+        .and_then(|name| name.as_str())
+        .ok_or("Cargo.toml に [package] の name がありません")?
+        .to_string();
+
+    let mut problems = Vec::new();
+    let bins = cargo_toml
+        .get("bin")
+        .and_then(|bins| bins.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for bin in bins {
+        let Some(path) = bin.get("path").and_then(|path| path.as_str()) else {
+            continue;
+        };
+        let source = source_dir.join(path);
+        let Some(problem_name) = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_lowercase())
+        else {
+            continue;
+        };
+
+        let testcases_dir = source_dir.join("testcases");
+        let suite_path = testcases_dir.join(format!("{}.yml", problem_name));
+        let suite: Option<CompeteTestSuite> = if suite_path.is_file() {
+            Some(serde_norway::from_str(&fs::read_to_string(&suite_path)?)?)
+        } else {
+            None
+        };
+        let timeout = suite
+            .as_ref()
+            .and_then(|suite| suite.timelimit.as_deref())
+            .and_then(parse_timelimit)
+            .unwrap_or(DEFAULT_TIMEOUT);
+        let mut test_cases: Vec<(String, Sample)> = suite
+            .map(|suite| suite.cases)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(i, case)| {
+                (
+                    format!("sample_{}", i + 1),
+                    Sample {
+                        input: case.input,
+                        output: case.out.unwrap_or_default(),
+                    },
+                )
+            })
+            .collect();
+        let system_dir = testcases_dir.join(&problem_name);
+        test_cases.extend(read_test_case_pairs(
+            &system_dir.join("in"),
+            &system_dir.join("out"),
+            "txt",
+            "txt",
+        )?);
+
+        problems.push(ImportedProblem {
+            info: ProblemInfo {
+                problem_name,
+                timeout,
+                samples: Vec::new(),
+                memory_limit: None,
+                score: None,
+                statement: None,
+                title: None,
+                task_screen_name: None,
+            },
+            source: Some(source).filter(|source| source.is_file()),
+            test_cases,
+        });
+    }
+    if problems.is_empty() {
+        return Err("Cargo.toml に [[bin]] がありません".into());
+    }
+    Ok((contest_name, problems))
+}
+
+/// atcoder-cli のコンテスト情報 (`contest.acc.json`)
+#[derive(Deserialize, Debug)]
+struct AccContest {
+    contest: AccContestInfo,
+    tasks: Vec<AccTask>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AccContestInfo {
+    id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AccTask {
+    id: Option<String>,
+    label: String,
+    directory: Option<AccDirectory>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AccDirectory {
+    path: String,
+    testdir: Option<String>,
+    submit: Option<String>,
+}
+
+/// atcoder-cli のプロジェクトからコンテスト名と問題の一覧を読み込む
+///
+/// 問題名は問題のラベル(`A`, `B` など)を小文字にしたものとする。
+/// ディレクトリが作成されていない問題(`acc add` で選択しなかった問題)は取り込まない。
+/// テストケースは oj 形式(`sample-1.in`)の名前を `sample_1` の形式に変換する。
+fn read_atcoder_cli(source_dir: &Path) -> Result<(String, Vec<ImportedProblem>), Box<dyn Error>> {
+    let contest: AccContest =
+        serde_json::from_str(&fs::read_to_string(source_dir.join("contest.acc.json"))?)?;
+
+    let mut problems = Vec::new();
+    for task in contest.tasks {
+        let Some(directory) = task.directory else {
+            continue;
+        };
+        let problem_dir = source_dir.join(&directory.path);
+        let source = problem_dir.join(directory.submit.as_deref().unwrap_or("main.rs"));
+        let tests_dir = problem_dir.join(directory.testdir.as_deref().unwrap_or("tests"));
+        let test_cases = read_test_case_pairs(&tests_dir, &tests_dir, "in", "out")?
+            .into_iter()
+            .map(|(name, case)| (from_oj_name(&name), case))
+            .collect();
+        problems.push(ImportedProblem {
+            info: ProblemInfo {
+                problem_name: task.label.to_lowercase(),
+                timeout: DEFAULT_TIMEOUT,
+                samples: Vec::new(),
+                memory_limit: None,
+                score: None,
+                statement: None,
+                title: None,
+                task_screen_name: task.id,
+            },
+            source: Some(source).filter(|source| source.is_file()),
+            test_cases,
+        });
+    }
+    if problems.is_empty() {
+        return Err("contest.acc.json に取り込める問題がありません".into());
+    }
+    Ok((contest.contest.id, problems))
+}
+
+/// 入力と出力の組になるファイルをテストケースとして名前順に読み込む
+///
+/// # 引数
+/// - `input_dir`: 入力ファイルのディレクトリ
+/// - `output_dir`: 出力ファイルのディレクトリ
+/// - `input_ext`: 入力ファイルの拡張子
+/// - `output_ext`: 出力ファイルの拡張子
+///
+/// # 戻り値
+/// ファイル名(拡張子を除く)と入出力の組。出力ファイルが存在しない入力ファイルは除く。
+/// 入力ファイルのディレクトリが存在しない場合は空を返す。
+pub fn read_test_case_pairs(
+    input_dir: &Path,
+    output_dir: &Path,
+    input_ext: &str,
+    output_ext: &str,
+) -> Result<Vec<(String, Sample)>, Box<dyn Error>> {
+    if !input_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut test_cases = Vec::new();
+    for entry in fs::read_dir(input_dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != input_ext) {
+            continue;
+        }
+        let Some(name) = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let output_path = output_dir.join(format!("{}.{}", name, output_ext));
+        if !output_path.is_file() {
+            continue;
+        }
+        test_cases.push((
+            name,
+            Sample {
+                input: fs::read_to_string(&path)?,
+                output: fs::read_to_string(&output_path)?,
+            },
+        ));
+    }
+    test_cases.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(test_cases)
+}
+
+/// oj 形式のテストケース名(`sample-1`)をこのツールの形式(`sample_1`)に変換する
+///
+/// サンプル以外のテストケース名はそのまま返す。
+pub fn from_oj_name(name: &str) -> String {
+    match name.strip_prefix("sample-") {
+        Some(number) if number.parse::<usize>().is_ok() => format!("sample_{}", number),
+        _ => name.to_string(),
+    }
+}
+
+/// cargo-compete の実行時間制限(`2s`, `2500ms` など)をミリ秒に変換する
+fn parse_timelimit(timelimit: &str) -> Option<u128> {
+    let timelimit = timelimit.trim();
+    if let Some(ms) = timelimit.strip_suffix("ms") {
+        return ms.trim().parse().ok();
+    }
+    let seconds: f64 = timelimit.strip_suffix('s')?.trim().parse().ok()?;
+    Some((seconds * 1000.0).round() as u128)
+}
+
+/// `Cargo.toml` の `[dependencies]` を読み込む。ファイルが存在しない場合は空を返す
+fn read_dependencies(cargo_toml_path: &Path) -> Result<toml::Table, Box<dyn Error>> {
+    if !cargo_toml_path.is_file() {
+        return Ok(toml::Table::new());
+    }
+    let parsed: toml::Table = toml::from_str(&fs::read_to_string(cargo_toml_path)?)?;
+    Ok(parsed
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.as_table())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// 生成した `Cargo.toml` に含まれない依存クレートを `[dependencies]` に追記し、追記したクレート名を返す
+///
+/// `generate_cargo_toml` は `[dependencies]` を末尾に出力するため、ファイル末尾に追記する。
+fn merge_dependencies(
+    cargo_toml_path: &Path,
+    dependencies: &toml::Table,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut content = fs::read_to_string(cargo_toml_path)?;
+    let existing = read_dependencies(cargo_toml_path)?;
+    let added: Vec<String> = dependencies
+        .keys()
+        .filter(|name| !existing.contains_key(*name))
+        .cloned()
+        .collect();
+    if added.is_empty() {
+        return Ok(added);
+    }
+    if !content.lines().any(|line| line.trim() == "[dependencies]") {
+        content.push_str("\n[dependencies]\n");
+    } else if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for name in &added {
+        content.push_str(&format!("{} = {}\n", name, dependencies[name]));
+    }
+    fs::write(cargo_toml_path, content)?;
+    Ok(added)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_parse_timelimit_and_oj_name() {
+        assert_eq!(parse_timelimit("2s"), Some(2000));
+        assert_eq!(parse_timelimit("2.5s"), Some(2500));
+        assert_eq!(parse_timelimit("5250ms"), Some(5250));
+        assert_eq!(parse_timelimit("2 minutes"), None);
+
+        assert_eq!(from_oj_name("sample-1"), "sample_1");
+        assert_eq!(from_oj_name("sample-ex"), "sample-ex");
+        assert_eq!(from_oj_name("random_01"), "random_01");
+    }
+
+    #[test]
+    fn test_import_cargo_compete() {
+        let root = tempfile::tempdir().expect("");
+        let source_dir = root.path().join("compete/abc388");
+        fs::create_dir_all(source_dir.join("src/bin")).unwrap();
+        fs::create_dir_all(source_dir.join("testcases/a/in")).unwrap();
+        fs::create_dir_all(source_dir.join("testcases/a/out")).unwrap();
+        fs::write(
+            source_dir.join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+
+[package.metadata.cargo-compete.bin]
+abc388-a = { alias = "a", problem = "https://atcoder.jp/contests/abc388/tasks/abc388_a" }
+
+[[bin]]
+name = "abc388-a"
+path = "src/bin/a.rs"
+
+[dependencies]
+proconio = { version = "=0.4.5", features = ["derive"] }
+"#,
+        )
+        .unwrap();
+        fs::write(source_dir.join("src/bin/a.rs"), "fn main() { /* a */ }\n").unwrap();
+        fs::write(
+            source_dir.join("testcases/a.yml"),
+            "---\ntype: Batch\ntimelimit: 3s\nmatch: Lines\n\ncases:\n  - name: sample1\n    in: |\n      3\n    out: |\n      6\n  - name: sample2\n    in: |\n      1\n    out: |\n      2\n\nextend:\n  - type: Text\n    path: \"./a\"\n    in: /in/*.txt\n    out: /out/*.txt\n",
+        )
+        .unwrap();
+        fs::write(source_dir.join("testcases/a/in/random_01.txt"), "5\n").unwrap();
+        fs::write(source_dir.join("testcases/a/out/random_01.txt"), "10\n").unwrap();
+        assert_eq!(detect_layout(&source_dir), Some(Layout::CargoCompete));
+
+        let work_dir = root.path().join("work");
+        fs::create_dir_all(work_dir.join("templates")).unwrap();
+        fs::write(work_dir.join("templates/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(
+            work_dir.join("templates/Cargo.toml"),
+            "[dependencies]\nitertools = \"0.11.0\"\n",
+        )
+        .unwrap();
+        execute(&work_dir, &source_dir).unwrap();
+
+        let contest_dir = work_dir.join("abc388");
+        assert_eq!(
+            fs::read_to_string(contest_dir.join("a/main.rs")).unwrap(),
+            "fn main() { /* a */ }\n"
+        );
+        assert_eq!(
+            fs::read_to_string(contest_dir.join("a/tests/sample_2.in")).unwrap(),
+            "1\n"
+        );
+        assert_eq!(
+            fs::read_to_string(contest_dir.join("a/tests/random_01.out")).unwrap(),
+            "10\n"
+        );
+        let cargo_toml = fs::read_to_string(contest_dir.join("Cargo.toml")).unwrap();
+        let parsed: toml::Table = toml::from_str(&cargo_toml).unwrap();
+        assert_eq!(
+            parsed["package"]["metadata"]["timeout"]["a"].as_integer(),
+            Some(3000)
+        );
+        assert!(parsed["dependencies"].get("itertools").is_some());
+        assert_eq!(
+            parsed["dependencies"]["proconio"]["version"].as_str(),
+            Some("=0.4.5")
+        );
+
+        // 既存のディレクトリには取り込まない
+        assert!(execute(&work_dir, &source_dir).is_err());
+    }
+
+    #[test]
+    fn test_read_atcoder_cli() {
+        let root = tempfile::tempdir().expect("");
+        let source_dir = root.path().join("abc388");
+        fs::create_dir_all(source_dir.join("a/tests")).unwrap();
+        fs::write(
+            source_dir.join("contest.acc.json"),
+            r#"{
+  "contest": { "id": "abc388", "title": "AtCoder Beginner Contest 388", "url": "https://atcoder.jp/contests/abc388" },
+  "tasks": [
+    { "id": "abc388_a", "label": "A", "title": "?UPC", "url": "https://atcoder.jp/contests/abc388/tasks/abc388_a",
+      "directory": { "path": "a", "testdir": "tests", "submit": "main.rs" } },
+    { "id": "abc388_b", "label": "B", "title": "Heavy Snake", "url": "https://atcoder.jp/contests/abc388/tasks/abc388_b" }
+  ]
+}"#,
+        )
+        .unwrap();
+        fs::write(source_dir.join("a/tests/sample-1.in"), "Kyoto\n").unwrap();
+        fs::write(source_dir.join("a/tests/sample-1.out"), "KUPC\n").unwrap();
+        fs::write(source_dir.join("a/tests/sample-2.in"), "Tohoku\n").unwrap();
+        assert_eq!(detect_layout(&source_dir), Some(Layout::AtCoderCli));
+        assert_eq!(detect_layout(root.path()), None);
+
+        let (contest_name, problems) = read_atcoder_cli(&source_dir).unwrap();
+        assert_eq!(contest_name, "abc388");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].info.problem_name, "a");
+        assert_eq!(problems[0].info.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(problems[0].source, None);
+        // 出力ファイルが存在しないテストケースは取り込まない
+        assert_eq!(problems[0].test_cases.len(), 1);
+        assert_eq!(problems[0].test_cases[0].0, "sample_1");
+        assert_eq!(problems[0].test_cases[0].1.output, "KUPC\n");
+    }
+
+    #[test]
+    fn test_execute_oj() {
+        let work_dir = tempfile::tempdir().expect("");
+        let work_dir_path = work_dir.path().to_path_buf();
+        let oj_dir = work_dir.path().join("a").join(OJ_TEST_DIR);
+        fs::create_dir_all(&oj_dir).unwrap();
+        assert!(execute_oj(&work_dir_path, "a").is_err());
+
+        fs::write(oj_dir.join("sample-2.in"), "3\n").unwrap();
+        fs::write(oj_dir.join("sample-2.out"), "6\n").unwrap();
+        execute_oj(&work_dir_path, "a").unwrap();
+        assert_eq!(
+            fs::read_to_string(work_dir.path().join("a/tests/sample_2.out")).unwrap(),
+            "6\n"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+//! 初回利用時のセットアップを行うモジュール
+//!
+//! テンプレートディレクトリ(既定値は `templates`)に既定のテンプレートを作成し、
+//! 設定ファイル(`config.toml`)が存在しない場合は設定項目をコメントで記載した雛形を作成する。
+//! 既に存在するファイルは `force` を指定しない限り上書きしない。
+//!
+//! ```text
+//! .
+//! └── templates
+//!     ├── Cargo.toml      # ジャッジ環境に合わせた [dependencies]
+//!     └── main.rs         # proconio を利用した main.rs
+//! ```
+use std::{error::Error, fs, path::Path};
+
+use super::config::{get_template_dir, get_user_config_file};
+
+/// 既定の `templates/main.rs` (テンプレートが存在しない場合は `download` でも利用する)
+pub const DEFAULT_MAIN_RS: &str = r#"use proconio::{fastout, input};
+
+#[fastout]
+fn main() {
+    input! {
+        n: usize,
+        a: [i64; n],
+    }
+    println!("{}", a.iter().sum::<i64>());
+}
+"#;
+
+/// 既定の `templates/Cargo.toml` (ジャッジ環境で利用可能なクレート)
+pub const DEFAULT_CARGO_TOML: &str = include_str!("../../templates/Cargo.toml");
+
+/// 既定の `config.toml` (設定項目はすべてコメントアウトしている)
+const DEFAULT_CONFIG_TOML: &str = r#"# cargo atc の設定ファイル
+# `cargo atc config set <key> <value>` でも変更できます
+
+# アーカイブ先ディレクトリ (相対パスは作業ディレクトリ基準)
+# archive_dir = "archive"
+
+# AtCoder のベース URL
+# base_url = "https://atcoder.jp"
+
+# `download` でビルドを高速化する .cargo/config.toml を作成する (ワークスペースの場合はワークスペースのルートに作成)
+# cargo_config = false
+
+# .cargo/config.toml に設定するリンカ (lld または mold、未設定の場合は既定のリンカ)
+# cargo_linker = "mold"
+
+# .cargo/config.toml に設定するビルド成果物の格納先 (コンテスト間で共有する場合に指定、`~/` はホームディレクトリ)
+# cargo_target_dir = "~/.cache/atc/target"
+
+# `download` でコンテストを作成するディレクトリ (未設定の場合は作業ディレクトリ、`~/` はホームディレクトリ)
+# contests_dir = "~/atcoder"
+
+# `test` で AC 以外の結果が出た時点で残りのテストケースを実行しない (`--keep-going` で無効にできる)
+# fail_fast = true
+
+# ページ取得の最大試行回数 (5xx・タイムアウト時に再試行する)
+# fetch_retries = 3
+
+# `download` でコンテストディレクトリを Git リポジトリとして初期化する
+# git_init = true
+
+# 提出言語 ID
+# language_id = "5054"
+
+# HTTP リクエストに利用するプロキシ (未設定の場合は環境変数 HTTP_PROXY・HTTPS_PROXY に従う)
+# proxy = "http://proxy.example.ac.jp:8080"
+
+# 自分のレート (`contests --next` の対象判定に利用)
+# rating = 1200
+
+# `--remote` で利用するリモートマシン (コンテストの Cargo.toml の [package.metadata.remote] が優先されます)
+# remote_host = "user@desktop"
+# remote_dir = "~/atcoder"   # この下にコンテストディレクトリと同じ名前のディレクトリを作成して同期する
+
+# AtCoder へのリクエストの最小間隔 (ms、0 で待機しない)
+# request_interval = 200
+
+# `download` で作成する rust-toolchain.toml の channel (未設定の場合はジャッジの rustc、`none` で作成しない)
+# rust_toolchain = "1.70.0"
+
+# サンプルを取得する問題文の言語 (en または ja、もう一方にのみサンプルがある場合はそちらを利用する)
+# sample_lang = "en"
+
+# 既定のテンプレートプロファイル (`template use` で切り替える)
+# template = "default"
+
+# テンプレートディレクトリ (相対パスは作業ディレクトリ基準、`~/` はホームディレクトリ)
+# template_dir = "~/.config/atc/templates"
+
+# 公式のテストケースを公開している共有フォルダの URL (`testcases` で利用)
+# testcase_url = "https://www.dropbox.com/sh/nx3tnilzqz7df8a/AAAYlTq2tiEHl5hsESw6-yfLa"
+
+# 実行時間制限に加算する猶予 (ms)
+# timeout_margin = 0
+
+# 実行時間制限に掛ける倍率 (ローカルの実行環境がジャッジより遅い場合に 2.0 などを指定する)
+# timeout_multiplier = 1.0
+
+# コンテストごとの設定は、このファイルではなくコンテストの Cargo.toml に記述します
+# [package.metadata.format]
+# auto = true    # 提出前に rustfmt でソースコードを整形する
+# check = true   # フォーマットされていないソースコードの提出を拒否する
+"#;
+
+/// 初期設定処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ
+/// - `force`: `true` の場合、既存のテンプレートを上書きする
+///
+/// # エラーの可能性
+/// - ディレクトリの作成、ファイルの書き込みに失敗した場合
+pub fn execute(work_dir: &Path, force: bool) -> Result<(), Box<dyn Error>> {
+    let template_dir = get_template_dir(work_dir);
+    let files = [
+        (template_dir.join("main.rs"), DEFAULT_MAIN_RS, force),
+        (template_dir.join("Cargo.toml"), DEFAULT_CARGO_TOML, force),
+        // 設定ファイルはユーザーの設定を失わないよう `force` を指定しても上書きしない
+        (get_user_config_file(), DEFAULT_CONFIG_TOML, false),
+    ];
+
+    for (path, content, overwrite) in files {
+        if write_file(&path, content, overwrite)? {
+            println!("Created {}", path.display());
+        } else {
+            println!("Skipping {} (already exists)", path.display());
+        }
+    }
+    println!("Initialization completed successfully");
+    Ok(())
+}
+
+/// ファイルを作成する
+///
+/// # 戻り値
+/// - `true`: ファイルを作成した場合
+/// - `false`: ファイルが既に存在し、`overwrite` が `false` のため作成しなかった場合
+fn write_file(path: &Path, content: &str, overwrite: bool) -> Result<bool, Box<dyn Error>> {
+    if path.exists() && !overwrite {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content).map_err(|e| format!("{} を作成できません: {}", path.display(), e))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::config::UserConfig;
+    use tempfile;
+
+    #[test]
+    fn test_write_file() {
+        let work_dir = tempfile::tempdir().expect("");
+        let path = work_dir.path().join("templates/main.rs");
+        assert!(write_file(&path, DEFAULT_MAIN_RS, false).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), DEFAULT_MAIN_RS);
+
+        // 既存のファイルは上書きしない
+        fs::write(&path, "fn main() {}").unwrap();
+        assert!(!write_file(&path, DEFAULT_MAIN_RS, false).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fn main() {}");
+        assert!(write_file(&path, DEFAULT_MAIN_RS, true).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), DEFAULT_MAIN_RS);
+    }
+
+    #[test]
+    fn test_default_templates() {
+        let cargo_toml: toml::Value = toml::from_str(DEFAULT_CARGO_TOML).unwrap();
+        assert!(cargo_toml["dependencies"].get("proconio").is_some());
+
+        // 雛形の設定ファイルはすべて既定値として読み込める
+        let work_dir = tempfile::tempdir().expect("");
+        let path = work_dir.path().join("config.toml");
+        fs::write(&path, DEFAULT_CONFIG_TOML).unwrap();
+        let config = UserConfig::load(&path).unwrap();
+        assert!(config.get("template_dir").unwrap().is_none());
+    }
+}
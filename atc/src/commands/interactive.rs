@@ -0,0 +1,305 @@
+//! インタラクティブな問題(リアクティブ問題)をローカルのジャッジで検証するモジュール
+//!
+//! 問題ディレクトリに `judge.rs` を配置すると、`test` は解答と `judge.rs` のプログラム(インタラクタ)を同時に起動し、
+//! 解答の標準出力をインタラクタの標準入力に、インタラクタの標準出力を解答の標準入力に接続して実行する。
+//!
+//! インタラクタは以下の引数で起動する。
+//! - 第 1 引数: テストケースの入力ファイル(`tests/sample_1.in` など)のパス
+//! - 第 2 引数: 対応する `.out` ファイルのパス(存在する場合のみ)
+//!
+//! インタラクタが終了コード 0 で終了した場合は AC、0 以外で終了した場合は WA とし、
+//! インタラクタが標準エラー出力に出力した内容をエラーメッセージとして表示する。
+//! 解答が実行時間制限を超えた場合は TLE、異常終了した場合は RE とする。
+//!
+//! ```text
+//! .
+//! ├── Cargo.toml        # `{problem_name}-judge` のバイナリ定義が自動で追加される
+//! └── problem_name
+//!     ├── main.rs
+//!     ├── judge.rs      # インタラクタ
+//!     └── tests
+//!         └── sample_1.in   # インタラクタに渡す入力(`.out` は任意)
+//! ```
+use std::{
+    error::Error,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::process::describe_exit_status;
+use super::test::{get_execution_path, sort_test_cases, TestCaseResult, TestStatus};
+use super::validator::ensure_problem_bin;
+
+/// インタラクタのファイル名
+pub const JUDGE_FILE: &str = "judge.rs";
+
+/// インタラクタのバイナリ名を返す
+pub fn judge_bin_name(problem_name: &str) -> String {
+    format!("{}-judge", problem_name)
+}
+
+/// `judge.rs` が存在する場合、`Cargo.toml` にインタラクタのバイナリ定義を追加する
+///
+/// # 戻り値
+/// - `Ok(true)`: `judge.rs` が存在する場合(バイナリ定義は追加済み)
+/// - `Ok(false)`: `judge.rs` が存在しない場合
+/// - `Err(Box<dyn Error>)`: `Cargo.toml` の読み書きに失敗した場合
+pub fn ensure_judge_bin(work_dir: &Path, problem_name: &str) -> Result<bool, Box<dyn Error>> {
+    ensure_problem_bin(
+        work_dir,
+        problem_name,
+        JUDGE_FILE,
+        &judge_bin_name(problem_name),
+    )
+}
+
+/// 指定されたディレクトリ直下の `.in` ファイルを収集し、対応する `.out` のパスと組にする
+///
+/// インタラクティブな問題では期待する出力がないことが多いため、`.out` が存在しない入力も対象とする。
+pub fn collect_interactive_cases(
+    tests_dir: &Path,
+) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Error>> {
+    let mut test_cases = Vec::new();
+    for entry in fs::read_dir(tests_dir)? {
+        let input_file_path = entry?.path();
+        if input_file_path.extension().unwrap_or_default() == "in" {
+            let output_file_path = input_file_path.with_extension("out");
+            test_cases.push((input_file_path, output_file_path));
+        }
+    }
+    sort_test_cases(&mut test_cases);
+    Ok(test_cases)
+}
+
+/// テストケースごとに解答とインタラクタを接続して実行し、実行結果を返す
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `test_cases`: `collect_interactive_cases` で収集したテストケース
+/// - `timeout`: 解答の実行時間制限(ミリ秒)
+/// - `verbose`: `true` の場合、テストケースごとに実行結果を表示する
+pub fn run_interactive_cases(
+    work_dir: &Path,
+    problem_name: &str,
+    test_cases: Vec<(PathBuf, PathBuf)>,
+    timeout: u64,
+    verbose: bool,
+) -> Result<Vec<TestCaseResult>, Box<dyn Error>> {
+    let solution = get_execution_path(work_dir, problem_name)?;
+    let judge = get_execution_path(work_dir, &judge_bin_name(problem_name))?;
+    for executable in [&solution, &judge] {
+        if !executable.exists() {
+            return Err(format!("Executable does not exist: {}", executable.display()).into());
+        }
+    }
+
+    let mut results = Vec::new();
+    for (input_file, output_file) in test_cases {
+        let result = run_interactive(
+            &solution,
+            &judge,
+            &input_file,
+            Some(output_file.as_path()).filter(|path| path.exists()),
+            Duration::from_millis(timeout),
+        )?;
+        if verbose {
+            println!("Test Case: {}", result.test_case_name);
+            println!("Status: {}", result.status);
+            println!("Execution Time: {} ms", result.execution_time);
+            if let Some(message) = &result.error_message {
+                println!("Judge:\n{}", message);
+            }
+            println!();
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// 1 つのテストケースについて解答とインタラクタを接続して実行する
+///
+/// # エラーの可能性
+/// - 解答・インタラクタのプロセスを起動できない場合
+fn run_interactive(
+    solution: &Path,
+    judge: &Path,
+    input_file: &Path,
+    output_file: Option<&Path>,
+    timeout: Duration,
+) -> Result<TestCaseResult, Box<dyn Error>> {
+    let test_case_name = input_file
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    let mut judge_command = Command::new(judge);
+    judge_command.arg(input_file);
+    if let Some(output_file) = output_file {
+        judge_command.arg(output_file);
+    }
+    let mut judge_child = judge_command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("インタラクタを起動できませんでした: {}", e))?;
+    let judge_stdin = judge_child.stdin.take().unwrap();
+    let judge_stdout = judge_child.stdout.take().unwrap();
+    let mut judge_stderr = judge_child.stderr.take().unwrap();
+    // インタラクタの標準エラー出力がパイプを埋めて停止しないよう、別スレッドで読み続ける
+    let stderr_reader = thread::spawn(move || {
+        let mut message = String::new();
+        let _ = judge_stderr.read_to_string(&mut message);
+        message
+    });
+
+    let start_time = Instant::now();
+    let mut solution_child = match Command::new(solution)
+        .stdin(Stdio::from(judge_stdout))
+        .stdout(Stdio::from(judge_stdin))
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = judge_child.kill();
+            let _ = judge_child.wait();
+            return Err(format!("解答を起動できませんでした: {}", e).into());
+        }
+    };
+
+    let solution_status = wait_with_timeout(&mut solution_child, timeout)?;
+    let execution_time = start_time.elapsed().as_millis();
+    let judge_status = match solution_status {
+        // 解答が終了した後もインタラクタが終了しない場合は、実行時間制限まで待って打ち切る
+        Some(_) => wait_with_timeout(&mut judge_child, timeout)?,
+        None => None,
+    };
+    if judge_status.is_none() {
+        let _ = judge_child.kill();
+        let _ = judge_child.wait();
+    }
+    let judge_message = stderr_reader.join().unwrap_or_default();
+    let judge_message = Some(judge_message.trim().to_string()).filter(|m| !m.is_empty());
+
+    let (status, error_message) = match (solution_status, judge_status) {
+        (None, _) => (TestStatus::TLE, None),
+        (Some(_), None) => (
+            TestStatus::TLE,
+            Some("インタラクタが終了しませんでした".to_string()),
+        ),
+        (Some(solution), Some(_)) if !solution.success() => (
+            TestStatus::RE,
+            Some(format!(
+                "Execution failed ({})",
+                describe_exit_status(&solution)
+            )),
+        ),
+        (Some(_), Some(judge)) if judge.success() => (TestStatus::AC, judge_message),
+        (Some(_), Some(_)) => (TestStatus::WA, judge_message),
+    };
+    Ok(TestCaseResult {
+        test_case_name,
+        status,
+        execution_time,
+        error_message,
+        stderr: None,
+        timing: None,
+    })
+}
+
+/// プロセスの終了を `timeout` まで待機する
+///
+/// # 戻り値
+/// - `Ok(Some(status))`: 時間内に終了した場合
+/// - `Ok(None)`: 時間内に終了しなかった場合(プロセスは強制終了する)
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Duration,
+) -> Result<Option<std::process::ExitStatus>, Box<dyn Error>> {
+    let start_time = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if start_time.elapsed() > timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_collect_interactive_cases() {
+        let tests_dir = tempfile::tempdir().expect("");
+        fs::write(tests_dir.path().join("sample_1.in"), "3\n").unwrap();
+        fs::write(tests_dir.path().join("sample_2.in"), "5\n").unwrap();
+        fs::write(tests_dir.path().join("sample_2.out"), "").unwrap();
+        fs::write(tests_dir.path().join("memo.txt"), "").unwrap();
+
+        let test_cases = collect_interactive_cases(tests_dir.path()).unwrap();
+        assert_eq!(
+            test_cases,
+            vec![
+                (
+                    tests_dir.path().join("sample_1.in"),
+                    tests_dir.path().join("sample_1.out")
+                ),
+                (
+                    tests_dir.path().join("sample_2.in"),
+                    tests_dir.path().join("sample_2.out")
+                ),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_interactive() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("");
+        let write_script = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+            path
+        };
+        // 入力ファイルの数を解答に送り、2 倍の値が返ってくれば AC とするインタラクタ
+        let judge = write_script(
+            "judge.sh",
+            "#!/bin/sh\nn=$(cat \"$1\")\necho \"$n\"\nread answer\nif [ \"$answer\" = \"$((n * 2))\" ]; then exit 0; fi\necho \"expected $((n * 2)), got $answer\" >&2\nexit 1\n",
+        );
+        let correct = write_script("correct.sh", "#!/bin/sh\nread n\necho $((n * 2))\n");
+        let wrong = write_script("wrong.sh", "#!/bin/sh\nread n\necho $n\n");
+        let slow = write_script("slow.sh", "#!/bin/sh\nread n\nsleep 5\n");
+        let input = dir.path().join("sample_1.in");
+        fs::write(&input, "21\n").unwrap();
+        let timeout = Duration::from_millis(2000);
+
+        let result = run_interactive(&correct, &judge, &input, None, timeout).unwrap();
+        assert_eq!(result.test_case_name, "sample_1.in");
+        assert_eq!(result.status, TestStatus::AC);
+
+        let result = run_interactive(&wrong, &judge, &input, None, timeout).unwrap();
+        assert_eq!(result.status, TestStatus::WA);
+        assert_eq!(result.error_message.as_deref(), Some("expected 42, got 21"));
+
+        let result =
+            run_interactive(&slow, &judge, &input, None, Duration::from_millis(300)).unwrap();
+        assert_eq!(result.status, TestStatus::TLE);
+    }
+}
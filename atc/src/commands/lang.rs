@@ -0,0 +1,159 @@
+//! 提出可能な言語の一覧を表示・キャッシュするモジュール
+//!
+//! 提出ページの言語選択肢(`data.LanguageId`)から言語IDと言語名の一覧を取得して表示し、
+//! 設定ディレクトリの `languages.json` にキャッシュする。
+//! `submit` は提出前にキャッシュを参照し、提出に利用する言語IDが一覧に含まれない場合は提出を中止する。
+//! (キャッシュが存在しない場合は検証しない)
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+
+use super::config::{get_base_url, get_languages_file, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+use super::migrate::{get_language_id, parse_languages};
+
+/// 言語一覧表示処理のエントリーポイント
+///
+/// # 引数
+/// - `contest_name`: 言語一覧を取得する提出ページのコンテスト名
+/// - `filter`: 指定された場合、言語名に含まれる言語のみを表示する (大文字・小文字は区別しない)
+///
+/// # エラーの可能性
+/// - 提出ページの取得に失敗した場合、または言語の選択肢が見つからない場合
+pub async fn execute(contest_name: &str, filter: Option<&str>) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+
+    let url = format!("{}/contests/{}/submit", get_base_url(), contest_name);
+    let html = fetch_with_session(&client, &url, &session).await?;
+    let cache = LanguageCache {
+        languages: parse_languages(&Html::parse_document(&html))
+            .into_iter()
+            .map(|(id, name)| Language { id, name })
+            .collect(),
+    };
+    if cache.languages.is_empty() {
+        return Err("提出ページに言語の選択肢が見つかりません".into());
+    }
+    cache.save(&get_languages_file())?;
+
+    let current = get_language_id()?;
+    let filter = filter.map(|f| f.to_lowercase());
+    for language in &cache.languages {
+        if filter
+            .as_ref()
+            .is_some_and(|f| !language.name.to_lowercase().contains(f.as_str()))
+        {
+            continue;
+        }
+        let mark = if language.id == current { "*" } else { " " };
+        println!("{} {:>5}  {}", mark, language.id, language.name);
+    }
+    println!("\n{} language(s) cached", cache.languages.len());
+    Ok(())
+}
+
+/// 提出可能な言語
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Language {
+    pub id: String,   // 言語ID
+    pub name: String, // 言語名 (例: "Rust (rustc 1.70.0)")
+}
+
+/// 提出可能な言語一覧のキャッシュ
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct LanguageCache {
+    pub languages: Vec<Language>,
+}
+
+impl LanguageCache {
+    /// キャッシュを読み込む
+    pub fn load(path: &Path) -> Result<Option<Self>, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    /// キャッシュを保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 言語IDに対応する言語を返す
+    pub fn find(&self, language_id: &str) -> Option<&Language> {
+        self.languages
+            .iter()
+            .find(|language| language.id == language_id)
+    }
+}
+
+/// 提出に利用する言語IDがキャッシュした言語一覧に含まれるかを検証する
+///
+/// # 引数
+/// - `path`: 言語一覧のキャッシュファイル
+/// - `language_id`: 検証する言語ID
+///
+/// # 戻り値
+/// - `Ok(Some(Language))`: 言語IDが一覧に含まれる場合
+/// - `Ok(None)`: キャッシュが存在しないため検証できない場合
+///
+/// # エラーの可能性
+/// - 言語IDが一覧に含まれない場合
+pub fn validate_language_id(
+    path: &Path,
+    language_id: &str,
+) -> Result<Option<Language>, Box<dyn Error>> {
+    let Some(cache) = LanguageCache::load(path)? else {
+        return Ok(None);
+    };
+    match cache.find(language_id) {
+        Some(language) => Ok(Some(language.clone())),
+        None => Err(format!(
+            "言語ID {} は提出可能な言語に含まれていません。`cargo atc lang` で確認してください",
+            language_id
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_validate_language_id() {
+        let work_dir = tempfile::tempdir().expect("");
+        let path = work_dir.path().join("languages.json");
+        // キャッシュが存在しない場合は検証しない
+        assert_eq!(validate_language_id(&path, "5054").unwrap(), None);
+
+        let cache = LanguageCache {
+            languages: vec![
+                Language {
+                    id: "5001".to_string(),
+                    name: "C++ 20 (gcc 12.2)".to_string(),
+                },
+                Language {
+                    id: "5054".to_string(),
+                    name: "Rust (rustc 1.70.0)".to_string(),
+                },
+            ],
+        };
+        cache.save(&path).unwrap();
+        assert_eq!(LanguageCache::load(&path).unwrap(), Some(cache));
+
+        let language = validate_language_id(&path, "5054").unwrap().unwrap();
+        assert_eq!(language.name, "Rust (rustc 1.70.0)");
+        assert!(validate_language_id(&path, "4050").is_err());
+    }
+}
@@ -12,8 +12,10 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use super::config::{get_session_file, BASE_URL};
-const SESSION_EXPIRY: u64 = 86400; // 24時間
+use super::config::{get_base_url, get_session_file};
+use super::http::{client, client_builder, throttle};
+use super::whoami::fetch_username;
+pub const SESSION_EXPIRY: u64 = 86400; // 24時間
 
 /// ログイン処理のエントリーポイント
 pub async fn execute() -> Result<(), Box<dyn Error>> {
@@ -28,7 +30,7 @@ pub async fn execute() -> Result<(), Box<dyn Error>> {
     println!("login:");
     let credentials =
         get_credentials().map_err(|e| format!("認証情報の取得に失敗しました: {}", e))?;
-    let session = login_to_atcoder(&credentials, BASE_URL)
+    let session = login_to_atcoder(&credentials, &get_base_url())
         .await
         .map_err(|e| format!("ログイン中にエラーが発生しました: {}", e))?;
     session.save(&session_path)?;
@@ -36,6 +38,68 @@ pub async fn execute() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// 対話的なログインを行わずにセッションの有効性を確認する (`login --check`)
+///
+/// 確認結果を 1 行の JSON (`SessionCheck`) として標準出力に出力する。
+///
+/// # エラーの可能性
+/// - セッションが存在しない、または無効な場合 (終了コード 1 で終了させるため)
+pub async fn execute_check() -> Result<(), Box<dyn Error>> {
+    let session = Session::load(&get_session_file())?;
+    let result = check_session(&get_base_url(), &client()?, session.as_ref()).await;
+    println!("{}", serde_json::to_string(&result)?);
+    if result.valid {
+        Ok(())
+    } else {
+        Err(result.message.into())
+    }
+}
+
+/// セッションの確認結果
+#[derive(Serialize, Debug, PartialEq)]
+pub struct SessionCheck {
+    pub valid: bool,              // セッションが有効かどうか
+    pub username: Option<String>, // ログイン中のユーザー名
+    pub message: String,          // 確認結果の説明
+}
+
+/// 認証付きでページを取得し、セッションが有効かを確認する
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 確認するセッション情報 (`None` の場合は未ログイン)
+pub async fn check_session(
+    base_url: &str,
+    client: &Client,
+    session: Option<&Session>,
+) -> SessionCheck {
+    let Some(session) = session else {
+        return SessionCheck {
+            valid: false,
+            username: None,
+            message: "not logged in".to_string(),
+        };
+    };
+    match fetch_username(base_url, client, session).await {
+        Ok(Some(username)) => SessionCheck {
+            valid: true,
+            message: format!("logged in as {}", username),
+            username: Some(username),
+        },
+        Ok(None) => SessionCheck {
+            valid: false,
+            username: None,
+            message: format!("session for {} is no longer valid", session.username),
+        },
+        Err(e) => SessionCheck {
+            valid: false,
+            username: None,
+            message: format!("failed to verify session: {}", e),
+        },
+    }
+}
+
 /// ログイン情報の構造体
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Session {
@@ -74,14 +138,54 @@ impl Session {
     ///
     /// - `SESSION_EXPIRY` を超えている場合`true`を返す。
     pub fn is_expired(&self) -> bool {
+        self.age() > SESSION_EXPIRY
+    }
+
+    /// ログインからの経過秒数を返す
+    pub fn age(&self) -> u64 {
         let current_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        current_time - self.last_login_time > SESSION_EXPIRY
+        current_time.saturating_sub(self.last_login_time)
+    }
+
+    /// 認証付きリクエストに付与する `Cookie` ヘッダーの値を返す
+    pub fn cookie_header(&self) -> String {
+        format!(
+            "REVEL_SESSION={};",
+            self.session_cookie.trim_start_matches("REVEL_SESSION=")
+        )
     }
 }
 
+/// セッション情報を付与して指定されたURLのページを取得する
+///
+/// # 引数
+/// - `client`: `reqwest::Client` オブジェクト
+/// - `url`: 取得するページのURL
+/// - `session`: 認証に利用するセッション情報
+///
+/// # 戻り値
+/// - `Ok(String)`: レスポンスボディ
+/// - `Err(Box<dyn Error>)`: HTTPリクエストが失敗した場合、またはステータスコードが成功範囲(200-299)でない場合
+pub async fn fetch_with_session(
+    client: &Client,
+    url: &str,
+    session: &Session,
+) -> Result<String, Box<dyn Error>> {
+    throttle(url).await;
+    let response = client
+        .get(url)
+        .header("Cookie", session.cookie_header())
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP request failed with status: {}", response.status()).into());
+    }
+    Ok(response.text().await?)
+}
+
 /// ユーザーIDとパスワードを受け取る構造体
 pub struct UserCredentials {
     pub user_id: String,
@@ -162,7 +266,7 @@ pub async fn login_to_atcoder(
     let cookie_store = Arc::new(Jar::default());
     let login_url = format!("{}/login", base_url);
 
-    let client = Client::builder()
+    let client = client_builder()?
         .cookie_store(true)
         .cookie_provider(Arc::clone(&cookie_store))
         .redirect(reqwest::redirect::Policy::none()) // リダイレクトを無効化
@@ -177,6 +281,7 @@ pub async fn login_to_atcoder(
         ("password", credentials.password.as_str()),
         ("csrf_token", csrf_token.as_str()),
     ];
+    throttle(&login_url).await;
     let login_response = client.post(&login_url).form(&login_form).send().await?;
     validate_login(&login_response)?;
     // セッションCookie
@@ -206,6 +311,7 @@ pub async fn login_to_atcoder(
 /// - ネットワークエラーによりページが取得できない場合はエラーを返す
 async fn get_csrf_token(client: &Client, url: &str) -> Result<String, Box<dyn Error>> {
     let selector = Selector::parse("input[name=\"csrf_token\"]").unwrap();
+    throttle(url).await;
     let body = client.get(url).send().await?.text().await?;
     let document = Html::parse_document(&body);
     let csrf_token = document
@@ -281,6 +387,48 @@ mod test {
     use mockito::{Matcher, Server};
     use tempfile;
 
+    #[tokio::test]
+    async fn test_check_session() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/home")
+            .match_header("Cookie", "REVEL_SESSION=mock_session_cookie;")
+            .with_status(200)
+            .with_body(r#"<script>var userScreenName = "mock_user";</script>"#)
+            .create_async()
+            .await;
+        let _expired = server
+            .mock("GET", "/home")
+            .match_header("Cookie", "REVEL_SESSION=expired_cookie;")
+            .with_status(200)
+            .with_body(r#"<script>var userScreenName = "";</script>"#)
+            .create_async()
+            .await;
+        let client = Client::new();
+        let mut session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+
+        let result = check_session(&server.url(), &client, Some(&session)).await;
+        assert!(result.valid);
+        assert_eq!(result.username.as_deref(), Some("mock_user"));
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"valid":true,"username":"mock_user","message":"logged in as mock_user"}"#
+        );
+
+        session.session_cookie = "REVEL_SESSION=expired_cookie".to_string();
+        assert!(
+            !check_session(&server.url(), &client, Some(&session))
+                .await
+                .valid
+        );
+        assert!(!check_session(&server.url(), &client, None).await.valid);
+    }
+
     #[tokio::test]
     async fn test_login_to_atcoder_success() {
         let mut server = Server::new_async().await;
@@ -429,6 +577,20 @@ mod test {
         assert!(expired_session.is_expired());
     }
 
+    #[test]
+    fn test_cookie_header() {
+        let session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+        assert_eq!(
+            session.cookie_header(),
+            "REVEL_SESSION=mock_session_cookie;"
+        );
+    }
+
     #[tokio::test]
     async fn test_get_csrf_token() {
         let mut server = Server::new_async().await;
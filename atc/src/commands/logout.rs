@@ -0,0 +1,87 @@
+//! 保存されているセッション情報を削除するモジュール
+//!
+//! 通常は `session.json` のみを削除する。`all_profiles` を指定した場合は、
+//! 設定ディレクトリ内のすべてのセッションファイル(`session*.json`)を削除する。
+use std::{error::Error, fs, path::Path};
+
+use super::config::{get_config_dir, get_session_file};
+
+/// ログアウト処理のエントリーポイント
+///
+/// # 引数
+/// - `all_profiles`: `true` の場合、すべてのアカウントのセッションファイルを削除する
+pub fn execute(all_profiles: bool) -> Result<(), Box<dyn Error>> {
+    let removed = if all_profiles {
+        remove_all_sessions(&get_config_dir())?
+    } else {
+        remove_session(&get_session_file())? as usize
+    };
+    if removed == 0 {
+        println!("Not logged in");
+    } else {
+        println!("Logged out ({} session(s) removed)", removed);
+    }
+    Ok(())
+}
+
+/// セッションファイルを削除する
+///
+/// # 戻り値
+/// - `Ok(true)`: セッションファイルを削除した場合
+/// - `Ok(false)`: セッションファイルが存在しなかった場合
+fn remove_session(session_file: &Path) -> Result<bool, Box<dyn Error>> {
+    if !session_file.exists() {
+        return Ok(false);
+    }
+    fs::remove_file(session_file)?;
+    Ok(true)
+}
+
+/// 設定ディレクトリ内のすべてのセッションファイルを削除し、削除した数を返す
+fn remove_all_sessions(config_dir: &Path) -> Result<usize, Box<dyn Error>> {
+    if !config_dir.exists() {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for entry in fs::read_dir(config_dir)? {
+        let path = entry?.path();
+        let file_name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        if path.is_file() && file_name.starts_with("session") && file_name.ends_with(".json") {
+            fs::remove_file(&path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_remove_session() {
+        let config_dir = tempfile::tempdir().expect("");
+        let session_file = config_dir.path().join("session.json");
+        fs::write(&session_file, "{}").unwrap();
+
+        assert!(remove_session(&session_file).unwrap());
+        assert!(!session_file.exists());
+        assert!(!remove_session(&session_file).unwrap());
+    }
+
+    #[test]
+    fn test_remove_all_sessions() {
+        let config_dir = tempfile::tempdir().expect("");
+        fs::write(config_dir.path().join("session.json"), "{}").unwrap();
+        fs::write(config_dir.path().join("session-sub.json"), "{}").unwrap();
+        fs::write(config_dir.path().join("environment.json"), "{}").unwrap();
+
+        assert_eq!(remove_all_sessions(config_dir.path()).unwrap(), 2);
+        assert!(config_dir.path().join("environment.json").exists());
+    }
+}
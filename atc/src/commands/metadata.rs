@@ -0,0 +1,298 @@
+//! コンテストの問題情報(`contest.json`)を読み書きするモジュール
+//!
+//! `download`・`add` で取得した各問題の実行時間制限・メモリ制限・配点・問題ページの URL と、
+//! ダウンロード日時をコンテストディレクトリの `contest.json` に保存する。
+//! `test`・`submit`・`open`・`status` など、問題ページを再取得せずにコンテスト名や制限を参照したいコマンドから利用する。
+//!
+//! 問題 ID(`task_screen_name`)は問題一覧ページのリンクから取得したものを保存する。
+//! 古いコンテストや `ex` などの問題では問題名から組み立てた `<コンテスト名>_<問題名>` と一致しないため、
+//! `submit`・`open` は `task_screen_name` で問題 ID を解決する。
+//!
+//! `editorial` で取得した解説ページへのリンクは `editorials` に保存する。再度 `download` した場合も保持する。
+//!
+//! ```json
+//! {
+//!   "contest": "abc388",
+//!   "downloaded_at": "2025-01-11T21:00:00+09:00",
+//!   "problems": [
+//!     {
+//!       "problem": "a", "timeout": 2000, "memory_limit": 1024, "score": 100, "task_screen_name": "abc388_a",
+//!       "url": "https://atcoder.jp/contests/abc388/tasks/abc388_a"
+//!     }
+//!   ]
+//! }
+//! ```
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::Path};
+
+use super::config::get_base_url;
+use super::download::ProblemInfo;
+
+/// コンテストの問題情報を保存するファイル名 (コンテストディレクトリからの相対パス)
+pub const CONTEST_METADATA_FILE: &str = "contest.json";
+
+/// コンテストの問題情報
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct ContestMetadata {
+    pub contest: String, // コンテスト名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub downloaded_at: Option<String>, // 最後にダウンロードした日時 (RFC 3339)
+    pub problems: Vec<ProblemMetadata>, // 問題ごとの情報 (問題一覧ページの順)
+}
+
+/// 問題ごとの情報
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProblemMetadata {
+    pub problem: String,           // 問題名
+    pub timeout: u128,             // 実行時間制限 (ミリ秒)
+    pub memory_limit: Option<u64>, // メモリ制限 (MB)
+    pub score: Option<u64>,        // 配点
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_screen_name: Option<String>, // 問題 ID (`abc388_a` など)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>, // 問題ページの URL
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub editorials: Vec<EditorialLink>, // 解説ページへのリンク (`editorial` で取得)
+}
+
+/// 解説ページへのリンク
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EditorialLink {
+    pub title: String, // 解説の見出し (`公式 解説 by en_translator` など)
+    pub url: String,   // 解説ページの URL
+}
+
+impl ProblemMetadata {
+    /// 問題の情報から `contest.json` に保存する情報を作成する
+    ///
+    /// 問題ページの URL は問題 ID から組み立てる (問題 ID が不明な場合は保存しない)。
+    fn new(base_url: &str, contest_name: &str, problem: &ProblemInfo) -> Self {
+        ProblemMetadata {
+            problem: problem.problem_name.clone(),
+            timeout: problem.timeout,
+            memory_limit: problem.memory_limit,
+            score: problem.score,
+            task_screen_name: problem.task_screen_name.clone(),
+            url: problem
+                .task_screen_name
+                .as_ref()
+                .map(|_| problem.url(base_url, contest_name)),
+            editorials: Vec::new(),
+        }
+    }
+}
+
+impl ContestMetadata {
+    /// 問題名に対応する情報を返す
+    pub fn problem(&self, problem_name: &str) -> Option<&ProblemMetadata> {
+        self.problems
+            .iter()
+            .find(|problem| problem.problem == problem_name)
+    }
+
+    /// 問題 ID に対応する問題名を返す
+    pub fn problem_name_of(&self, task_screen_name: &str) -> Option<&str> {
+        self.problems
+            .iter()
+            .find(|problem| problem.task_screen_name.as_deref() == Some(task_screen_name))
+            .map(|problem| problem.problem.as_str())
+    }
+}
+
+/// `contest.json` を読み込む。存在しない場合は `None` を返す
+///
+/// # エラーの可能性
+/// - `contest.json` の読み込み・解析に失敗した場合
+pub fn load_contest_metadata(
+    contest_dir: &Path,
+) -> Result<Option<ContestMetadata>, Box<dyn Error>> {
+    let path = contest_dir.join(CONTEST_METADATA_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)?;
+    let metadata = serde_json::from_str(&content)
+        .map_err(|e| format!("{} の解析に失敗しました: {}", path.display(), e))?;
+    Ok(Some(metadata))
+}
+
+/// 問題の問題 ID を返す
+///
+/// `contest.json` に問題 ID が保存されていればそれを返し、なければ `<コンテスト名>_<問題名>` を返す。
+///
+/// # 引数
+/// - `contest_dir`: コンテストディレクトリ
+/// - `contest_name`: コンテスト名
+/// - `problem_name`: 問題名 (`a`, `ex` など)
+pub fn task_screen_name(contest_dir: &Path, contest_name: &str, problem_name: &str) -> String {
+    load_contest_metadata(contest_dir)
+        .ok()
+        .flatten()
+        .and_then(|metadata| metadata.problem(problem_name)?.task_screen_name.clone())
+        .unwrap_or_else(|| format!("{}_{}", contest_name, problem_name))
+}
+
+/// 問題の情報を `contest.json` に書き込む
+///
+/// 既に `contest.json` が存在する場合は、同名の問題の情報を置き換え、それ以外の問題は末尾に追加する。
+/// ダウンロード日時は現在時刻に更新する。
+///
+/// # 引数
+/// - `contest_dir`: コンテストディレクトリ
+/// - `contest_name`: コンテスト名
+/// - `problems`: 書き込む問題
+pub fn save_contest_metadata(
+    contest_dir: &Path,
+    contest_name: &str,
+    problems: &[ProblemInfo],
+) -> Result<(), Box<dyn Error>> {
+    let base_url = get_base_url();
+    let mut metadata = load_contest_metadata(contest_dir)?.unwrap_or_default();
+    metadata.contest = contest_name.to_string();
+    metadata.downloaded_at = Some(Local::now().to_rfc3339());
+    for problem in problems
+        .iter()
+        .map(|problem| ProblemMetadata::new(&base_url, contest_name, problem))
+    {
+        match metadata
+            .problems
+            .iter_mut()
+            .find(|existing| existing.problem == problem.problem)
+        {
+            Some(existing) => {
+                let editorials = std::mem::take(&mut existing.editorials);
+                *existing = ProblemMetadata {
+                    editorials,
+                    ..problem
+                };
+            }
+            None => metadata.problems.push(problem),
+        }
+    }
+    write_contest_metadata(contest_dir, &metadata)
+}
+
+/// `contest.json` を書き込む
+///
+/// # エラーの可能性
+/// - `contest.json` の書き込みに失敗した場合
+pub fn write_contest_metadata(
+    contest_dir: &Path,
+    metadata: &ContestMetadata,
+) -> Result<(), Box<dyn Error>> {
+    fs::write(
+        contest_dir.join(CONTEST_METADATA_FILE),
+        serde_json::to_string_pretty(metadata)? + "\n",
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    fn problem(name: &str, timeout: u128, score: Option<u64>) -> ProblemInfo {
+        ProblemInfo {
+            problem_name: name.to_string(),
+            timeout,
+            memory_limit: Some(1024),
+            score,
+            samples: vec![],
+            statement: None,
+            title: None,
+            task_screen_name: None,
+        }
+    }
+
+    #[test]
+    fn test_save_contest_metadata() {
+        let contest_dir = tempfile::tempdir().expect("");
+        assert_eq!(load_contest_metadata(contest_dir.path()).unwrap(), None);
+
+        save_contest_metadata(
+            contest_dir.path(),
+            "abc388",
+            &[problem("a", 2000, Some(100)), problem("b", 2000, None)],
+        )
+        .unwrap();
+        save_contest_metadata(
+            contest_dir.path(),
+            "abc388",
+            &[problem("b", 3000, Some(200)), problem("c", 2000, Some(300))],
+        )
+        .unwrap();
+
+        let metadata = load_contest_metadata(contest_dir.path()).unwrap().unwrap();
+        assert_eq!(metadata.contest, "abc388");
+        let names: Vec<&str> = metadata
+            .problems
+            .iter()
+            .map(|problem| problem.problem.as_str())
+            .collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        let b = metadata.problem("b").unwrap();
+        assert_eq!(
+            (b.timeout, b.memory_limit, b.score),
+            (3000, Some(1024), Some(200))
+        );
+        assert!(metadata.problem("d").is_none());
+
+        // 再度書き込んでも解説へのリンクは保持する
+        let mut metadata = metadata;
+        metadata.problems[0].editorials = vec![EditorialLink {
+            title: "公式 解説 by en_translator".to_string(),
+            url: "https://atcoder.jp/contests/abc388/editorial/1".to_string(),
+        }];
+        write_contest_metadata(contest_dir.path(), &metadata).unwrap();
+        save_contest_metadata(contest_dir.path(), "abc388", &[problem("a", 3000, None)]).unwrap();
+        let metadata = load_contest_metadata(contest_dir.path()).unwrap().unwrap();
+        let a = metadata.problem("a").unwrap();
+        assert_eq!((a.timeout, a.editorials.len()), (3000, 1));
+    }
+
+    #[test]
+    fn test_task_screen_name() {
+        let contest_dir = tempfile::tempdir().expect("");
+        assert_eq!(
+            task_screen_name(contest_dir.path(), "abc388", "a"),
+            "abc388_a"
+        );
+
+        let mut ex = problem("ex", 2000, None);
+        ex.task_screen_name = Some("abc300_h".to_string());
+        save_contest_metadata(
+            contest_dir.path(),
+            "abc300",
+            &[ex, problem("a", 2000, None)],
+        )
+        .unwrap();
+        assert_eq!(
+            task_screen_name(contest_dir.path(), "abc300", "ex"),
+            "abc300_h"
+        );
+        let metadata = load_contest_metadata(contest_dir.path()).unwrap().unwrap();
+        assert!(metadata.downloaded_at.is_some());
+        assert!(metadata
+            .problem("ex")
+            .unwrap()
+            .url
+            .as_deref()
+            .is_some_and(|url| url.ends_with("/contests/abc300/tasks/abc300_h")));
+        assert_eq!(metadata.problem("a").unwrap().url, None);
+        assert_eq!(metadata.problem_name_of("abc300_h"), Some("ex"));
+        assert_eq!(metadata.problem_name_of("abc300_a"), None);
+        assert_eq!(
+            task_screen_name(contest_dir.path(), "abc300", "a"),
+            "abc300_a"
+        );
+    }
+
+    #[test]
+    fn test_load_contest_metadata_invalid() {
+        let contest_dir = tempfile::tempdir().expect("");
+        fs::write(contest_dir.path().join(CONTEST_METADATA_FILE), "{").unwrap();
+        assert!(load_contest_metadata(contest_dir.path()).is_err());
+    }
+}
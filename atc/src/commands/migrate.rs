@@ -0,0 +1,396 @@
+//! AtCoder の言語アップデートに追従するための移行支援モジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - 提出ページの言語一覧から最新の Rust の言語IDを検出(`detect_latest_rust_language`)
+//! - 提出に利用する言語IDの更新(`JudgeEnvironment`)
+//! - `templates/rust-toolchain.toml` の再生成(`write_rust_toolchain`)
+//! - 依存関係テンプレートの再生成と、利用できなくなったクレートの報告(`migrate_dependencies`)
+//!
+//! ## 処理対象のファイル
+//! ```text
+//! .
+//! └── templates
+//!     ├── Cargo.toml           # 依存関係テンプレート (新しいジャッジの依存関係で置き換える)
+//!     ├── Cargo.toml.bak       # 置き換え前の依存関係テンプレート
+//!     └── rust-toolchain.toml  # ジャッジの rustc バージョンに合わせたツールチェイン設定
+//! ```
+//!
+//! 検出した言語IDは設定ディレクトリの `environment.json` に保存され、`submit` で利用される。
+use reqwest::Client;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, error::Error, fs, path::Path};
+use toml::Value;
+
+use super::config::{
+    get_base_url, get_environment_file, get_session_file, get_template_dir, load_user_config,
+};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+
+/// 言語IDが未設定の場合に利用する Rust の言語ID
+pub const DEFAULT_LANGUAGE_ID: &str = "5054";
+
+/// ジャッジの rustc のバージョンが未検出の場合に利用するバージョン
+pub const DEFAULT_JUDGE_RUSTC_VERSION: &str = "1.70.0";
+
+/// 言語アップデートの移行処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `templates` ディレクトリが存在する作業ディレクトリ
+/// - `contest_name`: 言語一覧を取得する提出ページのコンテスト名
+/// - `new_dependencies`: 新しいジャッジ環境の依存関係を記載した `Cargo.toml` のパス
+pub async fn execute(
+    work_dir: &Path,
+    contest_name: &str,
+    new_dependencies: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+
+    let latest =
+        detect_latest_rust_language(&get_base_url(), &client, &session, contest_name).await?;
+    let environment_path = get_environment_file();
+    let current = JudgeEnvironment::load(&environment_path)?;
+    let current_id = current
+        .as_ref()
+        .map(|env| env.language_id.as_str())
+        .unwrap_or(DEFAULT_LANGUAGE_ID);
+
+    if current_id == latest.language_id {
+        println!(
+            "Language is up to date: {} ({})",
+            latest.language_name, latest.language_id
+        );
+    } else {
+        println!(
+            "Language update detected: {} -> {} ({})",
+            current_id, latest.language_id, latest.language_name
+        );
+        latest.save(&environment_path)?;
+        if let Some(version) = &latest.rustc_version {
+            write_rust_toolchain(work_dir, version)?;
+            println!(
+                "Updated templates/rust-toolchain.toml (channel = {})",
+                version
+            );
+        }
+    }
+
+    if let Some(new_dependencies) = new_dependencies {
+        let removed = migrate_dependencies(work_dir, new_dependencies)?;
+        if removed.is_empty() {
+            println!("All crates in the dependency template are still available.");
+        } else {
+            println!("Crates no longer available on the judge:");
+            for name in removed {
+                println!("  - {}", name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// ジャッジ環境の情報
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JudgeEnvironment {
+    pub language_id: String,           // 提出に利用する言語ID
+    pub language_name: String,         // 言語名 (例: "Rust (rustc 1.70.0)")
+    pub rustc_version: Option<String>, // rustc のバージョン (例: "1.70.0")
+}
+
+impl JudgeEnvironment {
+    /// ジャッジ環境の情報をロードする
+    pub fn load(path: &Path) -> Result<Option<Self>, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data).ok())
+    }
+
+    /// ジャッジ環境の情報を保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 提出に利用する言語IDを取得する
+///
+/// ユーザー設定の `language_id` を優先し、未設定かつ `environment.json` が存在しない場合は
+/// `DEFAULT_LANGUAGE_ID` を返す。
+pub fn get_language_id() -> Result<String, Box<dyn Error>> {
+    if let Some(language_id) = &load_user_config().language_id {
+        return Ok(language_id.clone());
+    }
+    Ok(JudgeEnvironment::load(&get_environment_file())?
+        .map(|env| env.language_id)
+        .unwrap_or_else(|| DEFAULT_LANGUAGE_ID.to_string()))
+}
+
+/// 提出ページから最新の Rust の言語情報を検出する
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 認証に利用するセッション情報
+/// - `contest_name`: 提出ページを取得するコンテスト名
+///
+/// # 戻り値
+/// - `Ok(JudgeEnvironment)`: rustc のバージョンが最も新しい Rust の言語情報
+/// - `Err(Box<dyn Error>)`: 提出ページの取得に失敗した場合、または Rust の言語が見つからない場合
+pub async fn detect_latest_rust_language(
+    base_url: &str,
+    client: &Client,
+    session: &Session,
+    contest_name: &str,
+) -> Result<JudgeEnvironment, Box<dyn Error>> {
+    let url = format!("{}/contests/{}/submit", base_url, contest_name);
+    let html = fetch_with_session(client, &url, session).await?;
+    parse_languages(&Html::parse_document(&html))
+        .into_iter()
+        .filter(|(_, name)| name.starts_with("Rust"))
+        .map(|(id, name)| JudgeEnvironment {
+            language_id: id,
+            rustc_version: parse_rustc_version(&name),
+            language_name: name,
+        })
+        .max_by_key(|env| version_key(env.rustc_version.as_deref()))
+        .ok_or_else(|| "提出ページに Rust の言語が見つかりません".into())
+}
+
+/// 提出ページの言語選択肢から `(言語ID, 言語名)` の一覧を取得する
+///
+/// 問題ごとに同じ選択肢が繰り返されるため、言語IDの重複は除去する。
+pub fn parse_languages(document: &Html) -> Vec<(String, String)> {
+    let option_selector = Selector::parse("select[name=\"data.LanguageId\"] option").unwrap();
+    let mut seen = BTreeSet::new();
+    let mut languages = Vec::new();
+    for option in document.select(&option_selector) {
+        let Some(id) = option.value().attr("value") else {
+            continue;
+        };
+        if id.is_empty() || !seen.insert(id.to_string()) {
+            continue;
+        }
+        let name = option.text().collect::<String>().trim().to_string();
+        languages.push((id.to_string(), name));
+    }
+    languages
+}
+
+/// 言語名から rustc のバージョンを抽出する (例: `"Rust (rustc 1.70.0)"` -> `"1.70.0"`)
+fn parse_rustc_version(language_name: &str) -> Option<String> {
+    language_name
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find(|token| token.split('.').count() >= 2 && !token.starts_with('.'))
+        .map(|token| token.trim_end_matches('.').to_string())
+}
+
+/// バージョン文字列を比較可能な数値列に変換する
+fn version_key(version: Option<&str>) -> Vec<u32> {
+    version
+        .map(|v| v.split('.').filter_map(|n| n.parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// `templates/rust-toolchain.toml` を指定した rustc のバージョンで生成する
+fn write_rust_toolchain(work_dir: &Path, version: &str) -> Result<(), Box<dyn Error>> {
+    let templates_dir = get_template_dir(work_dir);
+    fs::create_dir_all(&templates_dir)?;
+    fs::write(
+        templates_dir.join("rust-toolchain.toml"),
+        rust_toolchain_toml(version),
+    )?;
+    Ok(())
+}
+
+/// 指定した rustc のバージョンに固定する `rust-toolchain.toml` の内容を返す
+pub fn rust_toolchain_toml(version: &str) -> String {
+    format!("[toolchain]\nchannel = \"{}\"\n", version)
+}
+
+/// ジャッジの rustc のバージョンを取得する
+///
+/// `environment.json` に検出済みのバージョンがない場合は `DEFAULT_JUDGE_RUSTC_VERSION` を返す。
+pub fn get_judge_rustc_version() -> String {
+    JudgeEnvironment::load(&get_environment_file())
+        .ok()
+        .flatten()
+        .and_then(|env| env.rustc_version)
+        .unwrap_or_else(|| DEFAULT_JUDGE_RUSTC_VERSION.to_string())
+}
+
+/// 依存関係テンプレートを新しいジャッジ環境のものに置き換える
+///
+/// 置き換え前のテンプレートは `templates/Cargo.toml.bak` として保存する。
+///
+/// # 戻り値
+/// - `Ok(Vec<String>)`: 置き換え前のテンプレートに存在し、新しい環境で利用できなくなったクレート名
+/// - `Err(Box<dyn Error>)`: ファイルの読み書きや TOML の解析に失敗した場合
+fn migrate_dependencies(
+    work_dir: &Path,
+    new_dependencies: &Path,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let template_path = get_template_dir(work_dir).join("Cargo.toml");
+    let new_content = fs::read_to_string(new_dependencies)?;
+    let new_crates = dependency_names(&new_content)?;
+
+    let mut removed = Vec::new();
+    if template_path.exists() {
+        let old_content = fs::read_to_string(&template_path)?;
+        removed = dependency_names(&old_content)?
+            .difference(&new_crates)
+            .cloned()
+            .collect();
+        fs::write(template_path.with_extension("toml.bak"), &old_content)?;
+    }
+
+    let parsed: Value = toml::from_str(&new_content)?;
+    let dependencies = parsed
+        .get("dependencies")
+        .ok_or("新しい依存関係に [dependencies] セクションがありません")?;
+    let mut template = toml::map::Map::new();
+    template.insert("dependencies".to_string(), dependencies.clone());
+    if let Some(parent) = template_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&template_path, toml::to_string(&Value::Table(template))?)?;
+    Ok(removed)
+}
+
+/// `Cargo.toml` の `[dependencies]` に含まれるクレート名を取得する
+fn dependency_names(cargo_toml_content: &str) -> Result<BTreeSet<String>, Box<dyn Error>> {
+    let parsed: Value = toml::from_str(cargo_toml_content)?;
+    Ok(parsed
+        .get("dependencies")
+        .and_then(|deps| deps.as_table())
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::Server;
+    use tempfile;
+
+    const SUBMIT_PAGE: &str = r#"
+<html>
+    <select name="data.LanguageId">
+        <option value=""></option>
+        <option value="4050">C++ 20 (gcc 12.2)</option>
+        <option value="5054">Rust (rustc 1.70.0)</option>
+        <option value="6088">Rust (rustc 1.89.0)</option>
+    </select>
+    <select name="data.LanguageId">
+        <option value="5054">Rust (rustc 1.70.0)</option>
+        <option value="6088">Rust (rustc 1.89.0)</option>
+    </select>
+</html>
+"#;
+
+    #[test]
+    fn test_parse_languages() {
+        let languages = parse_languages(&Html::parse_document(SUBMIT_PAGE));
+        assert_eq!(
+            languages,
+            vec![
+                ("4050".to_string(), "C++ 20 (gcc 12.2)".to_string()),
+                ("5054".to_string(), "Rust (rustc 1.70.0)".to_string()),
+                ("6088".to_string(), "Rust (rustc 1.89.0)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rustc_version() {
+        assert_eq!(
+            parse_rustc_version("Rust (rustc 1.70.0)"),
+            Some("1.70.0".to_string())
+        );
+        assert_eq!(parse_rustc_version("Rust"), None);
+    }
+
+    #[tokio::test]
+    async fn test_detect_latest_rust_language() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/contests/practice/submit")
+            .match_header("Cookie", "REVEL_SESSION=mock_session_cookie;")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body(SUBMIT_PAGE)
+            .create_async()
+            .await;
+        let session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+
+        let result =
+            detect_latest_rust_language(&server.url(), &Client::new(), &session, "practice").await;
+        _mock.assert();
+        let environment = result.unwrap();
+        assert_eq!(environment.language_id, "6088");
+        assert_eq!(environment.rustc_version, Some("1.89.0".to_string()));
+    }
+
+    #[test]
+    fn test_save_and_load_judge_environment() {
+        let work_dir = tempfile::tempdir().expect("");
+        let path = work_dir.path().join("atc/environment.json");
+        assert!(JudgeEnvironment::load(&path).unwrap().is_none());
+
+        let environment = JudgeEnvironment {
+            language_id: "6088".to_string(),
+            language_name: "Rust (rustc 1.89.0)".to_string(),
+            rustc_version: Some("1.89.0".to_string()),
+        };
+        environment.save(&path).unwrap();
+        assert_eq!(JudgeEnvironment::load(&path).unwrap(), Some(environment));
+    }
+
+    #[test]
+    fn test_migrate_dependencies() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::create_dir_all(work_dir.path().join("templates")).unwrap();
+        fs::write(
+            work_dir.path().join("templates/Cargo.toml"),
+            "[dependencies]\nproconio = \"0.4.5\"\nvarisat = \"0.2.2\"\n",
+        )
+        .unwrap();
+        let new_dependencies = work_dir.path().join("new.toml");
+        fs::write(
+            &new_dependencies,
+            "[package]\nname = \"main\"\n\n[dependencies]\nproconio = \"0.5.0\"\n",
+        )
+        .unwrap();
+
+        let removed = migrate_dependencies(work_dir.path(), &new_dependencies).unwrap();
+        assert_eq!(removed, vec!["varisat".to_string()]);
+
+        let template = fs::read_to_string(work_dir.path().join("templates/Cargo.toml")).unwrap();
+        assert!(template.contains("proconio = \"0.5.0\""));
+        assert!(!template.contains("[package]"));
+        assert!(work_dir.path().join("templates/Cargo.toml.bak").exists());
+    }
+
+    #[test]
+    fn test_write_rust_toolchain() {
+        let work_dir = tempfile::tempdir().expect("");
+        write_rust_toolchain(work_dir.path(), "1.89.0").unwrap();
+        let content =
+            fs::read_to_string(work_dir.path().join("templates/rust-toolchain.toml")).unwrap();
+        assert_eq!(content, "[toolchain]\nchannel = \"1.89.0\"\n");
+    }
+}
@@ -1,5 +1,61 @@
+pub mod add;
+pub mod archive;
+pub mod bench;
+pub mod build_cache;
+pub mod cache;
+pub mod cargo_config;
+pub mod case;
+pub mod clar;
+pub mod clean;
+pub mod compare;
 pub mod config;
+pub mod contests;
+pub mod diff;
+pub mod doctor;
 pub mod download;
+pub mod editorial;
+pub mod expand;
+pub mod export;
+pub mod format;
+pub mod gen;
+pub mod gen_spec;
+pub mod git;
+pub mod history;
+pub mod http;
+pub mod import;
+pub mod init;
+pub mod interactive;
+pub mod lang;
 pub mod login;
+pub mod logout;
+pub mod metadata;
+pub mod migrate;
+pub mod new;
+pub mod open;
+pub mod output;
+pub mod performance;
+pub mod plugin;
+pub mod problems_api;
+pub mod process;
+pub mod rating;
+pub mod remote;
+pub mod result;
+pub mod run;
+pub mod score;
+pub mod shrink;
+pub mod standings;
+pub mod statement;
+pub mod stats;
+pub mod status;
+pub mod stress;
+pub mod submissions;
 pub mod submit;
+pub mod template;
 pub mod test;
+pub mod testcases;
+pub mod timer;
+pub mod update;
+pub mod validator;
+pub mod watch;
+pub mod whoami;
+pub mod workspace;
@@ -0,0 +1,123 @@
+//! コンテストに紐づかない練習用プロジェクトを作成するモジュール
+//!
+//! `download` と同じディレクトリ構造で、問題が 1 つだけのプロジェクトを作成する。
+//! サンプル入出力は取得しないため、`tests/` には空のディレクトリが作成される。
+//!
+//! ```text
+//! .
+//! └── project_name
+//!     ├── Cargo.toml      # 問題 1 つ分の [[bin]] と既定の実行時間制限
+//!     └── a
+//!         ├── main.rs     # templates/main.rs のコピー
+//!         └── tests       # 空のディレクトリ
+//! ```
+use std::{error::Error, path::Path};
+
+use super::download::{
+    copy_rustfmt_toml, create_contest_directory, create_main_rs, generate_cargo_toml, ContestInfo,
+    ProblemInfo,
+};
+use super::format::format_file;
+use super::template::resolve_template_dir;
+use super::workspace::{add_workspace_member, is_workspace_root};
+
+/// 練習用プロジェクトの既定の実行時間制限 (ms)
+pub const DEFAULT_TIMEOUT: u128 = 2000;
+
+/// 練習用プロジェクト作成処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ
+/// - `project_name`: 作成するプロジェクト名
+/// - `problem_name`: 問題名 (バイナリ名・ディレクトリ名として利用)
+/// - `timeout`: 実行時間制限 (ms)
+///
+/// # エラーの可能性
+/// - 同名のディレクトリが既に存在する場合
+/// - `templates/main.rs` が存在しない場合
+pub fn execute(
+    work_dir: &Path,
+    project_name: &str,
+    problem_name: &str,
+    timeout: u128,
+) -> Result<(), Box<dyn Error>> {
+    create_project(work_dir, project_name, problem_name, timeout)?;
+    let template_dir = resolve_template_dir(work_dir, None)?;
+    if copy_rustfmt_toml(work_dir, &template_dir, project_name)? {
+        let main_rs_path = work_dir
+            .join(project_name)
+            .join(problem_name)
+            .join("main.rs");
+        if let Err(e) = format_file(&main_rs_path) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+    println!("Project setup completed successfully: {}", project_name);
+    Ok(())
+}
+
+/// 問題が 1 つだけのプロジェクトのディレクトリ、`Cargo.toml`、`main.rs` を作成する
+fn create_project(
+    work_dir: &Path,
+    project_name: &str,
+    problem_name: &str,
+    timeout: u128,
+) -> Result<(), Box<dyn Error>> {
+    if work_dir.join(project_name).exists() {
+        return Err(format!("ディレクトリが既に存在します: {}", project_name).into());
+    }
+    let project_info = ContestInfo {
+        contest_name: project_name.to_string(),
+        problems: vec![ProblemInfo {
+            problem_name: problem_name.to_string(),
+            timeout,
+            samples: Vec::new(),
+            memory_limit: None,
+            score: None,
+            statement: None,
+            title: None,
+            task_screen_name: None,
+        }],
+    };
+    let template_dir = resolve_template_dir(work_dir, None)?;
+    create_contest_directory(work_dir, &project_info)?;
+    generate_cargo_toml(
+        work_dir,
+        &template_dir,
+        project_name,
+        &project_info.problems,
+    )?;
+    create_main_rs(work_dir, &template_dir, project_name, problem_name)?;
+    if is_workspace_root(work_dir) {
+        add_workspace_member(work_dir, project_name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use tempfile;
+
+    #[test]
+    fn test_create_project() {
+        let work_dir = tempfile::tempdir().expect("");
+        let work_dir_path = work_dir.path().to_path_buf();
+        fs::create_dir_all(work_dir.path().join("templates")).unwrap();
+        fs::write(work_dir.path().join("templates/main.rs"), "fn main() {}").unwrap();
+
+        create_project(&work_dir_path, "practice", "a", DEFAULT_TIMEOUT).unwrap();
+
+        let project_dir = work_dir.path().join("practice");
+        assert!(project_dir.join("a/main.rs").exists());
+        assert!(project_dir.join("a/tests").is_dir());
+        let cargo_toml = fs::read_to_string(project_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"practice\""));
+        assert_eq!(cargo_toml.matches("[[bin]]").count(), 1);
+        assert!(cargo_toml.contains("\"a\" = 2000"));
+
+        // 既存のディレクトリは上書きしない
+        assert!(create_project(&work_dir_path, "practice", "a", DEFAULT_TIMEOUT).is_err());
+    }
+}
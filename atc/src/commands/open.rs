@@ -0,0 +1,90 @@
+//! 問題ページや提出一覧ページをブラウザで開くモジュール
+//!
+//! `Cargo.toml` の `package.name` からコンテスト名を、`contest.json` から問題 ID を取得し、
+//! 問題の URL を組み立てて既定のブラウザで開く。環境変数 `BROWSER` が設定されている場合はそのコマンドを利用する。
+use std::{env, error::Error, path::Path, process::Command};
+
+use super::config::get_base_url;
+use super::metadata::task_screen_name;
+use super::submit::get_contest_info;
+
+/// 問題ページを開く処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+/// - `submissions`: `true` の場合、問題ページの代わりに自分の提出一覧ページを開く
+pub fn execute(
+    work_dir: &Path,
+    problem_name: &str,
+    submissions: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (contest_name, _) = get_contest_info(work_dir, problem_name)?;
+    let base_url = get_base_url();
+    let task_screen_name = task_screen_name(work_dir, &contest_name, problem_name);
+    let url = if submissions {
+        submissions_url(&base_url, &contest_name, &task_screen_name)
+    } else {
+        task_url(&base_url, &contest_name, &task_screen_name)
+    };
+    println!("Opening {}", url);
+    open_in_browser(&url)
+}
+
+/// 問題ページの URL を返す
+pub fn task_url(base_url: &str, contest_name: &str, task_screen_name: &str) -> String {
+    format!(
+        "{}/contests/{}/tasks/{}",
+        base_url, contest_name, task_screen_name
+    )
+}
+
+/// 問題に対する自分の提出一覧ページの URL を返す
+pub fn submissions_url(base_url: &str, contest_name: &str, task_screen_name: &str) -> String {
+    format!(
+        "{}/contests/{}/submissions/me?f.Task={}",
+        base_url, contest_name, task_screen_name
+    )
+}
+
+/// URL を既定のブラウザで開く
+///
+/// # エラーの可能性
+/// - ブラウザを起動するコマンドが存在しない、または異常終了した場合
+fn open_in_browser(url: &str) -> Result<(), Box<dyn Error>> {
+    let mut command = match env::var("BROWSER") {
+        Ok(browser) if !browser.is_empty() => Command::new(browser),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(target_os = "windows") => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+    let status = command
+        .arg(url)
+        .status()
+        .map_err(|e| format!("ブラウザを起動できませんでした: {}", e))?;
+    if !status.success() {
+        return Err(format!("ブラウザの起動に失敗しました: {}", url).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_task_url() {
+        assert_eq!(
+            task_url("https://atcoder.jp", "abc388", "abc388_a"),
+            "https://atcoder.jp/contests/abc388/tasks/abc388_a"
+        );
+        assert_eq!(
+            submissions_url("https://atcoder.jp", "abc388", "abc388_a"),
+            "https://atcoder.jp/contests/abc388/submissions/me?f.Task=abc388_a"
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! 出力形式(テキスト・JSON)を切り替えるモジュール
+//!
+//! トップレベルの `--json` を指定した場合、以下のコマンドは結果を JSON で標準出力に出力する。
+//! 進捗などのメッセージは出力しないため、エディタやスクリプトから標準出力をそのまま解析できる。
+//! - `test`: テストケースごとの結果
+//! - `download`: 作成した問題の一覧
+//! - `submit`: 提出先の URL
+//! - `status`・`submissions`・`result`: 提出の判定結果
+//! - `whoami`: ログイン状態
+//!
+//! 上記以外のコマンドは `--json` を指定してもテキストで出力する。エラーは常に標準エラー出力に出力する。
+use serde::Serialize;
+use std::{
+    error::Error,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// JSON 出力の有効・無効を設定する (`main` で 1 度だけ呼び出す)
+pub fn set_json(enabled: bool) {
+    JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+/// JSON 出力が有効かどうかを返す
+pub fn is_json() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// 値を 1 行の JSON として標準出力に出力する
+///
+/// `watch` でポーリングするコマンドは結果ごとに 1 行ずつ出力する (JSON Lines)。
+pub fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
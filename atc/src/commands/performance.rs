@@ -0,0 +1,379 @@
+//! コンテスト終了後のパフォーマンスとレート変動を推定するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - 順位表(`standings/json`)の取得と自分の順位の特定(`estimate_result`)
+//! - 順位と参加者のレートからのパフォーマンスの推定(`estimate_performance`)
+//! - コンテスト成績履歴(`history/json`)の取得(`fetch_history`)
+//! - 過去のパフォーマンス履歴からのレートの計算(`calculate_rating`)
+//! - 推定結果の取り組み記録(`stats`)への保存(`record_estimate`)
+//!
+//! 推定結果は `perf` のほか、`archive` でコンテストをアーカイブした際にも表示し(`estimate_with_saved_session`)、
+//! 保存した推定結果は `stats` で一覧表示する。
+//!
+//! ## 推定方法
+//! - 参加者の APerf は旧レートで近似し、初参加者は `DEFAULT_APERF` とする。
+//! - パフォーマンス `X` は `Σ 1 / (1 + 6^((X - APerf_i) / 400)) = 順位 - 0.5` を二分探索で解いて求める。
+//! - レートは過去のパフォーマンスを新しい順に `0.9^i` で重み付けして平均し、参加回数による補正を行う。
+//!
+//! APerf の近似を行うため、算出される値は公式の値と一致しない場合がある。
+use reqwest::Client;
+use serde::Deserialize;
+use std::error::Error;
+
+use super::config::{get_base_url, get_session_file, get_stats_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+use super::stats::{record_performance, PerformanceRecord};
+
+/// 初参加者の APerf
+const DEFAULT_APERF: f64 = 1200.0;
+
+/// パフォーマンス推定処理のエントリーポイント
+///
+/// # 引数
+/// - `contest_name`: コンテスト名 (`abc388` など)
+pub async fn execute(contest_name: &str) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+
+    let estimate = estimate_result(&get_base_url(), &client, &session, contest_name).await?;
+    record_estimate(contest_name, &estimate);
+    print_estimate(contest_name, &estimate);
+    Ok(())
+}
+
+/// 保存済みのセッションでパフォーマンスとレート変動を推定し、表示と記録を行う
+///
+/// `archive` から呼び出される。ログインを求めないよう、有効なセッションが保存されていない場合は何もしない。
+///
+/// # 引数
+/// - `contest_name`: コンテスト名
+///
+/// # エラーの可能性
+/// - 順位表や成績履歴の取得に失敗した場合、または順位表に自分が存在しない場合
+pub async fn estimate_with_saved_session(contest_name: &str) -> Result<(), Box<dyn Error>> {
+    let session = match Session::load(&get_session_file())? {
+        Some(session) if !session.is_expired() => session,
+        _ => return Ok(()),
+    };
+    let estimate = estimate_result(&get_base_url(), &client()?, &session, contest_name).await?;
+    record_estimate(contest_name, &estimate);
+    print_estimate(contest_name, &estimate);
+    Ok(())
+}
+
+/// 推定結果を表示する
+fn print_estimate(contest_name: &str, estimate: &PerformanceEstimate) {
+    println!("=== Performance Estimate: {} ===", contest_name);
+    println!("Rank        : {}", estimate.rank);
+    println!(
+        "Performance : {}",
+        to_display_rating(estimate.performance).round()
+    );
+    println!(
+        "Rating      : {}",
+        format_rating_change(estimate.old_rating, estimate.new_rating)
+    );
+}
+
+/// 推定結果を取り組み記録(`stats`)に保存する
+///
+/// 保存に失敗した場合は警告を表示し、呼び出し元の処理は継続する。
+fn record_estimate(contest_name: &str, estimate: &PerformanceEstimate) {
+    let record = PerformanceRecord {
+        contest: contest_name.to_string(),
+        rank: estimate.rank,
+        performance: to_display_rating(estimate.performance).round() as i64,
+        old_rating: estimate.old_rating,
+        new_rating: estimate.new_rating,
+    };
+    if let Err(e) = record_performance(&get_stats_file(), record) {
+        eprintln!("Warning: 推定結果の保存に失敗しました: {}", e);
+    }
+}
+
+/// レート変動を `1200 -> 1234 (+34)` の形式で返す
+///
+/// コンテスト前のレートが存在しない(初参加の)場合は `1234 (first rated contest)` とする。
+pub fn format_rating_change(old_rating: Option<i64>, new_rating: i64) -> String {
+    match old_rating {
+        Some(old_rating) => format!(
+            "{} -> {} ({:+})",
+            old_rating,
+            new_rating,
+            new_rating - old_rating
+        ),
+        None => format!("{} (first rated contest)", new_rating),
+    }
+}
+
+/// パフォーマンスとレート変動の推定結果
+#[derive(Debug)]
+pub struct PerformanceEstimate {
+    pub rank: u32,               // 順位
+    pub performance: f64,        // 推定パフォーマンス(補正前)
+    pub old_rating: Option<i64>, // コンテスト前のレート
+    pub new_rating: i64,         // 推定レート
+}
+
+/// 順位表の JSON
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Standings {
+    standings_data: Vec<StandingsEntry>,
+}
+
+/// 順位表の参加者ごとのデータ
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct StandingsEntry {
+    rank: u32,
+    user_screen_name: String,
+    old_rating: i64,
+    is_rated: bool,
+    competitions: u32,
+}
+
+/// コンテスト成績履歴の JSON の各要素
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct HistoryEntry {
+    pub is_rated: bool,
+    pub inner_performance: i64,
+    #[serde(default)]
+    pub old_rating: i64,
+    pub new_rating: i64,
+    pub contest_screen_name: String,
+}
+
+/// ユーザーのコンテスト成績履歴(`history/json`)を取得する
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 認証に利用するセッション情報
+/// - `username`: 履歴を取得するユーザー名
+pub async fn fetch_history(
+    base_url: &str,
+    client: &Client,
+    session: &Session,
+    username: &str,
+) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+    let history_url = format!("{}/users/{}/history/json", base_url, username);
+    Ok(serde_json::from_str(
+        &fetch_with_session(client, &history_url, session).await?,
+    )?)
+}
+
+/// 順位表と成績履歴を取得し、パフォーマンスとレート変動を推定する
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 認証に利用するセッション情報
+/// - `contest_name`: コンテスト名
+///
+/// # 戻り値
+/// - `Ok(PerformanceEstimate)`: 推定結果
+/// - `Err(Box<dyn Error>)`: 順位表や成績履歴の取得に失敗した場合、または順位表に自分が存在しない場合
+pub async fn estimate_result(
+    base_url: &str,
+    client: &Client,
+    session: &Session,
+    contest_name: &str,
+) -> Result<PerformanceEstimate, Box<dyn Error>> {
+    let standings_url = format!("{}/contests/{}/standings/json", base_url, contest_name);
+    let standings: Standings =
+        serde_json::from_str(&fetch_with_session(client, &standings_url, session).await?)?;
+
+    let me = standings
+        .standings_data
+        .iter()
+        .find(|entry| entry.user_screen_name == session.username)
+        .ok_or("順位表に参加記録が見つかりません")?;
+    let rated: Vec<&StandingsEntry> = standings
+        .standings_data
+        .iter()
+        .filter(|entry| entry.is_rated)
+        .collect();
+    let better = rated.iter().filter(|entry| entry.rank < me.rank).count();
+    let ties = rated
+        .iter()
+        .filter(|entry| entry.rank == me.rank)
+        .count()
+        .max(1);
+    let rated_rank = better as f64 + (ties as f64 + 1.0) / 2.0;
+    let aperfs: Vec<f64> = rated
+        .iter()
+        .map(|entry| {
+            if entry.competitions == 0 {
+                DEFAULT_APERF
+            } else {
+                entry.old_rating as f64
+            }
+        })
+        .collect();
+    let performance = estimate_performance(rated_rank, &aperfs);
+
+    let history = fetch_history(base_url, client, session, &session.username).await?;
+    let contest_prefix = format!("{}.", contest_name);
+    let past: Vec<&HistoryEntry> = history
+        .iter()
+        .filter(|entry| entry.is_rated && !entry.contest_screen_name.starts_with(&contest_prefix))
+        .collect();
+
+    let mut performances = vec![performance];
+    performances.extend(
+        past.iter()
+            .rev()
+            .map(|entry| entry.inner_performance as f64),
+    );
+    let new_rating = to_display_rating(calculate_rating(&performances)).round() as i64;
+
+    Ok(PerformanceEstimate {
+        rank: me.rank,
+        performance,
+        old_rating: past.last().map(|entry| entry.new_rating),
+        new_rating,
+    })
+}
+
+/// 順位と参加者の APerf からパフォーマンスを推定する
+///
+/// # 引数
+/// - `rank`: レート対象者内での順位(同順位の場合は平均順位)
+/// - `aperfs`: レート対象者の APerf の一覧
+pub fn estimate_performance(rank: f64, aperfs: &[f64]) -> f64 {
+    let expected_rank = |x: f64| -> f64 {
+        aperfs
+            .iter()
+            .map(|aperf| 1.0 / (1.0 + 6.0_f64.powf((x - aperf) / 400.0)))
+            .sum()
+    };
+    let target = rank - 0.5;
+    let (mut low, mut high) = (-10000.0_f64, 10000.0_f64);
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if expected_rank(mid) > target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// パフォーマンス履歴からレート(補正前)を計算する
+///
+/// # 引数
+/// - `performances`: パフォーマンスの履歴(新しい順)
+pub fn calculate_rating(performances: &[f64]) -> f64 {
+    if performances.is_empty() {
+        return 0.0;
+    }
+    let mut numerator = 0.0;
+    let mut denominator = 0.0;
+    for (i, performance) in performances.iter().enumerate() {
+        let weight = 0.9_f64.powi(i as i32 + 1);
+        numerator += 2.0_f64.powf(performance / 800.0) * weight;
+        denominator += weight;
+    }
+    let n = performances.len() as i32;
+    let correction = ((1.0 - 0.81_f64.powi(n)).sqrt() / (1.0 - 0.9_f64.powi(n)) - 1.0)
+        / (19.0_f64.sqrt() - 1.0)
+        * 1200.0;
+    (numerator / denominator).log2() * 800.0 - correction
+}
+
+/// 400 以下のレート・パフォーマンスを表示用の値に変換する
+pub fn to_display_rating(rating: f64) -> f64 {
+    if rating <= 400.0 {
+        400.0 / ((400.0 - rating) / 400.0).exp()
+    } else {
+        rating
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::Server;
+
+    #[test]
+    fn test_estimate_performance() {
+        let aperfs = vec![1000.0, 1000.0, 1000.0];
+        let performance = estimate_performance(2.0, &aperfs);
+        assert!((performance - 1000.0).abs() < 1e-6);
+
+        let first = estimate_performance(1.0, &aperfs);
+        let last = estimate_performance(3.0, &aperfs);
+        assert!(first > performance);
+        assert!(last < performance);
+    }
+
+    #[test]
+    fn test_calculate_rating() {
+        // 初参加時は 1200 の補正がかかる
+        let rating = calculate_rating(&[2000.0]);
+        assert!((rating - 800.0).abs() < 1e-6);
+        assert_eq!(calculate_rating(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_format_rating_change() {
+        assert_eq!(format_rating_change(Some(1200), 1234), "1200 -> 1234 (+34)");
+        assert_eq!(format_rating_change(Some(1200), 1180), "1200 -> 1180 (-20)");
+        assert_eq!(format_rating_change(None, 150), "150 (first rated contest)");
+    }
+
+    #[test]
+    fn test_to_display_rating() {
+        assert_eq!(to_display_rating(1000.0), 1000.0);
+        assert_eq!(to_display_rating(400.0), 400.0);
+        assert!(to_display_rating(0.0) < 400.0);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_result() {
+        let mut server = Server::new_async().await;
+        let _standings_mock = server
+            .mock("GET", "/contests/abc388/standings/json")
+            .with_status(200)
+            .with_body(
+                r#"{"StandingsData": [
+                    {"Rank": 1, "UserScreenName": "alice", "OldRating": 1000, "IsRated": true, "Competitions": 5},
+                    {"Rank": 2, "UserScreenName": "mock_user", "OldRating": 1000, "IsRated": true, "Competitions": 5},
+                    {"Rank": 3, "UserScreenName": "bob", "OldRating": 1000, "IsRated": true, "Competitions": 5}
+                ]}"#,
+            )
+            .create_async()
+            .await;
+        let _history_mock = server
+            .mock("GET", "/users/mock_user/history/json")
+            .with_status(200)
+            .with_body(
+                r#"[
+                    {"IsRated": true, "InnerPerformance": 1000, "NewRating": 250, "ContestScreenName": "abc387.contest.atcoder.jp"}
+                ]"#,
+            )
+            .create_async()
+            .await;
+        let session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+
+        let estimate = estimate_result(&server.url(), &Client::new(), &session, "abc388")
+            .await
+            .unwrap();
+        assert_eq!(estimate.rank, 2);
+        assert!((estimate.performance - 1000.0).abs() < 1e-6);
+        assert_eq!(estimate.old_rating, Some(250));
+        assert_eq!(estimate.new_rating, 278);
+    }
+}
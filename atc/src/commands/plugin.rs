@@ -0,0 +1,257 @@
+//! 外部プラグインによるサブコマンドの拡張を行うモジュール
+//!
+//! 組み込みでないサブコマンド `cargo atc <name>` が指定された場合、`PATH` 上の
+//! `cargo-atc-<name>` 実行ファイルを探索し、残りの引数を渡して実行する。
+//!
+//! プラグインには以下の環境変数でコンテキストを渡す。
+//! - `ATC_WORK_DIR`: コマンドを実行した作業ディレクトリ
+//! - `ATC_CONTEST`: コンテスト名 (`Cargo.toml` の `package.name`。特定できない場合は未設定)
+//! - `ATC_PROBLEM`: 問題名 (問題ディレクトリ内で実行した場合のみ設定)
+//! - `ATC_CONFIG_DIR`: 設定ディレクトリ
+//! - `ATC_SESSION_FILE`: セッションファイルのパス
+//! - `ATC_BASE_URL`: AtCoder のベース URL
+//! - `ATC_JSON`: トップレベルの `--json` を指定した場合のみ `1` を設定
+//! - `ATC_CONTEXT`: 上記をまとめた JSON
+//!
+//! プラグインの標準入出力はそのまま引き継がれるため、プラグインは `ATC_CONTEXT` を解析し、
+//! 結果を JSON で標準出力に書き出すことで他のツールと連携できる。
+//! プラグインが 0 以外の終了コードで終了した場合、`cargo atc` も同じ終了コードで終了する(`PluginExit`)。
+use serde::Serialize;
+use std::{
+    env,
+    error::Error,
+    ffi::OsStr,
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use toml::Value;
+
+use super::config::{get_base_url, get_config_dir, get_session_file};
+use super::output::is_json;
+
+/// プラグインの実行ファイル名の接頭辞
+const PLUGIN_PREFIX: &str = "cargo-atc-";
+
+/// プラグイン実行のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ
+/// - `args`: サブコマンド名とその引数 (例: `["vis", "a"]`)
+pub fn execute(work_dir: &PathBuf, args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (name, plugin_args) = args
+        .split_first()
+        .ok_or("サブコマンドが指定されていません")?;
+    let path_var = env::var_os("PATH").unwrap_or_default();
+    let plugin = find_plugin(name, &path_var).ok_or_else(|| {
+        format!(
+            "不明なサブコマンドです: {} ({}{} が見つかりません)",
+            name, PLUGIN_PREFIX, name
+        )
+    })?;
+
+    let context = PluginContext::new(work_dir);
+    let status = Command::new(&plugin)
+        .args(plugin_args)
+        .current_dir(work_dir)
+        .envs(context.to_env()?)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PluginExit {
+            plugin,
+            code: status.code(),
+        }
+        .into())
+    }
+}
+
+/// プラグインが 0 以外の終了コードで終了したことを表すエラー
+#[derive(Debug)]
+pub struct PluginExit {
+    pub plugin: PathBuf,   // プラグインの実行ファイルのパス
+    pub code: Option<i32>, // 終了コード (シグナルで終了した場合は `None`)
+}
+
+impl PluginExit {
+    /// `cargo atc` の終了コード (シグナルで終了した場合は `1`)
+    pub fn exit_code(&self) -> i32 {
+        self.code.unwrap_or(1)
+    }
+}
+
+impl fmt::Display for PluginExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "プラグイン {} が異常終了しました (exit code: {:?})",
+            self.plugin.display(),
+            self.code
+        )
+    }
+}
+
+impl Error for PluginExit {}
+
+/// プラグインに渡すコンテキスト
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PluginContext {
+    pub work_dir: PathBuf,
+    pub contest: Option<String>,
+    pub problem: Option<String>,
+    pub config_dir: PathBuf,
+    pub session_file: PathBuf,
+    pub base_url: String,
+    pub json: bool, // トップレベルの `--json` を指定したか
+}
+
+impl PluginContext {
+    /// 作業ディレクトリからコンテスト名・問題名を特定してコンテキストを作成する
+    ///
+    /// - 作業ディレクトリに `Cargo.toml` が存在する場合、作業ディレクトリをコンテストディレクトリとみなす
+    /// - 親ディレクトリに `Cargo.toml` が存在する場合、作業ディレクトリを問題ディレクトリとみなす
+    pub fn new(work_dir: &Path) -> Self {
+        let (contest, problem) = if work_dir.join("Cargo.toml").exists() {
+            (read_package_name(work_dir), None)
+        } else {
+            match work_dir.parent() {
+                Some(parent) if parent.join("Cargo.toml").exists() => (
+                    read_package_name(parent),
+                    work_dir
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string()),
+                ),
+                _ => (None, None),
+            }
+        };
+        PluginContext {
+            work_dir: work_dir.to_path_buf(),
+            contest,
+            problem,
+            config_dir: get_config_dir(),
+            session_file: get_session_file(),
+            base_url: get_base_url(),
+            json: is_json(),
+        }
+    }
+
+    /// プラグインに渡す環境変数の一覧を返す
+    pub fn to_env(&self) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+        let mut vars = vec![
+            (
+                "ATC_WORK_DIR".to_string(),
+                self.work_dir.display().to_string(),
+            ),
+            (
+                "ATC_CONFIG_DIR".to_string(),
+                self.config_dir.display().to_string(),
+            ),
+            (
+                "ATC_SESSION_FILE".to_string(),
+                self.session_file.display().to_string(),
+            ),
+            ("ATC_BASE_URL".to_string(), self.base_url.clone()),
+            ("ATC_CONTEXT".to_string(), serde_json::to_string(self)?),
+        ];
+        if let Some(contest) = &self.contest {
+            vars.push(("ATC_CONTEST".to_string(), contest.clone()));
+        }
+        if let Some(problem) = &self.problem {
+            vars.push(("ATC_PROBLEM".to_string(), problem.clone()));
+        }
+        if self.json {
+            vars.push(("ATC_JSON".to_string(), "1".to_string()));
+        }
+        Ok(vars)
+    }
+}
+
+/// `Cargo.toml` の `package.name` を取得する
+fn read_package_name(dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let parsed: Value = toml::from_str(&content).ok()?;
+    parsed
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(|name| name.to_string())
+}
+
+/// `PATH` からプラグインの実行ファイルを探索する
+///
+/// # 引数
+/// - `name`: サブコマンド名
+/// - `path_var`: `PATH` 環境変数の値
+///
+/// # 戻り値
+/// 見つかった場合は実行ファイルのパスを返す。
+pub fn find_plugin(name: &str, path_var: &OsStr) -> Option<PathBuf> {
+    let file_name = format!("{}{}{}", PLUGIN_PREFIX, name, env::consts::EXE_SUFFIX);
+    env::split_paths(path_var)
+        .map(|dir| dir.join(&file_name))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_find_plugin() {
+        let dir_1 = tempfile::tempdir().expect("");
+        let dir_2 = tempfile::tempdir().expect("");
+        let plugin_name = format!("cargo-atc-vis{}", env::consts::EXE_SUFFIX);
+        fs::write(dir_2.path().join(&plugin_name), "").unwrap();
+        let path_var =
+            env::join_paths([dir_1.path().to_path_buf(), dir_2.path().to_path_buf()]).unwrap();
+
+        assert_eq!(
+            find_plugin("vis", &path_var),
+            Some(dir_2.path().join(&plugin_name))
+        );
+        assert_eq!(find_plugin("unknown", &path_var), None);
+    }
+
+    #[test]
+    fn test_plugin_context_in_problem_directory() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(work_dir.path().join("a")).unwrap();
+
+        let context = PluginContext::new(&work_dir.path().join("a"));
+        assert_eq!(context.contest, Some("abc388".to_string()));
+        assert_eq!(context.problem, Some("a".to_string()));
+
+        let context = PluginContext::new(work_dir.path());
+        assert_eq!(context.contest, Some("abc388".to_string()));
+        assert_eq!(context.problem, None);
+
+        let vars = context.to_env().unwrap();
+        assert!(vars.contains(&("ATC_CONTEST".to_string(), "abc388".to_string())));
+        assert!(vars.iter().all(|(key, _)| key != "ATC_PROBLEM"));
+        assert!(vars.iter().all(|(key, _)| key != "ATC_JSON"));
+
+        let context = PluginContext {
+            json: true,
+            ..context
+        };
+        let vars = context.to_env().unwrap();
+        assert!(vars.contains(&("ATC_JSON".to_string(), "1".to_string())));
+    }
+
+    #[test]
+    fn test_plugin_exit_code() {
+        let exit = |code| PluginExit {
+            plugin: PathBuf::from("cargo-atc-vis"),
+            code,
+        };
+        assert_eq!(exit(Some(3)).exit_code(), 3);
+        assert_eq!(exit(None).exit_code(), 1);
+    }
+}
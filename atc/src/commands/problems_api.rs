@@ -0,0 +1,119 @@
+//! AtCoder Problems の API から問題一覧を取得するモジュール
+//!
+//! AtCoder の HTML の構造が変わり、問題一覧ページや問題ページを解析できなくなった場合の代替として利用する。
+//! API からは問題名・問題 ID・配点のみを取得できるため、実行時間制限は既定値(`new::DEFAULT_TIMEOUT`)とし、
+//! サンプル入出力は取得しない(問題ページを解析できるようになってから `update` で取得する)。
+//!
+//! API のレスポンスは全コンテストの問題を含むため、指定したコンテストの問題のみを取り出す。
+use serde::Deserialize;
+use std::{error::Error, time::Duration};
+
+use super::download::ProblemInfo;
+use super::http::client_builder;
+use super::new::DEFAULT_TIMEOUT;
+
+/// 全コンテストの問題の情報 (配点を含む) を返す API
+pub const MERGED_PROBLEMS_URL: &str = "https://kenkoooo.com/atcoder/resources/merged-problems.json";
+
+/// API のリクエストのタイムアウト (レスポンスが大きいため、問題ページの取得より長くする)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// API が返す問題の情報
+#[derive(Deserialize, Debug)]
+struct ApiProblem {
+    id: String,            // 問題 ID (`abc388_a` など)
+    contest_id: String,    // コンテスト名
+    problem_index: String, // 問題のラベル (`A`, `Ex` など)
+    name: String,          // 問題のタイトル
+    point: Option<f64>,    // 配点
+}
+
+/// AtCoder Problems の API からコンテストの問題一覧を取得する
+///
+/// # 引数
+/// - `api_url`: API の URL (`MERGED_PROBLEMS_URL`)
+/// - `contest_name`: コンテスト名 (`abc388` など)
+///
+/// # エラーの可能性
+/// - API の取得・解析に失敗した場合
+pub async fn fetch_contest_problems(
+    api_url: &str,
+    contest_name: &str,
+) -> Result<Vec<ProblemInfo>, Box<dyn Error>> {
+    let json = client_builder()?
+        .timeout(REQUEST_TIMEOUT)
+        .build()?
+        .get(api_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    parse_contest_problems(&json, contest_name)
+}
+
+/// API のレスポンスから指定したコンテストの問題を取り出し、問題のラベル順に並べて返す
+fn parse_contest_problems(
+    json: &str,
+    contest_name: &str,
+) -> Result<Vec<ProblemInfo>, Box<dyn Error>> {
+    let problems: Vec<ApiProblem> = serde_json::from_str(json)
+        .map_err(|e| format!("AtCoder Problems の API の解析に失敗しました: {}", e))?;
+    let mut problems: Vec<ApiProblem> = problems
+        .into_iter()
+        .filter(|problem| problem.contest_id == contest_name)
+        .collect();
+    // `A` ～ `H` の後に `Ex` が並ぶように、ラベルの長さを優先して並べる
+    problems.sort_by(|a, b| {
+        (a.problem_index.len(), &a.problem_index).cmp(&(b.problem_index.len(), &b.problem_index))
+    });
+    Ok(problems
+        .into_iter()
+        .map(|problem| ProblemInfo {
+            problem_name: problem.problem_index.to_lowercase(),
+            timeout: DEFAULT_TIMEOUT,
+            memory_limit: None,
+            score: problem.point.map(|point| point as u64),
+            samples: Vec::new(),
+            statement: None,
+            title: Some(format!("{} - {}", problem.problem_index, problem.name)),
+            task_screen_name: Some(problem.id),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MERGED_PROBLEMS_JSON: &str = r#"[
+        {"id": "abc300_h", "contest_id": "abc300", "problem_index": "Ex", "name": "Fibonacci: Revisited", "title": "Ex. Fibonacci: Revisited", "point": 600.0, "solver_count": 100},
+        {"id": "abc300_b", "contest_id": "abc300", "problem_index": "B", "name": "Same Map in the RPG World", "title": "B. Same Map in the RPG World", "point": 200.0},
+        {"id": "abc300_a", "contest_id": "abc300", "problem_index": "A", "name": "N-choice question", "title": "A. N-choice question", "point": null},
+        {"id": "abc301_a", "contest_id": "abc301", "problem_index": "A", "name": "Overall Winner", "title": "A. Overall Winner", "point": 100.0}
+    ]"#;
+
+    #[test]
+    fn test_parse_contest_problems() {
+        let problems = parse_contest_problems(MERGED_PROBLEMS_JSON, "abc300").unwrap();
+        let names: Vec<&str> = problems
+            .iter()
+            .map(|problem| problem.problem_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["a", "b", "ex"]);
+        assert_eq!(problems[0].score, None);
+        assert_eq!(problems[2].score, Some(600));
+        assert_eq!(problems[2].task_screen_name.as_deref(), Some("abc300_h"));
+        assert_eq!(
+            problems[2].title.as_deref(),
+            Some("Ex - Fibonacci: Revisited")
+        );
+        assert_eq!(problems[2].timeout, DEFAULT_TIMEOUT);
+        assert!(problems[2].samples.is_empty());
+
+        assert!(parse_contest_problems(MERGED_PROBLEMS_JSON, "abc999")
+            .unwrap()
+            .is_empty());
+        assert!(parse_contest_problems("{", "abc300").is_err());
+    }
+}
@@ -0,0 +1,373 @@
+//! 実行時間制限を設けて解答のプログラムを実行するモジュール
+//!
+//! 終了の待機は別スレッドで `Child::wait` を呼び出し、`recv_timeout` で制限時間まで待つため、
+//! 定期的に終了を確認する必要がなく、計測した実行時間に確認の間隔の誤差が含まれない。
+//!
+//! Unix ではプログラムを新しいプロセスグループで起動し、制限時間を超えた場合はプロセスグループ全体を強制終了する。
+//! プログラムが起動した子プロセスも終了するため、子プロセスが残って出力の読み込みが終わらなくなることはない。
+//! 終了した場合も、終了を検知してから回収するまでの間にプロセスグループ全体を強制終了する(`wait_in_background`)。
+//! 回収前はプロセス ID が再利用されないため、無関係なプロセスを強制終了することはない。
+//! Windows では `taskkill /T` で子プロセスを含めて強制終了する。
+//!
+//! 入力ファイルは読み込まずに標準入力へ直接接続し、標準出力は `output_limit` バイトまでのみ保持する。
+//! 上限を超えた出力も最後まで読み捨てるため、大量に出力するプログラムがパイプへの書き込みで停止することはない。
+use std::{
+    error::Error,
+    fs::File,
+    io::Read,
+    path::Path,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{mpsc, Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// 保持する標準エラー出力の最大のバイト数
+const STDERR_CAPTURE_LIMIT: usize = 1024 * 1024;
+
+/// 実行結果
+pub enum Outcome {
+    /// 制限時間内に終了した場合
+    Exited {
+        status: ExitStatus,
+        stdout: Vec<u8>,
+        stdout_exceeded: bool, // 標準出力が `output_limit` を超えたため切り捨てたかどうか
+        stderr: String,
+        elapsed: Duration,
+    },
+    /// 制限時間を超えたため強制終了した場合
+    TimedOut { stderr: String, elapsed: Duration },
+}
+
+/// 入力ファイル `input` を標準入力に接続してプログラムを実行し、終了するか `timeout` を超えるまで待機する
+///
+/// # 引数
+/// - `executable`: 実行するプログラムのパス
+/// - `input`: 標準入力に接続する入力ファイルのパス
+/// - `timeout`: 実行時間制限
+/// - `output_limit`: 保持する標準出力の最大のバイト数
+///
+/// # エラーの可能性
+/// - プログラムを起動できない、または入力ファイルを開けない場合
+pub fn run_with_timeout(
+    executable: &Path,
+    input: &Path,
+    timeout: Duration,
+    output_limit: usize,
+) -> Result<Outcome, Box<dyn Error>> {
+    let mut command = Command::new(executable);
+    command
+        .stdin(Stdio::from(File::open(input)?))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let mut child = command.spawn()?;
+    let start_time = Instant::now();
+    let pid = child.id();
+
+    let stdout_reader = read_in_background(child.stdout.take(), output_limit);
+    let stderr_reader = read_in_background(child.stderr.take(), STDERR_CAPTURE_LIMIT);
+    let reaped = Arc::new(Mutex::new(false));
+    let exited = wait_in_background(child, Arc::clone(&reaped));
+
+    let outcome = match exited.recv_timeout(timeout.saturating_sub(start_time.elapsed())) {
+        Ok((status, end_time)) => {
+            let (stdout, stdout_exceeded) = join_reader(stdout_reader);
+            Outcome::Exited {
+                status: status?,
+                stdout,
+                stdout_exceeded,
+                stderr: String::from_utf8_lossy(&join_reader(stderr_reader).0).to_string(),
+                elapsed: end_time.duration_since(start_time),
+            }
+        }
+        Err(_) => {
+            // 回収済みの場合はプロセス ID が再利用されている可能性があるため強制終了しない
+            let reaped = reaped.lock().unwrap_or_else(|e| e.into_inner());
+            if !*reaped {
+                kill_tree(pid);
+            }
+            drop(reaped);
+            let _ = exited.recv();
+            join_reader(stdout_reader);
+            Outcome::TimedOut {
+                stderr: String::from_utf8_lossy(&join_reader(stderr_reader).0).to_string(),
+                elapsed: timeout,
+            }
+        }
+    };
+    Ok(outcome)
+}
+
+/// 異常終了した理由(終了コード、またはシグナル)を説明する文字列を返す
+///
+/// 例: `exit code 101`、`signal 11 (SIGSEGV)`
+pub fn describe_exit_status(status: &ExitStatus) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return match signal_name(signal) {
+                Some(name) => format!("signal {} ({})", signal, name),
+                None => format!("signal {}", signal),
+            };
+        }
+    }
+    match status.code() {
+        Some(code) => format!("exit code {}", code),
+        None => status.to_string(),
+    }
+}
+
+/// 解答の異常終了でよく見られるシグナルの名前を返す
+#[cfg(unix)]
+fn signal_name(signal: i32) -> Option<&'static str> {
+    let name = match signal {
+        libc::SIGILL => "SIGILL",
+        libc::SIGABRT => "SIGABRT",
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGPIPE => "SIGPIPE",
+        libc::SIGTERM => "SIGTERM",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// 出力を別スレッドで最後まで読み込み、先頭の `limit` バイトと、`limit` を超えたかどうかを返す
+///
+/// `limit` を超えた部分は保持せずに読み捨てる。
+fn read_in_background<R: Read + Send + 'static>(
+    source: Option<R>,
+    limit: usize,
+) -> Option<JoinHandle<(Vec<u8>, bool)>> {
+    source.map(|source| thread::spawn(move || read_limited(source, limit)))
+}
+
+/// `source` を最後まで読み込み、先頭の `limit` バイトと、`limit` を超えたかどうかを返す
+fn read_limited<R: Read>(mut source: R, limit: usize) -> (Vec<u8>, bool) {
+    let mut buffer = Vec::new();
+    let _ = source.by_ref().take(limit as u64).read_to_end(&mut buffer);
+    let mut exceeded = false;
+    let mut chunk = [0; 8192];
+    while let Ok(read) = source.read(&mut chunk) {
+        if read == 0 {
+            break;
+        }
+        exceeded = true;
+    }
+    (buffer, exceeded)
+}
+
+fn join_reader(reader: Option<JoinHandle<(Vec<u8>, bool)>>) -> (Vec<u8>, bool) {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
+
+/// 別スレッドでプロセスの終了を待ち、終了状態と終了を検知した時刻を送る
+///
+/// 終了したプログラムが起動した子プロセスが出力を保持したままにならないよう、
+/// 終了を検知した後、回収する前にプロセスとその子プロセスを強制終了する。
+/// 回収した時点で `reaped` を `true` にする。
+fn wait_in_background(
+    mut child: Child,
+    reaped: Arc<Mutex<bool>>,
+) -> mpsc::Receiver<(std::io::Result<ExitStatus>, Instant)> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let exited = wait_exited(&mut child);
+        let end_time = Instant::now();
+        let mut reaped = reaped.lock().unwrap_or_else(|e| e.into_inner());
+        if exited.is_ok() {
+            kill_tree(child.id());
+        }
+        let status = child.wait();
+        *reaped = true;
+        drop(reaped);
+        let _ = sender.send((status, end_time));
+    });
+    receiver
+}
+
+/// プロセスが終了するまで待機する(回収はしない)
+///
+/// 終了したプロセスはゾンビプロセスとして残るため、回収するまでプロセス ID は再利用されない。
+#[cfg(unix)]
+fn wait_exited(child: &mut Child) -> std::io::Result<()> {
+    let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+    loop {
+        let result = unsafe {
+            libc::waitid(
+                libc::P_PID,
+                child.id() as libc::id_t,
+                &mut info,
+                libc::WEXITED | libc::WNOWAIT,
+            )
+        };
+        if result == 0 {
+            return Ok(());
+        }
+        let error = std::io::Error::last_os_error();
+        if error.kind() != std::io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+/// プロセスが終了するまで待機する
+///
+/// `Child` がプロセスのハンドルを保持している間はプロセス ID が再利用されない。
+#[cfg(windows)]
+fn wait_exited(child: &mut Child) -> std::io::Result<()> {
+    child.wait().map(|_| ())
+}
+
+/// プロセスとその子プロセスを強制終了する
+#[cfg(unix)]
+pub fn kill_tree(pid: u32) {
+    // プロセスグループ ID は `process_group(0)` で起動したプロセスの ID と等しい
+    unsafe {
+        libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+    }
+}
+
+/// プロセスとその子プロセスを強制終了する
+#[cfg(windows)]
+pub fn kill_tree(pid: u32) {
+    let _ = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    use super::*;
+    use std::{fs, os::unix::fs::PermissionsExt};
+    use tempfile;
+
+    fn script(dir: &Path, content: &str) -> std::path::PathBuf {
+        let path = dir.join("solution.sh");
+        fs::write(&path, content).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn input_file(dir: &Path, content: &str) -> std::path::PathBuf {
+        let path = dir.join("sample_1.in");
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_run_with_timeout_exited() {
+        let dir = tempfile::tempdir().expect("");
+        let executable = script(
+            dir.path(),
+            "#!/bin/sh\nread n\necho $((n * 2))\necho debug >&2\n",
+        );
+        let input_path = input_file(dir.path(), "21\n");
+        match run_with_timeout(&executable, &input_path, Duration::from_secs(5), 1024).unwrap() {
+            Outcome::Exited {
+                status,
+                stdout,
+                stdout_exceeded,
+                stderr,
+                elapsed,
+            } => {
+                assert!(status.success());
+                assert_eq!(stdout, b"42\n");
+                assert!(!stdout_exceeded);
+                assert_eq!(stderr, "debug\n");
+                assert!(elapsed < Duration::from_secs(5));
+            }
+            Outcome::TimedOut { .. } => panic!("should not time out"),
+        }
+    }
+
+    #[test]
+    fn test_run_with_timeout_limits_output() {
+        let dir = tempfile::tempdir().expect("");
+        let input_path = input_file(dir.path(), "100000\n");
+        // パイプの容量を超える出力でも、上限を超えた部分を読み捨てて終了を待つ
+        let executable = script(dir.path(), "#!/bin/sh\nread n\nseq $n\n");
+        let Outcome::Exited {
+            status,
+            stdout,
+            stdout_exceeded,
+            ..
+        } = run_with_timeout(&executable, &input_path, Duration::from_secs(10), 6).unwrap()
+        else {
+            panic!("should not time out");
+        };
+        assert!(status.success());
+        assert_eq!(stdout, b"1\n2\n3\n");
+        assert!(stdout_exceeded);
+    }
+
+    #[test]
+    fn test_read_limited() {
+        assert_eq!(read_limited(&b"abc"[..], 3), (b"abc".to_vec(), false));
+        assert_eq!(read_limited(&b"abcd"[..], 3), (b"abc".to_vec(), true));
+        assert_eq!(read_limited(&b""[..], 0), (Vec::new(), false));
+    }
+
+    #[test]
+    fn test_describe_exit_status() {
+        let dir = tempfile::tempdir().expect("");
+        let input_path = input_file(dir.path(), "");
+        let executable = script(dir.path(), "#!/bin/sh\nexit 101\n");
+        let Outcome::Exited { status, .. } =
+            run_with_timeout(&executable, &input_path, Duration::from_secs(5), 1024).unwrap()
+        else {
+            panic!("should not time out");
+        };
+        assert_eq!(describe_exit_status(&status), "exit code 101");
+
+        let executable = script(dir.path(), "#!/bin/sh\nkill -SEGV $$\n");
+        let Outcome::Exited { status, .. } =
+            run_with_timeout(&executable, &input_path, Duration::from_secs(5), 1024).unwrap()
+        else {
+            panic!("should not time out");
+        };
+        assert_eq!(describe_exit_status(&status), "signal 11 (SIGSEGV)");
+    }
+
+    #[test]
+    fn test_run_with_timeout_kills_process_tree() {
+        let dir = tempfile::tempdir().expect("");
+        // 子プロセスの `sleep` が標準出力を保持したままでも、制限時間で終了する
+        let executable = script(dir.path(), "#!/bin/sh\nsleep 30 &\nsleep 30\n");
+        let input_path = input_file(dir.path(), "");
+        let start_time = Instant::now();
+        let outcome =
+            run_with_timeout(&executable, &input_path, Duration::from_millis(200), 1024).unwrap();
+        assert!(matches!(outcome, Outcome::TimedOut { .. }));
+        assert!(start_time.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_run_with_timeout_kills_remaining_children_after_exit() {
+        let dir = tempfile::tempdir().expect("");
+        // 終了したプログラムが起動した `sleep` が標準出力を保持したままでも、出力の読み込みを待ち続けない
+        let executable = script(dir.path(), "#!/bin/sh\nsleep 30 &\necho done\n");
+        let input_path = input_file(dir.path(), "");
+        let start_time = Instant::now();
+        let Outcome::Exited { status, stdout, .. } =
+            run_with_timeout(&executable, &input_path, Duration::from_secs(20), 1024).unwrap()
+        else {
+            panic!("should not time out");
+        };
+        assert!(status.success());
+        assert_eq!(stdout, b"done\n");
+        assert!(start_time.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,143 @@
+//! レート履歴を表示するモジュール
+//!
+//! コンテスト成績履歴(`history/json`)を取得し、現在のレート・最高レートと
+//! 直近のレート対象コンテストでのレート変動を表示する。
+//! ユーザー名を指定しない場合は、ログイン中のユーザーの履歴を表示する。
+use std::error::Error;
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::Session;
+use super::performance::{fetch_history, HistoryEntry};
+
+/// レート履歴表示処理のエントリーポイント
+///
+/// # 引数
+/// - `username`: 履歴を表示するユーザー名 (`None` の場合はログイン中のユーザー)
+/// - `count`: 表示する直近のコンテスト数
+pub async fn execute(username: Option<&str>, count: usize) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let username = username.unwrap_or(&session.username);
+
+    let history = fetch_history(&get_base_url(), &client()?, &session, username).await?;
+    let summary = summarize(&history, count)
+        .ok_or_else(|| format!("{} のレート対象コンテストの参加記録がありません", username))?;
+
+    println!("=== Rating: {} ===", username);
+    println!("Rating   : {}", summary.current);
+    println!("Highest  : {}", summary.highest);
+    println!("Contests : {}", summary.contests);
+    println!();
+    for change in &summary.recent {
+        println!(
+            "{:<12} {:>5} -> {:>5} ({:+})",
+            change.contest_name,
+            change.old_rating,
+            change.new_rating,
+            change.new_rating - change.old_rating
+        );
+    }
+    Ok(())
+}
+
+/// レート履歴の要約
+#[derive(Debug, PartialEq)]
+pub struct RatingSummary {
+    pub current: i64,              // 現在のレート
+    pub highest: i64,              // 最高レート
+    pub contests: usize,           // レート対象コンテストの参加回数
+    pub recent: Vec<RatingChange>, // 直近のレート変動(新しい順)
+}
+
+/// 1 コンテストでのレート変動
+#[derive(Debug, PartialEq)]
+pub struct RatingChange {
+    pub contest_name: String, // コンテスト名 (`abc388` など)
+    pub old_rating: i64,      // コンテスト前のレート
+    pub new_rating: i64,      // コンテスト後のレート
+}
+
+/// 成績履歴からレート対象コンテストのみを集計する
+///
+/// # 引数
+/// - `history`: コンテスト成績履歴(古い順)
+/// - `count`: `recent` に含める直近のコンテスト数
+///
+/// # 戻り値
+/// レート対象コンテストへの参加記録がない場合は `None`
+pub fn summarize(history: &[HistoryEntry], count: usize) -> Option<RatingSummary> {
+    let rated: Vec<&HistoryEntry> = history.iter().filter(|entry| entry.is_rated).collect();
+    let current = rated.last()?.new_rating;
+    let highest = rated.iter().map(|entry| entry.new_rating).max()?;
+    let recent = rated
+        .iter()
+        .rev()
+        .take(count)
+        .map(|entry| RatingChange {
+            // `ContestScreenName` は `abc388.contest.atcoder.jp` の形式
+            contest_name: entry
+                .contest_screen_name
+                .split('.')
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            old_rating: entry.old_rating,
+            new_rating: entry.new_rating,
+        })
+        .collect();
+    Some(RatingSummary {
+        current,
+        highest,
+        contests: rated.len(),
+        recent,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(contest: &str, is_rated: bool, old_rating: i64, new_rating: i64) -> HistoryEntry {
+        HistoryEntry {
+            is_rated,
+            inner_performance: 0,
+            old_rating,
+            new_rating,
+            contest_screen_name: format!("{}.contest.atcoder.jp", contest),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let history = vec![
+            entry("abc386", true, 0, 400),
+            entry("abc387", true, 400, 900),
+            entry("arc190", false, 900, 900),
+            entry("abc388", true, 900, 850),
+        ];
+        let summary = summarize(&history, 2).unwrap();
+        assert_eq!(summary.current, 850);
+        assert_eq!(summary.highest, 900);
+        assert_eq!(summary.contests, 3);
+        assert_eq!(
+            summary.recent,
+            vec![
+                RatingChange {
+                    contest_name: "abc388".to_string(),
+                    old_rating: 900,
+                    new_rating: 850,
+                },
+                RatingChange {
+                    contest_name: "abc387".to_string(),
+                    old_rating: 400,
+                    new_rating: 900,
+                },
+            ]
+        );
+
+        assert_eq!(summarize(&[entry("arc190", false, 0, 0)], 5), None);
+    }
+}
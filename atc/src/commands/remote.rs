@@ -0,0 +1,387 @@
+//! SSH 経由でリモートマシン上にコマンドを委譲するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - `Cargo.toml` からリモート実行設定を取得(`load_remote_settings`)
+//! - `rsync` によるプロジェクトの同期(`sync_project`)
+//! - `ssh` によるリモートでの `cargo atc` の実行(`run_remote`)
+//!
+//! リモート実行設定はコンテストの `Cargo.toml` に以下の形式で記述する。
+//! ```toml
+//! [package.metadata.remote]
+//! host = "user@desktop"           # ssh の接続先
+//! work_dir = "~/atcoder/abc388"   # リモート側の作業ディレクトリ
+//! ```
+//!
+//! `Cargo.toml` に設定がない場合は、ユーザー設定 (`config.toml`) の `remote_host` と `remote_dir` を利用する。
+//! `remote_dir` の場合は、その下のコンテストディレクトリと同じ名前のディレクトリを作業ディレクトリとする。
+//! ```toml
+//! remote_host = "user@desktop"
+//! remote_dir = "~/atcoder"        # abc388 の場合は ~/atcoder/abc388
+//! ```
+//!
+//! `test --remote` と `stress --remote` から利用する。
+//! リモートマシンにはあらかじめ `cargo-atc` をインストールしておく必要がある。
+//! ビルドと実行はリモート側の `cargo atc` が行い、結果は標準出力・標準エラー出力を通じてそのまま表示される。
+//! `--json` を指定した場合はリモートの `cargo atc` にも指定する。
+//! リモートの `cargo atc` が 0 以外の終了コードで終了した場合は `RemoteExit` を返し、`cargo atc` も同じ終了コードで終了する。
+use std::{error::Error, fmt, fs, path::Path, process::Command};
+use toml::Value;
+
+use super::config::{load_user_config, UserConfig};
+use super::output::is_json;
+
+/// リモート実行のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `args`: リモートで実行する `cargo atc` の引数 (例: `["test", "a"]`、`["stress", "a", "--count", "1000"]`)
+pub fn execute(work_dir: &Path, args: &[&str]) -> Result<(), Box<dyn Error>> {
+    let settings = load_remote_settings(work_dir)?;
+    sync_project(work_dir, &settings)?;
+    let mut args = args.to_vec();
+    if is_json() {
+        args.push("--json");
+    }
+    run_remote(&settings, &args)
+}
+
+/// リモート実行設定
+#[derive(Debug, PartialEq)]
+pub struct RemoteSettings {
+    pub host: String,     // ssh の接続先
+    pub work_dir: String, // リモート側の作業ディレクトリ
+}
+
+/// リモート実行設定を取得する
+///
+/// `Cargo.toml` の `[package.metadata.remote]` を優先し、設定されていない項目はユーザー設定から取得する。
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+///
+/// # 戻り値
+/// - `Ok(RemoteSettings)`: リモート実行設定
+/// - `Err(Box<dyn Error>)`: `Cargo.toml` の読み込みに失敗した場合、または設定が存在しない場合
+pub fn load_remote_settings(work_dir: &Path) -> Result<RemoteSettings, Box<dyn Error>> {
+    resolve_remote_settings(work_dir, load_user_config())
+}
+
+/// `Cargo.toml` の `[package.metadata.remote]` と、ユーザー設定 `config` からリモート実行設定を決定する
+fn resolve_remote_settings(
+    work_dir: &Path,
+    config: &UserConfig,
+) -> Result<RemoteSettings, Box<dyn Error>> {
+    let cargo_toml_content = fs::read_to_string(work_dir.join("Cargo.toml"))?;
+    let parsed: Value = toml::from_str(&cargo_toml_content)?;
+    let remote = parsed
+        .get("package")
+        .and_then(|pkg| pkg.get("metadata"))
+        .and_then(|meta| meta.get("remote"));
+    let get = |key: &str| {
+        remote
+            .and_then(|remote| remote.get(key))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+
+    let host = get("host")
+        .or_else(|| config.remote_host.clone())
+        .ok_or("リモートの接続先が設定されていません ([package.metadata.remote] の host、または config の remote_host を設定してください)")?;
+    let work_dir = match get("work_dir") {
+        Some(dir) => dir,
+        None => {
+            let remote_dir = config.remote_dir.as_ref().ok_or(
+                "リモートの作業ディレクトリが設定されていません ([package.metadata.remote] の work_dir、または config の remote_dir を設定してください)",
+            )?;
+            let contest_name = work_dir
+                .file_name()
+                .ok_or("作業ディレクトリの名前を取得できません")?;
+            format!("{}/{}", remote_dir, contest_name.to_string_lossy())
+        }
+    };
+    Ok(RemoteSettings { host, work_dir })
+}
+
+/// `rsync` でローカルのプロジェクトをリモートに同期する
+///
+/// ビルド成果物 (`target/`) と、実行履歴などのリモート側で記録した状態 (`.atc/`) は同期の対象外とする。
+/// `--delete` を指定しても対象外のファイルは削除されないため、リモート側の状態は保持される。
+pub fn sync_project(local_dir: &Path, settings: &RemoteSettings) -> Result<(), Box<dyn Error>> {
+    let status = build_rsync_command(local_dir, settings).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("リモートへの同期に失敗しました: {}", settings.host).into())
+    }
+}
+
+/// `ssh` でリモートの `cargo atc` を実行し、出力をそのまま表示する
+///
+/// # エラーの可能性
+/// - `ssh` を起動できない場合
+/// - リモートの `cargo atc` (または `ssh`) が 0 以外の終了コードで終了した場合 (`RemoteExit`)
+pub fn run_remote(settings: &RemoteSettings, args: &[&str]) -> Result<(), Box<dyn Error>> {
+    let status = build_ssh_command(settings, args).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(RemoteExit {
+            host: settings.host.clone(),
+            code: status.code(),
+        }
+        .into())
+    }
+}
+
+/// リモートの `cargo atc` が 0 以外の終了コードで終了したことを表すエラー
+#[derive(Debug)]
+pub struct RemoteExit {
+    pub host: String,      // ssh の接続先
+    pub code: Option<i32>, // 終了コード (シグナルで終了した場合は `None`)
+}
+
+impl RemoteExit {
+    /// `cargo atc` の終了コード (シグナルで終了した場合は `1`)
+    pub fn exit_code(&self) -> i32 {
+        self.code.unwrap_or(1)
+    }
+}
+
+impl fmt::Display for RemoteExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "リモートでの実行に失敗しました: {} (exit code: {:?})",
+            self.host, self.code
+        )
+    }
+}
+
+impl Error for RemoteExit {}
+
+fn build_rsync_command(local_dir: &Path, settings: &RemoteSettings) -> Command {
+    let mut command = Command::new("rsync");
+    command
+        .arg("-az")
+        .arg("--delete")
+        .arg("--exclude")
+        .arg("target/")
+        .arg("--exclude")
+        .arg(".atc/")
+        .arg(format!("{}/", local_dir.display()))
+        .arg(format!("{}:{}/", settings.host, settings.work_dir));
+    command
+}
+
+fn build_ssh_command(settings: &RemoteSettings, args: &[&str]) -> Command {
+    let remote_args: Vec<String> = args.iter().map(|arg| shell_quote(arg)).collect();
+    let mut command = Command::new("ssh");
+    command.arg(&settings.host).arg(format!(
+        "cd {} && cargo atc {}",
+        quote_remote_dir(&settings.work_dir),
+        remote_args.join(" ")
+    ));
+    command
+}
+
+/// リモートの作業ディレクトリをシェルに渡せる形にする
+///
+/// 先頭の `~/` のみリモートのシェルでホームディレクトリに展開させ、残りはシングルクォートで囲む
+fn quote_remote_dir(dir: &str) -> String {
+    match dir.strip_prefix("~/") {
+        Some(rest) => format!("~/{}", shell_quote(rest)),
+        None if dir == "~" => dir.to_string(),
+        None => shell_quote(dir),
+    }
+}
+
+/// リモートのシェルに渡す引数をシングルクォートで囲む
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_load_remote_settings_success() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+
+[package.metadata.remote]
+host = "user@desktop"
+work_dir = "~/atcoder/abc388"
+"#,
+        )
+        .unwrap();
+
+        let settings = load_remote_settings(work_dir.path()).unwrap();
+        assert_eq!(
+            settings,
+            RemoteSettings {
+                host: "user@desktop".to_string(),
+                work_dir: "~/atcoder/abc388".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_remote_settings_missing_section() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+"#,
+        )
+        .unwrap();
+
+        let result = resolve_remote_settings(work_dir.path(), &UserConfig::default());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("remote_host"));
+    }
+
+    #[test]
+    fn test_load_remote_settings_from_user_config() {
+        let temp_dir = tempfile::tempdir().expect("");
+        let work_dir = temp_dir.path().join("abc388");
+        fs::create_dir_all(&work_dir).unwrap();
+        fs::write(
+            work_dir.join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+"#,
+        )
+        .unwrap();
+        let config = UserConfig {
+            remote_host: Some("user@desktop".to_string()),
+            remote_dir: Some("~/atcoder".to_string()),
+            ..UserConfig::default()
+        };
+
+        let settings = resolve_remote_settings(&work_dir, &config).unwrap();
+        assert_eq!(
+            settings,
+            RemoteSettings {
+                host: "user@desktop".to_string(),
+                work_dir: "~/atcoder/abc388".to_string(),
+            }
+        );
+
+        // `Cargo.toml` の設定を優先する
+        fs::write(
+            work_dir.join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+version = "0.1.0"
+edition = "2021"
+
+[package.metadata.remote]
+work_dir = "/work/abc388"
+"#,
+        )
+        .unwrap();
+        let settings = resolve_remote_settings(&work_dir, &config).unwrap();
+        assert_eq!(settings.host, "user@desktop");
+        assert_eq!(settings.work_dir, "/work/abc388");
+
+        let config = UserConfig {
+            remote_host: Some("user@desktop".to_string()),
+            ..UserConfig::default()
+        };
+        fs::write(
+            work_dir.join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\n",
+        )
+        .unwrap();
+        let result = resolve_remote_settings(&work_dir, &config);
+        assert!(result.unwrap_err().to_string().contains("remote_dir"));
+    }
+
+    #[test]
+    fn test_build_rsync_command() {
+        let settings = RemoteSettings {
+            host: "user@desktop".to_string(),
+            work_dir: "~/atcoder/abc388".to_string(),
+        };
+        let command = build_rsync_command(Path::new("/home/user/abc388"), &settings);
+        let args: Vec<String> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(command.get_program(), "rsync");
+        assert_eq!(
+            args,
+            vec![
+                "-az",
+                "--delete",
+                "--exclude",
+                "target/",
+                "--exclude",
+                ".atc/",
+                "/home/user/abc388/",
+                "user@desktop:~/atcoder/abc388/"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_ssh_command() {
+        let settings = RemoteSettings {
+            host: "user@desktop".to_string(),
+            work_dir: "~/atcoder/abc388".to_string(),
+        };
+        let command = build_ssh_command(&settings, &["test", "a"]);
+        let args: Vec<String> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(command.get_program(), "ssh");
+        assert_eq!(
+            args,
+            vec![
+                "user@desktop",
+                "cd ~/'atcoder/abc388' && cargo atc 'test' 'a'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remote_exit_code() {
+        let exit = |code| RemoteExit {
+            host: "user@desktop".to_string(),
+            code,
+        };
+        assert_eq!(exit(Some(3)).exit_code(), 3);
+        assert_eq!(exit(None).exit_code(), 1);
+    }
+
+    #[test]
+    fn test_quote_remote_dir() {
+        assert_eq!(quote_remote_dir("~/atcoder/abc 388"), "~/'atcoder/abc 388'");
+        assert_eq!(quote_remote_dir("~"), "~");
+        assert_eq!(quote_remote_dir("/work/a;rm"), "'/work/a;rm'");
+        assert_eq!(quote_remote_dir("~user/atcoder"), "'~user/atcoder'");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("a"), "'a'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
@@ -0,0 +1,265 @@
+//! 提出の詳細(判定結果・テストケースごとの結果)を表示するモジュール
+//!
+//! 提出詳細ページ(`submissions/{id}`)を認証済みのセッションで取得し、以下を表示する。
+//! - 問題・判定結果・得点・実行時間・メモリ使用量
+//! - コンパイルエラー・警告の出力 (存在する場合)
+//! - テストケースごとの判定結果・実行時間・メモリ使用量 (ジャッジ完了後のみ)
+//!
+//! ジャッジ中の場合はテストケースの表が存在しないため、進捗のみを表示する。
+//! `watch` を指定した場合はジャッジが完了するまでポーリングする。
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use std::{collections::HashMap, error::Error, path::Path, time::Duration};
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+use super::output::{is_json, print_json};
+use super::submissions::is_judging_status;
+use super::submit::get_contest_name;
+
+/// ジャッジ完了を待つ際のポーリング間隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 提出詳細表示処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `submission_id`: 提出 ID
+/// - `watch`: `true` の場合、ジャッジが完了するまでポーリングする
+///
+/// # エラーの可能性
+/// - 提出詳細ページの取得に失敗した場合 (他のユーザーの非公開の提出など)
+/// - 提出詳細ページから判定結果を取得できない場合
+pub async fn execute(
+    work_dir: &Path,
+    submission_id: &str,
+    watch: bool,
+) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+    let contest_name = get_contest_name(work_dir)?;
+    let url = format!(
+        "{}/contests/{}/submissions/{}",
+        get_base_url(),
+        contest_name,
+        submission_id
+    );
+
+    loop {
+        let html = fetch_with_session(&client, &url, &session).await?;
+        let detail = parse_submission_detail(&Html::parse_document(&html))
+            .ok_or_else(|| format!("提出 {} の判定結果を取得できません", submission_id))?;
+        let judging = is_judging_status(&detail.status);
+        if !watch || !judging {
+            if is_json() {
+                return print_json(&detail);
+            }
+            print_detail(submission_id, &detail);
+            return Ok(());
+        }
+        if !is_json() {
+            println!("Judging... {}", detail.status);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// 提出詳細
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SubmissionDetail {
+    pub task: String,                    // 問題名 (`A - Welcome to AtCoder` など)
+    pub status: String,                  // 判定結果 (`AC`, `WA`, `WJ`, `3/20` など)
+    pub score: String,                   // 得点
+    pub exec_time: Option<String>,       // 実行時間 (ジャッジ中・CE の場合は `None`)
+    pub memory: Option<String>,          // メモリ使用量 (ジャッジ中・CE の場合は `None`)
+    pub compile_error: Option<String>,   // コンパイルエラー・警告の出力
+    pub test_cases: Vec<TestCaseDetail>, // テストケースごとの結果 (ジャッジ中は空)
+}
+
+/// テストケースごとの結果
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TestCaseDetail {
+    pub name: String,      // テストケース名
+    pub status: String,    // 判定結果
+    pub exec_time: String, // 実行時間
+    pub memory: String,    // メモリ使用量
+}
+
+/// 提出詳細ページの HTML から提出詳細を抽出する
+///
+/// 提出情報の表は見出し(`th`)と値(`td`)の組で構成されるため、見出しの文字列(英語・日本語)で値を取得する。
+/// テストケースの表は「ケース名・結果・実行時間・メモリ」の 4 列で構成される。
+///
+/// # 戻り値
+/// - `Some(SubmissionDetail)`: 提出詳細
+/// - `None`: 判定結果が見つからない場合
+pub fn parse_submission_detail(document: &Html) -> Option<SubmissionDetail> {
+    let row_selector = Selector::parse("table tr").unwrap();
+    let th_selector = Selector::parse("th").unwrap();
+    let td_selector = Selector::parse("td").unwrap();
+    let h4_selector = Selector::parse("h4").unwrap();
+
+    let text = |cell: &ElementRef| cell.text().collect::<String>().trim().to_string();
+    let mut fields = HashMap::new();
+    let mut test_cases = Vec::new();
+    for row in document.select(&row_selector) {
+        let headers: Vec<ElementRef> = row.select(&th_selector).collect();
+        let cells: Vec<ElementRef> = row.select(&td_selector).collect();
+        match (headers.len(), cells.len()) {
+            (1, 1) => {
+                fields.insert(text(&headers[0]), text(&cells[0]));
+            }
+            (0, 4) => test_cases.push(TestCaseDetail {
+                name: text(&cells[0]),
+                status: text(&cells[1]),
+                exec_time: text(&cells[2]),
+                memory: text(&cells[3]),
+            }),
+            _ => {}
+        }
+    }
+    let field = |keys: [&str; 2]| keys.iter().find_map(|key| fields.get(*key).cloned());
+
+    // コンパイルエラーの出力は見出し(`h4`)の直後の `pre` に含まれる
+    let compile_error = document
+        .select(&h4_selector)
+        .filter(|h4| {
+            let title = text(h4);
+            title.contains("Compile Error") || title.contains("コンパイルエラー")
+        })
+        .find_map(|h4| {
+            h4.next_siblings()
+                .filter_map(ElementRef::wrap)
+                .find(|element| element.value().name() == "pre")
+        })
+        .map(|pre| pre.text().collect::<String>())
+        .filter(|output| !output.trim().is_empty());
+
+    Some(SubmissionDetail {
+        task: field(["Task", "問題"]).unwrap_or_default(),
+        status: field(["Status", "結果"])?,
+        score: field(["Score", "得点"]).unwrap_or_default(),
+        exec_time: field(["Exec Time", "実行時間"]),
+        memory: field(["Memory", "メモリ"]),
+        compile_error,
+        test_cases,
+    })
+}
+
+/// 提出詳細を表示する
+fn print_detail(submission_id: &str, detail: &SubmissionDetail) {
+    println!("{} #{}", detail.task, submission_id);
+    println!(
+        "  {} | score: {} | time: {} | memory: {}",
+        detail.status,
+        detail.score,
+        detail.exec_time.as_deref().unwrap_or("-"),
+        detail.memory.as_deref().unwrap_or("-")
+    );
+    if let Some(output) = &detail.compile_error {
+        println!("\n=== Compile Error ===\n{}", output.trim_end());
+    }
+    if is_judging_status(&detail.status) {
+        println!("\nJudging in progress ({})", detail.status);
+        return;
+    }
+    if !detail.test_cases.is_empty() {
+        println!(
+            "\n{:<32} {:>6} {:>8} {:>10}",
+            "Case", "Status", "Exec", "Memory"
+        );
+        for case in &detail.test_cases {
+            println!(
+                "{:<32} {:>6} {:>8} {:>10}",
+                case.name, case.status, case.exec_time, case.memory
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DETAIL_HTML: &str = r##"
+<table class="table table-bordered table-striped">
+  <tr><th>Submission Time</th><td class="text-center"><time class="fixtime-second">2025-01-11 21:05:00+0900</time></td></tr>
+  <tr><th>Task</th><td class="text-center"><a href="/contests/abc388/tasks/abc388_a">A - ?UPC</a></td></tr>
+  <tr><th>User</th><td class="text-center"><a href="/users/mock_user">mock_user</a></td></tr>
+  <tr><th>Language</th><td class="text-center"><a href="#">Rust (rustc 1.70.0)</a></td></tr>
+  <tr><th>Score</th><td class="text-center">0</td></tr>
+  <tr><th>Status</th><td id="judge-status" class="text-center"><span class="label label-warning">WA</span></td></tr>
+  <tr><th>Exec Time</th><td class="text-center">1 ms</td></tr>
+  <tr><th>Memory</th><td class="text-center">2004 KB</td></tr>
+</table>
+<h4>Compile Error</h4>
+<pre>warning: unused variable: `x`</pre>
+<table class="table table-bordered table-striped th-center">
+  <thead><tr><th>Set Name</th><th>Score / Max Score</th><th>Test Cases</th></tr></thead>
+  <tbody><tr><td class="text-center">All</td><td class="text-center">0 / 100</td><td class="text-center">00_sample_00.txt, 01_random_00.txt</td></tr></tbody>
+</table>
+<table class="table table-bordered table-striped th-center">
+  <thead><tr><th>Case Name</th><th>Status</th><th>Exec Time</th><th>Memory</th></tr></thead>
+  <tbody>
+    <tr><td class="text-center">00_sample_00.txt</td><td class="text-center"><span class="label label-success">AC</span></td><td class="text-right">1 ms</td><td class="text-right">1968 KB</td></tr>
+    <tr><td class="text-center">01_random_00.txt</td><td class="text-center"><span class="label label-warning">WA</span></td><td class="text-right">1 ms</td><td class="text-right">2004 KB</td></tr>
+  </tbody>
+</table>
+"##;
+
+    const JUDGING_HTML: &str = r#"
+<table class="table table-bordered table-striped">
+  <tr><th>提出日時</th><td><time>2025-01-11 21:05:00+0900</time></td></tr>
+  <tr><th>問題</th><td><a href="/contests/abc388/tasks/abc388_b">B - Heavy Snake</a></td></tr>
+  <tr><th>得点</th><td>0</td></tr>
+  <tr><th>結果</th><td id="judge-status" class="waiting-judge"><span class="label label-default">3/20</span></td></tr>
+</table>
+"#;
+
+    #[test]
+    fn test_parse_submission_detail() {
+        let detail = parse_submission_detail(&Html::parse_document(DETAIL_HTML)).unwrap();
+        assert_eq!(detail.task, "A - ?UPC");
+        assert_eq!(detail.status, "WA");
+        assert_eq!(detail.score, "0");
+        assert_eq!(detail.exec_time.as_deref(), Some("1 ms"));
+        assert_eq!(
+            detail.compile_error.as_deref(),
+            Some("warning: unused variable: `x`")
+        );
+        assert_eq!(
+            detail.test_cases,
+            vec![
+                TestCaseDetail {
+                    name: "00_sample_00.txt".to_string(),
+                    status: "AC".to_string(),
+                    exec_time: "1 ms".to_string(),
+                    memory: "1968 KB".to_string(),
+                },
+                TestCaseDetail {
+                    name: "01_random_00.txt".to_string(),
+                    status: "WA".to_string(),
+                    exec_time: "1 ms".to_string(),
+                    memory: "2004 KB".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_submission_detail_judging() {
+        let detail = parse_submission_detail(&Html::parse_document(JUDGING_HTML)).unwrap();
+        assert_eq!(detail.task, "B - Heavy Snake");
+        assert_eq!(detail.status, "3/20");
+        assert!(is_judging_status(&detail.status));
+        assert_eq!(detail.exec_time, None);
+        assert_eq!(detail.compile_error, None);
+        assert!(detail.test_cases.is_empty());
+
+        assert!(parse_submission_detail(&Html::parse_document("<table></table>")).is_none());
+    }
+}
@@ -0,0 +1,99 @@
+//! 問題のプログラムを端末に接続して実行するモジュール
+//!
+//! サンプルとの比較は行わず、標準入出力・標準エラー出力を端末に接続したまま実行する。
+//! `input` を指定した場合は、指定したファイルの内容を標準入力として与える。
+use std::{
+    error::Error,
+    fs::File,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use super::test::{compile, find_problem_directory, get_execution_path};
+
+/// 実行処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+/// - `input`: 標準入力として与えるファイル (`None` の場合は端末から入力する)
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない、またはコンパイルに失敗した場合
+/// - `input` のファイルを開けない場合
+/// - プログラムが 0 以外の終了コードで終了した場合
+pub fn execute(
+    work_dir: &Path,
+    problem_name: &str,
+    input: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    compile(&problem_dir)?;
+    let executable = get_execution_path(work_dir, problem_name)?;
+
+    let stdin = match input {
+        Some(path) => Stdio::from(
+            File::open(path).map_err(|e| format!("{} を開けません: {}", path.display(), e))?,
+        ),
+        None => Stdio::inherit(),
+    };
+    let status = Command::new(executable).stdin(stdin).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("Process exited with {}", status).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use tempfile;
+
+    #[test]
+    fn test_execute_with_input_file() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "run_test"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+"#,
+        )
+        .unwrap();
+        fs::create_dir_all(work_dir.path().join("a")).unwrap();
+        fs::write(
+            work_dir.path().join("a/main.rs"),
+            r#"
+use std::io::Read;
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    assert_eq!(input.trim(), "ok");
+}
+"#,
+        )
+        .unwrap();
+        let valid_input = work_dir.path().join("valid.in");
+        let invalid_input = work_dir.path().join("invalid.in");
+        fs::write(&valid_input, "ok\n").unwrap();
+        fs::write(&invalid_input, "ng\n").unwrap();
+
+        let work_dir_path = work_dir.path().to_path_buf();
+        assert!(execute(&work_dir_path, "a", Some(&valid_input)).is_ok());
+        assert!(execute(&work_dir_path, "a", Some(&invalid_input)).is_err());
+        assert!(execute(
+            &work_dir_path,
+            "a",
+            Some(&work_dir.path().join("missing.in"))
+        )
+        .is_err());
+    }
+}
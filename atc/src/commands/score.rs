@@ -0,0 +1,517 @@
+//! AHC (AtCoder Heuristic Contest) のスコア履歴を記録・表示するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - ローカル実行・提出ごとのスコアの記録(`record_score`、`record_test_score`、`record_submission_score`)
+//! - スコア履歴の読み込みと保存(`ScoreHistory`)
+//! - ターミナル向けスパークラインの描画(`render_sparkline`)
+//! - SVG 形式のスコア推移グラフの生成(`render_svg`)
+//!
+//! スコア履歴はコンテストディレクトリ直下の以下のファイルに保存される。
+//! ```text
+//! .
+//! ├── Cargo.toml
+//! ├── .atc
+//! │   └── score_history.json  # スコア履歴
+//! └── a
+//!     └── main.rs             # 記録時にハッシュ値を取得するソースコード
+//! ```
+//!
+//! 提出のスコア(プレテスト)は、AHC の提出のジャッジ完了を `status` で確認した時に自動で記録する。
+//! 同じ提出は 1 度だけ記録する。
+//! ローカル実行のスコアはこのツールでは計算しない。AHC のコンテストで `test` を実行した場合、
+//! 解答が標準エラー出力に `Score = <スコア>` の形式で出力したスコアを集計して自動で記録する(`record_test_score`)。
+//! 解答がスコアを出力しない場合は、手元のテスターで得た合計スコアを `score record` で記録する。
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// スコア履歴ファイルのコンテストディレクトリからの相対パス
+const SCORE_HISTORY_FILE: &str = ".atc/score_history.json";
+
+/// スパークラインの描画に利用する文字
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// スコアの記録処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `problem_name`: 問題名 (`a` など)
+/// - `total`: 全ケースの合計スコア
+/// - `cases`: スコアを計測したケース数
+/// - `submission`: 提出時のスコア(プレテスト)であれば `true`
+pub fn execute_record(
+    work_dir: &Path,
+    problem_name: &str,
+    total: i64,
+    cases: usize,
+    submission: bool,
+) -> Result<(), Box<dyn Error>> {
+    let kind = if submission {
+        ScoreKind::Submission
+    } else {
+        ScoreKind::Local
+    };
+    let record = record_score(work_dir, problem_name, kind, total, cases)?;
+    println!(
+        "Recorded {} score for {}: total = {}, average = {:.2}",
+        record.kind, record.problem_name, record.total, record.average
+    );
+    Ok(())
+}
+
+/// スコア履歴の表示処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `problem_name`: 問題名 (`a` など)
+/// - `svg_path`: 指定された場合、スコア推移グラフを SVG として書き出す
+pub fn execute_show(
+    work_dir: &Path,
+    problem_name: &str,
+    svg_path: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let history = ScoreHistory::load(&work_dir.join(SCORE_HISTORY_FILE))?;
+    let records: Vec<&ScoreRecord> = history
+        .records
+        .iter()
+        .filter(|r| r.problem_name == problem_name)
+        .collect();
+    if records.is_empty() {
+        return Err(format!("'{}' のスコア履歴がありません", problem_name).into());
+    }
+
+    println!("=== Score History: {} ===", problem_name);
+    for record in &records {
+        println!(
+            "{} [{}] total = {}, average = {:.2}, cases = {}, code = {}",
+            record.timestamp,
+            record.kind,
+            record.total,
+            record.average,
+            record.cases,
+            record.code_hash
+        );
+    }
+    for kind in [ScoreKind::Local, ScoreKind::Submission] {
+        let values: Vec<f64> = records
+            .iter()
+            .filter(|r| r.kind == kind)
+            .map(|r| r.average)
+            .collect();
+        if !values.is_empty() {
+            println!("{:<10} {}", kind.to_string(), render_sparkline(&values));
+        }
+    }
+
+    if let Some(svg_path) = svg_path {
+        let owned: Vec<ScoreRecord> = records.into_iter().cloned().collect();
+        fs::write(svg_path, render_svg(&owned))?;
+        println!("Score chart written to {}", svg_path.display());
+    }
+    Ok(())
+}
+
+/// スコアの種別
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreKind {
+    /// ローカルでの実行結果
+    Local,
+    /// 提出時のプレテスト結果
+    Submission,
+}
+
+impl std::fmt::Display for ScoreKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            ScoreKind::Local => "local",
+            ScoreKind::Submission => "submission",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+/// 1回分のスコア記録
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScoreRecord {
+    pub problem_name: String,
+    pub kind: ScoreKind,
+    pub timestamp: u64,    // 記録時刻(UNIX時間)
+    pub total: i64,        // 合計スコア
+    pub average: f64,      // 1ケースあたりの平均スコア
+    pub cases: usize,      // ケース数
+    pub code_hash: String, // 記録時点の main.rs のハッシュ値
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission_id: Option<String>, // 提出から自動で記録した場合の提出 ID
+}
+
+/// スコア履歴
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ScoreHistory {
+    pub records: Vec<ScoreRecord>,
+}
+
+impl ScoreHistory {
+    /// スコア履歴をロードする。ファイルが存在しない場合は空の履歴を返す。
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(ScoreHistory::default());
+        }
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// スコア履歴を保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// スコアを履歴に追記する
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `kind`: スコアの種別
+/// - `total`: 合計スコア
+/// - `cases`: ケース数
+///
+/// # 戻り値
+/// - `Ok(ScoreRecord)`: 追記した記録
+/// - `Err(Box<dyn Error>)`: 履歴ファイルの読み書きに失敗した場合
+pub fn record_score(
+    work_dir: &Path,
+    problem_name: &str,
+    kind: ScoreKind,
+    total: i64,
+    cases: usize,
+) -> Result<ScoreRecord, Box<dyn Error>> {
+    append_record(work_dir, problem_name, kind, total, cases, None)
+}
+
+/// `test` の実行結果の標準エラー出力からスコアを集計し、ローカル実行のスコアとして履歴に追記する
+///
+/// テストケースごとに最後に出力された `Score = <スコア>` の行をそのケースのスコアとする(`parse_score`)。
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `stderrs`: テストケースごとの標準エラー出力
+///
+/// # 戻り値
+/// - `Ok(Some(ScoreRecord))`: 追記した記録
+/// - `Ok(None)`: スコアを出力したテストケースがない場合
+/// - `Err(Box<dyn Error>)`: 履歴ファイルの読み書きに失敗した場合
+pub fn record_test_score<'a>(
+    work_dir: &Path,
+    problem_name: &str,
+    stderrs: impl IntoIterator<Item = &'a str>,
+) -> Result<Option<ScoreRecord>, Box<dyn Error>> {
+    let scores: Vec<i64> = stderrs.into_iter().filter_map(parse_score).collect();
+    if scores.is_empty() {
+        return Ok(None);
+    }
+    record_score(
+        work_dir,
+        problem_name,
+        ScoreKind::Local,
+        scores.iter().sum(),
+        scores.len(),
+    )
+    .map(Some)
+}
+
+/// 標準エラー出力から最後に出力された `Score = <スコア>` のスコアを取り出す
+pub fn parse_score(stderr: &str) -> Option<i64> {
+    stderr.lines().rev().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "Score" {
+            return None;
+        }
+        value.trim().parse().ok()
+    })
+}
+
+/// 提出のスコア(プレテスト)を履歴に追記する
+///
+/// 同じ提出 ID の記録が既に存在する場合は追記しない。
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `submission_id`: 提出 ID
+/// - `total`: 合計スコア
+/// - `cases`: プレテストのケース数
+///
+/// # 戻り値
+/// - `Ok(Some(ScoreRecord))`: 追記した記録
+/// - `Ok(None)`: 既に記録されている場合
+/// - `Err(Box<dyn Error>)`: 履歴ファイルの読み書きに失敗した場合
+pub fn record_submission_score(
+    work_dir: &Path,
+    problem_name: &str,
+    submission_id: &str,
+    total: i64,
+    cases: usize,
+) -> Result<Option<ScoreRecord>, Box<dyn Error>> {
+    if is_submission_recorded(work_dir, submission_id)? {
+        return Ok(None);
+    }
+    append_record(
+        work_dir,
+        problem_name,
+        ScoreKind::Submission,
+        total,
+        cases,
+        Some(submission_id),
+    )
+    .map(Some)
+}
+
+/// 提出 ID のスコアが既に記録されているかどうかを返す
+pub fn is_submission_recorded(
+    work_dir: &Path,
+    submission_id: &str,
+) -> Result<bool, Box<dyn Error>> {
+    Ok(ScoreHistory::load(&work_dir.join(SCORE_HISTORY_FILE))?
+        .records
+        .iter()
+        .any(|record| record.submission_id.as_deref() == Some(submission_id)))
+}
+
+fn append_record(
+    work_dir: &Path,
+    problem_name: &str,
+    kind: ScoreKind,
+    total: i64,
+    cases: usize,
+    submission_id: Option<&str>,
+) -> Result<ScoreRecord, Box<dyn Error>> {
+    let history_path = work_dir.join(SCORE_HISTORY_FILE);
+    let mut history = ScoreHistory::load(&history_path)?;
+    let source_path = work_dir.join(problem_name).join("main.rs");
+    let code_hash = match fs::read(&source_path) {
+        Ok(content) => format!("{:016x}", hash_bytes(&content)),
+        Err(_) => "-".to_string(),
+    };
+    let record = ScoreRecord {
+        problem_name: problem_name.to_string(),
+        kind,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        total,
+        average: if cases == 0 {
+            0.0
+        } else {
+            total as f64 / cases as f64
+        },
+        cases,
+        code_hash,
+        submission_id: submission_id.map(str::to_string),
+    };
+    history.records.push(record.clone());
+    history.save(&history_path)?;
+    Ok(record)
+}
+
+/// バイト列のハッシュ値(FNV-1a)を計算する
+///
+/// Rust のバージョンに依存せず同じ値となるよう、標準ライブラリの `DefaultHasher` は使用しない。
+pub fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// スコア列をスパークライン文字列に変換する
+pub fn render_sparkline(values: &[f64]) -> String {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max == min {
+                SPARK_CHARS[SPARK_CHARS.len() / 2]
+            } else {
+                let ratio = (v - min) / (max - min);
+                SPARK_CHARS[(ratio * (SPARK_CHARS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// スコア履歴を SVG の折れ線グラフに変換する
+///
+/// ローカル実行は青、提出は赤の線で描画する。
+pub fn render_svg(records: &[ScoreRecord]) -> String {
+    const WIDTH: f64 = 640.0;
+    const HEIGHT: f64 = 320.0;
+    const MARGIN: f64 = 20.0;
+
+    let min = records
+        .iter()
+        .map(|r| r.average)
+        .fold(f64::INFINITY, f64::min);
+    let max = records
+        .iter()
+        .map(|r| r.average)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let step = if records.len() > 1 {
+        (WIDTH - 2.0 * MARGIN) / (records.len() - 1) as f64
+    } else {
+        0.0
+    };
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">
+<rect width="100%" height="100%" fill="white"/>
+"#,
+        WIDTH, HEIGHT
+    );
+    for (kind, color) in [(ScoreKind::Local, "blue"), (ScoreKind::Submission, "red")] {
+        let points: Vec<String> = records
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.kind == kind)
+            .map(|(i, r)| {
+                let ratio = if max == min {
+                    0.5
+                } else {
+                    (r.average - min) / (max - min)
+                };
+                let x = MARGIN + step * i as f64;
+                let y = HEIGHT - MARGIN - ratio * (HEIGHT - 2.0 * MARGIN);
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect();
+        if !points.is_empty() {
+            svg.push_str(&format!(
+                "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" points=\"{}\"/>\n",
+                color,
+                points.join(" ")
+            ));
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_record_score_and_load() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::create_dir_all(work_dir.path().join("a")).unwrap();
+        fs::write(work_dir.path().join("a/main.rs"), "fn main() {}").unwrap();
+
+        record_score(work_dir.path(), "a", ScoreKind::Local, 1000, 10).unwrap();
+        record_score(work_dir.path(), "a", ScoreKind::Submission, 3000, 50).unwrap();
+
+        let history = ScoreHistory::load(&work_dir.path().join(SCORE_HISTORY_FILE)).unwrap();
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(history.records[0].kind, ScoreKind::Local);
+        assert_eq!(history.records[0].average, 100.0);
+        assert_eq!(history.records[1].kind, ScoreKind::Submission);
+        assert_eq!(history.records[1].average, 60.0);
+        assert_eq!(history.records[0].code_hash, history.records[1].code_hash);
+    }
+
+    #[test]
+    fn test_record_submission_score() {
+        let work_dir = tempfile::tempdir().expect("");
+        let record = record_submission_score(work_dir.path(), "a", "12345", 3000, 50)
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.kind, ScoreKind::Submission);
+        assert_eq!(record.submission_id.as_deref(), Some("12345"));
+        assert_eq!(record.code_hash, "-");
+
+        // 同じ提出は 1 度だけ記録する
+        assert!(
+            record_submission_score(work_dir.path(), "a", "12345", 3000, 50)
+                .unwrap()
+                .is_none()
+        );
+        assert!(is_submission_recorded(work_dir.path(), "12345").unwrap());
+        assert!(!is_submission_recorded(work_dir.path(), "12346").unwrap());
+        let history = ScoreHistory::load(&work_dir.path().join(SCORE_HISTORY_FILE)).unwrap();
+        assert_eq!(history.records.len(), 1);
+    }
+
+    #[test]
+    fn test_record_test_score() {
+        let work_dir = tempfile::tempdir().expect("");
+        let stderrs = [
+            "Score = 100\n",
+            "debug\nScore = 10\nScore = 300\n",
+            "debug\n",
+        ];
+        let record = record_test_score(work_dir.path(), "a", stderrs)
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.kind, ScoreKind::Local);
+        assert_eq!(record.total, 400);
+        assert_eq!(record.cases, 2);
+        assert_eq!(record.average, 200.0);
+
+        // スコアを出力したケースがない場合は記録しない
+        assert!(record_test_score(work_dir.path(), "a", ["debug\n"])
+            .unwrap()
+            .is_none());
+        let history = ScoreHistory::load(&work_dir.path().join(SCORE_HISTORY_FILE)).unwrap();
+        assert_eq!(history.records.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_score() {
+        assert_eq!(parse_score("Score = 12345"), Some(12345));
+        assert_eq!(parse_score("Score=0\n"), Some(0));
+        assert_eq!(parse_score("score = 1\nturn = 3"), None);
+        assert_eq!(parse_score("Score = abc"), None);
+        assert_eq!(parse_score(""), None);
+    }
+
+    #[test]
+    fn test_load_missing_history() {
+        let work_dir = tempfile::tempdir().expect("");
+        let history = ScoreHistory::load(&work_dir.path().join(SCORE_HISTORY_FILE)).unwrap();
+        assert!(history.records.is_empty());
+    }
+
+    #[test]
+    fn test_render_sparkline() {
+        assert_eq!(render_sparkline(&[0.0, 7.0]), "▁█");
+        assert_eq!(render_sparkline(&[5.0, 5.0]), "▅▅");
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let record = ScoreRecord {
+            problem_name: "a".to_string(),
+            kind: ScoreKind::Local,
+            timestamp: 0,
+            total: 100,
+            average: 10.0,
+            cases: 10,
+            code_hash: "-".to_string(),
+            submission_id: None,
+        };
+        let svg = render_svg(&[record]);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("stroke=\"blue\""));
+        assert!(!svg.contains("stroke=\"red\""));
+    }
+}
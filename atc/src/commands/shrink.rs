@@ -0,0 +1,279 @@
+//! ストレステストで見つかった反例(出力が一致しない入力)を縮小するモジュール
+//!
+//! 入力を行ごとに空白区切りのトークンとして扱い、反例であり続ける(`still_fails` が `true` を返す)間、
+//! 次の縮小を繰り返す。
+//!
+//! - 長さの縮小: 整数のトークン `N` に対応する配列(`N` 個のトークンからなる後続の行)、文字列(長さ `N` の後続の行)、
+//!   または `N` 行のブロックを見つけた場合、`N` を半分(または 1 減らした値)にして配列・文字列・行の先頭側か末尾側を残す
+//! - 値の縮小: 長さとして使われていない整数のトークンを、符号を保ったまま絶対値が 1、または半分の値に置き換える
+//!
+//! 入力の形式は推測によるため、縮小した入力が問題の制約を満たすとは限らない。
+//! 制約を満たさない入力は `still_fails` で `false` を返して除外する(`stress` では `validator.rs` で検証する)。
+use std::collections::BTreeSet;
+
+/// 縮小の候補
+enum Shrink {
+    /// 長さのトークンと、それに対応する配列・文字列・行を切り詰める
+    Length {
+        at: (usize, usize),       // 長さのトークンの位置 (行, 列)
+        lens: Vec<(usize, bool)>, // 試す長さと、末尾側を残すかどうか (短い順)
+        targets: Vec<Target>,     // 切り詰める対象
+    },
+    /// 整数のトークンを小さい値に置き換える
+    Value {
+        at: (usize, usize), // トークンの位置 (行, 列)
+        values: Vec<i64>,   // 試す値 (絶対値の小さい順)
+    },
+}
+
+/// 長さに合わせて切り詰める対象
+#[derive(Debug, PartialEq)]
+enum Target {
+    Tokens(usize),                      // 行のトークン
+    Chars(usize),                       // 1 トークンの行の文字列
+    Lines { start: usize, len: usize }, // `start` 行目から `len` 行のブロック
+}
+
+/// 縮小の結果
+#[derive(Debug, PartialEq)]
+pub struct Minimized {
+    pub input: String,   // 縮小した入力
+    pub attempts: usize, // `still_fails` を呼び出した回数
+}
+
+/// 反例であり続ける範囲で入力を縮小する
+///
+/// # 引数
+/// - `input`: 縮小する入力
+/// - `max_attempts`: `still_fails` を呼び出す最大回数
+/// - `still_fails`: 候補の入力が反例であれば `true` を返す関数
+///
+/// # 戻り値
+/// 縮小できなかった場合も、空白を正規化した入力を返す
+pub fn minimize(
+    input: &str,
+    max_attempts: usize,
+    mut still_fails: impl FnMut(&str) -> bool,
+) -> Minimized {
+    let mut current: Vec<Vec<String>> = input
+        .lines()
+        .map(|line| line.split_whitespace().map(str::to_string).collect())
+        .collect();
+    let mut attempts = 0;
+    'search: loop {
+        let mut progressed = false;
+        for shrink in find_shrinks(&current) {
+            let candidates: Vec<Vec<Vec<String>>> = match &shrink {
+                Shrink::Length { at, lens, targets } => lens
+                    .iter()
+                    .map(|&(len, keep_tail)| truncate(&current, *at, len, keep_tail, targets))
+                    .collect(),
+                Shrink::Value { at, values } => values
+                    .iter()
+                    .map(|value| {
+                        let mut candidate = current.clone();
+                        candidate[at.0][at.1] = value.to_string();
+                        candidate
+                    })
+                    .collect(),
+            };
+            for candidate in candidates {
+                if attempts >= max_attempts {
+                    break 'search;
+                }
+                attempts += 1;
+                if still_fails(&render(&candidate)) {
+                    current = candidate;
+                    // 長さを縮小した場合は位置が変わるため、候補を探し直す
+                    if matches!(shrink, Shrink::Length { .. }) {
+                        continue 'search;
+                    }
+                    progressed = true;
+                    break;
+                }
+            }
+        }
+        if !progressed {
+            break;
+        }
+    }
+    Minimized {
+        input: render(&current),
+        attempts,
+    }
+}
+
+/// 縮小の候補を、長さの縮小、値の縮小の順に返す
+fn find_shrinks(lines: &[Vec<String>]) -> Vec<Shrink> {
+    let mut lengths = Vec::new();
+    let mut values = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        for (j, token) in line.iter().enumerate() {
+            let Ok(value) = token.parse::<i64>() else {
+                continue;
+            };
+            let targets = find_targets(lines, i, value);
+            if !targets.is_empty() {
+                let n = value as usize;
+                let mut lens = vec![n / 2, n - 1];
+                lens.dedup();
+                let lens = lens
+                    .into_iter()
+                    .flat_map(|len| [(len, false), (len, true)])
+                    .collect();
+                lengths.push(Shrink::Length {
+                    at: (i, j),
+                    lens,
+                    targets,
+                });
+            } else if value.abs() > 1 {
+                let mut candidates = vec![value.signum(), value / 2];
+                candidates.dedup();
+                values.push(Shrink::Value {
+                    at: (i, j),
+                    values: candidates,
+                });
+            }
+        }
+    }
+    lengths.extend(values);
+    lengths
+}
+
+/// `line` 行目の整数 `n` を長さとする配列・文字列・行のブロックを探す
+fn find_targets(lines: &[Vec<String>], line: usize, n: i64) -> Vec<Target> {
+    if n < 2 {
+        return Vec::new();
+    }
+    let n = n as usize;
+    let mut targets = Vec::new();
+    for (k, tokens) in lines.iter().enumerate().skip(line + 1) {
+        if tokens.len() == n {
+            targets.push(Target::Tokens(k));
+        } else if tokens.len() == 1
+            && tokens[0].parse::<i64>().is_err()
+            && tokens[0].chars().count() == n
+        {
+            targets.push(Target::Chars(k));
+        }
+    }
+    // 直後の n 行、または末尾の n 行がすべて同じトークン数であれば、n 行のブロックとみなす
+    let uniform = |start: usize| {
+        start + n <= lines.len()
+            && lines[start..start + n]
+                .iter()
+                .all(|tokens| !tokens.is_empty() && tokens.len() == lines[start].len())
+    };
+    for start in [line + 1, lines.len().saturating_sub(n)] {
+        let block = Target::Lines { start, len: n };
+        if start > line && uniform(start) && !targets.contains(&block) {
+            targets.push(block);
+        }
+    }
+    targets
+}
+
+/// 長さのトークンを `len` に置き換え、対象を `len` 個(先頭側、または `keep_tail` の場合は末尾側)に切り詰めた入力を返す
+fn truncate(
+    lines: &[Vec<String>],
+    at: (usize, usize),
+    len: usize,
+    keep_tail: bool,
+    targets: &[Target],
+) -> Vec<Vec<String>> {
+    let mut result = lines.to_vec();
+    result[at.0][at.1] = len.to_string();
+    let mut removed = BTreeSet::new();
+    for target in targets {
+        match target {
+            Target::Tokens(k) => {
+                let skip = if keep_tail { result[*k].len() - len } else { 0 };
+                result[*k].drain(..skip);
+                result[*k].truncate(len);
+            }
+            Target::Chars(k) => {
+                let chars: Vec<char> = result[*k][0].chars().collect();
+                let skip = if keep_tail { chars.len() - len } else { 0 };
+                result[*k][0] = chars[skip..skip + len].iter().collect();
+            }
+            Target::Lines { start, len: n } => {
+                if keep_tail {
+                    removed.extend(*start..start + n - len);
+                } else {
+                    removed.extend(start + len..start + n);
+                }
+            }
+        }
+    }
+    result
+        .into_iter()
+        .enumerate()
+        .filter(|(k, _)| !removed.contains(k))
+        .map(|(_, tokens)| tokens)
+        .collect()
+}
+
+/// トークンを空白・改行区切りの入力に戻す
+fn render(lines: &[Vec<String>]) -> String {
+    let mut input = String::new();
+    for tokens in lines {
+        input.push_str(&tokens.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 入力に含まれる整数が `threshold` 以上の値を含むかどうか
+    fn contains_at_least(input: &str, threshold: i64) -> bool {
+        input
+            .split_whitespace()
+            .skip(1)
+            .any(|token| token.parse::<i64>().is_ok_and(|value| value >= threshold))
+    }
+
+    #[test]
+    fn test_minimize_array() {
+        // 配列に 100 以上の値が含まれると失敗する
+        let input = "8\n3 1 4 1 5 9 200 6\n";
+        let minimized = minimize(input, 1000, |input| {
+            let lines: Vec<&str> = input.lines().collect();
+            let n: usize = lines[0].parse().unwrap();
+            lines[1].split(' ').count() == n && contains_at_least(input, 100)
+        });
+        assert_eq!(minimized.input, "1\n100\n");
+    }
+
+    #[test]
+    fn test_minimize_lines_and_strings() {
+        // 文字列に `x` が含まれると失敗する
+        let input = "3 4\nabxd\nefgh\nijkl\n";
+        let minimized = minimize(input, 1000, |input| {
+            let lines: Vec<&str> = input.lines().collect();
+            let size: Vec<usize> = lines[0].split(' ').map(|v| v.parse().unwrap()).collect();
+            lines.len() == size[0] + 1
+                && lines[1..].iter().all(|line| line.len() == size[1])
+                && input.contains('x')
+        });
+        assert_eq!(minimized.input, "1 1\nx\n");
+    }
+
+    #[test]
+    fn test_minimize_max_attempts() {
+        let input = "4\n10 20 30 40\n";
+        let minimized = minimize(input, 0, |_| true);
+        assert_eq!(
+            minimized,
+            Minimized {
+                input: input.to_string(),
+                attempts: 0,
+            }
+        );
+        let minimized = minimize(input, 3, |_| false);
+        assert_eq!(minimized.input, input);
+        assert_eq!(minimized.attempts, 3);
+    }
+}
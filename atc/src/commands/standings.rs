@@ -0,0 +1,228 @@
+//! コンテストの順位表から自分の成績を表示するモジュール
+//!
+//! 順位表(`standings/json`)を認証済みのセッションで取得し、自分の順位・得点・ペナルティと
+//! 問題ごとの得点・ペナルティ・最終提出時間を表示する。
+//! `watch` に更新間隔(秒)を指定した場合は、指定した間隔で順位表を再取得して表示を更新する。
+//!
+//! ## 順位表の JSON の単位
+//! - `Score`: 実際の得点の 100 倍
+//! - `Elapsed`: コンテスト開始からの経過時間(ナノ秒)
+use reqwest::Client;
+use serde::Deserialize;
+use std::{collections::HashMap, error::Error, time::Duration};
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+
+/// 順位表の得点の倍率
+const SCORE_SCALE: i64 = 100;
+
+/// 順位表の経過時間の単位(ナノ秒)から秒への変換係数
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+/// 順位表表示処理のエントリーポイント
+///
+/// # 引数
+/// - `contest_name`: コンテスト名 (`abc388` など)
+/// - `watch`: 指定された場合、その間隔(秒)で順位表を再取得して表示する
+pub async fn execute(contest_name: &str, watch: Option<u64>) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+    let base_url = get_base_url();
+
+    loop {
+        let standings = fetch_standings(&base_url, &client, &session, contest_name).await?;
+        let lines = render_my_standing(&standings, &session.username)?;
+        if watch.is_some() {
+            // 画面をクリアしてから再描画する
+            print!("\x1b[2J\x1b[H");
+        }
+        println!("=== Standings: {} ===", contest_name);
+        for line in lines {
+            println!("{}", line);
+        }
+        match watch {
+            Some(interval) => tokio::time::sleep(Duration::from_secs(interval.max(1))).await,
+            None => return Ok(()),
+        }
+    }
+}
+
+/// 順位表の JSON
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct Standings {
+    pub task_info: Vec<TaskInfo>,
+    pub standings_data: Vec<StandingsEntry>,
+}
+
+/// 順位表の問題情報
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct TaskInfo {
+    pub assignment: String,       // 問題の記号 (`A` など)
+    pub task_screen_name: String, // 問題の ID (`abc388_a` など)
+}
+
+/// 順位表の参加者ごとのデータ
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+pub struct StandingsEntry {
+    pub rank: u32,
+    pub user_screen_name: String,
+    pub total_result: TaskResult,
+    #[serde(default)]
+    pub task_results: HashMap<String, TaskResult>,
+}
+
+/// 合計または問題ごとの成績
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "PascalCase", default)]
+pub struct TaskResult {
+    pub score: i64,   // 得点(100 倍)
+    pub penalty: u32, // ペナルティ(不正解の提出数)
+    pub elapsed: i64, // 最終的な得点を得た提出の経過時間(ナノ秒)
+}
+
+/// 順位表を取得する
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 認証に利用するセッション情報
+/// - `contest_name`: コンテスト名
+pub async fn fetch_standings(
+    base_url: &str,
+    client: &Client,
+    session: &Session,
+    contest_name: &str,
+) -> Result<Standings, Box<dyn Error>> {
+    let url = format!("{}/contests/{}/standings/json", base_url, contest_name);
+    Ok(serde_json::from_str(
+        &fetch_with_session(client, &url, session).await?,
+    )?)
+}
+
+/// 順位表から自分の成績を表示用の行に整形する
+///
+/// # エラーの可能性
+/// - 順位表に自分が存在しない場合
+fn render_my_standing(
+    standings: &Standings,
+    username: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let me = standings
+        .standings_data
+        .iter()
+        .find(|entry| entry.user_screen_name == username)
+        .ok_or("順位表に参加記録が見つかりません")?;
+
+    let mut lines = vec![
+        format!("Rank    : {} / {}", me.rank, standings.standings_data.len()),
+        format!(
+            "Score   : {} ({})",
+            format_score(me.total_result.score),
+            format_elapsed(me.total_result.elapsed)
+        ),
+        format!("Penalty : {}", me.total_result.penalty),
+        String::new(),
+    ];
+    for task in &standings.task_info {
+        let line = match me.task_results.get(&task.task_screen_name) {
+            Some(result) if result.score > 0 => format!(
+                "{:<3} {:>6}  {:>8}  penalty {}",
+                task.assignment,
+                format_score(result.score),
+                format_elapsed(result.elapsed),
+                result.penalty
+            ),
+            Some(result) => format!(
+                "{:<3} {:>6}  {:>8}  penalty {}",
+                task.assignment, "-", "-", result.penalty
+            ),
+            None => format!("{:<3} {:>6}", task.assignment, "-"),
+        };
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+/// 順位表の得点(100 倍)を表示用に変換する
+fn format_score(score: i64) -> String {
+    if score % SCORE_SCALE == 0 {
+        (score / SCORE_SCALE).to_string()
+    } else {
+        format!("{:.2}", score as f64 / SCORE_SCALE as f64)
+    }
+}
+
+/// 経過時間(ナノ秒)を `H:MM:SS` 形式に変換する
+fn format_elapsed(elapsed: i64) -> String {
+    let secs = elapsed / NANOS_PER_SEC;
+    format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn test_fetch_and_render_standings() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/contests/abc388/standings/json")
+            .with_status(200)
+            .with_body(
+                r#"{
+                    "TaskInfo": [
+                        {"Assignment": "A", "TaskName": "Task A", "TaskScreenName": "abc388_a"},
+                        {"Assignment": "B", "TaskName": "Task B", "TaskScreenName": "abc388_b"},
+                        {"Assignment": "C", "TaskName": "Task C", "TaskScreenName": "abc388_c"}
+                    ],
+                    "StandingsData": [
+                        {"Rank": 1, "UserScreenName": "alice",
+                         "TotalResult": {"Score": 60000, "Penalty": 0, "Elapsed": 600000000000},
+                         "TaskResults": {}},
+                        {"Rank": 2, "UserScreenName": "mock_user",
+                         "TotalResult": {"Score": 30000, "Penalty": 1, "Elapsed": 3723000000000},
+                         "TaskResults": {
+                            "abc388_a": {"Score": 10000, "Penalty": 0, "Elapsed": 120000000000},
+                            "abc388_b": {"Score": 20000, "Penalty": 1, "Elapsed": 3723000000000},
+                            "abc388_c": {"Score": 0, "Penalty": 2, "Elapsed": 0}
+                         }}
+                    ]
+                }"#,
+            )
+            .create_async()
+            .await;
+        let session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+
+        let standings = fetch_standings(&server.url(), &Client::new(), &session, "abc388")
+            .await
+            .unwrap();
+        let lines = render_my_standing(&standings, "mock_user").unwrap();
+        assert_eq!(lines[0], "Rank    : 2 / 2");
+        assert_eq!(lines[1], "Score   : 300 (1:02:03)");
+        assert_eq!(lines[2], "Penalty : 1");
+        assert_eq!(lines[4], "A      100   0:02:00  penalty 0");
+        assert_eq!(lines[6], "C        -         -  penalty 2");
+
+        assert!(render_my_standing(&standings, "unknown").is_err());
+    }
+
+    #[test]
+    fn test_format_score() {
+        assert_eq!(format_score(10000), "100");
+        assert_eq!(format_score(12345), "123.45");
+    }
+}
@@ -0,0 +1,404 @@
+//! 問題文をローカルに保存するモジュール
+//!
+//! 問題ページの問題文(`#task-statement`)を取り出し、問題ディレクトリに以下の 2 つの形式で保存する。
+//! - `problem.html`: 問題文の HTML (取り出したものをそのまま保存)
+//! - `problem.md`: 問題文を Markdown に変換したもの (数式の `<var>` は `$...$` に変換)
+//!
+//! あわせて、問題の見出し・URL・実行時間制限・メモリ制限と制約(`Constraints`・`制約`)のみをまとめた
+//! `README.md` を保存する(`write_readme`)。ブラウザに切り替えずに解き始められるようにするため。
+//!
+//! 問題文が日本語と英語の両方を含む場合は英語(`span.lang-en`)を優先する。
+//! 画像・リンクの相対 URL は、オフラインでも参照先が分かるように絶対 URL に書き換える。
+//!
+//! ```text
+//! a
+//! ├── README.md
+//! ├── main.rs
+//! ├── problem.html
+//! ├── problem.md
+//! └── tests
+//! ```
+use scraper::{ElementRef, Html, Node, Selector};
+use std::{error::Error, fs, path::Path};
+
+use super::download::ProblemInfo;
+
+/// 問題ページの HTML から問題文の HTML を取り出す
+///
+/// # 引数
+/// - `document`: 問題ページの HTML
+/// - `base_url`: 相対 URL の基準 (`https://atcoder.jp` など)
+///
+/// # 戻り値
+/// - `Some(String)`: 画像・リンクの URL を絶対 URL に書き換えた問題文の HTML
+/// - `None`: 問題文が見つからない場合
+pub fn extract_statement(document: &Html, base_url: &str) -> Option<String> {
+    let statement_selector = Selector::parse("#task-statement").unwrap();
+    let statement = document.select(&statement_selector).next()?;
+    let html = ["span.lang-en", "span.lang-ja"]
+        .iter()
+        .find_map(|lang| {
+            let selector = Selector::parse(lang).unwrap();
+            statement.select(&selector).next()
+        })
+        .unwrap_or(statement)
+        .inner_html();
+    Some(absolutize_urls(&html, base_url))
+}
+
+/// `src`・`href` 属性の相対 URL を絶対 URL に書き換える
+fn absolutize_urls(html: &str, base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let mut html = html.to_string();
+    for attr in ["src", "href"] {
+        html = html
+            .replace(&format!("{}=\"//", attr), &format!("{}=\"https://", attr))
+            .replace(
+                &format!("{}=\"/", attr),
+                &format!("{}=\"{}/", attr, base_url),
+            );
+    }
+    html
+}
+
+/// 問題文を `problem.html`・`problem.md` として問題ディレクトリに保存する
+///
+/// # 引数
+/// - `problem_dir`: 問題ディレクトリ
+/// - `statement`: `extract_statement` で取り出した問題文の HTML
+pub fn write_statement(problem_dir: &Path, statement: &str) -> Result<(), Box<dyn Error>> {
+    fs::write(
+        problem_dir.join("problem.html"),
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n<body>\n{}\n</body>\n</html>\n",
+            statement.trim()
+        ),
+    )?;
+    fs::write(problem_dir.join("problem.md"), html_to_markdown(statement))?;
+    Ok(())
+}
+
+/// 問題の概要を `README.md` として問題ディレクトリに保存する
+///
+/// 問題の見出し・URL・実行時間制限・メモリ制限・配点と、問題文の制約を Markdown で書き込む。
+///
+/// # 引数
+/// - `problem_dir`: 問題ディレクトリ
+/// - `problem`: 問題の情報
+/// - `url`: 問題ページの URL
+pub fn write_readme(
+    problem_dir: &Path,
+    problem: &ProblemInfo,
+    url: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut readme = format!(
+        "# {}\n\n- URL: {}\n- Time Limit: {} sec\n",
+        problem.title.as_deref().unwrap_or(&problem.problem_name),
+        url,
+        problem.timeout as f64 / 1000.0
+    );
+    if let Some(memory_limit) = problem.memory_limit {
+        readme.push_str(&format!("- Memory Limit: {} MB\n", memory_limit));
+    }
+    if let Some(score) = problem.score {
+        readme.push_str(&format!("- Score: {}\n", score));
+    }
+    if let Some(constraints) = problem.statement.as_deref().and_then(extract_constraints) {
+        readme.push_str(&format!("\n## Constraints\n\n{}", constraints));
+    }
+    fs::write(problem_dir.join("README.md"), readme)?;
+    Ok(())
+}
+
+/// 問題文の HTML から制約の節(見出しが `Constraints`・`制約`)を取り出し、Markdown に変換して返す
+///
+/// 制約の節がない場合は `None` を返す。
+pub fn extract_constraints(statement: &str) -> Option<String> {
+    let fragment = Html::parse_fragment(statement);
+    let section_selector = Selector::parse("section").unwrap();
+    let heading_selector = Selector::parse("h3").unwrap();
+    let section = fragment.select(&section_selector).find(|section| {
+        section
+            .select(&heading_selector)
+            .next()
+            .is_some_and(|heading| {
+                matches!(
+                    heading.text().collect::<String>().trim(),
+                    "Constraints" | "制約"
+                )
+            })
+    })?;
+    let html: String = section
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().name() != "h3")
+        .map(|child| child.html())
+        .collect();
+    let markdown = html_to_markdown(&html);
+    (!markdown.trim().is_empty()).then_some(markdown)
+}
+
+/// 問題文の HTML を Markdown に変換する
+///
+/// 見出し・段落・リスト・表・コードブロック・画像・リンクに対応し、それ以外の要素は中身のみを出力する。
+/// サンプルのコピー用ボタン(`btn-copy`)は出力しない。
+pub fn html_to_markdown(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut markdown = String::new();
+    render_children(fragment.root_element(), &mut markdown);
+
+    // 3 行以上の空行をまとめ、前後の空白を取り除く
+    let mut output = String::new();
+    let mut blank_lines = 0;
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output.trim().to_string() + "\n"
+}
+
+/// 子要素を順に Markdown に変換する
+fn render_children(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => {
+                let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                let has_leading = text.starts_with(char::is_whitespace);
+                let has_trailing = text.ends_with(char::is_whitespace);
+                if collapsed.is_empty() {
+                    if has_leading && !out.ends_with([' ', '\n']) && !out.is_empty() {
+                        out.push(' ');
+                    }
+                    continue;
+                }
+                if has_leading && !out.ends_with([' ', '\n']) && !out.is_empty() {
+                    out.push(' ');
+                }
+                out.push_str(&collapsed);
+                if has_trailing {
+                    out.push(' ');
+                }
+            }
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    render_element(child, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 子要素を変換した結果を前後の空白を除いて返す
+fn render_inline(element: ElementRef) -> String {
+    let mut out = String::new();
+    render_children(element, &mut out);
+    out.trim().to_string()
+}
+
+/// ブロック要素の区切り(空行)を出力する
+fn push_block_break(out: &mut String) {
+    let trimmed = out.trim_end_matches(' ').len();
+    out.truncate(trimmed);
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+}
+
+/// 要素を Markdown に変換する
+fn render_element(element: ElementRef, out: &mut String) {
+    let value = element.value();
+    if value
+        .classes()
+        .any(|class| class == "btn-copy" || class == "div-btn-copy")
+    {
+        return;
+    }
+    let text = || element.text().collect::<String>();
+    match value.name() {
+        name @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+            push_block_break(out);
+            let level = name[1..].parse::<usize>().unwrap_or(1);
+            out.push_str(&format!("{} {}", "#".repeat(level), render_inline(element)));
+            push_block_break(out);
+        }
+        "p" | "div" | "section" | "blockquote" => {
+            push_block_break(out);
+            render_children(element, out);
+            push_block_break(out);
+        }
+        "pre" => {
+            push_block_break(out);
+            out.push_str(&format!("```\n{}\n```", text().trim_end()));
+            push_block_break(out);
+        }
+        "ul" | "ol" => {
+            push_block_break(out);
+            let ordered = value.name() == "ol";
+            let items = element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|child| child.value().name() == "li");
+            for (i, item) in items.enumerate() {
+                let marker = if ordered {
+                    format!("{}.", i + 1)
+                } else {
+                    "-".to_string()
+                };
+                out.push_str(&format!("{} {}\n", marker, render_inline(item)));
+            }
+            push_block_break(out);
+        }
+        "table" => {
+            push_block_break(out);
+            let row_selector = Selector::parse("tr").unwrap();
+            for (i, row) in element.select(&row_selector).enumerate() {
+                let cells: Vec<String> = row
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|cell| matches!(cell.value().name(), "th" | "td"))
+                    .map(|cell| render_inline(cell).replace('|', "\\|"))
+                    .collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                if i == 0 {
+                    out.push_str(&format!("|{}\n", " --- |".repeat(cells.len())));
+                }
+            }
+            push_block_break(out);
+        }
+        "hr" => {
+            push_block_break(out);
+            out.push_str("---");
+            push_block_break(out);
+        }
+        "br" => out.push('\n'),
+        "var" => out.push_str(&format!("${}$", text().trim())),
+        "code" => out.push_str(&format!("`{}`", text().trim())),
+        "strong" | "b" => out.push_str(&format!("**{}**", render_inline(element))),
+        "em" | "i" => out.push_str(&format!("*{}*", render_inline(element))),
+        "img" => out.push_str(&format!(
+            "![{}]({})",
+            value.attr("alt").unwrap_or(""),
+            value.attr("src").unwrap_or("")
+        )),
+        "a" => match value.attr("href") {
+            Some(href) => out.push_str(&format!("[{}]({})", render_inline(element), href)),
+            None => render_children(element, out),
+        },
+        "script" | "style" => {}
+        _ => render_children(element, out),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    const TASK_HTML: &str = r#"
+<div id="task-statement">
+  <span class="lang">
+    <span class="lang-ja"><div class="part"><section><h3>問題文</h3><p>日本語</p></section></div></span>
+    <span class="lang-en">
+      <div class="part"><section>
+        <h3>Problem Statement</h3>
+        <p>You are given an integer <var>N</var>. Print <var>2 \times N</var>.</p>
+        <p><img src="/img/other/abc388_a.png" alt="figure"></p>
+      </section></div>
+      <div class="part"><section>
+        <h3>Constraints</h3>
+        <ul><li><var>1 \leq N \leq 100</var></li><li>All values in input are integers.</li></ul>
+      </section></div>
+      <div class="part"><section>
+        <h3>Sample Input 1 <span class="btn btn-default btn-sm btn-copy">Copy</span></h3>
+        <pre id="pre-sample0">3
+</pre>
+      </section></div>
+    </span>
+  </span>
+</div>
+"#;
+
+    #[test]
+    fn test_extract_statement() {
+        let statement =
+            extract_statement(&Html::parse_document(TASK_HTML), "https://atcoder.jp/").unwrap();
+        assert!(statement.contains("Problem Statement"));
+        assert!(!statement.contains("日本語"));
+        assert!(statement.contains("src=\"https://atcoder.jp/img/other/abc388_a.png\""));
+        assert!(extract_statement(&Html::parse_document("<div></div>"), "").is_none());
+    }
+
+    #[test]
+    fn test_html_to_markdown() {
+        let statement =
+            extract_statement(&Html::parse_document(TASK_HTML), "https://atcoder.jp").unwrap();
+        assert_eq!(
+            html_to_markdown(&statement),
+            "### Problem Statement\n\n\
+             You are given an integer $N$. Print $2 \\times N$.\n\n\
+             ![figure](https://atcoder.jp/img/other/abc388_a.png)\n\n\
+             ### Constraints\n\n\
+             - $1 \\leq N \\leq 100$\n\
+             - All values in input are integers.\n\n\
+             ### Sample Input 1\n\n\
+             ```\n3\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_write_readme() {
+        let problem_dir = tempfile::tempdir().expect("");
+        let statement =
+            extract_statement(&Html::parse_document(TASK_HTML), "https://atcoder.jp").unwrap();
+        let problem = ProblemInfo {
+            problem_name: "a".to_string(),
+            timeout: 2000,
+            memory_limit: Some(1024),
+            score: Some(100),
+            samples: vec![],
+            statement: Some(statement),
+            task_screen_name: Some("abc388_a".to_string()),
+            title: Some("A - ?UPC".to_string()),
+        };
+        write_readme(
+            problem_dir.path(),
+            &problem,
+            "https://atcoder.jp/contests/abc388/tasks/abc388_a",
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(problem_dir.path().join("README.md")).unwrap(),
+            "# A - ?UPC\n\n\
+             - URL: https://atcoder.jp/contests/abc388/tasks/abc388_a\n\
+             - Time Limit: 2 sec\n\
+             - Memory Limit: 1024 MB\n\
+             - Score: 100\n\n\
+             ## Constraints\n\n\
+             - $1 \\leq N \\leq 100$\n\
+             - All values in input are integers.\n"
+        );
+        assert_eq!(extract_constraints("<p>Print N.</p>"), None);
+    }
+
+    #[test]
+    fn test_write_statement() {
+        let problem_dir = tempfile::tempdir().expect("");
+        write_statement(problem_dir.path(), "<p>Print <var>N</var>.</p>").unwrap();
+        let html = fs::read_to_string(problem_dir.path().join("problem.html")).unwrap();
+        assert!(html.contains("<meta charset=\"utf-8\">"));
+        assert_eq!(
+            fs::read_to_string(problem_dir.path().join("problem.md")).unwrap(),
+            "Print $N$.\n"
+        );
+    }
+}
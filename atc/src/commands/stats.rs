@@ -0,0 +1,499 @@
+//! 問題ごとの取り組み状況を記録し、統計を表示するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - 取り組み記録の追記(`record_event`、`record_accepted`)
+//! - 記録の読み込みと保存(`StatsDatabase`)
+//! - 統計の集計(`summarize`)
+//!
+//! 記録は設定ディレクトリの `stats.json` に保存され、各コマンドから以下のタイミングで追記される。
+//! - `download` / `add`: 問題のダウンロード時 (`downloaded`)
+//! - `test`: ローカルでのテスト実行時 (`tested`)
+//! - `submit`: 提出時 (`submitted`)
+//! - `status`: 提出が AC と判定されたことを確認した時 (`accepted`、同じ提出は 1 度だけ記録する)
+//!
+//! また、`archive` でアーカイブしたコンテストの最終的な状況(`ArchivedContest`)と、
+//! `perf` / `archive` で推定したパフォーマンスとレート変動(`PerformanceRecord`)も保存する。
+//!
+//! 記録の失敗は各コマンドの処理を妨げないよう、警告の表示のみとする。
+use chrono::{Local, NaiveDate, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    error::Error,
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::config::get_stats_file;
+use super::performance::format_rating_change;
+
+/// 統計表示処理のエントリーポイント
+pub fn execute() -> Result<(), Box<dyn Error>> {
+    let database = StatsDatabase::load(&get_stats_file())?;
+    if database.events.is_empty() && database.performances.is_empty() {
+        println!("No records yet");
+        return Ok(());
+    }
+    print_performances(&database.performances);
+    if database.events.is_empty() {
+        return Ok(());
+    }
+    let summary = summarize(&database.events, today());
+
+    println!("=== Solve Statistics ===");
+    for (contest, solved) in &summary.solves_per_contest {
+        println!("{:<12} {} / {} solved", contest, solved.0, solved.1);
+    }
+    println!();
+    println!("Total solved   : {}", summary.total_solved);
+    println!("Current streak : {} day(s)", summary.current_streak);
+    println!("Longest streak : {} day(s)", summary.longest_streak);
+    match summary.average_time_to_ac {
+        Some(secs) => println!("Avg time to AC : {}", format_duration(secs)),
+        None => println!("Avg time to AC : -"),
+    }
+    Ok(())
+}
+
+/// 取り組み記録の種別
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    /// 問題をダウンロードした
+    Downloaded,
+    /// ローカルでテストを実行した
+    Tested,
+    /// 提出した
+    Submitted,
+    /// 提出が AC と判定された
+    Accepted,
+}
+
+/// 1 件の取り組み記録
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProblemEvent {
+    pub contest: String, // コンテスト名 (`abc388` など)
+    pub problem: String, // 問題名 (`a` など)
+    pub kind: EventKind,
+    pub timestamp: u64, // 記録時刻(UNIX時間)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission_id: Option<String>, // AC と判定された提出の ID (`accepted` の場合)
+}
+
+/// アーカイブしたコンテストの最終的な状況
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArchivedContest {
+    pub contest: String,       // コンテスト名
+    pub problems: Vec<String>, // コンテストディレクトリに存在した問題
+    pub solved: Vec<String>,   // AC した問題
+    pub timestamp: u64,        // アーカイブした時刻(UNIX時間)
+}
+
+/// コンテストのパフォーマンスとレート変動の推定結果
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PerformanceRecord {
+    pub contest: String,         // コンテスト名
+    pub rank: u32,               // 順位
+    pub performance: i64,        // 推定パフォーマンス
+    pub old_rating: Option<i64>, // コンテスト前のレート (初参加の場合は `None`)
+    pub new_rating: i64,         // 推定レート
+}
+
+/// 取り組み記録のデータベース
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct StatsDatabase {
+    pub events: Vec<ProblemEvent>,
+    #[serde(default)]
+    pub archived: Vec<ArchivedContest>,
+    #[serde(default)]
+    pub performances: Vec<PerformanceRecord>,
+}
+
+impl StatsDatabase {
+    /// 記録をロードする。ファイルが存在しない場合は空の記録を返す。
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(StatsDatabase::default());
+        }
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    /// 記録を保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 取り組み記録を追記する
+///
+/// 記録に失敗した場合は警告を表示し、呼び出し元の処理は継続する。
+///
+/// # 引数
+/// - `contest`: コンテスト名
+/// - `problem`: 問題名
+/// - `kind`: 記録の種別
+pub fn record_event(contest: &str, problem: &str, kind: EventKind) {
+    if let Err(e) = append_event(&get_stats_file(), contest, problem, kind, None) {
+        eprintln!("Warning: 取り組み記録の保存に失敗しました: {}", e);
+    }
+}
+
+/// 提出が AC と判定されたことを記録する
+///
+/// `status` を実行するたびに同じ提出を記録しないよう、提出 ID ごとに 1 度だけ記録する。
+/// 記録に失敗した場合は警告を表示し、呼び出し元の処理は継続する。
+///
+/// # 引数
+/// - `contest`: コンテスト名
+/// - `problem`: 問題名
+/// - `submission_id`: AC と判定された提出の ID
+pub fn record_accepted(contest: &str, problem: &str, submission_id: &str) {
+    if let Err(e) = append_event(
+        &get_stats_file(),
+        contest,
+        problem,
+        EventKind::Accepted,
+        Some(submission_id),
+    ) {
+        eprintln!("Warning: 取り組み記録の保存に失敗しました: {}", e);
+    }
+}
+
+/// コンテストのアーカイブ時の状況を記録する
+///
+/// AC した問題は、取り組み記録のうち `accepted` の記録がある問題とする。
+///
+/// # 引数
+/// - `path`: 記録ファイルのパス
+/// - `contest`: コンテスト名
+/// - `problems`: コンテストディレクトリに存在した問題
+///
+/// # 戻り値
+/// 記録したアーカイブ時の状況
+pub fn record_archive(
+    path: &Path,
+    contest: &str,
+    problems: &[String],
+) -> Result<ArchivedContest, Box<dyn Error>> {
+    let mut database = StatsDatabase::load(path)?;
+    let solved: BTreeSet<String> = database
+        .events
+        .iter()
+        .filter(|event| event.contest == contest && event.kind == EventKind::Accepted)
+        .map(|event| event.problem.clone())
+        .collect();
+    let archived = ArchivedContest {
+        contest: contest.to_string(),
+        problems: problems.to_vec(),
+        solved: solved.into_iter().collect(),
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    };
+    database.archived.push(archived.clone());
+    database.save(path)?;
+    Ok(archived)
+}
+
+/// パフォーマンスとレート変動の推定結果を記録する
+///
+/// 同じコンテストの推定結果が既に存在する場合は置き換える。
+///
+/// # 引数
+/// - `path`: 記録ファイルのパス
+/// - `record`: 推定結果
+pub fn record_performance(path: &Path, record: PerformanceRecord) -> Result<(), Box<dyn Error>> {
+    let mut database = StatsDatabase::load(path)?;
+    database
+        .performances
+        .retain(|performance| performance.contest != record.contest);
+    database.performances.push(record);
+    database.save(path)
+}
+
+///
+/// `submission_id` を指定した場合、同じ種別・提出 ID の記録が既に存在すれば追記しない。
+fn append_event(
+    path: &Path,
+    contest: &str,
+    problem: &str,
+    kind: EventKind,
+    submission_id: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let mut database = StatsDatabase::load(path)?;
+    if submission_id.is_some()
+        && database
+            .events
+            .iter()
+            .any(|event| event.kind == kind && event.submission_id.as_deref() == submission_id)
+    {
+        return Ok(());
+    }
+    database.events.push(ProblemEvent {
+        contest: contest.to_string(),
+        problem: problem.to_lowercase(),
+        kind,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        submission_id: submission_id.map(str::to_string),
+    });
+    database.save(path)
+}
+
+/// 統計の集計結果
+#[derive(Debug, PartialEq)]
+pub struct StatsSummary {
+    pub solves_per_contest: BTreeMap<String, (usize, usize)>, // コンテストごとの (AC 数, 記録のある問題数)
+    pub total_solved: usize,                                  // AC した問題の総数
+    pub current_streak: usize, // 今日(または昨日)まで連続して AC した日数
+    pub longest_streak: usize, // 連続して AC した最長の日数
+    pub average_time_to_ac: Option<u64>, // ダウンロードから初 AC までの平均時間(秒)
+}
+
+/// 取り組み記録から統計を集計する
+///
+/// # 引数
+/// - `events`: 取り組み記録
+/// - `today`: 連続記録の判定に利用する今日の日付
+pub fn summarize(events: &[ProblemEvent], today: NaiveDate) -> StatsSummary {
+    let mut problems: BTreeMap<String, BTreeSet<&str>> = BTreeMap::new();
+    let mut first_download: HashMap<(&str, &str), u64> = HashMap::new();
+    let mut first_ac: HashMap<(&str, &str), u64> = HashMap::new();
+    for event in events {
+        let key = (event.contest.as_str(), event.problem.as_str());
+        problems
+            .entry(event.contest.clone())
+            .or_default()
+            .insert(&event.problem);
+        let first = match event.kind {
+            EventKind::Downloaded => &mut first_download,
+            EventKind::Accepted => &mut first_ac,
+            _ => continue,
+        };
+        let timestamp = first.entry(key).or_insert(event.timestamp);
+        *timestamp = (*timestamp).min(event.timestamp);
+    }
+
+    let solves_per_contest = problems
+        .iter()
+        .map(|(contest, names)| {
+            let solved = names
+                .iter()
+                .filter(|name| first_ac.contains_key(&(contest.as_str(), **name)))
+                .count();
+            (contest.clone(), (solved, names.len()))
+        })
+        .collect();
+
+    let times: Vec<u64> = first_ac
+        .iter()
+        .filter_map(|(key, ac)| first_download.get(key).map(|dl| ac.saturating_sub(*dl)))
+        .collect();
+    let average_time_to_ac = if times.is_empty() {
+        None
+    } else {
+        Some(times.iter().sum::<u64>() / times.len() as u64)
+    };
+
+    let ac_days: BTreeSet<NaiveDate> = events
+        .iter()
+        .filter(|event| event.kind == EventKind::Accepted)
+        .filter_map(|event| local_date(event.timestamp))
+        .collect();
+    let (current_streak, longest_streak) = streaks(&ac_days, today);
+
+    StatsSummary {
+        solves_per_contest,
+        total_solved: first_ac.len(),
+        current_streak,
+        longest_streak,
+        average_time_to_ac,
+    }
+}
+
+/// 記録したパフォーマンスとレート変動を表示する
+fn print_performances(performances: &[PerformanceRecord]) {
+    if performances.is_empty() {
+        return;
+    }
+    println!("=== Performance Estimates ===");
+    for record in performances {
+        println!(
+            "{:<12} rank {:>5}  perf {:>4}  rating {}",
+            record.contest,
+            record.rank,
+            record.performance,
+            format_rating_change(record.old_rating, record.new_rating)
+        );
+    }
+    println!();
+}
+
+/// AC した日付の集合から (現在の連続日数, 最長の連続日数) を求める
+///
+/// 今日まだ AC していない場合も、昨日まで連続していれば現在の連続記録として扱う。
+fn streaks(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> (usize, usize) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        run = match previous {
+            Some(prev) if prev.succ_opt() == Some(*day) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+    let current = match previous {
+        Some(last) if last == today || last.succ_opt() == Some(today) => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+fn local_date(timestamp: u64) -> Option<NaiveDate> {
+    Local
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|time| time.date_naive())
+}
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// 秒数を `1d 2h 3m` 形式に変換する
+fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    fn event(contest: &str, problem: &str, kind: EventKind, timestamp: u64) -> ProblemEvent {
+        ProblemEvent {
+            contest: contest.to_string(),
+            problem: problem.to_string(),
+            kind,
+            timestamp,
+            submission_id: None,
+        }
+    }
+
+    #[test]
+    fn test_append_event() {
+        let config_dir = tempfile::tempdir().expect("");
+        let path = config_dir.path().join("stats.json");
+        append_event(&path, "abc388", "A", EventKind::Downloaded, None).unwrap();
+        append_event(&path, "abc388", "a", EventKind::Accepted, None).unwrap();
+
+        let database = StatsDatabase::load(&path).unwrap();
+        assert_eq!(database.events.len(), 2);
+        assert_eq!(database.events[0].problem, "a");
+        assert_eq!(database.events[1].kind, EventKind::Accepted);
+    }
+
+    #[test]
+    fn test_append_event_once_per_submission() {
+        let config_dir = tempfile::tempdir().expect("");
+        let path = config_dir.path().join("stats.json");
+        // 同じ提出の AC は何度確認しても 1 度だけ記録する
+        for _ in 0..3 {
+            append_event(&path, "abc388", "a", EventKind::Accepted, Some("1001")).unwrap();
+        }
+        append_event(&path, "abc388", "a", EventKind::Accepted, Some("1002")).unwrap();
+
+        let database = StatsDatabase::load(&path).unwrap();
+        assert_eq!(database.events.len(), 2);
+        assert_eq!(database.events[0].submission_id.as_deref(), Some("1001"));
+        assert_eq!(database.events[1].submission_id.as_deref(), Some("1002"));
+    }
+
+    #[test]
+    fn test_record_archive() {
+        let config_dir = tempfile::tempdir().expect("");
+        let path = config_dir.path().join("stats.json");
+        append_event(&path, "abc388", "b", EventKind::Accepted, None).unwrap();
+        append_event(&path, "abc388", "a", EventKind::Accepted, None).unwrap();
+        append_event(&path, "abc388", "c", EventKind::Submitted, None).unwrap();
+        append_event(&path, "abc387", "d", EventKind::Accepted, None).unwrap();
+
+        let problems = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let archived = record_archive(&path, "abc388", &problems).unwrap();
+        assert_eq!(archived.solved, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(StatsDatabase::load(&path).unwrap().archived, vec![archived]);
+    }
+
+    #[test]
+    fn test_record_performance() {
+        let config_dir = tempfile::tempdir().expect("");
+        let path = config_dir.path().join("stats.json");
+        let record = |contest: &str, performance: i64| PerformanceRecord {
+            contest: contest.to_string(),
+            rank: 100,
+            performance,
+            old_rating: Some(1200),
+            new_rating: 1200 + (performance - 1200) / 4,
+        };
+        record_performance(&path, record("abc387", 1000)).unwrap();
+        record_performance(&path, record("abc388", 1400)).unwrap();
+        record_performance(&path, record("abc387", 1600)).unwrap();
+
+        let database = StatsDatabase::load(&path).unwrap();
+        assert_eq!(
+            database.performances,
+            vec![record("abc388", 1400), record("abc387", 1600)]
+        );
+    }
+
+    #[test]
+    fn test_summarize() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+        let at = |d: u32, hour: u32| {
+            Local
+                .from_local_datetime(&day(d).and_hms_opt(hour, 0, 0).unwrap())
+                .unwrap()
+                .timestamp() as u64
+        };
+        let events = vec![
+            event("abc387", "a", EventKind::Downloaded, at(1, 10)),
+            event("abc387", "a", EventKind::Accepted, at(1, 11)),
+            event("abc387", "b", EventKind::Downloaded, at(1, 10)),
+            event("abc387", "b", EventKind::Submitted, at(1, 12)),
+            event("abc388", "a", EventKind::Downloaded, at(3, 10)),
+            event("abc388", "a", EventKind::Accepted, at(3, 13)),
+            event("abc388", "a", EventKind::Accepted, at(4, 9)),
+            event("abc388", "b", EventKind::Accepted, at(5, 9)),
+        ];
+        let summary = summarize(&events, day(6));
+        assert_eq!(summary.solves_per_contest["abc387"], (1, 2));
+        assert_eq!(summary.solves_per_contest["abc388"], (2, 2));
+        assert_eq!(summary.total_solved, 3);
+        assert_eq!(summary.current_streak, 3);
+        assert_eq!(summary.longest_streak, 3);
+        // (1 時間 + 3 時間) / 2
+        assert_eq!(summary.average_time_to_ac, Some(7200));
+
+        let summary = summarize(&events, day(8));
+        assert_eq!(summary.current_streak, 0);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(59), "0m");
+        assert_eq!(format_duration(3 * 3600 + 120), "3h 2m");
+        assert_eq!(format_duration(90000), "1d 1h 0m");
+    }
+}
@@ -0,0 +1,161 @@
+//! 最新の提出の判定結果を取得するモジュール
+//!
+//! 提出一覧ページ(`submissions/me`)から最新の提出を取得し、判定結果を表示する。
+//! `watch` を指定した場合はジャッジが完了するまでポーリングする。
+//!
+//! 提出一覧ページを参照するため、CLI とブラウザのどちらから提出した場合でも判定結果を取得できる。
+//! 最新の提出が AC の場合は、取り組み記録(`stats`)に AC を記録する(同じ提出は 1 度だけ記録する)。
+//! 記録する問題名は `contest.json` の問題 ID から解決し、見つからない場合は提出一覧の問題名から取り出す。
+//!
+//! AHC の提出のジャッジが完了している場合は、得点をプレテストのスコアとしてスコア履歴(`score`)に記録する。
+//! ケース数は提出詳細ページのテストケースの表から取得する。
+use reqwest::Client;
+use scraper::Html;
+use std::{error::Error, path::Path, time::Duration};
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+use super::metadata::load_contest_metadata;
+use super::output::{is_json, print_json};
+use super::result::parse_submission_detail;
+use super::score::{is_submission_recorded, record_submission_score};
+use super::stats::record_accepted;
+use super::submissions::{fetch_submissions, Submission};
+use super::submit::get_contest_name;
+use super::template::contest_type;
+
+/// ジャッジ完了を待つ際のポーリング間隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 判定結果取得処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `watch`: `true` の場合、ジャッジが完了するまでポーリングする
+pub async fn execute(work_dir: &Path, watch: bool) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+    let base_url = get_base_url();
+    let contest_name = get_contest_name(work_dir)?;
+    let metadata = load_contest_metadata(work_dir)?;
+
+    loop {
+        let result = fetch_submissions(&base_url, &client, &session, &contest_name, 1).await?;
+        let latest = result
+            .submissions
+            .first()
+            .ok_or_else(|| format!("{} への提出が見つかりません", contest_name))?;
+        if is_json() {
+            print_json(latest)?;
+        } else {
+            print_submission(latest);
+        }
+        let problem_name = metadata
+            .as_ref()
+            .zip(latest.task_screen_name.as_deref())
+            .and_then(|(metadata, task_screen_name)| metadata.problem_name_of(task_screen_name))
+            .map(str::to_string)
+            .or_else(|| problem_name_of(&latest.task));
+        if let Some(problem_name) = &problem_name {
+            if latest.status == "AC" {
+                record_accepted(&contest_name, problem_name, &latest.id);
+            }
+            if contest_type(&contest_name) == "ahc" && !latest.is_judging() {
+                let url = format!(
+                    "{}/contests/{}/submissions/{}",
+                    base_url, contest_name, latest.id
+                );
+                if let Err(e) =
+                    record_pretest_score(work_dir, &client, &session, &url, problem_name, latest)
+                        .await
+                {
+                    eprintln!("Warning: スコア履歴の保存に失敗しました: {}", e);
+                }
+            }
+        }
+        if !watch || !latest.is_judging() {
+            return Ok(());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// 提出の判定結果を表示する
+fn print_submission(submission: &Submission) {
+    println!(
+        "[{}] {} #{}",
+        submission.submitted_at, submission.task, submission.id
+    );
+    println!(
+        "  {} | score: {} | time: {} | memory: {}",
+        submission.status,
+        submission.score,
+        submission.exec_time.as_deref().unwrap_or("-"),
+        submission.memory.as_deref().unwrap_or("-")
+    );
+}
+
+/// ジャッジが完了した提出の得点をプレテストのスコアとして記録する
+///
+/// 既に記録した提出の場合は提出詳細ページを取得しない。
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 認証に利用するセッション情報
+/// - `detail_url`: 提出詳細ページの URL
+/// - `problem_name`: 問題名
+/// - `submission`: ジャッジが完了した提出
+async fn record_pretest_score(
+    work_dir: &Path,
+    client: &Client,
+    session: &Session,
+    detail_url: &str,
+    problem_name: &str,
+    submission: &Submission,
+) -> Result<(), Box<dyn Error>> {
+    if is_submission_recorded(work_dir, &submission.id)? {
+        return Ok(());
+    }
+    let total: i64 = submission
+        .score
+        .parse()
+        .map_err(|_| format!("得点 '{}' を解釈できません", submission.score))?;
+    let html = fetch_with_session(client, detail_url, session).await?;
+    let cases = parse_submission_detail(&Html::parse_document(&html))
+        .map(|detail| detail.test_cases.len())
+        .unwrap_or_default();
+    if let Some(record) =
+        record_submission_score(work_dir, problem_name, &submission.id, total, cases)?
+    {
+        if !is_json() {
+            println!(
+                "Recorded submission score for {}: total = {}, cases = {}",
+                record.problem_name, record.total, record.cases
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 提出一覧の問題名 (`A - Happy New Year` など) から問題名 (`a`) を取り出す
+fn problem_name_of(task: &str) -> Option<String> {
+    task.split_once(" - ")
+        .map(|(name, _)| name.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_problem_name_of() {
+        assert_eq!(problem_name_of("A - Happy New Year"), Some("a".to_string()));
+        assert_eq!(problem_name_of("Ex - Foo - Bar"), Some("ex".to_string()));
+        assert_eq!(problem_name_of("unknown"), None);
+    }
+}
@@ -0,0 +1,368 @@
+//! 愚直解とのランダム比較(ストレステスト)を行うモジュール
+//!
+//! `gen.rs`(または `gen.toml`)で生成した入力を `main.rs` と `naive.rs`(愚直解)の両方に与え、出力が一致するかを繰り返し検証する。
+//! 出力が一致しない、または `main.rs` が異常終了した場合はその時点で終了し、
+//! 失敗した入力と愚直解の出力を `tests/random_{i}.in` / `tests/random_{i}.out` として保存する。
+//! 保存したテストケースは `test` でそのまま再現できる。
+//!
+//! 不一致が見つかった場合は、出力が一致しないまま配列の長さや値を小さくした入力を探し(`shrink` を参照)、
+//! 縮小した入力を保存する。`validator.rs` が存在する場合は、制約を満たす入力にのみ縮小する。
+//!
+//! 入力はすべてシード値から生成する(`case_seeds`)。開始時に最初のシード値を、不一致の場合はその入力のシード値と
+//! 再現用のコマンドを表示するため、`stress a --seed 12345` で解答を修正した後に同じ入力から検証をやり直せる。
+//!
+//! ```text
+//! .
+//! ├── Cargo.toml
+//! └── problem_name
+//!     ├── main.rs
+//!     ├── gen.rs     # 入力の生成プログラム(gen.toml がない場合は必須)
+//!     ├── gen.toml   # 入力の宣言的な記述(gen.rs がない場合に利用)
+//!     ├── naive.rs   # 愚直解(必須)
+//!     ├── validator.rs  # 入力の検証プログラム(任意、縮小した入力の検証に利用)
+//!     └── tests
+//!         ├── random_1.in   # 失敗した入力
+//!         └── random_1.out  # 愚直解の出力
+//! ```
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::gen::{
+    case_seeds, default_seed, load_generator, naive_bin_name, next_random_index, prepare_generator,
+    run_solution, Generator, NAIVE_FILE,
+};
+use super::shrink::minimize;
+use super::test::{compile, find_problem_directory, get_execution_path};
+use super::validator::{ensure_problem_bin, ensure_validator_bin, validator_bin_name};
+
+/// 反例の縮小で入力を試す最大回数
+const MAX_SHRINK_ATTEMPTS: usize = 300;
+
+/// 1 回の比較で見つかった不一致
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub seed: u64,                      // 入力を生成したシード値
+    pub input: String,                  // 生成された入力
+    pub expected: String,               // 愚直解の出力
+    pub actual: Result<String, String>, // `main.rs` の出力 (異常終了した場合はエラーメッセージ)
+}
+
+/// ストレステスト処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `count`: 比較を行う最大回数
+/// - `seed`: 最初の比較のシード値 (`None` の場合は現在時刻から決定する)
+///
+/// # 処理の流れ
+/// 1. `gen.rs`(存在しない場合は `gen.toml` を読み込む)と `naive.rs` のバイナリ定義を `Cargo.toml` に追加してコンパイルする
+/// 2. シード値を 1 ずつ増やしながら入力を生成し、`main.rs` と `naive.rs` の出力を比較する
+/// 3. 不一致が見つかった場合は入力を縮小し、縮小した入力と愚直解の出力を `tests/` に保存して終了する
+///
+/// # エラーの可能性
+/// - `gen.rs` と `gen.toml` のどちらも存在しない、または `naive.rs` が存在しない場合
+/// - コンパイル、生成プログラム・愚直解の実行に失敗した場合
+/// - 出力の不一致が見つかった場合
+pub fn execute(
+    work_dir: &Path,
+    problem_name: &str,
+    count: usize,
+    seed: Option<u64>,
+) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let spec = prepare_generator(work_dir, problem_name, &problem_dir)?;
+    let naive_bin = naive_bin_name(problem_name);
+    if !ensure_problem_bin(work_dir, problem_name, NAIVE_FILE, &naive_bin)? {
+        return Err(format!("{}/{} が見つかりません", problem_name, NAIVE_FILE).into());
+    }
+    let has_validator = ensure_validator_bin(work_dir, problem_name)?;
+    compile(&problem_dir)?;
+
+    let solution = get_execution_path(work_dir, problem_name)?;
+    let generator = load_generator(work_dir, problem_name, spec)?;
+    let naive = get_execution_path(work_dir, &naive_bin)?;
+    let validator = if has_validator {
+        Some(get_execution_path(
+            work_dir,
+            &validator_bin_name(problem_name),
+        )?)
+    } else {
+        None
+    };
+
+    let first_seed = seed.unwrap_or_else(default_seed);
+    println!("Running {} random tests from seed {}", count, first_seed);
+    match find_mismatch(&solution, &generator, &naive, first_seed, count)? {
+        None => {
+            println!("All {} random tests passed", count);
+            Ok(())
+        }
+        Some(mismatch) => {
+            println!("Mismatch found (seed = {})", mismatch.seed);
+            let original_size = mismatch.input.len();
+            let (mismatch, attempts) =
+                minimize_mismatch(&solution, &naive, validator.as_deref(), mismatch);
+            if mismatch.input.len() < original_size {
+                println!(
+                    "Minimized input from {} to {} bytes ({} attempts)",
+                    original_size,
+                    mismatch.input.len(),
+                    attempts
+                );
+            }
+            let input_path = save_mismatch(&problem_dir.join("tests"), &mismatch)?;
+            println!("Input:\n{}", mismatch.input);
+            println!("Expected (naive):\n{}", mismatch.expected);
+            match &mismatch.actual {
+                Ok(actual) => println!("Actual:\n{}", actual),
+                Err(e) => println!("Actual: {}", e),
+            }
+            println!("Saved to {}", input_path.display());
+            println!(
+                "Replay with: cargo atc stress {} --seed {}",
+                problem_name, mismatch.seed
+            );
+            Err("愚直解と出力が一致しない入力が見つかりました".into())
+        }
+    }
+}
+
+/// 入力を生成して `main.rs` と愚直解の出力を比較し、最初の不一致を返す
+///
+/// # 引数
+/// - `solution`: `main.rs` の実行ファイル
+/// - `generator`: 入力の生成方法
+/// - `naive`: 愚直解の実行ファイル
+/// - `first_seed`: 最初の比較のシード値
+/// - `count`: 比較を行う最大回数
+///
+/// # 戻り値
+/// 不一致が見つかった場合は `Some(Mismatch)`、`count` 回すべて一致した場合は `None`
+pub fn find_mismatch(
+    solution: &Path,
+    generator: &Generator,
+    naive: &Path,
+    first_seed: u64,
+    count: usize,
+) -> Result<Option<Mismatch>, Box<dyn Error>> {
+    for seed in case_seeds(first_seed, count) {
+        let input = generator.generate(seed)?;
+        if let Some(mismatch) = compare(solution, naive, seed, input)? {
+            return Ok(Some(mismatch));
+        }
+    }
+    Ok(None)
+}
+
+/// 1 つの入力で `main.rs` と愚直解の出力を比較し、一致しない場合は `Some(Mismatch)` を返す
+///
+/// # エラーの可能性
+/// - 愚直解の実行に失敗した場合
+fn compare(
+    solution: &Path,
+    naive: &Path,
+    seed: u64,
+    input: String,
+) -> Result<Option<Mismatch>, Box<dyn Error>> {
+    let expected = run_solution(naive, &input)?;
+    let actual = run_solution(solution, &input).map_err(|e| e.to_string());
+    let matched = actual
+        .as_ref()
+        .is_ok_and(|actual| actual.trim() == expected.trim());
+    if matched {
+        return Ok(None);
+    }
+    Ok(Some(Mismatch {
+        seed,
+        input,
+        expected,
+        actual,
+    }))
+}
+
+/// 出力が一致しないまま入力を縮小し、縮小した入力での不一致と入力を試した回数を返す
+///
+/// 愚直解が異常終了する入力や、`validator` の検証に失敗する入力には縮小しない。
+///
+/// # 引数
+/// - `solution`: `main.rs` の実行ファイル
+/// - `naive`: 愚直解の実行ファイル
+/// - `validator`: 検証プログラムの実行ファイル (存在しない場合は `None`)
+/// - `mismatch`: 縮小する不一致
+pub fn minimize_mismatch(
+    solution: &Path,
+    naive: &Path,
+    validator: Option<&Path>,
+    mismatch: Mismatch,
+) -> (Mismatch, usize) {
+    let seed = mismatch.seed;
+    let mut minimized = None;
+    let result = minimize(&mismatch.input, MAX_SHRINK_ATTEMPTS, |input| {
+        if validator.is_some_and(|validator| run_solution(validator, input).is_err()) {
+            return false;
+        }
+        match compare(solution, naive, seed, input.to_string()) {
+            Ok(Some(found)) => {
+                minimized = Some(found);
+                true
+            }
+            _ => false,
+        }
+    });
+    // 縮小に成功した場合、最後に不一致となった候補が縮小後の入力となる
+    match minimized {
+        Some(found) if found.input == result.input => (found, result.attempts),
+        _ => (mismatch, result.attempts),
+    }
+}
+
+/// 不一致となった入力と愚直解の出力をテストケースとして保存し、入力ファイルのパスを返す
+fn save_mismatch(tests_dir: &Path, mismatch: &Mismatch) -> Result<PathBuf, Box<dyn Error>> {
+    fs::create_dir_all(tests_dir)?;
+    let input_path = tests_dir.join(format!("random_{}.in", next_random_index(tests_dir)?));
+    fs::write(&input_path, &mismatch.input)?;
+    fs::write(input_path.with_extension("out"), &mismatch.expected)?;
+    Ok(input_path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::gen::GENERATOR_FILE;
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_execute() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "stress_test"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+"#,
+        )
+        .unwrap();
+        let problem_dir = work_dir.path().join("a");
+        fs::create_dir_all(&problem_dir).unwrap();
+        // 入力が 7 の場合のみ誤った答えを返す
+        fs::write(
+            problem_dir.join("main.rs"),
+            r#"
+use std::io::Read;
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let n: u64 = input.trim().parse().unwrap();
+    println!("{}", if n == 7 { 0 } else { n * 2 });
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            problem_dir.join(GENERATOR_FILE),
+            r#"
+fn main() {
+    let seed: u64 = std::env::args().nth(1).unwrap().parse().unwrap();
+    println!("{}", seed % 10);
+}
+"#,
+        )
+        .unwrap();
+        fs::write(
+            problem_dir.join(NAIVE_FILE),
+            r#"
+use std::io::Read;
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let n: u64 = input.trim().parse().unwrap();
+    println!("{}", n * 2);
+}
+"#,
+        )
+        .unwrap();
+
+        let work_dir_path = work_dir.path().to_path_buf();
+        // シード値 0..5 では不一致は見つからない
+        assert!(execute(&work_dir_path, "a", 5, Some(0)).is_ok());
+        assert!(!problem_dir.join("tests").exists());
+
+        assert!(execute(&work_dir_path, "a", 10, Some(0)).is_err());
+        let tests_dir = problem_dir.join("tests");
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("random_1.in")).unwrap(),
+            "7\n"
+        );
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("random_1.out")).unwrap(),
+            "14\n"
+        );
+    }
+
+    #[test]
+    fn test_execute_minimizes_mismatch() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "stress_shrink_test"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+"#,
+        )
+        .unwrap();
+        let problem_dir = work_dir.path().join("a");
+        fs::create_dir_all(&problem_dir).unwrap();
+        fs::write(
+            problem_dir.join("gen.toml"),
+            "n = \"int 20..=20\"\na = \"array n int 1..=1000\"\n",
+        )
+        .unwrap();
+        // 500 を超える値を無視して最大値を求める
+        let solution = r#"
+use std::io::Read;
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let a: Vec<u64> = input.split_whitespace().skip(1).map(|v| v.parse().unwrap()).collect();
+    println!("{}", a.iter().filter(|&&v| LIMIT).max().unwrap_or(&0));
+}
+"#;
+        fs::write(
+            problem_dir.join("main.rs"),
+            solution.replace("LIMIT", "v <= 500"),
+        )
+        .unwrap();
+        fs::write(
+            problem_dir.join(NAIVE_FILE),
+            solution.replace("LIMIT", "true"),
+        )
+        .unwrap();
+
+        assert!(execute(work_dir.path(), "a", 10, Some(0)).is_err());
+        let input = fs::read_to_string(problem_dir.join("tests/random_1.in")).unwrap();
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines[0], "1");
+        let value: u64 = lines[1].parse().unwrap();
+        assert!(value > 500);
+        assert_eq!(
+            fs::read_to_string(problem_dir.join("tests/random_1.out")).unwrap(),
+            format!("{}\n", value)
+        );
+    }
+}
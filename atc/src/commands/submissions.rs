@@ -0,0 +1,304 @@
+//! 自分の提出一覧を取得・表示するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - 提出一覧ページ(`submissions/me`)の取得(`fetch_submissions`)
+//! - 提出一覧ページの HTML の解析(`parse_submissions`, `parse_last_page`)
+//! - 提出一覧の表形式での表示(`execute`)
+//!
+//! 提出一覧ページは 1 ページあたり 20 件で表示されるため、`page` でページを指定して取得する。
+use reqwest::Client;
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use std::{error::Error, path::Path};
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::execute as login_execute;
+use super::login::{fetch_with_session, Session};
+use super::output::{is_json, print_json};
+use super::submit::get_contest_name;
+
+/// 提出一覧表示処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `page`: 表示するページ番号 (1 始まり)
+pub async fn execute(work_dir: &Path, page: u32) -> Result<(), Box<dyn Error>> {
+    login_execute().await?;
+    let session =
+        Session::load(&get_session_file())?.ok_or("セッション情報を取得できませんでした")?;
+    let client = client()?;
+    let contest_name = get_contest_name(work_dir)?;
+
+    let result = fetch_submissions(&get_base_url(), &client, &session, &contest_name, page).await?;
+    if is_json() {
+        return print_json(&SubmissionsOutput {
+            contest: &contest_name,
+            page,
+            last_page: result.last_page,
+            submissions: &result.submissions,
+        });
+    }
+    if result.submissions.is_empty() {
+        println!("{} への提出が見つかりません", contest_name);
+        return Ok(());
+    }
+    println!(
+        "{:<24} {:<32} {:>6} {:>6} {:>8} {:>10}",
+        "Time", "Task", "Status", "Score", "Exec", "Memory"
+    );
+    for submission in &result.submissions {
+        println!(
+            "{:<24} {:<32} {:>6} {:>6} {:>8} {:>10}",
+            submission.submitted_at,
+            truncate(&submission.task, 32),
+            submission.status,
+            submission.score,
+            submission.exec_time.as_deref().unwrap_or("-"),
+            submission.memory.as_deref().unwrap_or("-")
+        );
+    }
+    println!("--- page {}/{} ---", page, result.last_page);
+    Ok(())
+}
+
+/// `--json` を指定した場合に出力する提出一覧
+#[derive(Serialize)]
+struct SubmissionsOutput<'a> {
+    contest: &'a str,
+    page: u32,
+    last_page: u32,
+    submissions: &'a [Submission],
+}
+
+/// 提出一覧の各提出
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Submission {
+    pub id: String,                       // 提出 ID
+    pub submitted_at: String,             // 提出日時
+    pub task: String,                     // 問題名 (`A - Welcome to AtCoder` など)
+    pub task_screen_name: Option<String>, // 問題 ID (`abc388_a` など)
+    pub score: String,                    // 得点
+    pub status: String,                   // 判定結果 (`AC`, `WA`, `WJ`, `3/20` など)
+    pub exec_time: Option<String>,        // 実行時間 (ジャッジ中は `None`)
+    pub memory: Option<String>,           // メモリ使用量 (ジャッジ中は `None`)
+}
+
+impl Submission {
+    /// ジャッジ中かどうかを返す
+    pub fn is_judging(&self) -> bool {
+        is_judging_status(&self.status)
+    }
+}
+
+/// 判定結果がジャッジ中 (`WJ`, `WR`, `3/20` など) を表すかどうかを返す
+pub fn is_judging_status(status: &str) -> bool {
+    status == "WJ" || status == "WR" || status.contains('/') || status.contains("Judging")
+}
+
+/// 提出一覧の 1 ページ分の取得結果
+#[derive(Debug)]
+pub struct SubmissionPage {
+    pub submissions: Vec<Submission>, // 新しい順の提出の一覧
+    pub last_page: u32,               // 最終ページの番号
+}
+
+/// 提出一覧ページを取得し、提出の一覧を返す
+///
+/// # 引数
+/// - `base_url`: AtCoder のベース URL
+/// - `client`: `reqwest::Client` インスタンス
+/// - `session`: 認証に利用するセッション情報
+/// - `contest_name`: コンテスト名
+/// - `page`: 取得するページ番号 (1 始まり)
+///
+/// # 戻り値
+/// - `Ok(SubmissionPage)`: 指定したページの提出の一覧と最終ページの番号
+/// - `Err(Box<dyn Error>)`: ページの取得に失敗した場合
+pub async fn fetch_submissions(
+    base_url: &str,
+    client: &Client,
+    session: &Session,
+    contest_name: &str,
+    page: u32,
+) -> Result<SubmissionPage, Box<dyn Error>> {
+    let url = format!(
+        "{}/contests/{}/submissions/me?page={}",
+        base_url, contest_name, page
+    );
+    let html = fetch_with_session(client, &url, session).await?;
+    let document = Html::parse_document(&html);
+    Ok(SubmissionPage {
+        submissions: parse_submissions(&document),
+        last_page: parse_last_page(&document),
+    })
+}
+
+/// 提出一覧ページの HTML から提出の一覧を抽出する
+///
+/// ジャッジ中の提出は判定結果のセルが実行時間・メモリ使用量のセルと結合(`colspan="3"`)されるため、
+/// 判定結果以降のセルは存在する場合のみ取得する。
+pub fn parse_submissions(document: &Html) -> Vec<Submission> {
+    let row_selector = Selector::parse("table tbody tr").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+    let score_selector = Selector::parse("td.submission-score").unwrap();
+    let link_selector = Selector::parse("a").unwrap();
+
+    let text = |cell: &ElementRef| cell.text().collect::<String>().trim().to_string();
+    document
+        .select(&row_selector)
+        .filter_map(|row| {
+            let cells: Vec<ElementRef> = row.select(&cell_selector).collect();
+            if cells.len() < 7 {
+                return None;
+            }
+            let id = row
+                .select(&score_selector)
+                .next()
+                .and_then(|cell| cell.value().attr("data-id"))?
+                .to_string();
+            let judged = cells.len() >= 10;
+            Some(Submission {
+                id,
+                submitted_at: text(&cells[0]),
+                task: text(&cells[1]),
+                task_screen_name: cells[1]
+                    .select(&link_selector)
+                    .next()
+                    .and_then(|link| link.value().attr("href"))
+                    .and_then(|href| href.rsplit_once("/tasks/"))
+                    .map(|(_, task_screen_name)| task_screen_name.to_string()),
+                score: text(&cells[4]),
+                status: text(&cells[6]),
+                exec_time: judged.then(|| text(&cells[7])),
+                memory: judged.then(|| text(&cells[8])),
+            })
+        })
+        .collect()
+}
+
+/// 提出一覧ページのページネーションから最終ページの番号を取得する
+///
+/// ページネーションが存在しない場合は 1 を返す。
+pub fn parse_last_page(document: &Html) -> u32 {
+    let page_selector = Selector::parse("ul.pagination li a").unwrap();
+    document
+        .select(&page_selector)
+        .filter_map(|link| link.text().collect::<String>().trim().parse::<u32>().ok())
+        .max()
+        .unwrap_or(1)
+}
+
+/// 表示幅に収まるように文字列を切り詰める
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(width - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::Server;
+
+    const SUBMISSIONS_HTML: &str = r##"
+<table class="table table-bordered table-striped small th-center">
+  <thead><tr><th>Submission Time</th><th>Task</th><th>User</th><th>Language</th><th>Score</th><th>Code Size</th><th>Status</th><th>Exec Time</th><th>Memory</th><th></th></tr></thead>
+  <tbody>
+    <tr>
+      <td class="no-break"><time class="fixtime fixtime-second">2025-01-11 21:10:00+0900</time></td>
+      <td><a href="/contests/abc388/tasks/abc388_b">B - Heavy Snake</a></td>
+      <td><a href="/users/mock_user">mock_user</a></td>
+      <td><a href="#">Rust (rustc 1.70.0)</a></td>
+      <td class="text-right submission-score" data-id="61500002">0</td>
+      <td class="text-right">512 Byte</td>
+      <td class="text-center waiting-judge" colspan="3" data-id="61500002"><span class="label label-default">3/20</span></td>
+      <td class="text-center"><a href="/contests/abc388/submissions/61500002">Detail</a></td>
+    </tr>
+    <tr>
+      <td class="no-break"><time class="fixtime fixtime-second">2025-01-11 21:05:00+0900</time></td>
+      <td><a href="/contests/abc388/tasks/abc388_a">A - ?UPC</a></td>
+      <td><a href="/users/mock_user">mock_user</a></td>
+      <td><a href="#">Rust (rustc 1.70.0)</a></td>
+      <td class="text-right submission-score" data-id="61500001">100</td>
+      <td class="text-right">256 Byte</td>
+      <td class="text-center"><span class="label label-success" title="Accepted">AC</span></td>
+      <td class="text-right">1 ms</td>
+      <td class="text-right">2004 KB</td>
+      <td class="text-center"><a href="/contests/abc388/submissions/61500001">Detail</a></td>
+    </tr>
+  </tbody>
+</table>
+<ul class="pagination pagination-sm mt-0 mb-1">
+  <li class="active"><a href="/contests/abc388/submissions/me?page=1">1</a></li>
+  <li><a href="/contests/abc388/submissions/me?page=2">2</a></li>
+  <li><a href="/contests/abc388/submissions/me?page=3">3</a></li>
+</ul>
+"##;
+
+    #[test]
+    fn test_parse_submissions() {
+        let submissions = parse_submissions(&Html::parse_document(SUBMISSIONS_HTML));
+        assert_eq!(submissions.len(), 2);
+
+        assert_eq!(submissions[0].id, "61500002");
+        assert_eq!(submissions[0].status, "3/20");
+        assert_eq!(submissions[0].exec_time, None);
+        assert!(submissions[0].is_judging());
+
+        assert_eq!(
+            submissions[1],
+            Submission {
+                id: "61500001".to_string(),
+                submitted_at: "2025-01-11 21:05:00+0900".to_string(),
+                task: "A - ?UPC".to_string(),
+                task_screen_name: Some("abc388_a".to_string()),
+                score: "100".to_string(),
+                status: "AC".to_string(),
+                exec_time: Some("1 ms".to_string()),
+                memory: Some("2004 KB".to_string()),
+            }
+        );
+        assert!(!submissions[1].is_judging());
+    }
+
+    #[test]
+    fn test_parse_last_page() {
+        assert_eq!(parse_last_page(&Html::parse_document(SUBMISSIONS_HTML)), 3);
+        assert_eq!(parse_last_page(&Html::parse_document("<table></table>")), 1);
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("A - ?UPC", 32), "A - ?UPC");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_submissions() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/contests/abc388/submissions/me?page=2")
+            .match_header("Cookie", "REVEL_SESSION=mock_session_cookie;")
+            .with_status(200)
+            .with_body(SUBMISSIONS_HTML)
+            .create_async()
+            .await;
+        let session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+
+        let result = fetch_submissions(&server.url(), &Client::new(), &session, "abc388", 2)
+            .await
+            .unwrap();
+        assert_eq!(result.submissions.len(), 2);
+        assert_eq!(result.last_page, 3);
+    }
+}
@@ -26,51 +26,103 @@
 //! ## 提出フロー
 //! 1. `execute` を実行すると、まず `login_execute()` により AtCoder へのログインを試行。
 //! 2. `get_contest_info` により `Cargo.toml` を解析し、コンテスト名と提出対象の `main.rs` のパスを取得。
-//! 3. `read_source_code` により、`main.rs` のコードを取得。
-//! 4. `submit_code` を実行し、AtCoder API にコードを提出。
-//! 5. 提出が成功すると、提出結果の URL を出力する。
+//! 3. `prepare_submission` により、フォーマット設定に従って `main.rs` を整形・検査。
+//! 4. `read_source_code` により `main.rs` のコードを取得し、`expand_source` によりローカルのモジュール・ライブラリを展開。
+//! 5. `submit_code` を実行し、AtCoder API にコードを提出。
+//! 6. 提出が成功すると、提出結果の URL を出力する。
 //!
 //! ## 注意事項
 //! - `Cargo.toml` 内に `[bin]` セクションがない場合、エラーを返す。
 //! - `submit_code` の実行時、AtCoder の CSRF トークンおよびクッキーが必要。
 //! - `submit_code` のリクエストが `302 Found` を返さない場合、提出は失敗と見なされる。
-//! - の提出言語 ID (`LanguageId`) は Rustの `5054` に固定されている。
+//! - 提出言語 ID (`LanguageId`) は `migrate-env` で検出した値を利用する。未設定の場合は Rust の `5054` を利用する。
 
 use reqwest::{Client, StatusCode};
-use std::{error::Error, fs, path::PathBuf};
+use serde::Serialize;
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
 use toml::Value;
 
-use super::config::{get_session_file, BASE_URL};
+use super::config::{get_base_url, get_languages_file, get_session_file};
+use super::expand::expand_source;
+use super::format::prepare_submission;
+use super::http::{client, throttle};
+use super::lang::validate_language_id;
 use super::login::execute as login_execute;
 use super::login::Session;
+use super::metadata::{load_contest_metadata, task_screen_name};
+use super::migrate::get_language_id;
+use super::output::{is_json, print_json};
+use super::stats::{record_event, EventKind};
+use super::timer::print_remaining;
 
-pub async fn execute(work_dir: &PathBuf, problem_name: &str) -> Result<(), Box<dyn Error>> {
+pub async fn execute(work_dir: &Path, problem_name: &str) -> Result<(), Box<dyn Error>> {
     login_execute().await?;
     let session_path = get_session_file();
     let session = Session::load(&session_path)?.ok_or("セッション情報を取得できませんでした")?;
 
-    let client = Client::new();
+    let client = client()?;
     // Cargo.toml から contest_name と提出対象のソースコードパスを取得
     let (contest_name, source_path) = get_contest_info(work_dir, problem_name)?;
 
-    // ソースコードの読み込み
+    // フォーマット設定に従ってソースコードを整形・検査
+    prepare_submission(work_dir, Path::new(&source_path))?;
+
+    // ソースコードの読み込みと、ローカルのモジュール・ライブラリの展開
     let source_code = read_source_code(&PathBuf::from(&source_path))?;
+    let source_code = expand_source(work_dir, Path::new(&source_path), &source_code)?;
+
+    // 提出に利用する言語IDをキャッシュした言語一覧で検証
+    let language_id = get_language_id()?;
+    validate_language_id(&get_languages_file(), &language_id)?;
 
     // `SubmissionData` を作成
     let submission = SubmissionData {
+        task_screen_name: task_screen_name(work_dir, &contest_name, problem_name),
         contest_name,
         problem_name: problem_name.to_string(),
         source_code,
+        language_id,
     };
-    let _submission_url = submit_code(BASE_URL, &client, &session, &submission).await?;
+    let _submission_url = submit_code(&get_base_url(), &client, &session, &submission).await?;
+    record_event(
+        &submission.contest_name,
+        &submission.problem_name,
+        EventKind::Submitted,
+    );
+    if is_json() {
+        return print_json(&SubmitOutput {
+            contest: &submission.contest_name,
+            problem: &submission.problem_name,
+            submissions_url: format!(
+                "{}/contests/{}/submissions/me",
+                get_base_url(),
+                submission.contest_name
+            ),
+        });
+    }
     //println!("提出成功！結果URL: {}", submission_url);
+    print_remaining(work_dir);
     Ok(())
 }
 
+/// `--json` を指定した場合に出力する提出結果
+#[derive(Serialize)]
+struct SubmitOutput<'a> {
+    contest: &'a str,
+    problem: &'a str,
+    submissions_url: String, // 自分の提出一覧の URL
+}
+
 pub struct SubmissionData {
     pub contest_name: String,
     pub problem_name: String,
+    pub task_screen_name: String, // 問題 ID (`contest.json` の `task_screen_name`)
     pub source_code: String,
+    pub language_id: String,
 }
 
 /// `Cargo.toml` からコンテスト名と提出対象のソースコードのパスを取得する
@@ -94,8 +146,8 @@ pub struct SubmissionData {
 /// - `[package.name]` が `Cargo.toml` に定義されていない場合。
 /// - `[bin]` セクションが `Cargo.toml` に存在しない場合。
 /// - 指定された `problem_name` に対応する `[[bin]]` エントリが見つからない場合。
-fn get_contest_info(
-    work_dir: &PathBuf,
+pub fn get_contest_info(
+    work_dir: &Path,
     problem_name: &str,
 ) -> Result<(String, String), Box<dyn Error>> {
     let cargo_toml_path = work_dir.join("Cargo.toml");
@@ -134,6 +186,32 @@ fn get_contest_info(
     Ok((contest_name, problem_path))
 }
 
+/// コンテスト名を取得する
+///
+/// `contest.json` が存在する場合はそのコンテスト名を、存在しない場合は `Cargo.toml` の `package.name` を返す。
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス。
+///
+/// # エラーの可能性
+/// - `contest.json` の読み込みに失敗した場合。
+/// - `Cargo.toml` が存在しない、または読み込みに失敗した場合。
+/// - `[package.name]` が `Cargo.toml` に定義されていない場合。
+pub fn get_contest_name(work_dir: &Path) -> Result<String, Box<dyn Error>> {
+    if let Some(metadata) = load_contest_metadata(work_dir)? {
+        if !metadata.contest.is_empty() {
+            return Ok(metadata.contest);
+        }
+    }
+    let value: Value = toml::from_str(&fs::read_to_string(work_dir.join("Cargo.toml"))?)?;
+    Ok(value
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+        .ok_or("Cargo.toml にコンテスト名 (package.name) が見つかりません")?
+        .to_string())
+}
+
 /// 指定されたソースコードファイルを読み込む
 ///
 /// # 引数
@@ -165,7 +243,7 @@ fn read_source_code(source_path: &PathBuf) -> Result<String, Box<dyn Error>> {
 /// - `base_url`: AtCoder のベース URL (`https://atcoder.jp`)。
 /// - `client`: `reqwest::Client` インスタンス (HTTP リクエストを送信するため)。
 /// - `session`: `Session` 構造体 (CSRF トークンとセッション情報を保持)。
-/// - `submission`: `SubmissionData` 構造体 (コンテスト名、問題名、ソースコードを含む)。
+/// - `submission`: `SubmissionData` 構造体 (コンテスト名、問題名、ソースコード、言語IDを含む)。
 ///
 /// # 戻り値
 /// - `Ok(String)`: 提出結果ページの URL。
@@ -193,25 +271,18 @@ async fn submit_code(
     let submit_url = format!("{}/contests/{}/submit", base_url, submission.contest_name);
     let params = [
         ("csrf_token", &session.csrf_token),
-        (
-            "data.TaskScreenName",
-            &format!("{}_{}", submission.contest_name, submission.problem_name),
-        ),
-        ("data.LanguageId", &"5054".to_string()), // Rustの言語ID
+        ("data.TaskScreenName", &submission.task_screen_name),
+        ("data.LanguageId", &submission.language_id),
         ("sourceCode", &submission.source_code),
     ];
 
-    let cookie_header = format!(
-        "REVEL_SESSION={};",
-        session.session_cookie.trim_start_matches("REVEL_SESSION=")
-    );
-
+    throttle(&submit_url).await;
     let response = client
         .post(&submit_url)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .header("Referer", format!("{}/contests/{}/submit", base_url, submission.contest_name))
         .header("User-Agent", "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .header("Cookie", cookie_header) // ✅ 修正点: 適切な `Cookie` を送信
+        .header("Cookie", session.cookie_header())
         .form(&params)
         .send()
         .await?;
@@ -255,7 +326,7 @@ path = "{}/main.rs"
         );
 
         fs::write(&cargo_toml_path, &cargo_toml_content).expect("Cargo.toml の書き込みに失敗");
-        let result = get_contest_info(&work_dir.path().to_path_buf(), problem_name_1);
+        let result = get_contest_info(work_dir.path(), problem_name_1);
         assert!(result.is_ok());
         let (get_contest_name, get_problem_path) = result.unwrap();
         assert_eq!(get_contest_name, contest_name);
@@ -267,7 +338,7 @@ path = "{}/main.rs"
                 .to_string_lossy(),
         );
 
-        let result = get_contest_info(&work_dir.path().to_path_buf(), problem_name_2);
+        let result = get_contest_info(work_dir.path(), problem_name_2);
         assert!(result.is_ok());
         let (get_contest_name, get_problem_path) = result.unwrap();
         assert_eq!(get_contest_name, contest_name);
@@ -297,7 +368,7 @@ path = "test_problem/main.rs"
 
         fs::write(&cargo_toml_path, cargo_toml_content).expect("Failed to write Cargo.toml");
 
-        let result = get_contest_info(&work_dir.path().to_path_buf(), "test_problem");
+        let result = get_contest_info(work_dir.path(), "test_problem");
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -319,7 +390,7 @@ edition = "2021"
 
         fs::write(&cargo_toml_path, cargo_toml_content).expect("Failed to write Cargo.toml");
 
-        let result = get_contest_info(&work_dir.path().to_path_buf(), "test_problem");
+        let result = get_contest_info(work_dir.path(), "test_problem");
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -345,7 +416,7 @@ path = "different_problem/main.rs"
 
         fs::write(&cargo_toml_path, cargo_toml_content).expect("Failed to write Cargo.toml");
 
-        let result = get_contest_info(&work_dir.path().to_path_buf(), "test_problem");
+        let result = get_contest_info(work_dir.path(), "test_problem");
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
@@ -375,7 +446,7 @@ name = "invalid_syntax"
         fs::write(&cargo_toml_path, invalid_cargo_toml_content)
             .expect("Failed to write Cargo.toml");
 
-        let result = get_contest_info(&work_dir.path().to_path_buf(), "test_problem");
+        let result = get_contest_info(work_dir.path(), "test_problem");
         assert!(result.is_err());
     }
 
@@ -464,7 +535,9 @@ fn main() {
         let submission = SubmissionData {
             contest_name: contest_name.to_string(),
             problem_name: problem_name.to_string(),
+            task_screen_name: format!("{}_{}", contest_name, problem_name),
             source_code: source_code.to_string(),
+            language_id: "5054".to_string(),
         };
         let result = submit_code(&base_url.to_string(), &client, &session, &submission).await;
         _mock.assert();
@@ -0,0 +1,310 @@
+//! テンプレートのプロファイルを管理するモジュール
+//!
+//! テンプレートディレクトリ直下のファイルを `default` プロファイルとし、
+//! サブディレクトリをそれぞれ名前付きのプロファイルとして扱う。
+//! `main.rs` を含むサブディレクトリのみをプロファイルとみなす。
+//!
+//! ```text
+//! templates
+//! ├── Cargo.toml       # default
+//! ├── main.rs          # default
+//! ├── rustfmt.toml     # default (任意)
+//! └── graph            # プロファイル `graph`
+//!     ├── Cargo.toml
+//!     ├── main.rs
+//!     └── rustfmt.toml # 任意
+//! ```
+//!
+//! - `template list`: プロファイルの一覧を表示し、利用中のプロファイルに `*` を付ける
+//! - `template save`: コンテストディレクトリの問題の `main.rs` と依存クレートから新しいプロファイルを作成する
+//! - `template use`: 既定のプロファイルを切り替える (設定ファイルの `template` に保存する)
+//!
+//! `download`・`add` でプロファイルを指定せず、設定ファイルの `template` も未設定の場合は、
+//! コンテストの種類(コンテスト名の英字部分。`ahc040` なら `ahc`)と同名のプロファイルがあればそれを利用する。
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+use super::config::{get_template_dir, get_user_config_file, load_user_config, UserConfig};
+use super::test::find_problem_directory;
+
+/// テンプレートディレクトリ直下のファイルを表すプロファイル名
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// プロファイル名に対応するテンプレートディレクトリを返す
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ (テンプレートディレクトリの基準)
+/// - `profile`: プロファイル名。`None` の場合は設定ファイルの `template` (未設定の場合は `default`)
+///
+/// # エラーの可能性
+/// - 指定したプロファイルが存在しない (`main.rs` を含まない) 場合
+pub fn resolve_template_dir(
+    work_dir: &Path,
+    profile: Option<&str>,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let profile = match profile {
+        Some(profile) => profile.to_string(),
+        None => active_profile(),
+    };
+    profile_dir(&get_template_dir(work_dir), &profile)
+}
+
+/// コンテストに応じたテンプレートディレクトリを返す
+///
+/// `profile` を指定せず、設定ファイルの `template` も未設定の場合は、
+/// コンテストの種類と同名のプロファイル(`ahc040` なら `ahc`)が存在すればそのディレクトリを返す。
+/// それ以外の場合は `resolve_template_dir` と同じ。
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ (テンプレートディレクトリの基準)
+/// - `profile`: プロファイル名 (`download --template` で指定したもの)
+/// - `contest_name`: コンテスト名 (`abc388`, `ahc040` など)
+pub fn resolve_contest_template_dir(
+    work_dir: &Path,
+    profile: Option<&str>,
+    contest_name: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if profile.is_none() && load_user_config().template.is_none() {
+        let template_root = get_template_dir(work_dir);
+        let contest_type = contest_type(contest_name);
+        if !contest_type.is_empty() && template_root.join(contest_type).join("main.rs").is_file() {
+            return Ok(template_root.join(contest_type));
+        }
+    }
+    resolve_template_dir(work_dir, profile)
+}
+
+/// コンテスト名からコンテストの種類(先頭の英字部分)を返す (`ahc040` → `ahc`)
+pub fn contest_type(contest_name: &str) -> &str {
+    let end = contest_name
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(contest_name.len());
+    &contest_name[..end]
+}
+
+/// 利用中のプロファイル名を返す
+fn active_profile() -> String {
+    load_user_config()
+        .template
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// テンプレートディレクトリとプロファイル名からプロファイルのディレクトリを返す
+fn profile_dir(template_root: &Path, profile: &str) -> Result<PathBuf, Box<dyn Error>> {
+    if profile == DEFAULT_PROFILE {
+        return Ok(template_root.to_path_buf());
+    }
+    let dir = template_root.join(profile);
+    if !dir.join("main.rs").is_file() {
+        return Err(format!(
+            "テンプレートのプロファイル {} が見つかりません。`cargo atc template list` で確認してください",
+            profile
+        )
+        .into());
+    }
+    Ok(dir)
+}
+
+/// テンプレートディレクトリに存在するプロファイル名の一覧を返す (`default` を先頭に名前順)
+pub fn list_profiles(template_root: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut profiles = Vec::new();
+    if template_root.is_dir() {
+        for entry in fs::read_dir(template_root)? {
+            let entry = entry?;
+            if entry.path().join("main.rs").is_file() {
+                profiles.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+    }
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(profiles)
+}
+
+/// `template list` のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ (テンプレートディレクトリの基準)
+pub fn execute_list(work_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let template_root = get_template_dir(work_dir);
+    let active = active_profile();
+    for profile in list_profiles(&template_root)? {
+        let mark = if profile == active { "*" } else { " " };
+        println!("{} {}", mark, profile);
+    }
+    println!("\nTemplate directory: {}", template_root.display());
+    Ok(())
+}
+
+/// `template save` のエントリーポイント
+///
+/// コンテストディレクトリの問題の `main.rs`、`Cargo.toml` の `[dependencies]`、
+/// `rustfmt.toml` (存在する場合) を新しいプロファイルとして保存する。
+/// テンプレートディレクトリは `download` と同様に、コンテストディレクトリの親ディレクトリを基準に探索する。
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリ
+/// - `name`: 作成するプロファイル名
+/// - `problem_name`: `main.rs` をコピーする問題名
+///
+/// # エラーの可能性
+/// - プロファイル名が `default` または既に存在する場合
+/// - 問題ディレクトリ、`main.rs`、`Cargo.toml` が存在しない場合
+pub fn execute_save(work_dir: &Path, name: &str, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let root_dir = work_dir
+        .parent()
+        .ok_or("コンテストディレクトリの親ディレクトリを取得できません")?;
+    let profile_dir = save_profile(work_dir, &get_template_dir(root_dir), name, problem_name)?;
+    println!(
+        "Template profile saved: {} ({})",
+        name,
+        profile_dir.display()
+    );
+    Ok(())
+}
+
+/// コンテストディレクトリのファイルからプロファイルを作成し、作成したディレクトリを返す
+fn save_profile(
+    work_dir: &Path,
+    template_root: &Path,
+    name: &str,
+    problem_name: &str,
+) -> Result<PathBuf, Box<dyn Error>> {
+    if name == DEFAULT_PROFILE || name.is_empty() || name.contains(['/', '\\']) || name == ".." {
+        return Err(format!("プロファイル名 {} は利用できません", name).into());
+    }
+    let profile_dir = template_root.join(name);
+    if profile_dir.exists() {
+        return Err(format!("プロファイル {} は既に存在します", name).into());
+    }
+    let main_rs = find_problem_directory(work_dir, problem_name)?.join("main.rs");
+    if !main_rs.is_file() {
+        return Err(format!("{} が存在しません", main_rs.display()).into());
+    }
+    let dependencies = extract_dependencies(&fs::read_to_string(work_dir.join("Cargo.toml"))?)?;
+
+    fs::create_dir_all(&profile_dir)?;
+    fs::copy(&main_rs, profile_dir.join("main.rs"))?;
+    fs::write(profile_dir.join("Cargo.toml"), dependencies)?;
+    let rustfmt_toml = work_dir.join("rustfmt.toml");
+    if rustfmt_toml.is_file() {
+        fs::copy(&rustfmt_toml, profile_dir.join("rustfmt.toml"))?;
+    }
+    Ok(profile_dir)
+}
+
+/// `Cargo.toml` の `[dependencies]` のみを取り出し、テンプレートの `Cargo.toml` の形式で返す
+fn extract_dependencies(cargo_toml: &str) -> Result<String, Box<dyn Error>> {
+    let parsed: Value = toml::from_str(cargo_toml)?;
+    let mut table = toml::map::Map::new();
+    table.insert(
+        "dependencies".to_string(),
+        parsed
+            .get("dependencies")
+            .cloned()
+            .unwrap_or_else(|| Value::Table(toml::map::Map::new())),
+    );
+    Ok(toml::to_string(&Value::Table(table))?)
+}
+
+/// `template use` のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: 作業ディレクトリ (テンプレートディレクトリの基準)
+/// - `name`: 既定にするプロファイル名
+///
+/// # エラーの可能性
+/// - 指定したプロファイルが存在しない場合
+pub fn execute_use(work_dir: &Path, name: &str) -> Result<(), Box<dyn Error>> {
+    profile_dir(&get_template_dir(work_dir), name)?;
+    let path = get_user_config_file();
+    let mut config = UserConfig::load(&path)?;
+    config.set("template", name)?;
+    config.save(&path)?;
+    println!("Default template profile: {}", name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_profile_dir() {
+        let root = tempfile::tempdir().expect("");
+        let template_root = root.path().join("templates");
+        fs::create_dir_all(template_root.join("graph")).unwrap();
+        fs::create_dir_all(template_root.join("empty")).unwrap();
+        fs::write(template_root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(template_root.join("graph/main.rs"), "fn main() {}").unwrap();
+
+        assert_eq!(
+            profile_dir(&template_root, DEFAULT_PROFILE).unwrap(),
+            template_root
+        );
+        assert_eq!(
+            profile_dir(&template_root, "graph").unwrap(),
+            template_root.join("graph")
+        );
+        // main.rs を含まないディレクトリはプロファイルとみなさない
+        assert!(profile_dir(&template_root, "empty").is_err());
+        assert!(profile_dir(&template_root, "missing").is_err());
+
+        assert_eq!(
+            list_profiles(&template_root).unwrap(),
+            vec!["default".to_string(), "graph".to_string()]
+        );
+        assert_eq!(
+            list_profiles(&root.path().join("missing")).unwrap(),
+            vec!["default".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_contest_type() {
+        assert_eq!(contest_type("abc388"), "abc");
+        assert_eq!(contest_type("ahc040"), "ahc");
+        assert_eq!(contest_type("practice"), "practice");
+        assert_eq!(contest_type("2025"), "");
+    }
+
+    #[test]
+    fn test_save_profile() {
+        let root = tempfile::tempdir().expect("");
+        let template_root = root.path().join("templates");
+        let work_dir = root.path().join("abc388");
+        fs::create_dir_all(work_dir.join("a")).unwrap();
+        fs::write(
+            work_dir.join("a/main.rs"),
+            "use proconio::input;\nfn main() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            work_dir.join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\n\n[dependencies]\nproconio = \"0.4.5\"\n\n[[bin]]\nname = \"a\"\npath = \"a/main.rs\"\n",
+        )
+        .unwrap();
+
+        let profile_dir = save_profile(&work_dir, &template_root, "mine", "a").unwrap();
+        assert_eq!(
+            fs::read_to_string(profile_dir.join("main.rs")).unwrap(),
+            "use proconio::input;\nfn main() {}\n"
+        );
+        let cargo_toml = fs::read_to_string(profile_dir.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("[dependencies]"));
+        assert!(cargo_toml.contains("proconio = \"0.4.5\""));
+        assert!(!cargo_toml.contains("[[bin]]"));
+        assert!(!profile_dir.join("rustfmt.toml").exists());
+
+        // 既存のプロファイル・default は上書きしない
+        assert!(save_profile(&work_dir, &template_root, "mine", "a").is_err());
+        assert!(save_profile(&work_dir, &template_root, DEFAULT_PROFILE, "a").is_err());
+        assert!(save_profile(&work_dir, &template_root, "other", "z").is_err());
+    }
+}
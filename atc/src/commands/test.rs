@@ -4,90 +4,890 @@
 //! - テストケースの収集(`collect_test_cases`)
 //! - テスト対象資源のコンパイル(`compile`)
 //! - テスト対象バイナリファイルのパス取得(`get_execution_path`)
-//! - テストケースごとの実行結果の取得(`return_results`)
+//! - テストケースごとの実行結果の取得(`run_test_cases`)
+//! - `validator.rs` による入力ファイルの検証(`validator::validate_inputs`)
+//!
+//! `--release` を指定した場合(`set_release`)は、`cargo build --release` でコンパイルし、
+//! `target/release/` の実行ファイルで実行時間を計測する。`run`・`gen`・`stress` も同じ設定に従う。
+//!
+//! 実行ファイルのパスは `cargo build --message-format=json` が出力する
+//! `compiler-artifact` メッセージから取得する(`cargo_build`)。そのため、`CARGO_TARGET_DIR` や
+//! `build.target-dir`、ワークスペースで `target/` を共有している場合も実際に生成されたファイルを実行する。
+//!
+//! コンパイルエラーの場合は、rustc の診断メッセージからエラーのみを最大 `COMPILE_ERROR_DISPLAY_COUNT` 件表示し、
+//! テストケースを実行せずに結果を `CE` とする。
+//!
+//! `--case` を指定した場合は、指定したテストケースのみを実行する(`select_test_case`)。
+//! テストケース名は `sample_2` のほか、サンプルの番号のみ(`2`)でも指定できる。
+//!
+//! `tests/` 直下の `.in`・`.out` の組はすべて実行し、サンプル以外(`case add` で作成した
+//! `custom_1.in` など)は結果の一覧で `[custom]` と表示する。
+//!
+//! 問題ディレクトリに `judge.rs` がある場合はインタラクティブな問題として、
+//! 解答とインタラクタを接続して実行する(`interactive.rs`)。
+//!
+//! 解答の標準エラー出力はテストケースごとに取得し、入出力と合わせて表示する(`STDERR_DISPLAY_LINES` 行まで)。
+//! RE の結果には終了コード(Unix でシグナルにより終了した場合はシグナル名)と、`panic!` のメッセージを含める。
+//!
+//! AHC のコンテストでは、解答が標準エラー出力に出力した `Score = <スコア>` を集計してスコア履歴に記録する(`score.rs`)。
+//!
+//! `--fail-fast` を指定した場合は、AC 以外の結果が出た時点で残りのテストケースを実行せずに終了する。
+//! 既定ではすべてのテストケースを実行する。設定 `fail_fast` で既定を `--fail-fast` にでき、
+//! その場合も `--keep-going` を指定するとすべてのテストケースを実行する(`fail_fast_enabled`)。
+//!
+//! `--system` を指定した場合は、`testcases` で取得した公式のテストケース(`tests/system/`)をすべて実行する。
+//! テストケースごとに実行時間制限を適用し、実行中はプログレスバーで進捗と結果ごとの件数を表示する。
+//! 結果の一覧には AtCoder の提出結果と同じ形式の全体の結果(`Judge: WA (AC × 25, WA × 3), max 1234 ms`)を表示する。
+//!
+//! `--repeat <回数>` を指定した場合は、各テストケースを指定した回数実行し、実行時間の最小値・平均値・最大値・
+//! 95 パーセンタイルを表示する(`bench::BenchStats`)。テストケースの結果は最も優先度の高い結果(同じ結果の場合は最も遅い実行)とする。
+//!
+//! `--timeout <ミリ秒>` を指定した場合は、その実行限りで `contest.json`・`Cargo.toml` の実行時間制限の代わりに使う。
+//!
+//! 解答の出力は `compare.rs` の比較方法(`--compare`)で期待する出力と比較する。
+//!
+//! 入力ファイルは読み込まずに解答の標準入力へ接続し、解答の標準出力は期待する出力の 2 倍に `OUTPUT_LIMIT_MARGIN` を加えた
+//! サイズまでのみ保持する。上限を超えて出力した場合は WA とする。
+//! 表示する入出力は `OUTPUT_DISPLAY_LINES` 行・`OUTPUT_DISPLAY_BYTES` バイトまでとし、`--full-output` を指定した場合はすべて表示する。
+//!
+//! 出力量は `Verbosity` で切り替える。`--quiet` はテストケースごとの入出力を表示せず結果の一覧のみを、
+//! `--silent` は全体の結果(`AC`、`WA` など)のみを 1 行で表示する。
+//!
+//! `test` は結果に応じて以下の終了コードで終了する(`TestFailure::exit_code`)。
+//! AC 以外の結果が複数ある場合は、RE・TLE・WA の順に優先する。
+//! - `0`: すべてのテストケースが AC
+//! - `1`: 問題ディレクトリが存在しないなど、テストを実行できなかった場合
+//! - `2`: WA
+//! - `3`: TLE
+//! - `4`: RE
+//! - `5`: コンパイルエラー
 //!
 //! このモジュールで処理対象となるディレクトリ構造は以下となる:
 //! ```text
 //! .
 //! ├── Cargo.toml    # AtCoderに対応する依存関係を記録したファイル
 //! ├── Cargo.lock
+//! ├── contest.json  # 問題ごとの実行時間制限などを記録したファイル(`metadata.rs`)
 //! └── problem_name  # 入力として与える問題名
 //!     ├── main.rs   # 問題に回答するロジックを実装するファイル
+//!     ├── validator.rs  # 入力ファイルの検証プログラム(任意)
+//!     ├── judge.rs  # インタラクティブな問題のインタラクタ(任意)
 //!     └── tests     # AtCoderより取得したサンプル入出力を記録したディレクトリ
 //!         ├── sample_1.in
 //!         ├── sample_1.out    
 //!         ├── sample_2.in
-//!         └── sample_2.out
+//!         ├── sample_2.out
+//!         ├── custom_1.in   # ユーザが追加したテストケース(任意)
+//!         └── custom_1.out
 //! ```
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
+    env,
     error::Error,
     fmt::{Display, Formatter},
     fs,
-    io::Write,
+    io::{self, IsTerminal},
     path::{Path, PathBuf},
     process::{Command, Stdio},
-    time::Instant,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, PoisonError,
+    },
+    time::Duration,
 };
 use toml::Value;
 
+use super::bench::BenchStats;
+use super::build_cache::{load_fresh_executables, package_root, save_build, source_fingerprint};
+use super::case::is_custom_case;
+use super::compare::{first_mismatch, load_compare_mode, CompareMode, Judge};
+use super::config::{get_timeout_margin, get_timeout_multiplier};
+use super::history::{describe_regression, record_run, Regression};
+use super::interactive::{collect_interactive_cases, ensure_judge_bin, run_interactive_cases};
+use super::metadata::load_contest_metadata;
+use super::output::{is_json, print_json};
+use super::process::{describe_exit_status, run_with_timeout, Outcome};
+use super::remote::RemoteExit;
+use super::score::{record_test_score, ScoreRecord};
+use super::stats::{record_event, EventKind};
+use super::submit::get_contest_name;
+use super::template::contest_type;
+use super::testcases::SYSTEM_TESTS_DIR;
+use super::timer::print_remaining;
+use super::validator::{ensure_validator_bin, validate_inputs};
+use super::workspace::get_target_dir;
+
+/// `test` の実行オプション
+#[derive(Default, Clone, Copy)]
+pub struct TestOptions<'a> {
+    /// `true` の場合、サンプルの代わりに `testcases` で取得した公式のテストケース(`tests/system/`)を実行する。
+    /// テストケースが多いため、テストケースごとの入出力は表示しない
+    pub system: bool,
+    /// 実行するテストケース名(`sample_2`、`2` など)。`None` の場合はすべてのテストケースを実行する
+    pub case: Option<&'a str>,
+    /// 出力量
+    pub verbosity: Verbosity,
+    /// `true` の場合、AC 以外の結果が出た時点で残りのテストケースを実行しない
+    pub fail_fast: bool,
+    /// 出力の比較方法。`None` の場合は `Cargo.toml` の `[package.metadata.compare]` の設定(未設定の場合は `trim`)を使う
+    pub compare: Option<CompareMode>,
+    /// 実行時間制限(ミリ秒)。指定した場合は `contest.json`・`Cargo.toml` の設定と `timeout_margin` の代わりに使う
+    pub timeout: Option<u64>,
+    /// 各テストケースを実行する回数。2 以上の場合は実行時間の統計値を表示する(0 は 1 回とみなす)
+    pub repeat: usize,
+}
+
+/// `--fail-fast`・`--keep-going` と設定 `fail_fast` から、最初の失敗で打ち切るかを決定する
+///
+/// `--fail-fast`・`--keep-going` の指定を設定より優先する。
+pub fn fail_fast_enabled(fail_fast: bool, keep_going: bool, default: bool) -> bool {
+    fail_fast || (default && !keep_going)
+}
+
+/// `test` の出力量
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum Verbosity {
+    #[default]
+    Normal, // テストケースごとの入出力と結果の一覧を表示する
+    Quiet,  // 結果の一覧のみを表示する
+    Silent, // 全体の結果のみを表示する
+}
+
 /// 問題名を基にテストケースの収集、資源のコンパイル、テスト結果の検証を実行する
 ///
 /// # 引数
 ///
 /// * `problem_name` - 処理対象となる問題名
-pub fn execute(work_dir: &PathBuf, problem_name: &str) -> Result<(), Box<dyn Error>> {
+/// * `options` - 実行オプション
+pub fn execute(
+    work_dir: &PathBuf,
+    problem_name: &str,
+    options: &TestOptions,
+) -> Result<(), Box<dyn Error>> {
+    let system = options.system;
+    let verbose =
+        !is_json() && !system && options.repeat <= 1 && options.verbosity == Verbosity::Normal;
+    let results = match run_problem(work_dir, problem_name, options, verbose) {
+        Err(e) if matches!(e.downcast_ref::<TestFailure>(), Some(TestFailure::Compile)) => {
+            print_compile_error_summary(problem_name, options.verbosity)?;
+            return Err(e);
+        }
+        results => results?,
+    };
+    let regressions: Vec<String> = save_history(&work_dir.join(problem_name), &results, system)
+        .iter()
+        .map(describe_regression)
+        .collect();
+    let score = save_score(work_dir, problem_name, &results);
+
+    if is_json() {
+        print_json(&TestReport {
+            problem: problem_name,
+            status: overall_status(&results),
+            regressions: regressions.clone(),
+            passed: results
+                .iter()
+                .filter(|res| res.status == TestStatus::AC)
+                .count(),
+            total: results.len(),
+            release: is_release(),
+            results: &results,
+        })?;
+    } else if options.verbosity == Verbosity::Silent {
+        println!("{}", overall_status(&results));
+    } else {
+        if is_release() {
+            println!("\n=== Test Results Summary (release) ===");
+        } else {
+            println!("\n=== Test Results Summary ===");
+        }
+        for result in &results {
+            let label = if !system && is_custom_case(&result.test_case_name) {
+                " [custom]"
+            } else {
+                ""
+            };
+            println!(
+                "{}{}: Status = {:?}, Time = {} ms",
+                result.test_case_name, label, result.status, result.execution_time
+            );
+            if let Some(timing) = &result.timing {
+                println!("  {}", timing_line(timing, options.repeat));
+            }
+            if let Some(error) = &result.error_message {
+                println!("  Error: {}", error);
+            }
+        }
+        for regression in &regressions {
+            println!("{}", regression);
+        }
+        if system {
+            println!("{}", judge_line(&results));
+        }
+        if let Some(score) = &score {
+            println!(
+                "Score: total = {}, average = {:.2} ({} cases)",
+                score.total, score.average, score.cases
+            );
+        }
+        if options.timeout.is_none() {
+            if let Some(line) = time_limit_line(work_dir, problem_name) {
+                println!("{}", line);
+            }
+        }
+        println!("=============================\n");
+        print_remaining(work_dir);
+    }
+    if let Ok(contest_name) = get_contest_name(work_dir) {
+        record_event(&contest_name, problem_name, EventKind::Tested);
+    }
+
+    match overall_status(&results) {
+        TestStatus::AC => Ok(()),
+        status => Err(TestFailure::Verdict(status).into()),
+    }
+}
+
+/// ローカルで実行する際の実行時間制限 (ms) を返す
+///
+/// AtCoder の実行時間制限 `limit` に `timeout_multiplier` を掛け、`timeout_margin` を加算する。
+pub fn local_time_limit(limit: u64, multiplier: f64, margin: u64) -> u64 {
+    (limit as f64 * multiplier).round() as u64 + margin
+}
+
+/// 結果の一覧に表示する実行時間制限の行を返す
+///
+/// 倍率・猶予を設定している場合は、AtCoder の実行時間制限と合わせてローカルの制限を示す。
+/// 例: `Time limit: 2000 ms (local: 4000 ms)`
+fn time_limit_line(work_dir: &Path, problem_name: &str) -> Option<String> {
+    let limit = load_problem_timeout_settings(work_dir)
+        .ok()?
+        .get(problem_name)
+        .copied()?;
+    let local = local_time_limit(limit, get_timeout_multiplier(), get_timeout_margin());
+    if local == limit {
+        Some(format!("Time limit: {} ms", limit))
+    } else {
+        Some(format!("Time limit: {} ms (local: {} ms)", limit, local))
+    }
+}
+
+/// 実行結果を問題ディレクトリの履歴に記録し、前回の実行結果からのリグレッションを返す
+///
+/// 履歴の保存に失敗した場合は警告を表示し、テストの結果には影響させない。
+fn save_history(problem_dir: &Path, results: &[TestCaseResult], system: bool) -> Vec<Regression> {
+    record_run(problem_dir, results, system).unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to save the test history: {}", e);
+        Vec::new()
+    })
+}
+
+/// AHC のコンテストの場合、解答が標準エラー出力に出力したスコアをスコア履歴に記録する
+///
+/// 記録に失敗した場合は警告を表示し、テストの結果には影響させない。
+fn save_score(
+    work_dir: &Path,
+    problem_name: &str,
+    results: &[TestCaseResult],
+) -> Option<ScoreRecord> {
+    let contest_name = get_contest_name(work_dir).ok()?;
+    if contest_type(&contest_name) != "ahc" {
+        return None;
+    }
+    let stderrs = results.iter().filter_map(|result| result.stderr.as_deref());
+    record_test_score(work_dir, problem_name, stderrs).unwrap_or_else(|e| {
+        eprintln!("Warning: Failed to save the score history: {}", e);
+        None
+    })
+}
+
+/// コンパイルエラーでテストケースを実行しなかった場合の結果を表示する
+fn print_compile_error_summary(
+    problem_name: &str,
+    verbosity: Verbosity,
+) -> Result<(), Box<dyn Error>> {
+    if is_json() {
+        print_json(&TestReport {
+            problem: problem_name,
+            status: TestStatus::CE,
+            regressions: Vec::new(),
+            passed: 0,
+            total: 0,
+            release: is_release(),
+            results: &[],
+        })?;
+    } else if verbosity == Verbosity::Silent {
+        println!("{}", TestStatus::CE);
+    } else {
+        println!("\n=== Test Results Summary ===");
+        println!("Status = {}: test cases were not run", TestStatus::CE);
+        println!("=============================\n");
+    }
+    Ok(())
+}
+
+/// テストケースの収集、資源のコンパイル、テストケースの実行を行い、実行結果を返す
+///
+/// # 引数
+///
+/// * `problem_name` - 処理対象となる問題名
+/// * `options` - 実行オプション
+/// * `verbose` - `true` の場合、テストケースごとに入出力と実行結果の詳細を表示する
+fn run_problem(
+    work_dir: &PathBuf,
+    problem_name: &str,
+    options: &TestOptions,
+    verbose: bool,
+) -> Result<Vec<TestCaseResult>, Box<dyn Error>> {
+    let system = options.system;
     let problem_dir = find_problem_directory(&work_dir, problem_name)?;
+    let has_validator = !system && ensure_validator_bin(work_dir, problem_name)?;
+    let interactive = ensure_judge_bin(work_dir, problem_name)?;
     compile(&problem_dir)?;
-    let test_cases = collect_test_cases(&problem_dir)?;
-    let timeout_settings = load_problem_timeout_settings(&work_dir)?;
-    let results = return_results(&work_dir, test_cases, problem_name, &timeout_settings).unwrap();
-
-    println!("\n=== Test Results Summary ===");
-    for result in &results {
-        println!(
-            "{}: Status = {:?}, Time = {} ms",
-            result.test_case_name, result.status, result.execution_time
-        );
-        if let Some(error) = &result.error_message {
-            println!("  Error: {}", error);
+    let test_cases = if system {
+        let system_dir = problem_dir.join(SYSTEM_TESTS_DIR);
+        if !system_dir.is_dir() {
+            return Err(format!(
+                "公式のテストケースがありません。`cargo atc testcases {}` で取得してください",
+                problem_name
+            )
+            .into());
+        }
+        if interactive {
+            collect_interactive_cases(&system_dir)?
+        } else {
+            collect_test_cases_in(&system_dir)?
+        }
+    } else if interactive {
+        collect_interactive_cases(&problem_dir.join("tests"))?
+    } else {
+        collect_test_cases(&problem_dir)?
+    };
+    let test_cases = match options.case {
+        Some(case) => vec![select_test_case(test_cases, case)?],
+        None => test_cases,
+    };
+    if has_validator {
+        let inputs: Vec<PathBuf> = test_cases.iter().map(|(input, _)| input.clone()).collect();
+        validate_inputs(work_dir, problem_name, &inputs)?;
+    }
+    let timeout_margin = get_timeout_margin();
+    let timeout_multiplier = get_timeout_multiplier();
+    let timeout_settings = match options.timeout {
+        // 指定された場合は実行時間制限の設定がなくてもよい
+        Some(_) => load_problem_timeout_settings(work_dir).unwrap_or_default(),
+        None => load_problem_timeout_settings(work_dir)?,
+    };
+    let mut timeout_settings: HashMap<String, u64> = timeout_settings
+        .into_iter()
+        .map(|(name, timeout)| {
+            let timeout = local_time_limit(timeout, timeout_multiplier, timeout_margin);
+            (name, timeout)
+        })
+        .collect();
+    if let Some(timeout) = options.timeout {
+        timeout_settings.insert(problem_name.to_string(), timeout);
+    }
+    let interactive_timeout = if interactive {
+        let timeout = timeout_settings
+            .get(problem_name)
+            .copied()
+            .ok_or_else(|| format!("{} の実行時間制限が設定されていません", problem_name))?;
+        Some(timeout)
+    } else {
+        None
+    };
+    let compare = match options.compare {
+        Some(compare) => compare,
+        None => load_compare_mode(work_dir, problem_name)?.unwrap_or_default(),
+    };
+    let judge = compare.judge();
+    let run = |test_cases| match interactive_timeout {
+        Some(timeout) => {
+            run_interactive_cases(work_dir, problem_name, test_cases, timeout, verbose)
         }
+        None => run_test_cases(
+            work_dir,
+            test_cases,
+            problem_name,
+            &timeout_settings,
+            judge.as_ref(),
+            verbose,
+        ),
+    };
+    // `--repeat` の場合は各テストケースを繰り返し実行し、実行時間の統計値とともに 1 つの結果にまとめる
+    let run = |test_cases: Vec<(PathBuf, PathBuf)>| {
+        if options.repeat <= 1 {
+            return run(test_cases);
+        }
+        let mut results = Vec::new();
+        for test_case in test_cases {
+            let mut runs = Vec::new();
+            for _ in 0..options.repeat {
+                runs.append(&mut run(vec![test_case.clone()])?);
+            }
+            results.extend(merge_repeated_runs(runs));
+        }
+        Ok(results)
+    };
+    if !options.fail_fast && !system {
+        return run(test_cases);
     }
-    println!("=============================\n");
 
-    if results.iter().all(|res| res.status == TestStatus::AC) {
-        Ok(())
+    // 1 ケースずつ実行し、公式のテストケースでは進捗を表示する
+    // `--fail-fast` の場合は AC 以外の結果が出た時点で残りのテストケースを実行せずに終了する
+    let total = test_cases.len();
+    let progress = system_progress_bar(system && options.verbosity != Verbosity::Silent, total);
+    let mut results = Vec::new();
+    for test_case in test_cases {
+        let mut result = run(vec![test_case])?;
+        let failed = result.iter().any(|res| res.status != TestStatus::AC);
+        results.append(&mut result);
+        progress.inc(1);
+        progress.set_message(verdict_counts(&results));
+        if failed && options.fail_fast {
+            if results.len() < total && !is_json() {
+                progress.suspend(|| {
+                    eprintln!(
+                        "[{}] stopped at the first failure (--fail-fast): {} test cases skipped",
+                        problem_name,
+                        total - results.len()
+                    )
+                });
+            }
+            break;
+        }
+    }
+    progress.finish_and_clear();
+    Ok(results)
+}
+
+/// 同じテストケースを繰り返し実行した結果を 1 つにまとめる
+///
+/// 最も優先度の高い結果のうち最も遅い実行を代表とし、実行時間の統計値を `timing` に記録する。
+/// `runs` が空の場合は `None` を返す。
+fn merge_repeated_runs(runs: Vec<TestCaseResult>) -> Option<TestCaseResult> {
+    let status = worst_status(runs.iter().map(|result| result.status));
+    let times: Vec<Duration> = runs
+        .iter()
+        .map(|result| Duration::from_millis(result.execution_time as u64))
+        .collect();
+    let timing = (!times.is_empty()).then(|| BenchStats::from_durations(&times));
+    let mut result = runs
+        .into_iter()
+        .filter(|result| result.status == status)
+        .max_by_key(|result| result.execution_time)?;
+    result.timing = timing;
+    Some(result)
+}
+
+/// 結果の一覧に表示する実行時間の統計値の行を返す
+///
+/// 例: `Timing (x10): min 1701.0 ms, mean 1750.3 ms, max 1802.0 ms, p95 1800.0 ms`
+fn timing_line(timing: &BenchStats, repeat: usize) -> String {
+    format!(
+        "Timing (x{}): min {:.1} ms, mean {:.1} ms, max {:.1} ms, p95 {:.1} ms",
+        repeat, timing.min, timing.mean, timing.max, timing.p95
+    )
+}
+
+/// 公式のテストケースの実行状況を表示するプログレスバーを作成する
+///
+/// `enabled` が `false` の場合、または `--json` を指定した場合は表示しない。
+fn system_progress_bar(enabled: bool, len: usize) -> ProgressBar {
+    if !enabled || is_json() {
+        return ProgressBar::hidden();
+    }
+    let progress = ProgressBar::new(len as u64);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} [{bar:30}] {pos}/{len} cases {msg}")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    progress
+}
+
+/// 結果ごとのテストケース数を AtCoder の提出結果と同じ形式で返す (例: `AC × 25, WA × 3`)
+///
+/// 結果は AC・WA・TLE・RE の順に並べ、該当するテストケースがない結果は含めない。
+pub fn verdict_counts(results: &[TestCaseResult]) -> String {
+    let counts: Vec<String> = [
+        TestStatus::AC,
+        TestStatus::WA,
+        TestStatus::TLE,
+        TestStatus::RE,
+    ]
+    .into_iter()
+    .filter_map(|status| {
+        let count = results.iter().filter(|res| res.status == status).count();
+        (count > 0).then(|| format!("{} × {}", status, count))
+    })
+    .collect();
+    counts.join(", ")
+}
+
+/// 公式のテストケースでの全体の結果を表示する行を返す
+///
+/// 例: `Judge: WA (AC × 25, WA × 3), max 1234 ms`
+fn judge_line(results: &[TestCaseResult]) -> String {
+    let max_time = results
+        .iter()
+        .map(|res| res.execution_time)
+        .max()
+        .unwrap_or(0);
+    format!(
+        "Judge: {} ({}), max {} ms",
+        overall_status(results),
+        verdict_counts(results),
+        max_time
+    )
+}
+
+/// コンテストのすべての問題のテストを実行し、問題ごとの結果を一覧で表示する
+///
+/// `Cargo.toml` の `[[bin]]` のうち、`<問題名>/main.rs` を対象とする問題として扱う。
+///
+/// # 引数
+///
+/// * `options` - 実行オプション(`case` は利用しない)
+pub fn execute_all(work_dir: &PathBuf, options: &TestOptions) -> Result<(), Box<dyn Error>> {
+    let problems = list_problem_bins(work_dir)?;
+    if problems.is_empty() {
+        return Err("Cargo.toml にテスト対象の問題がありません".into());
+    }
+    let options = TestOptions {
+        case: None,
+        ..*options
+    };
+
+    let mut summaries = Vec::new();
+    for problem_name in problems {
+        if !is_json() && options.verbosity != Verbosity::Silent {
+            eprintln!("[{}] testing...", problem_name);
+        }
+        let summary = match run_problem(work_dir, &problem_name, &options, false) {
+            Ok(results) => {
+                save_history(&work_dir.join(&problem_name), &results, options.system);
+                save_score(work_dir, &problem_name, &results);
+                if let Ok(contest_name) = get_contest_name(work_dir) {
+                    record_event(&contest_name, &problem_name, EventKind::Tested);
+                }
+                ProblemSummary {
+                    verdict: overall_status(&results).to_string(),
+                    passed: results
+                        .iter()
+                        .filter(|res| res.status == TestStatus::AC)
+                        .count(),
+                    total: results.len(),
+                    results,
+                    error: None,
+                    problem: problem_name,
+                }
+            }
+            Err(e) => ProblemSummary {
+                verdict: match e.downcast_ref::<TestFailure>() {
+                    Some(TestFailure::Compile) => TestStatus::CE.to_string(),
+                    _ => "-".to_string(),
+                },
+                passed: 0,
+                total: 0,
+                results: Vec::new(),
+                error: Some(e.to_string()),
+                problem: problem_name,
+            },
+        };
+        summaries.push(summary);
+    }
+
+    if is_json() {
+        print_json(&summaries)?;
+    } else if options.verbosity == Verbosity::Silent {
+        for summary in &summaries {
+            println!("{} {}", summary.problem, summary.verdict);
+        }
     } else {
-        Err("Some tests failed.".into())
+        println!("\n=== Contest Test Summary ===");
+        println!("{:<8} {:<7} {:>6}  Cases", "Problem", "Verdict", "Passed");
+        for summary in &summaries {
+            let cases: Vec<String> = summary
+                .results
+                .iter()
+                .map(|result| result.status.to_string())
+                .collect();
+            let passed = format!("{}/{}", summary.passed, summary.total);
+            println!(
+                "{:<8} {:<7} {:>6}  {}",
+                summary.problem,
+                summary.verdict,
+                passed,
+                summary.error.as_deref().unwrap_or(&cases.join(" "))
+            );
+        }
+        println!("============================\n");
+        print_remaining(work_dir);
+    }
+
+    if summaries.iter().any(|summary| summary.verdict == "CE") {
+        return Err(TestFailure::Compile.into());
     }
+    if summaries.iter().any(|summary| summary.error.is_some()) {
+        return Err("テストを実行できなかった問題があります".into());
+    }
+    let results: Vec<TestCaseResult> = summaries
+        .into_iter()
+        .flat_map(|summary| summary.results)
+        .collect();
+    match overall_status(&results) {
+        TestStatus::AC => Ok(()),
+        status => Err(TestFailure::Verdict(status).into()),
+    }
+}
+
+/// `execute_all` で表示する問題ごとの結果
+#[derive(Serialize)]
+struct ProblemSummary {
+    problem: String,
+    verdict: String, // 全体の結果 (コンパイルエラーは `CE`、テストを実行できなかった場合は `-`)
+    passed: usize,
+    total: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    results: Vec<TestCaseResult>,
+}
+
+/// `Cargo.toml` の `[[bin]]` から問題名(`<問題名>/main.rs` を持つバイナリ名)の一覧を返す
+///
+/// `validator.rs` などの補助プログラムのバイナリは含まない。
+pub fn list_problem_bins(work_dir: &Path) -> Result<Vec<String>, Box<dyn Error>> {
+    let content = fs::read_to_string(work_dir.join("Cargo.toml"))?;
+    let parsed: Value = toml::from_str(&content)?;
+    let problems = parsed
+        .get("bin")
+        .and_then(|bins| bins.as_array())
+        .map(|bins| {
+            bins.iter()
+                .filter_map(|bin| {
+                    let name = bin.get("name")?.as_str()?;
+                    let path = bin.get("path")?.as_str()?;
+                    (Path::new(path) == Path::new(name).join("main.rs")).then(|| name.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(problems)
+}
+
+/// テストが失敗した場合のエラー
+///
+/// `main` は `exit_code` の値で終了し、スクリプトから失敗の種類を判別できるようにする。
+#[derive(Debug)]
+pub enum TestFailure {
+    Compile,             // コンパイルエラー
+    Verdict(TestStatus), // AC 以外のテストケースがある場合(最も優先度の高い結果)
+}
+
+impl TestFailure {
+    /// 失敗の種類に対応する終了コードを返す
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TestFailure::Verdict(TestStatus::AC) => 0,
+            TestFailure::Verdict(TestStatus::WA) => 2,
+            TestFailure::Verdict(TestStatus::TLE) => 3,
+            TestFailure::Verdict(TestStatus::RE) => 4,
+            TestFailure::Verdict(TestStatus::CE) | TestFailure::Compile => 5,
+        }
+    }
+}
+
+impl Display for TestFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestFailure::Compile => write!(f, "Compilation failed"),
+            TestFailure::Verdict(status) => write!(f, "Some tests failed. ({})", status),
+        }
+    }
+}
+
+impl Error for TestFailure {}
+
+/// `test` の実行結果のエラーに対応する終了コードを返す
+///
+/// `--remote` の場合(`RemoteExit`)はリモートの `cargo atc` の終了コード、
+/// `TestFailure` 以外のエラーは `1` を返す。
+pub fn exit_code(error: &(dyn Error + 'static)) -> i32 {
+    if let Some(exit) = error.downcast_ref::<RemoteExit>() {
+        return exit.exit_code();
+    }
+    error
+        .downcast_ref::<TestFailure>()
+        .map_or(1, TestFailure::exit_code)
+}
+
+/// テストケースの結果をまとめた全体の結果を返す
+///
+/// AC 以外の結果が複数ある場合は、RE・TLE・WA の順に優先する。
+pub fn overall_status(results: &[TestCaseResult]) -> TestStatus {
+    worst_status(results.iter().map(|result| result.status))
+}
+
+/// 結果のうち最も優先度の高いものを返す (空の場合は AC)
+pub fn worst_status(statuses: impl IntoIterator<Item = TestStatus>) -> TestStatus {
+    let rank = |status: TestStatus| match status {
+        TestStatus::AC => 0,
+        TestStatus::WA => 1,
+        TestStatus::TLE => 2,
+        TestStatus::RE => 3,
+        TestStatus::CE => 4,
+    };
+    statuses
+        .into_iter()
+        .max_by_key(|status| rank(*status))
+        .unwrap_or(TestStatus::AC)
+}
+
+/// `--json` を指定した場合に出力するテスト結果
+#[derive(Serialize)]
+struct TestReport<'a> {
+    problem: &'a str,
+    status: TestStatus, // 全体の結果 (コンパイルエラーの場合は `CE`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    regressions: Vec<String>, // 前回 AC で今回 AC 以外となったテストケース
+    passed: usize,
+    total: usize,
+    release: bool, // リリースビルドで計測したかどうか
+    results: &'a [TestCaseResult],
+}
+
+/// 標準エラー出力を表示する最大の行数
+const STDERR_DISPLAY_LINES: usize = 50;
+
+/// 入出力を表示する最大の行数 (`--full-output` を指定した場合は制限しない)
+const OUTPUT_DISPLAY_LINES: usize = 100;
+
+/// 入出力を表示する最大のバイト数 (`--full-output` を指定した場合は制限しない)
+const OUTPUT_DISPLAY_BYTES: usize = 4096;
+
+/// 解答の標準出力を保持するサイズの、期待する出力の 2 倍に加える余裕 (バイト)
+const OUTPUT_LIMIT_MARGIN: usize = 1024 * 1024;
+
+static FULL_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// 入出力を省略せずに表示するかどうかを設定する (`--full-output` を指定した場合に有効にする)
+pub fn set_full_output(enabled: bool) {
+    FULL_OUTPUT.store(enabled, Ordering::Relaxed);
 }
 
 /// テストケースごとの実行結果を保持する構造体
-struct TestCaseResult {
-    test_case_name: String,        // サンプルケース名(例: "sample_1.in")
-    status: TestStatus,            // 実行結果
-    execution_time: u128,          // 実行時間(ミリ秒)
-    error_message: Option<String>, // エラーが発生した場合のメッセージ
+#[derive(Serialize)]
+pub struct TestCaseResult {
+    pub test_case_name: String,        // サンプルケース名(例: "sample_1.in")
+    pub status: TestStatus,            // 実行結果
+    pub execution_time: u128,          // 実行時間(ミリ秒)
+    pub error_message: Option<String>, // エラーが発生した場合のメッセージ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>, // 解答が標準エラー出力に出力した内容
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<BenchStats>, // `--repeat` で繰り返し実行した場合の実行時間の統計値
 }
 
 impl TestCaseResult {
     pub fn display_details(&self, input: &str, expected_output: &str, actual_output: &str) {
+        let display = |text: &str| {
+            if FULL_OUTPUT.load(Ordering::Relaxed) {
+                text.to_string()
+            } else {
+                truncate_output(text, OUTPUT_DISPLAY_LINES, OUTPUT_DISPLAY_BYTES)
+            }
+        };
         println!("Test Case: {}", self.test_case_name);
-        println!("Input:\n{}", input);
-        println!("Expected Output:\n{}", expected_output);
-        println!("Actual Output:\n{}", actual_output);
+        println!("Input:\n{}", display(input));
+        println!("Expected Output:\n{}", display(expected_output));
+        println!("Actual Output:\n{}", display(actual_output));
+        if let Some(stderr) = &self.stderr {
+            println!("Stderr:\n{}", truncate_lines(stderr, STDERR_DISPLAY_LINES));
+        }
         println!("Status: {}", self.status);
         println!("Execution Time: {} ms\n", self.execution_time);
     }
 }
 
+/// `max_lines` 行を超える部分を省略した文字列を返す
+fn truncate_lines(text: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() <= max_lines {
+        return text.trim_end().to_string();
+    }
+    format!(
+        "{}\n... ({} more lines)",
+        lines[..max_lines].join("\n"),
+        lines.len() - max_lines
+    )
+}
+
+/// `max_lines` 行、または `max_bytes` バイトを超える部分を省略した文字列を返す
+fn truncate_output(text: &str, max_lines: usize, max_bytes: usize) -> String {
+    let mut end = text.len().min(max_bytes);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    if let Some((index, _)) = text.match_indices('\n').nth(max_lines.saturating_sub(1)) {
+        end = end.min(index + 1);
+    }
+    if end == text.len() {
+        return text.to_string();
+    }
+    format!(
+        "{}\n... ({} more bytes, use --full-output to show all)\n",
+        text[..end].trim_end_matches('\n'),
+        text.len() - end
+    )
+}
+
+/// RE の理由を説明するメッセージを返す
+///
+/// 終了コード・シグナルと、標準エラー出力から取り出したパニックのメッセージを含める。
+///
+/// 例: `Execution failed (exit code 101): thread 'main' panicked at a/main.rs:4:5: index out of bounds`
+fn runtime_error_message(status: &std::process::ExitStatus, stderr: &str) -> String {
+    let reason = describe_exit_status(status);
+    match panic_message(stderr) {
+        Some(panic) => format!("Execution failed ({}): {}", reason, panic),
+        None => format!("Execution failed ({})", reason),
+    }
+}
+
+/// 標準エラー出力からパニックのメッセージを取り出す
+///
+/// `thread 'main' panicked at src/main.rs:4:5:` の行と、続くメッセージの行を返す。
+/// スタックオーバーフローで異常終了した場合は、その旨を示す行を返す。
+fn panic_message(stderr: &str) -> Option<String> {
+    if let Some(line) = stderr
+        .lines()
+        .find(|line| line.contains("has overflowed its stack"))
+    {
+        return Some(line.trim().to_string());
+    }
+    let mut lines = stderr.lines();
+    let location = lines.find(|line| line.contains("panicked at"))?;
+    // Rust 1.73 より前はメッセージが同じ行に出力される
+    match lines.next() {
+        Some(message) if location.trim_end().ends_with(':') && !message.starts_with("note:") => {
+            Some(format!("{} {}", location.trim_end(), message.trim()))
+        }
+        _ => Some(location.trim().to_string()),
+    }
+}
+
 /// テストケースの実行結果ステータスを表す列挙型
-#[derive(PartialEq, Debug)]
-enum TestStatus {
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum TestStatus {
     AC,
     WA,
     TLE,
     RE,
+    CE, // コンパイルエラー (テストケースは実行しない)
 }
 
 impl Display for TestStatus {
@@ -97,8 +897,9 @@ impl Display for TestStatus {
             TestStatus::WA => "WA",
             TestStatus::TLE => "TLE",
             TestStatus::RE => "RE",
+            TestStatus::CE => "CE",
         };
-        write!(f, "{}", status)
+        f.pad(status)
     }
 }
 
@@ -110,8 +911,8 @@ impl Display for TestStatus {
 /// # 戻り値
 ///
 /// ディレクトリパスを返却する。
-fn find_problem_directory(
-    work_dir: &PathBuf,
+pub fn find_problem_directory(
+    work_dir: &Path,
     problem_name: &str,
 ) -> Result<PathBuf, Box<dyn Error>> {
     let dir = work_dir.join(problem_name);
@@ -122,21 +923,227 @@ fn find_problem_directory(
     }
 }
 
+static RELEASE_BUILD: AtomicBool = AtomicBool::new(false);
+
+/// リリースビルドの有効・無効を設定する (`main` で 1 度だけ呼び出す)
+pub fn set_release(enabled: bool) {
+    RELEASE_BUILD.store(enabled, Ordering::Relaxed);
+}
+
+/// リリースビルドが有効かどうかを返す
+pub fn is_release() -> bool {
+    RELEASE_BUILD.load(Ordering::Relaxed)
+}
+
+/// ビルドのプロファイルに対応する `target` 配下のディレクトリ名を返す
+fn profile_dir(release: bool) -> &'static str {
+    if release {
+        "release"
+    } else {
+        "debug"
+    }
+}
+
+/// `BUILT_EXECUTABLES` のキー (パッケージの `Cargo.toml` のパス, プロファイル, バイナリ名)
+type ExecutableKey = (PathBuf, &'static str, String);
+
+/// `compile` で生成された実行ファイルのパス
+///
+/// 別のパッケージやプロファイルでコンパイルした同名のバイナリを取り違えないよう、
+/// パッケージとプロファイルもキーに含める。
+static BUILT_EXECUTABLES: Mutex<BTreeMap<ExecutableKey, PathBuf>> = Mutex::new(BTreeMap::new());
+
+/// `dir` を含むパッケージの `Cargo.toml` のパスを返す
+///
+/// 同じパッケージを相対パスと絶対パスで指定しても同じパスになるよう、可能な場合は正規化する。
+fn manifest_path(dir: &Path) -> PathBuf {
+    let manifest = package_root(dir)
+        .unwrap_or_else(|| dir.to_path_buf())
+        .join("Cargo.toml");
+    fs::canonicalize(&manifest).unwrap_or(manifest)
+}
+
 /// 指定されたディレクトリ内の資源をコンパイルする
 ///
+/// リリースビルドが有効な場合は `--release` を付けてコンパイルする。
+/// 生成された実行ファイルのパスは記録し、`get_execution_path` で利用する。
+/// ソースが前回のビルドから変更されていない場合はコンパイルを省略する(`build_cache.rs`)。
+///
 /// # 引数
 ///
 /// * `dir` - コンパイル対象のディレクトリ。
-fn compile(dir: &Path) -> Result<(), Box<dyn Error>> {
-    let compile_status = Command::new("cargo")
-        .arg("build")
-        .current_dir(dir)
-        .status()?;
-    if compile_status.success() {
-        Ok(())
+pub fn compile(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let profile = profile_dir(is_release());
+    let package = package_root(dir).and_then(|root| {
+        source_fingerprint(&root)
+            .ok()
+            .map(|fingerprint| (root, fingerprint))
+    });
+    let fresh = package
+        .as_ref()
+        .and_then(|(root, fingerprint)| load_fresh_executables(root, profile, fingerprint));
+    let executables = match fresh {
+        Some(executables) => {
+            if !is_json() {
+                eprintln!("Build skipped: sources are unchanged (use --force-build to rebuild)");
+            }
+            executables
+        }
+        None => {
+            let mut command = Command::new("cargo");
+            command.arg("build");
+            if is_release() {
+                command.arg("--release");
+            }
+            let executables = cargo_build(command.current_dir(dir))?;
+            if let Some((root, fingerprint)) = &package {
+                save_build(root, profile, fingerprint, &executables)?;
+            }
+            executables
+        }
+    };
+    let manifest = manifest_path(dir);
+    BUILT_EXECUTABLES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .extend(
+            executables
+                .into_iter()
+                .map(|(name, path)| ((manifest.clone(), profile, name), path)),
+        );
+    Ok(())
+}
+
+/// コンパイルエラーの場合に表示するエラーの最大件数
+const COMPILE_ERROR_DISPLAY_COUNT: usize = 3;
+
+/// `cargo build` のコマンドに `--message-format=json` を付けて実行し、生成された実行ファイルを返す
+///
+/// コンパイルに失敗した場合は、診断メッセージのうちエラーのみを要約して標準エラー出力に表示する(`condense_diagnostics`)。
+/// 標準エラー出力が端末の場合(環境変数 `NO_COLOR` が未設定の場合)は rustc の色付けを保持する。
+/// 成功した場合は警告の件数のみを表示する。
+///
+/// # 戻り値
+/// - バイナリ名と実行ファイルのパスの組
+///
+/// # エラーの可能性
+/// - `cargo` を起動できない場合
+/// - コンパイルに失敗した場合 (`TestFailure::Compile`)
+pub fn cargo_build(command: &mut Command) -> Result<BTreeMap<String, PathBuf>, Box<dyn Error>> {
+    let color = io::stderr().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let message_format = if color {
+        "--message-format=json-diagnostic-rendered-ansi"
+    } else {
+        "--message-format=json"
+    };
+    let output = command
+        .arg(message_format)
+        .stderr(Stdio::inherit())
+        .output()?;
+    let messages = String::from_utf8_lossy(&output.stdout);
+    let diagnostics = parse_diagnostics(&messages);
+    if !output.status.success() {
+        eprintln!("{}", condense_diagnostics(&diagnostics, color));
+        return Err(TestFailure::Compile.into());
+    }
+    let warnings = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == "warning")
+        .count();
+    if warnings > 0 && !is_json() {
+        eprintln!(
+            "{} (run `cargo build` to see details)",
+            count_label(warnings, "warning")
+        );
+    }
+    Ok(parse_executables(&messages))
+}
+
+/// rustc の診断メッセージ
+#[derive(Debug, PartialEq)]
+struct Diagnostic {
+    level: String,    // `error`、`warning` など
+    rendered: String, // rustc が整形したメッセージ(ソースの該当箇所を含む)
+}
+
+/// `cargo build --message-format=json` の出力から、ソースの位置を持つ診断メッセージを取り出す
+///
+/// `aborting due to 1 previous error` のような位置を持たない要約のメッセージは除く。
+fn parse_diagnostics(messages: &str) -> Vec<Diagnostic> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-message")
+        .filter_map(|message| {
+            let diagnostic = &message["message"];
+            if diagnostic["spans"].as_array().is_none_or(Vec::is_empty) {
+                return None;
+            }
+            Some(Diagnostic {
+                level: diagnostic["level"].as_str()?.to_string(),
+                rendered: diagnostic["rendered"].as_str()?.trim_end().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// コンパイルエラーの診断メッセージを要約する
+///
+/// 警告は表示せず、エラーを最大 `COMPILE_ERROR_DISPLAY_COUNT` 件と、エラー・警告の件数を返す。
+fn condense_diagnostics(diagnostics: &[Diagnostic], color: bool) -> String {
+    let errors: Vec<&Diagnostic> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == "error")
+        .collect();
+    let warnings = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.level == "warning")
+        .count();
+    let mut lines: Vec<String> = errors
+        .iter()
+        .take(COMPILE_ERROR_DISPLAY_COUNT)
+        .map(|error| format!("{}\n", error.rendered))
+        .collect();
+    if errors.len() > COMPILE_ERROR_DISPLAY_COUNT {
+        lines.push(format!(
+            "... and {}",
+            count_label(errors.len() - COMPILE_ERROR_DISPLAY_COUNT, "more error")
+        ));
+    }
+    let summary = format!(
+        "Compilation failed: {}, {}",
+        count_label(errors.len(), "error"),
+        count_label(warnings, "warning")
+    );
+    if color {
+        lines.push(format!("\x1b[1;31m{}\x1b[0m", summary));
     } else {
-        Err("Compilation failed".into())
+        lines.push(summary);
     }
+    lines.join("\n")
+}
+
+/// 件数と単位を表示用に返す (`1 error`、`2 errors` など)
+fn count_label(count: usize, unit: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+/// `cargo build --message-format=json` の出力から、実行ファイルを生成した `compiler-artifact` のバイナリ名とパスを取り出す
+fn parse_executables(messages: &str) -> BTreeMap<String, PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter_map(|message| {
+            let name = message["target"]["name"].as_str()?;
+            let executable = message["executable"].as_str()?;
+            Some((name.to_string(), PathBuf::from(executable)))
+        })
+        .collect()
 }
 
 /// テストケースを収集する。
@@ -155,10 +1162,16 @@ fn compile(dir: &Path) -> Result<(), Box<dyn Error>> {
 ///
 /// * `tests`ディレクトリが存在しない場合。
 /// * ファイルの読み込みに失敗した場合。
-fn collect_test_cases(dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Error>> {
-    let tests_dir = dir.join("tests");
+pub fn collect_test_cases(dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Error>> {
+    collect_test_cases_in(&dir.join("tests"))
+}
+
+/// 指定されたディレクトリ直下の`.in`と`.out`ファイルのペアを収集する。
+///
+/// サブディレクトリ(`tests/system`など)は対象としない。
+pub fn collect_test_cases_in(tests_dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Error>> {
     let mut test_cases = Vec::new();
-    for entry in fs::read_dir(&tests_dir)? {
+    for entry in fs::read_dir(tests_dir)? {
         let input_file_path = entry?.path();
         if input_file_path.extension().unwrap_or_default() == "in" {
             let output_file_path = input_file_path.with_extension("out");
@@ -169,11 +1182,108 @@ fn collect_test_cases(dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Err
             }
         }
     }
+    sort_test_cases(&mut test_cases);
     Ok(test_cases)
 }
 
+/// テストケースを入力ファイル名の自然順(`sample_2` が `sample_10` より前)に並べ替える
+pub fn sort_test_cases(test_cases: &mut [(PathBuf, PathBuf)]) {
+    test_cases.sort_by(|(a, _), (b, _)| {
+        natural_cmp(
+            &a.file_name().unwrap_or_default().to_string_lossy(),
+            &b.file_name().unwrap_or_default().to_string_lossy(),
+        )
+    });
+}
+
+/// 文字列中の数字の並びを数値として比較する
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    let chunks = |s: &str| -> Vec<(bool, String)> {
+        let mut chunks: Vec<(bool, String)> = Vec::new();
+        for c in s.chars() {
+            let is_digit = c.is_ascii_digit();
+            match chunks.last_mut() {
+                Some((digit, chunk)) if *digit == is_digit => chunk.push(c),
+                _ => chunks.push((is_digit, c.to_string())),
+            }
+        }
+        chunks
+    };
+    let (a_chunks, b_chunks) = (chunks(a), chunks(b));
+    for ((a_digit, a_chunk), (b_digit, b_chunk)) in a_chunks.iter().zip(&b_chunks) {
+        let ordering = if *a_digit && *b_digit {
+            let (a_num, b_num) = (
+                a_chunk.trim_start_matches('0'),
+                b_chunk.trim_start_matches('0'),
+            );
+            a_num
+                .len()
+                .cmp(&b_num.len())
+                .then_with(|| a_num.cmp(b_num))
+                .then_with(|| a_chunk.len().cmp(&b_chunk.len()))
+        } else {
+            a_chunk.cmp(b_chunk)
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    a_chunks.len().cmp(&b_chunks.len())
+}
+
+/// 収集したテストケースから、名前が一致するテストケースを選ぶ。
+///
+/// 拡張子 `.in` は省略でき、一致するものがない場合は `sample_<case>` を探す。
+///
+/// # 引数
+///
+/// * `test_cases` - `collect_test_cases` で収集したテストケース
+/// * `case` - テストケース名(`sample_2`、`sample_2.in`、`2` など)
+///
+/// # エラー
+///
+/// * 一致するテストケースが存在しない場合。
+pub fn select_test_case(
+    test_cases: Vec<(PathBuf, PathBuf)>,
+    case: &str,
+) -> Result<(PathBuf, PathBuf), Box<dyn Error>> {
+    let name = case.strip_suffix(".in").unwrap_or(case);
+    let stem_of = |(input, _): &(PathBuf, PathBuf)| {
+        input
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+    let index = test_cases
+        .iter()
+        .position(|test_case| stem_of(test_case) == name)
+        .or_else(|| {
+            let sample = format!("sample_{}", name);
+            test_cases
+                .iter()
+                .position(|test_case| stem_of(test_case) == sample)
+        });
+    match index {
+        Some(index) => Ok(test_cases.into_iter().nth(index).unwrap()),
+        None => {
+            let mut names: Vec<String> = test_cases.iter().map(stem_of).collect();
+            names.sort();
+            Err(format!(
+                "テストケース '{}' が見つかりません (存在するテストケース: {})",
+                case,
+                names.join(", ")
+            )
+            .into())
+        }
+    }
+}
+
 /// 問題名に基づいて実行可能ファイルのパスを取得する関数
 ///
+/// `compile` で生成された実行ファイルがあればそのパスを返す。
+/// 記録がない場合は、ビルド成果物のディレクトリ(`get_target_dir`。環境変数 `CARGO_TARGET_DIR` も考慮する)の
+/// `release/`(リリースビルドが有効な場合)または `debug/` の実行ファイルを返す。
+///
 /// # 引数
 ///
 /// * `problem_name` - 処理対象となる問題名
@@ -181,8 +1291,22 @@ fn collect_test_cases(dir: &Path) -> Result<Vec<(PathBuf, PathBuf)>, Box<dyn Err
 /// # 戻り値
 ///
 /// 実行可能ファイルのパスを返す。
-fn get_execution_path(work_dir: &PathBuf, problem_name: &str) -> Result<PathBuf, Box<dyn Error>> {
-    let executable = work_dir.join(format!("target/debug/{}", problem_name));
+pub fn get_execution_path(work_dir: &Path, problem_name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let key = (
+        manifest_path(work_dir),
+        profile_dir(is_release()),
+        problem_name.to_string(),
+    );
+    let built = BUILT_EXECUTABLES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+        .cloned();
+    let executable = built.unwrap_or_else(|| {
+        get_target_dir(work_dir)
+            .join(profile_dir(is_release()))
+            .join(problem_name)
+    });
     if executable.exists() {
         Ok(executable)
     } else {
@@ -194,29 +1318,46 @@ fn get_execution_path(work_dir: &PathBuf, problem_name: &str) -> Result<PathBuf,
     }
 }
 
-/// Cargo.tomlから問題ごとのタイムアウト設定を取得する。
-fn load_problem_timeout_settings(
-    work_dir: &PathBuf,
+/// 問題ごとのタイムアウト設定を取得する。
+///
+/// `contest.json` に保存された実行時間制限を優先し、`contest.json` に含まれない問題は
+/// Cargo.tomlの `[package.metadata.timeout]` から取得する。
+pub fn load_problem_timeout_settings(
+    work_dir: &Path,
 ) -> Result<HashMap<String, u64>, Box<dyn Error>> {
     let cargo_toml_path = work_dir.join("Cargo.toml");
     if !cargo_toml_path.exists() {
         return Err("Cargo.toml not found in the current directory".into());
     }
 
+    let mut timeout_map: HashMap<String, u64> = load_contest_metadata(work_dir)?
+        .map(|metadata| {
+            metadata
+                .problems
+                .into_iter()
+                .map(|problem| (problem.problem, problem.timeout as u64))
+                .collect()
+        })
+        .unwrap_or_default();
+
     let cargo_toml_content = fs::read_to_string(cargo_toml_path)?;
     let parsed: Value = toml::from_str(&cargo_toml_content)?;
 
-    let timeout_section = parsed
+    let Some(timeout_section) = parsed
         .get("package")
         .and_then(|pkg| pkg.get("metadata"))
         .and_then(|meta| meta.get("timeout"))
-        .ok_or("Timeout section not found in Cargo.toml")?;
+    else {
+        if timeout_map.is_empty() {
+            return Err("Timeout section not found in Cargo.toml".into());
+        }
+        return Ok(timeout_map);
+    };
 
-    let mut timeout_map = HashMap::new();
     if let Value::Table(table) = timeout_section {
         for (key, value) in table {
             if let Some(timeout) = value.as_integer() {
-                timeout_map.insert(key.clone(), timeout as u64);
+                timeout_map.entry(key.clone()).or_insert(timeout as u64);
             }
         }
     } else {
@@ -226,90 +1367,99 @@ fn load_problem_timeout_settings(
     Ok(timeout_map)
 }
 
-fn return_results(
+/// テストケースを順に実行し、実行結果を返す
+///
+/// # 引数
+/// * `judge` - 解答の出力と期待する出力の比較方法
+/// * `verbose` - `true` の場合、テストケースごとに入出力と実行結果の詳細を表示する
+pub fn run_test_cases(
     work_dir: &PathBuf,
     test_cases: Vec<(PathBuf, PathBuf)>,
     problem_name: &str,
     timeout_settings: &HashMap<String, u64>,
+    judge: &dyn Judge,
+    verbose: bool,
 ) -> Result<Vec<TestCaseResult>, Box<dyn Error>> {
     let executable = get_execution_path(&work_dir, problem_name)?;
     let mut results = Vec::new();
-    let timeout = timeout_settings.get(problem_name).copied().unwrap();
+    let timeout = timeout_settings
+        .get(problem_name)
+        .copied()
+        .ok_or_else(|| format!("{} の実行時間制限が設定されていません", problem_name))?;
     if !executable.exists() {
-        println!("DEBUG: {}", executable.display());
         return Err(format!("Executable does not exist: {}", executable.display()).into());
     }
 
     for (input_file, expected_output_file) in test_cases {
-        let input = fs::read_to_string(&input_file)?;
         let expected_output = fs::read_to_string(&expected_output_file)?;
+        let output_limit = expected_output
+            .len()
+            .saturating_mul(2)
+            .saturating_add(OUTPUT_LIMIT_MARGIN);
         let test_case_name = input_file
             .file_name()
             .unwrap()
             .to_string_lossy()
             .to_string();
 
-        let start_time = Instant::now();
-        let mut child = Command::new(&executable)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()?;
-        println!("DEBUG: {}", executable.display());
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin.write_all(input.as_bytes())?;
-        }
-
-        let execution_result: Result<std::process::Output, Box<dyn Error>> = loop {
-            if start_time.elapsed().as_millis() > timeout as u128 {
-                // TLE
-                let _ = child.kill();
-                break Err("Execution timed out".into());
-            }
-            match child.try_wait()? {
-                Some(status) => {
-                    if status.success() {
-                        let output = child.wait_with_output()?;
-                        break Ok(output);
-                    } else {
-                        break Err("Execution failed".into());
-                    }
-                }
-                None => {
-                    std::thread::sleep(std::time::Duration::from_millis(10));
-                }
-            }
-        };
-        let execution_time = start_time.elapsed().as_millis();
-        let (actual_output, status, error_message) = match execution_result {
-            Ok(output) => {
-                let actual_output = String::from_utf8_lossy(&output.stdout).to_string();
-                if actual_output.trim() == expected_output.trim() {
-                    (actual_output, TestStatus::AC, None)
+        let outcome = run_with_timeout(
+            &executable,
+            &input_file,
+            Duration::from_millis(timeout),
+            output_limit,
+        )?;
+        let (actual_output, status, error_message, execution_time, stderr) = match outcome {
+            Outcome::Exited {
+                status,
+                stdout,
+                stdout_exceeded,
+                stderr,
+                elapsed,
+            } => {
+                let actual_output = String::from_utf8_lossy(&stdout).to_string();
+                let (status, error_message) = if !status.success() {
+                    (
+                        TestStatus::RE,
+                        Some(runtime_error_message(&status, &stderr)),
+                    )
+                } else if stdout_exceeded {
+                    let message = format!(
+                        "Output limit exceeded: more than {} bytes (expected output is {} bytes)",
+                        output_limit,
+                        expected_output.len()
+                    );
+                    (TestStatus::WA, Some(message))
+                } else if judge.judge(&expected_output, &actual_output) {
+                    (TestStatus::AC, None)
                 } else {
-                    (actual_output, TestStatus::WA, None)
-                }
+                    let mismatch = first_mismatch(&expected_output, &actual_output);
+                    (TestStatus::WA, Some(mismatch))
+                };
+                (actual_output, status, error_message, elapsed, stderr)
             }
-            Err(err) => {
-                if execution_time > timeout as u128 {
-                    ("".to_string(), TestStatus::TLE, None)
-                } else {
-                    ("".to_string(), TestStatus::RE, Some(err.to_string()))
-                }
+            Outcome::TimedOut { stderr, elapsed } => {
+                ("".to_string(), TestStatus::TLE, None, elapsed, stderr)
             }
         };
+        let execution_time = execution_time.as_millis();
+        let stderr = Some(stderr).filter(|stderr| !stderr.is_empty());
 
         results.push(TestCaseResult {
             test_case_name,
             status,
             execution_time,
             error_message,
+            stderr,
+            timing: None,
         });
 
-        results
-            .last()
-            .unwrap()
-            .display_details(&input, &expected_output, &actual_output);
+        if verbose {
+            let input = fs::read_to_string(&input_file)?;
+            results
+                .last()
+                .unwrap()
+                .display_details(&input, &expected_output, &actual_output);
+        }
     }
     Ok(results)
 }
@@ -317,9 +1467,26 @@ fn return_results(
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::commands::compare::TrimJudge;
     use std::{fs, path::Path};
     use tempfile::{self, TempDir};
 
+    #[test]
+    fn test_case_result_json() {
+        let result = TestCaseResult {
+            test_case_name: "sample_1.in".to_string(),
+            status: TestStatus::TLE,
+            execution_time: 2010,
+            error_message: Some("Execution timed out".to_string()),
+            stderr: None,
+            timing: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"test_case_name":"sample_1.in","status":"TLE","execution_time":2010,"error_message":"Execution timed out"}"#
+        );
+    }
+
     #[test]
     fn find_problem_directory_success() {
         let temp_dir = tempfile::tempdir().expect("");
@@ -327,7 +1494,7 @@ mod test {
         let problem_dir = temp_dir.path().join(problem_name);
         std::fs::create_dir(&problem_dir).expect("");
         // test
-        let result = find_problem_directory(&temp_dir.path().to_path_buf(), &problem_name);
+        let result = find_problem_directory(temp_dir.path(), &problem_name);
         assert!(result.is_ok());
         let expected_path = problem_dir.canonicalize().expect("");
         let found_path = result.unwrap().canonicalize().expect("");
@@ -338,7 +1505,7 @@ mod test {
     fn find_problem_directory_failed() {
         let temp_dir = tempfile::tempdir().expect("");
         let problem_name = "test_problem";
-        let result = find_problem_directory(&temp_dir.path().to_path_buf(), &problem_name);
+        let result = find_problem_directory(temp_dir.path(), &problem_name);
 
         // test
         assert!(result.is_err());
@@ -346,6 +1513,239 @@ mod test {
         assert!(error_message.contains("does not exist"));
     }
 
+    #[test]
+    fn fail_fast_by_flags_and_config() {
+        assert!(!fail_fast_enabled(false, false, false));
+        assert!(fail_fast_enabled(true, false, false));
+        assert!(fail_fast_enabled(false, false, true));
+        // `--keep-going` は設定 `fail_fast` を無効にする
+        assert!(!fail_fast_enabled(false, true, true));
+    }
+
+    #[test]
+    fn profile_dir_by_release() {
+        assert_eq!(profile_dir(false), "debug");
+        assert_eq!(profile_dir(true), "release");
+    }
+
+    #[test]
+    fn manifest_path_of_problem_dir() {
+        let temp_dir = tempfile::tempdir().expect("");
+        let contest_dir = temp_dir.path().join("abc388");
+        fs::create_dir_all(contest_dir.join("a")).unwrap();
+        fs::write(contest_dir.join("Cargo.toml"), "[package]\n").unwrap();
+        // 問題ディレクトリからも、コンテストディレクトリの `Cargo.toml` を返す
+        let manifest = manifest_path(&contest_dir);
+        assert_eq!(manifest_path(&contest_dir.join("a")), manifest);
+        assert_eq!(
+            manifest,
+            fs::canonicalize(contest_dir.join("Cargo.toml")).unwrap()
+        );
+    }
+
+    #[test]
+    fn overall_status_and_exit_code() {
+        let result = |status| TestCaseResult {
+            test_case_name: "sample_1.in".to_string(),
+            status,
+            execution_time: 0,
+            error_message: None,
+            stderr: None,
+            timing: None,
+        };
+        assert_eq!(overall_status(&[]), TestStatus::AC);
+        assert_eq!(
+            overall_status(&[result(TestStatus::AC), result(TestStatus::WA)]),
+            TestStatus::WA
+        );
+        assert_eq!(
+            overall_status(&[
+                result(TestStatus::RE),
+                result(TestStatus::TLE),
+                result(TestStatus::WA)
+            ]),
+            TestStatus::RE
+        );
+
+        let error: Box<dyn Error> = TestFailure::Verdict(TestStatus::TLE).into();
+        assert_eq!(exit_code(error.as_ref()), 3);
+        let error: Box<dyn Error> = TestFailure::Compile.into();
+        assert_eq!(exit_code(error.as_ref()), 5);
+        let error: Box<dyn Error> = "Directory 'z' does not exist".into();
+        assert_eq!(exit_code(error.as_ref()), 1);
+        // `--remote` の場合はリモートの終了コードを引き継ぐ
+        let error: Box<dyn Error> = RemoteExit {
+            host: "user@desktop".to_string(),
+            code: Some(2),
+        }
+        .into();
+        assert_eq!(exit_code(error.as_ref()), 2);
+    }
+
+    #[test]
+    fn verdict_counts_like_atcoder() {
+        let result = |status, execution_time| TestCaseResult {
+            test_case_name: "01_random_01.txt".to_string(),
+            status,
+            execution_time,
+            error_message: None,
+            stderr: None,
+            timing: None,
+        };
+        assert_eq!(verdict_counts(&[]), "");
+        let results = [
+            result(TestStatus::TLE, 2005),
+            result(TestStatus::AC, 12),
+            result(TestStatus::WA, 30),
+            result(TestStatus::AC, 1500),
+        ];
+        assert_eq!(verdict_counts(&results), "AC × 2, WA × 1, TLE × 1");
+        assert_eq!(
+            judge_line(&results),
+            "Judge: TLE (AC × 2, WA × 1, TLE × 1), max 2005 ms"
+        );
+    }
+
+    #[test]
+    fn merge_repeated_runs_keeps_worst() {
+        let result = |status, execution_time| TestCaseResult {
+            test_case_name: "sample_1.in".to_string(),
+            status,
+            execution_time,
+            error_message: None,
+            stderr: None,
+            timing: None,
+        };
+        assert!(merge_repeated_runs(Vec::new()).is_none());
+
+        let merged = merge_repeated_runs(vec![
+            result(TestStatus::AC, 30),
+            result(TestStatus::AC, 10),
+            result(TestStatus::AC, 20),
+        ])
+        .unwrap();
+        assert_eq!(merged.status, TestStatus::AC);
+        assert_eq!(merged.execution_time, 30);
+        let timing = merged.timing.unwrap();
+        assert_eq!((timing.min, timing.max, timing.p95), (10.0, 30.0, 30.0));
+        assert_eq!(
+            timing_line(&timing, 3),
+            "Timing (x3): min 10.0 ms, mean 20.0 ms, max 30.0 ms, p95 30.0 ms"
+        );
+
+        let merged = merge_repeated_runs(vec![
+            result(TestStatus::AC, 1900),
+            result(TestStatus::TLE, 2010),
+            result(TestStatus::AC, 1950),
+        ])
+        .unwrap();
+        assert_eq!(merged.status, TestStatus::TLE);
+        assert_eq!(merged.execution_time, 2010);
+    }
+
+    #[test]
+    fn truncate_output_by_lines_and_bytes() {
+        assert_eq!(truncate_output("1\n2\n", 2, 100), "1\n2\n");
+        assert_eq!(
+            truncate_output("1\n2\n3\n4\n", 2, 100),
+            "1\n2\n... (4 more bytes, use --full-output to show all)\n"
+        );
+        assert_eq!(
+            truncate_output("1 2 3 4 5\n", 100, 4),
+            "1 2 \n... (6 more bytes, use --full-output to show all)\n"
+        );
+        // 文字の途中では切らない
+        assert_eq!(
+            truncate_output("あい", 100, 4),
+            "あ\n... (3 more bytes, use --full-output to show all)\n"
+        );
+    }
+
+    #[test]
+    fn panic_message_from_stderr() {
+        assert_eq!(
+            panic_message(
+                "debug\nthread 'main' panicked at a/main.rs:4:5:\nindex out of bounds\nnote: run with `RUST_BACKTRACE=1`\n"
+            ),
+            Some("thread 'main' panicked at a/main.rs:4:5: index out of bounds".to_string())
+        );
+        assert_eq!(
+            panic_message("thread 'main' panicked at 'boom', a/main.rs:4:5\nnote: run with\n"),
+            Some("thread 'main' panicked at 'boom', a/main.rs:4:5".to_string())
+        );
+        assert_eq!(
+            panic_message(
+                "\nthread 'main' has overflowed its stack\nfatal runtime error: stack overflow\n"
+            ),
+            Some("thread 'main' has overflowed its stack".to_string())
+        );
+        assert_eq!(panic_message("debug output\n"), None);
+    }
+
+    #[test]
+    fn truncate_lines_over_limit() {
+        assert_eq!(truncate_lines("1\n2\n", 2), "1\n2");
+        assert_eq!(
+            truncate_lines("1\n2\n3\n4\n", 2),
+            "1\n2\n... (2 more lines)"
+        );
+    }
+
+    #[test]
+    fn list_problem_bins_excludes_helpers() {
+        let temp_dir = tempfile::tempdir().expect("");
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            r#"
+[package]
+name = "abc388"
+
+[[bin]]
+name = "a"
+path = "a/main.rs"
+
+[[bin]]
+name = "b"
+path = "b/main.rs"
+
+[[bin]]
+name = "a-validator"
+path = "a/validator.rs"
+"#,
+        )
+        .unwrap();
+        assert_eq!(list_problem_bins(temp_dir.path()).unwrap(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn sort_test_cases_naturally() {
+        let mut test_cases: Vec<(PathBuf, PathBuf)> = [
+            "sample_10.in",
+            "custom.in",
+            "sample_2.in",
+            "sample_1.in",
+            "sample_02.in",
+        ]
+        .iter()
+        .map(|name| (PathBuf::from(name), PathBuf::new()))
+        .collect();
+        sort_test_cases(&mut test_cases);
+        let names: Vec<&str> = test_cases
+            .iter()
+            .map(|(input, _)| input.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "custom.in",
+                "sample_1.in",
+                "sample_2.in",
+                "sample_02.in",
+                "sample_10.in"
+            ]
+        );
+    }
+
     #[test]
     fn compile_success() {
         let temp_dir = tempfile::tempdir().expect("");
@@ -373,6 +1773,56 @@ mod test {
         // test
         let result = compile(temp_dir.path());
         assert!(result.is_ok());
+        let executable = get_execution_path(temp_dir.path(), "test_project_success").unwrap();
+        assert!(executable.is_file());
+    }
+
+    #[test]
+    fn parse_executables_from_messages() {
+        let messages = [
+            r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"proconio"},"executable":null,"fresh":true}"#,
+            r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"a"},"executable":"/tmp/target/debug/a","fresh":false}"#,
+            r#"{"reason":"build-finished","success":true}"#,
+        ]
+        .join("\n");
+        let executables = parse_executables(&messages);
+        assert_eq!(executables.len(), 1);
+        assert_eq!(executables["a"], PathBuf::from("/tmp/target/debug/a"));
+    }
+
+    #[test]
+    fn local_time_limit_with_multiplier() {
+        assert_eq!(local_time_limit(2000, 1.0, 0), 2000);
+        assert_eq!(local_time_limit(2000, 2.0, 0), 4000);
+        assert_eq!(local_time_limit(2000, 1.5, 100), 3100);
+    }
+
+    #[test]
+    fn condense_compile_errors() {
+        let message = |level: &str, rendered: &str, spans: &str| {
+            format!(
+                r#"{{"reason":"compiler-message","message":{{"level":"{}","rendered":"{}","spans":{}}}}}"#,
+                level, rendered, spans
+            )
+        };
+        let span = r#"[{"file_name":"a/main.rs"}]"#;
+        let messages = [
+            message("warning", "warning: unused variable: `n`\\n", span),
+            message("error", "error[E0425]: cannot find value `x`\\n", span),
+            message("error", "error[E0308]: mismatched types\\n", span),
+            message("error", "error[E0599]: no method named `foo`\\n", span),
+            message("error", "error[E0433]: failed to resolve\\n", span),
+            message("error", "error: aborting due to 4 previous errors\\n", "[]"),
+        ]
+        .join("\n");
+        let diagnostics = parse_diagnostics(&messages);
+        assert_eq!(diagnostics.len(), 5);
+        assert_eq!(diagnostics[0].rendered, "warning: unused variable: `n`");
+
+        assert_eq!(
+            condense_diagnostics(&diagnostics, false),
+            "error[E0425]: cannot find value `x`\n\nerror[E0308]: mismatched types\n\nerror[E0599]: no method named `foo`\n\n... and 1 more error\nCompilation failed: 4 errors, 1 warning"
+        );
     }
 
     #[test]
@@ -429,6 +1879,44 @@ mod test {
         assert_eq!(test_cases.len(), 0);
     }
 
+    #[test]
+    fn select_test_case_by_name() {
+        let test_cases = vec![
+            (
+                PathBuf::from("tests/sample_1.in"),
+                PathBuf::from("tests/sample_1.out"),
+            ),
+            (
+                PathBuf::from("tests/sample_2.in"),
+                PathBuf::from("tests/sample_2.out"),
+            ),
+            (
+                PathBuf::from("tests/custom.in"),
+                PathBuf::from("tests/custom.out"),
+            ),
+        ];
+        let expected = PathBuf::from("tests/sample_2.in");
+        assert_eq!(
+            select_test_case(test_cases.clone(), "2").unwrap().0,
+            expected
+        );
+        assert_eq!(
+            select_test_case(test_cases.clone(), "sample_2").unwrap().0,
+            expected
+        );
+        assert_eq!(
+            select_test_case(test_cases.clone(), "sample_2.in")
+                .unwrap()
+                .0,
+            expected
+        );
+        assert_eq!(
+            select_test_case(test_cases.clone(), "custom").unwrap().0,
+            PathBuf::from("tests/custom.in")
+        );
+        assert!(select_test_case(test_cases, "3").is_err());
+    }
+
     #[test]
     fn load_problem_timeout_settings_success() {
         let temp_dir = tempfile::tempdir().expect("");
@@ -448,8 +1936,7 @@ mod test {
         fs::write(cargo_toml_path, cargo_toml_content).unwrap();
 
         // テスト
-        let timeout_settings =
-            load_problem_timeout_settings(&temp_dir.path().to_path_buf()).unwrap();
+        let timeout_settings = load_problem_timeout_settings(temp_dir.path()).unwrap();
         assert_eq!(timeout_settings.get("a"), Some(&2000));
         assert_eq!(timeout_settings.get("b"), Some(&4000));
     }
@@ -474,11 +1961,37 @@ mod test {
 
         // テスト
 
-        let timeout_settings =
-            load_problem_timeout_settings(&temp_dir.path().to_path_buf()).unwrap();
+        let timeout_settings = load_problem_timeout_settings(temp_dir.path()).unwrap();
         assert!(timeout_settings.get("c").is_none());
     }
 
+    #[test]
+    fn load_problem_timeout_settings_from_contest_json() {
+        let temp_dir = tempfile::tempdir().expect("");
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\n\n[package.metadata.timeout]\na = 2000\nb = 4000\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("contest.json"),
+            r#"{"contest": "abc388", "problems": [{"problem": "b", "timeout": 3000, "memory_limit": 1024, "score": 200}]}"#,
+        )
+        .unwrap();
+
+        let timeout_settings = load_problem_timeout_settings(temp_dir.path()).unwrap();
+        assert_eq!(timeout_settings.get("a"), Some(&2000));
+        assert_eq!(timeout_settings.get("b"), Some(&3000));
+
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\n",
+        )
+        .unwrap();
+        let timeout_settings = load_problem_timeout_settings(temp_dir.path()).unwrap();
+        assert_eq!(timeout_settings.get("b"), Some(&3000));
+    }
+
     /// テスト環境構築
     fn setup_test_environment(
         work_dir: &TempDir,
@@ -548,7 +2061,7 @@ fn main() {
     }
 
     #[test]
-    fn return_results_ac() {
+    fn run_test_cases_ac() {
         let work_dir = tempfile::tempdir().expect("");
 
         // テスト環境をセットアップ
@@ -568,11 +2081,13 @@ fn main() {
         let _ = compile(&work_dir.path());
 
         // テスト結果を確認
-        let results = return_results(
+        let results = run_test_cases(
             &work_dir.path().to_path_buf(),
             test_cases,
             problem_name,
             &timeout_settings,
+            &TrimJudge,
+            true,
         );
         assert!(results.is_ok());
         let results = results.unwrap();
@@ -584,7 +2099,7 @@ fn main() {
     }
 
     #[test]
-    fn return_results_wa() {
+    fn run_test_cases_wa() {
         let work_dir = tempfile::tempdir().expect("");
 
         // テスト環境をセットアップ
@@ -604,11 +2119,13 @@ fn main() {
         let _ = compile(&work_dir.path());
 
         // テスト結果を確認
-        let results = return_results(
+        let results = run_test_cases(
             &work_dir.path().to_path_buf(),
             test_cases,
             problem_name,
             &timeout_settings,
+            &TrimJudge,
+            true,
         );
         assert!(results.is_ok());
         let results = results.unwrap();
@@ -620,7 +2137,7 @@ fn main() {
     }
 
     #[test]
-    fn return_results_tle() {
+    fn run_test_cases_tle() {
         let work_dir = tempfile::tempdir().expect("");
 
         // テスト環境をセットアップ
@@ -631,6 +2148,12 @@ fn main() {
             problem_name,
             5,
         );
+        // 実行時間制限を確実に超えるよう、1 秒待機してから終了するプログラムに置き換える
+        fs::write(
+            work_dir.path().join(problem_name).join("main.rs"),
+            "fn main() {\n    std::thread::sleep(std::time::Duration::from_secs(1));\n}\n",
+        )
+        .unwrap();
 
         // テストケース収集
         let problem_dir = &work_dir.path().join(problem_name);
@@ -640,11 +2163,13 @@ fn main() {
         let _ = compile(&work_dir.path());
 
         // テスト結果を確認
-        let results = return_results(
+        let results = run_test_cases(
             &work_dir.path().to_path_buf(),
             test_cases,
             problem_name,
             &timeout_settings,
+            &TrimJudge,
+            true,
         );
         assert!(results.is_ok());
         let results = results.unwrap();
@@ -656,7 +2181,7 @@ fn main() {
     }
 
     #[test]
-    fn return_results_re() {
+    fn run_test_cases_re() {
         let work_dir = tempfile::tempdir().expect("");
 
         // テスト環境をセットアップ
@@ -676,11 +2201,13 @@ fn main() {
         let _ = compile(&work_dir.path());
 
         // テスト結果を確認
-        let results = return_results(
+        let results = run_test_cases(
             &work_dir.path().to_path_buf(),
             test_cases,
             problem_name,
             &timeout_settings,
+            &TrimJudge,
+            true,
         );
         assert!(results.is_ok());
         let results = results.unwrap();
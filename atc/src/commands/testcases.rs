@@ -0,0 +1,232 @@
+//! コンテスト終了後に公開される公式のテストケースを取得するモジュール
+//!
+//! AtCoder はコンテスト終了後、全テストケースを Dropbox の共有フォルダで公開している。
+//! 共有フォルダは `<コンテスト名(大文字)>/<問題のラベル>/{in,out}/<テストケース名>.txt` の構成のため、
+//! 問題のフォルダを zip 形式で取得し、問題ディレクトリの `tests/system/` に展開する。
+//! 共有フォルダの URL は設定 `testcase_url` で変更できる。
+//!
+//! 展開したテストケースは `test --system` で実行する。`tests/` 直下のサンプルとは別のディレクトリのため、
+//! 通常の `test` では実行しない。
+//!
+//! ```text
+//! a
+//! └── tests
+//!     ├── sample_1.in
+//!     ├── sample_1.out
+//!     └── system
+//!         ├── 01_random_00.in
+//!         └── 01_random_00.out
+//! ```
+use std::{
+    error::Error,
+    fs,
+    io::{Cursor, Read},
+    path::{Component, Path},
+    time::Duration,
+};
+use zip::ZipArchive;
+
+use super::config::get_testcase_url;
+use super::http::client_builder;
+use super::output::is_json;
+use super::submit::get_contest_name;
+use super::test::find_problem_directory;
+
+/// 公式のテストケースを公開している Dropbox の共有フォルダ
+pub const TESTCASE_ARCHIVE_URL: &str =
+    "https://www.dropbox.com/sh/nx3tnilzqz7df8a/AAAYlTq2tiEHl5hsESw6-yfLa";
+
+/// 公式のテストケースを展開するディレクトリ (問題ディレクトリからの相対パス)
+pub const SYSTEM_TESTS_DIR: &str = "tests/system";
+
+/// アーカイブのダウンロードのタイムアウト (テストケースが大きい問題があるため長くする)
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// テストケース取得処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在するコンテストディレクトリのパス
+/// - `problem_name`: 問題名 (`a`, `ex` など)
+/// - `force`: `true` の場合、取得済みのテストケースを削除して取得し直す
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない場合
+/// - テストケースが公開されていない、またはアーカイブの取得・展開に失敗した場合
+pub async fn execute(
+    work_dir: &Path,
+    problem_name: &str,
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let system_dir = problem_dir.join(SYSTEM_TESTS_DIR);
+    if system_dir.is_dir() && !force {
+        return Err(format!(
+            "テストケースは取得済みです: {} (取得し直す場合は --force を指定してください)",
+            system_dir.display()
+        )
+        .into());
+    }
+
+    let contest_name = get_contest_name(work_dir)?;
+    let url = archive_url(&get_testcase_url(), &contest_name, problem_name);
+    if !is_json() {
+        eprintln!("[{}] fetching {}", problem_name, url);
+    }
+    let response = client_builder()?
+        .timeout(DOWNLOAD_TIMEOUT)
+        .build()?
+        .get(&url)
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "{} の {} のテストケースを取得できませんでした (HTTP {})。コンテスト終了後に公開されるまでお待ちください",
+            contest_name,
+            problem_name,
+            response.status()
+        )
+        .into());
+    }
+    let archive = response.bytes().await?;
+
+    if system_dir.exists() {
+        fs::remove_dir_all(&system_dir)?;
+    }
+    let count = extract_testcases(&archive, &system_dir)?;
+    if count == 0 {
+        fs::remove_dir_all(&system_dir)?;
+        return Err(format!("{} にテストケースが含まれていません", url).into());
+    }
+    if !is_json() {
+        println!(
+            "[{}] {} test cases written to {}",
+            problem_name,
+            count,
+            system_dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// 問題のフォルダを zip 形式で取得する URL を返す
+///
+/// 共有フォルダ内のフォルダ名は、コンテスト名は大文字(`ABC388`)、問題のラベルは先頭のみ大文字(`A`, `Ex`)となる。
+fn archive_url(base_url: &str, contest_name: &str, problem_name: &str) -> String {
+    let mut chars = problem_name.chars();
+    let label: String = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default();
+    format!(
+        "{}/{}/{}?dl=1",
+        base_url.trim_end_matches('/'),
+        contest_name.to_uppercase(),
+        label
+    )
+}
+
+/// zip アーカイブの `in/`・`out/` のファイルを `<テストケース名>.in`・`<テストケース名>.out` として展開する
+///
+/// # 戻り値
+/// - `Ok(usize)`: 展開した入力ファイルの数
+///
+/// # エラーの可能性
+/// - zip アーカイブの読み込みに失敗した場合
+/// - ファイルの書き込みに失敗した場合
+fn extract_testcases(archive: &[u8], system_dir: &Path) -> Result<usize, Box<dyn Error>> {
+    let mut archive = ZipArchive::new(Cursor::new(archive))
+        .map_err(|e| format!("テストケースのアーカイブを展開できません: {}", e))?;
+    fs::create_dir_all(system_dir)?;
+    let mut count = 0;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+        let Some(path) = file.enclosed_name() else {
+            continue;
+        };
+        let components: Vec<String> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+        let [.., kind, name] = components.as_slice() else {
+            continue;
+        };
+        let extension = match kind.as_str() {
+            "in" => "in",
+            "out" => "out",
+            _ => continue,
+        };
+        let stem = Path::new(name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| name.clone());
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)?;
+        fs::write(system_dir.join(format!("{}.{}", stem, extension)), content)?;
+        if extension == "in" {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+    use tempfile;
+    use zip::{write::SimpleFileOptions, ZipWriter};
+
+    fn zip_archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_archive_url() {
+        assert_eq!(
+            archive_url(TESTCASE_ARCHIVE_URL, "abc388", "a"),
+            format!("{}/ABC388/A?dl=1", TESTCASE_ARCHIVE_URL)
+        );
+        assert_eq!(
+            archive_url("http://localhost/", "abc300", "ex"),
+            "http://localhost/ABC300/Ex?dl=1"
+        );
+    }
+
+    #[test]
+    fn test_extract_testcases() {
+        let problem_dir = tempfile::tempdir().expect("");
+        let system_dir = problem_dir.path().join(SYSTEM_TESTS_DIR);
+        let archive = zip_archive(&[
+            ("in/01_random_00.txt", "3\n"),
+            ("out/01_random_00.txt", "6\n"),
+            ("in/sample_01", "1\n"),
+            ("out/sample_01", "2\n"),
+            ("README.txt", "ignored"),
+        ]);
+
+        assert_eq!(extract_testcases(&archive, &system_dir).unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(system_dir.join("01_random_00.in")).unwrap(),
+            "3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(system_dir.join("sample_01.out")).unwrap(),
+            "2\n"
+        );
+        assert!(!system_dir.join("README.txt").exists());
+        assert!(extract_testcases(b"not a zip", &system_dir).is_err());
+    }
+}
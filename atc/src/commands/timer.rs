@@ -0,0 +1,189 @@
+//! 過去のコンテストを本番と同じ制限時間で解くためのバーチャルコンテストのタイマーを管理するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - バーチャルコンテストの開始・終了(`execute_start` / `execute_stop`)
+//! - 残り時間の表示(`execute_status`、`print_remaining`)
+//!
+//! 開始時刻と制限時間はコンテストディレクトリ直下の以下のファイルに保存される。
+//! `test` と `submit` は、このファイルが存在する場合に残り時間を表示し、終了後は警告を表示する。
+//! ```text
+//! .
+//! ├── Cargo.toml
+//! └── .atc
+//!     └── virtual.json  # バーチャルコンテストの開始時刻と制限時間
+//! ```
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// バーチャルコンテストの状態ファイルのコンテストディレクトリからの相対パス
+const VIRTUAL_CONTEST_FILE: &str = ".atc/virtual.json";
+
+/// バーチャルコンテストの状態
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct VirtualContest {
+    pub start: u64,    // 開始時刻(UNIX時間)
+    pub duration: u64, // 制限時間(秒)
+}
+
+impl VirtualContest {
+    /// 状態をロードする。ファイルが存在しない場合は `None` を返す。
+    pub fn load(path: &Path) -> Result<Option<Self>, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read_to_string(path)?;
+        Ok(Some(serde_json::from_str(&data)?))
+    }
+
+    /// 状態を保存する
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 指定した時刻における残り時間(秒)を返す。終了後は負の値となる。
+    pub fn remaining(&self, now: u64) -> i64 {
+        (self.start + self.duration) as i64 - now as i64
+    }
+}
+
+/// バーチャルコンテストの開始処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+/// - `minutes`: 制限時間(分)
+///
+/// # エラーの可能性
+/// - 既にバーチャルコンテストが開始されている場合
+pub fn execute_start(work_dir: &Path, minutes: u64) -> Result<(), Box<dyn Error>> {
+    let path = work_dir.join(VIRTUAL_CONTEST_FILE);
+    if VirtualContest::load(&path)?.is_some() {
+        return Err(
+            "バーチャルコンテストは既に開始されています (終了するには `timer stop`)".into(),
+        );
+    }
+    let contest = VirtualContest {
+        start: now()?,
+        duration: minutes * 60,
+    };
+    contest.save(&path)?;
+    println!("Virtual contest started: {} minutes", minutes);
+    Ok(())
+}
+
+/// バーチャルコンテストの残り時間の表示処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+pub fn execute_status(work_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let contest = VirtualContest::load(&work_dir.join(VIRTUAL_CONTEST_FILE))?
+        .ok_or("バーチャルコンテストが開始されていません")?;
+    let now = now()?;
+    println!(
+        "Elapsed: {}",
+        format_clock(now.saturating_sub(contest.start) as i64)
+    );
+    println!("{}", remaining_message(&contest, now));
+    Ok(())
+}
+
+/// バーチャルコンテストの終了処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリのパス
+pub fn execute_stop(work_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let path = work_dir.join(VIRTUAL_CONTEST_FILE);
+    let contest = VirtualContest::load(&path)?.ok_or("バーチャルコンテストが開始されていません")?;
+    fs::remove_file(&path)?;
+    println!(
+        "Virtual contest stopped (elapsed: {})",
+        format_clock(now()?.saturating_sub(contest.start) as i64)
+    );
+    Ok(())
+}
+
+/// バーチャルコンテスト中であれば残り時間を表示する
+///
+/// `test` や `submit` から呼び出される。状態ファイルが存在しない、または読み込めない場合は何も表示しない。
+pub fn print_remaining(work_dir: &Path) {
+    if let (Ok(Some(contest)), Ok(now)) = (
+        VirtualContest::load(&work_dir.join(VIRTUAL_CONTEST_FILE)),
+        now(),
+    ) {
+        if contest.remaining(now) > 0 {
+            println!("{}", remaining_message(&contest, now));
+        } else {
+            eprintln!("Warning: {}", remaining_message(&contest, now));
+        }
+    }
+}
+
+/// 残り時間、または終了後の経過時間を表すメッセージを返す
+fn remaining_message(contest: &VirtualContest, now: u64) -> String {
+    let remaining = contest.remaining(now);
+    if remaining > 0 {
+        format!("Virtual contest: {} remaining", format_clock(remaining))
+    } else {
+        format!(
+            "Virtual contest has ended ({} ago)",
+            format_clock(-remaining)
+        )
+    }
+}
+
+/// 秒数を `H:MM:SS` 形式に変換する
+fn format_clock(secs: i64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+fn now() -> Result<u64, Box<dyn Error>> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_start_and_stop() {
+        let work_dir = tempfile::tempdir().expect("");
+        let work_dir_path = work_dir.path().to_path_buf();
+        let path = work_dir.path().join(VIRTUAL_CONTEST_FILE);
+
+        assert!(execute_status(&work_dir_path).is_err());
+        execute_start(&work_dir_path, 100).unwrap();
+        let contest = VirtualContest::load(&path).unwrap().unwrap();
+        assert_eq!(contest.duration, 6000);
+        // 二重に開始することはできない
+        assert!(execute_start(&work_dir_path, 100).is_err());
+
+        execute_stop(&work_dir_path).unwrap();
+        assert!(!path.exists());
+        assert!(execute_stop(&work_dir_path).is_err());
+    }
+
+    #[test]
+    fn test_remaining_message() {
+        let contest = VirtualContest {
+            start: 1000,
+            duration: 6000,
+        };
+        assert_eq!(
+            remaining_message(&contest, 1000),
+            "Virtual contest: 1:40:00 remaining"
+        );
+        assert_eq!(
+            remaining_message(&contest, 7065),
+            "Virtual contest has ended (0:01:05 ago)"
+        );
+    }
+}
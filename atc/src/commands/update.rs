@@ -0,0 +1,179 @@
+//! ダウンロード済みのコンテストのサンプル入出力を更新するモジュール
+//!
+//! コンテスト中・終了後にサンプルが修正された場合に利用する。
+//! 問題ページを再取得し、`tests/sample_{i}.in` / `tests/sample_{i}.out` のみを更新する。
+//! `main.rs` や `Cargo.toml`、`sample_*` 以外のテストケースは変更しない。
+//!
+//! `download` と同様に、コンテストディレクトリの親ディレクトリで実行する。
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs,
+    path::Path,
+};
+
+use super::config::get_base_url;
+use super::download::{get_problem_list, Sample};
+
+/// サンプル更新処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: コンテストディレクトリを含む作業ディレクトリ
+/// - `contest_name`: コンテスト名 (`abc388` など)
+///
+/// # エラーの可能性
+/// - コンテストディレクトリが存在しない場合
+/// - 問題ページの取得・解析に失敗した場合
+pub async fn execute(work_dir: &Path, contest_name: &str) -> Result<(), Box<dyn Error>> {
+    let contest_dir = work_dir.join(contest_name);
+    if !contest_dir.is_dir() {
+        return Err(format!(
+            "コンテストディレクトリ {} が存在しません",
+            contest_dir.display()
+        )
+        .into());
+    }
+
+    let contest_info = get_problem_list(&get_base_url(), contest_name).await?;
+    let mut total = 0;
+    for problem in &contest_info.problems {
+        let problem_dir = contest_dir.join(&problem.problem_name);
+        if !problem_dir.is_dir() {
+            println!("Skipping {} (directory not found)", problem.problem_name);
+            continue;
+        }
+        let changes = update_samples(&problem_dir.join("tests"), &problem.samples)?;
+        for change in &changes {
+            println!("{}: {}", problem.problem_name, change);
+        }
+        total += changes.len();
+    }
+    if total == 0 {
+        println!("All samples are up to date");
+    } else {
+        println!("Updated {} sample file(s)", total);
+    }
+    Ok(())
+}
+
+/// サンプルファイルの変更内容
+#[derive(Debug, PartialEq)]
+pub enum SampleChange {
+    /// 新たに追加されたファイル
+    Added(String),
+    /// 内容が変更されたファイル
+    Modified(String),
+    /// 取得したサンプルに存在しないため削除したファイル
+    Removed(String),
+}
+
+impl Display for SampleChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SampleChange::Added(name) => write!(f, "added    {}", name),
+            SampleChange::Modified(name) => write!(f, "modified {}", name),
+            SampleChange::Removed(name) => write!(f, "removed  {}", name),
+        }
+    }
+}
+
+/// テストディレクトリのサンプルファイルを取得したサンプルで更新する
+///
+/// # 引数
+/// - `tests_dir`: 問題の `tests` ディレクトリ
+/// - `samples`: 問題ページから取得したサンプル入出力
+///
+/// # 戻り値
+/// 変更したファイルの一覧 (内容が同一のファイルは含まない)
+pub fn update_samples(
+    tests_dir: &Path,
+    samples: &[Sample],
+) -> Result<Vec<SampleChange>, Box<dyn Error>> {
+    fs::create_dir_all(tests_dir)?;
+    let mut changes = Vec::new();
+    for (i, sample) in samples.iter().enumerate() {
+        for (extension, content) in [("in", &sample.input), ("out", &sample.output)] {
+            let file_name = format!("sample_{}.{}", i + 1, extension);
+            let path = tests_dir.join(&file_name);
+            let change = match fs::read_to_string(&path) {
+                Ok(current) if current == *content => continue,
+                Ok(_) => SampleChange::Modified(file_name),
+                Err(_) => SampleChange::Added(file_name),
+            };
+            fs::write(&path, content)?;
+            changes.push(change);
+        }
+    }
+
+    // 取得したサンプルより番号が大きいサンプルファイルを削除する
+    let mut removed = Vec::new();
+    for entry in fs::read_dir(tests_dir)? {
+        let file_name = entry?.file_name().to_string_lossy().to_string();
+        let index = file_name
+            .strip_prefix("sample_")
+            .and_then(|rest| rest.split_once('.'))
+            .filter(|(_, extension)| *extension == "in" || *extension == "out")
+            .and_then(|(index, _)| index.parse::<usize>().ok());
+        if index.is_some_and(|index| index > samples.len()) {
+            fs::remove_file(tests_dir.join(&file_name))?;
+            removed.push(file_name);
+        }
+    }
+    removed.sort();
+    changes.extend(removed.into_iter().map(SampleChange::Removed));
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    fn sample(input: &str, output: &str) -> Sample {
+        Sample {
+            input: input.to_string(),
+            output: output.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_update_samples() {
+        let work_dir = tempfile::tempdir().expect("");
+        let tests_dir = work_dir.path().join("a/tests");
+        fs::create_dir_all(&tests_dir).unwrap();
+        fs::write(tests_dir.join("sample_1.in"), "1\n").unwrap();
+        fs::write(tests_dir.join("sample_1.out"), "2\n").unwrap();
+        fs::write(tests_dir.join("sample_2.in"), "3\n").unwrap();
+        fs::write(tests_dir.join("sample_2.out"), "5\n").unwrap();
+        fs::write(tests_dir.join("sample_3.in"), "5\n").unwrap();
+        fs::write(tests_dir.join("sample_3.out"), "10\n").unwrap();
+        fs::write(tests_dir.join("random_1.in"), "7\n").unwrap();
+        fs::write(work_dir.path().join("a/main.rs"), "fn main() {}").unwrap();
+
+        let changes =
+            update_samples(&tests_dir, &[sample("1\n", "2\n"), sample("3\n", "6\n")]).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                SampleChange::Modified("sample_2.out".to_string()),
+                SampleChange::Removed("sample_3.in".to_string()),
+                SampleChange::Removed("sample_3.out".to_string()),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(tests_dir.join("sample_2.out")).unwrap(),
+            "6\n"
+        );
+        assert!(!tests_dir.join("sample_3.in").exists());
+        assert!(tests_dir.join("random_1.in").exists());
+        assert_eq!(
+            fs::read_to_string(work_dir.path().join("a/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+
+        // 変更がない場合は何も報告しない
+        let changes =
+            update_samples(&tests_dir, &[sample("1\n", "2\n"), sample("3\n", "6\n")]).unwrap();
+        assert!(changes.is_empty());
+    }
+}
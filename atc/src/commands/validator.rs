@@ -0,0 +1,263 @@
+//! 入力ファイルを問題の制約に照らして検証するモジュール
+//!
+//! このモジュールには以下の機能が含まれる。
+//! - `validator.rs` のバイナリ定義の追加(`ensure_validator_bin`)
+//! - 補助プログラムのバイナリ定義の追加(`ensure_problem_bin`)
+//! - 追加・変更された入力ファイルの検証(`validate_inputs`)
+//!
+//! 問題ディレクトリに `validator.rs` を配置すると、`test` の実行時に入力ファイルが検証される。
+//! `validator.rs` は標準入力から入力ファイルの内容を読み込み、制約を満たす場合は終了コード 0 で、
+//! 満たさない場合は 0 以外(`panic!`、`assert!` など)で終了するプログラムとして実装する。
+//!
+//! ```text
+//! .
+//! ├── Cargo.toml            # `{problem_name}-validator` のバイナリ定義が自動で追加される
+//! └── problem_name
+//!     ├── main.rs
+//!     ├── validator.rs      # 入力ファイルの検証プログラム
+//!     ├── .atc
+//!     │   └── validated.json  # 検証済み入力ファイルのハッシュ値
+//!     └── tests
+//!         └── sample_1.in
+//! ```
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+use toml::Value;
+
+use super::score::hash_bytes;
+use super::test::get_execution_path;
+
+/// 検証プログラムのファイル名
+pub const VALIDATOR_FILE: &str = "validator.rs";
+
+/// 検証済み入力ファイルのハッシュ値を記録するファイルの問題ディレクトリからの相対パス
+const VALIDATED_CACHE_FILE: &str = ".atc/validated.json";
+
+/// 検証プログラムのバイナリ名を返す
+pub fn validator_bin_name(problem_name: &str) -> String {
+    format!("{}-validator", problem_name)
+}
+
+/// `validator.rs` が存在する場合、`Cargo.toml` に検証プログラムのバイナリ定義を追加する
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名
+///
+/// # 戻り値
+/// - `Ok(true)`: `validator.rs` が存在する場合(バイナリ定義は追加済み)
+/// - `Ok(false)`: `validator.rs` が存在しない場合
+/// - `Err(Box<dyn Error>)`: `Cargo.toml` の読み書きに失敗した場合
+pub fn ensure_validator_bin(work_dir: &Path, problem_name: &str) -> Result<bool, Box<dyn Error>> {
+    ensure_problem_bin(
+        work_dir,
+        problem_name,
+        VALIDATOR_FILE,
+        &validator_bin_name(problem_name),
+    )
+}
+
+/// 問題ディレクトリに補助プログラムのファイルが存在する場合、`Cargo.toml` にバイナリ定義を追加する
+///
+/// `validator.rs` の他、`gen.rs` や `naive.rs` などの補助プログラムにも利用する。
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `file_name`: 問題ディレクトリ内のソースファイル名
+/// - `bin_name`: 追加するバイナリ名
+///
+/// # 戻り値
+/// - `Ok(true)`: ソースファイルが存在する場合(バイナリ定義は追加済み)
+/// - `Ok(false)`: ソースファイルが存在しない場合
+/// - `Err(Box<dyn Error>)`: `Cargo.toml` の読み書きに失敗した場合
+pub fn ensure_problem_bin(
+    work_dir: &Path,
+    problem_name: &str,
+    file_name: &str,
+    bin_name: &str,
+) -> Result<bool, Box<dyn Error>> {
+    if !work_dir.join(problem_name).join(file_name).exists() {
+        return Ok(false);
+    }
+    let cargo_toml_path = work_dir.join("Cargo.toml");
+    let parsed: Value = toml::from_str(&fs::read_to_string(&cargo_toml_path)?)?;
+    let registered = parsed
+        .get("bin")
+        .and_then(|b| b.as_array())
+        .map(|bins| {
+            bins.iter()
+                .any(|bin| bin.get("name").and_then(|n| n.as_str()) == Some(bin_name))
+        })
+        .unwrap_or(false);
+    if !registered {
+        let mut file = OpenOptions::new().append(true).open(&cargo_toml_path)?;
+        write!(
+            file,
+            "\n[[bin]]\nname = \"{}\"\npath = \"{}/{}\"\n",
+            bin_name, problem_name, file_name
+        )?;
+    }
+    Ok(true)
+}
+
+/// 追加・変更された入力ファイルを検証プログラムで検証する
+///
+/// 一度検証に成功した入力ファイルはハッシュ値を記録し、内容が変わらない限り再検証しない。
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名
+/// - `inputs`: 検証対象の入力ファイルのパス
+///
+/// # 戻り値
+/// - `Ok(())`: すべての入力ファイルが制約を満たす場合
+/// - `Err(Box<dyn Error>)`: 制約を満たさない入力ファイルが存在する場合、または検証プログラムの実行に失敗した場合
+pub fn validate_inputs(
+    work_dir: &Path,
+    problem_name: &str,
+    inputs: &[PathBuf],
+) -> Result<(), Box<dyn Error>> {
+    let validator = get_execution_path(work_dir, &validator_bin_name(problem_name))?;
+    let cache_path = work_dir.join(problem_name).join(VALIDATED_CACHE_FILE);
+    let mut cache: BTreeMap<String, String> = if cache_path.exists() {
+        serde_json::from_str(&fs::read_to_string(&cache_path)?).unwrap_or_default()
+    } else {
+        BTreeMap::new()
+    };
+
+    let mut invalid = Vec::new();
+    for input_path in inputs {
+        let content = fs::read(input_path)?;
+        let key = input_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let hash = format!("{:016x}", hash_bytes(&content));
+        if cache.get(&key) == Some(&hash) {
+            continue;
+        }
+
+        let mut child = Command::new(&validator)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // 検証プログラムが入力を読み切らずに終了した場合の書き込みエラーは無視する
+            let _ = stdin.write_all(&content);
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            cache.insert(key, hash);
+        } else {
+            let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            invalid.push(format!("{}: {}", key, message));
+        }
+    }
+
+    if let Some(parent) = cache_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)?;
+
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "制約を満たさない入力ファイルがあります:\n  {}",
+            invalid.join("\n  ")
+        )
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    fn setup_validator_project(work_dir: &Path, problem_name: &str) {
+        fs::write(
+            work_dir.join("Cargo.toml"),
+            format!(
+                r#"
+[package]
+name = "validator_test"
+version = "0.1.0"
+edition = "2021"
+
+[[bin]]
+name = "{}"
+path = "{}/main.rs"
+"#,
+                problem_name, problem_name
+            ),
+        )
+        .unwrap();
+        let problem_dir = work_dir.join(problem_name);
+        fs::create_dir_all(problem_dir.join("tests")).unwrap();
+        fs::write(problem_dir.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(
+            problem_dir.join(VALIDATOR_FILE),
+            r#"
+use std::io::Read;
+fn main() {
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input).unwrap();
+    let n: u32 = input.trim().parse().expect("n must be an integer");
+    assert!(n <= 10, "n must be at most 10");
+}
+"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_ensure_validator_bin() {
+        let work_dir = tempfile::tempdir().expect("");
+        setup_validator_project(work_dir.path(), "a");
+
+        assert!(ensure_validator_bin(work_dir.path(), "a").unwrap());
+        // 2回目の呼び出しでは重複して追加しない
+        assert!(ensure_validator_bin(work_dir.path(), "a").unwrap());
+        let content = fs::read_to_string(work_dir.path().join("Cargo.toml")).unwrap();
+        assert_eq!(content.matches("name = \"a-validator\"").count(), 1);
+        assert!(content.contains("path = \"a/validator.rs\""));
+
+        assert!(!ensure_validator_bin(work_dir.path(), "b").unwrap());
+    }
+
+    #[test]
+    fn test_validate_inputs() {
+        let work_dir = tempfile::tempdir().expect("");
+        setup_validator_project(work_dir.path(), "a");
+        ensure_validator_bin(work_dir.path(), "a").unwrap();
+        let status = Command::new("cargo")
+            .arg("build")
+            .current_dir(work_dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let valid = work_dir.path().join("a/tests/sample_1.in");
+        let invalid = work_dir.path().join("a/tests/random_1.in");
+        fs::write(&valid, "5\n").unwrap();
+        fs::write(&invalid, "100\n").unwrap();
+
+        let work_dir_path = work_dir.path().to_path_buf();
+        assert!(validate_inputs(&work_dir_path, "a", std::slice::from_ref(&valid)).is_ok());
+        let result = validate_inputs(&work_dir_path, "a", &[valid, invalid]);
+        assert!(result.is_err());
+        let error_message = result.unwrap_err().to_string();
+        assert!(error_message.contains("random_1.in"));
+        assert!(!error_message.contains("sample_1.in"));
+    }
+}
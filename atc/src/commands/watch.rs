@@ -0,0 +1,226 @@
+//! `main.rs` の保存を監視し、コンパイルとサンプルテストを自動で再実行するモジュール
+//!
+//! 問題ディレクトリを監視し、`main.rs` が変更されるたびに以下を行う。
+//! 1. 画面を消去し、`cargo build` を実行する (コンパイルエラーの場合はエラー出力のみを表示)
+//! 2. `tests/sample_*.in` のテストケースを実行し、結果を 1 行ずつの要約で表示する
+//!
+//! エディタの保存処理では短時間に複数のイベントが発生するため、
+//! 最後のイベントから `DEBOUNCE` の間イベントがなければ再実行する。
+//! `Ctrl-C` で終了する。
+use chrono::Local;
+use notify::{Event, RecursiveMode, Watcher};
+use std::{
+    collections::HashMap,
+    error::Error,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::mpsc,
+    time::Duration,
+};
+
+use super::compare::load_compare_mode;
+use super::config::{get_timeout_margin, get_timeout_multiplier};
+use super::test::{
+    collect_test_cases, find_problem_directory, load_problem_timeout_settings, local_time_limit,
+    run_test_cases, TestCaseResult, TestStatus,
+};
+
+/// 再実行までに待機する時間
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 監視処理のエントリーポイント
+///
+/// # 引数
+/// - `work_dir`: `Cargo.toml` が存在する作業ディレクトリのパス
+/// - `problem_name`: 問題名 (`a`, `b`, `c` など)
+///
+/// # エラーの可能性
+/// - 問題ディレクトリが存在しない場合
+/// - `Cargo.toml` に実行時間制限が設定されていない場合
+/// - ファイルの監視を開始できない場合
+pub fn execute(work_dir: &PathBuf, problem_name: &str) -> Result<(), Box<dyn Error>> {
+    let problem_dir = find_problem_directory(work_dir, problem_name)?;
+    let timeout_margin = get_timeout_margin();
+    let timeout_multiplier = get_timeout_multiplier();
+    let timeout_settings: HashMap<String, u64> = load_problem_timeout_settings(work_dir)?
+        .into_iter()
+        .map(|(name, timeout)| {
+            let timeout = local_time_limit(timeout, timeout_multiplier, timeout_margin);
+            (name, timeout)
+        })
+        .collect();
+    if !timeout_settings.contains_key(problem_name) {
+        return Err(format!("Cargo.toml に {} の実行時間制限がありません", problem_name).into());
+    }
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    watcher.watch(&problem_dir, RecursiveMode::NonRecursive)?;
+
+    run_once(work_dir, &problem_dir, problem_name, &timeout_settings);
+    while let Ok(event) = rx.recv() {
+        if !is_main_rs_event(&event) {
+            continue;
+        }
+        // 連続したイベントをまとめる
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        run_once(work_dir, &problem_dir, problem_name, &timeout_settings);
+    }
+    Ok(())
+}
+
+/// `main.rs` の変更を表すイベントかを返す
+fn is_main_rs_event(event: &notify::Result<Event>) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    (event.kind.is_modify() || event.kind.is_create())
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name().is_some_and(|name| name == "main.rs"))
+}
+
+/// コンパイルとサンプルテストを 1 回実行し、結果を表示する
+fn run_once(
+    work_dir: &PathBuf,
+    problem_dir: &Path,
+    problem_name: &str,
+    timeout_settings: &HashMap<String, u64>,
+) {
+    print!("\x1b[2J\x1b[H");
+    println!(
+        "[{}] Watching {} (Ctrl-C to quit)\n",
+        Local::now().format("%H:%M:%S"),
+        problem_dir.join("main.rs").display()
+    );
+
+    let output = Command::new("cargo")
+        .args(["build", "--quiet", "--bin", problem_name])
+        .current_dir(work_dir)
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            println!("Compilation failed\n");
+            print!("{}", String::from_utf8_lossy(&output.stderr));
+            return;
+        }
+        Err(e) => {
+            println!("Error: cargo を実行できません: {}", e);
+            return;
+        }
+    }
+
+    let test_cases = match collect_test_cases(problem_dir) {
+        Ok(test_cases) => test_cases
+            .into_iter()
+            .filter(|(input, _)| {
+                input
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("sample_"))
+            })
+            .collect(),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    let judge = match load_compare_mode(work_dir, problem_name) {
+        Ok(compare) => compare.unwrap_or_default().judge(),
+        Err(e) => {
+            println!("Error: {}", e);
+            return;
+        }
+    };
+    match run_test_cases(
+        work_dir,
+        test_cases,
+        problem_name,
+        timeout_settings,
+        judge.as_ref(),
+        false,
+    ) {
+        Ok(mut results) => {
+            results.sort_by(|a, b| a.test_case_name.cmp(&b.test_case_name));
+            for line in summarize(&results) {
+                println!("{}", line);
+            }
+        }
+        Err(e) => println!("Error: {}", e),
+    }
+}
+
+/// テスト結果の要約を返す
+///
+/// 1 行目に全体の結果、2 行目以降にテストケースごとの結果を 1 行ずつ返す。
+fn summarize(results: &[TestCaseResult]) -> Vec<String> {
+    let passed = results
+        .iter()
+        .filter(|result| result.status == TestStatus::AC)
+        .count();
+    let overall = if results.is_empty() {
+        "No sample cases".to_string()
+    } else if passed == results.len() {
+        format!("All {} sample(s) passed", passed)
+    } else {
+        format!("{} / {} sample(s) passed", passed, results.len())
+    };
+    let mut lines = vec![overall];
+    lines.extend(results.iter().map(|result| {
+        let mut line = format!(
+            "  {:<4} {:<16} {:>5} ms",
+            result.status, result.test_case_name, result.execution_time
+        );
+        if let Some(error) = &result.error_message {
+            line.push_str(&format!("  {}", error));
+        }
+        line
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use notify::event::{CreateKind, EventKind, ModifyKind};
+
+    fn result(name: &str, status: TestStatus, execution_time: u128) -> TestCaseResult {
+        TestCaseResult {
+            test_case_name: name.to_string(),
+            status,
+            execution_time,
+            error_message: None,
+            stderr: None,
+            timing: None,
+        }
+    }
+
+    #[test]
+    fn test_is_main_rs_event() {
+        let modify = Event::new(EventKind::Modify(ModifyKind::Any))
+            .add_path(PathBuf::from("/contest/a/main.rs"));
+        assert!(is_main_rs_event(&Ok(modify)));
+        let create = Event::new(EventKind::Create(CreateKind::File))
+            .add_path(PathBuf::from("/a/.main.rs.swp"));
+        assert!(!is_main_rs_event(&Ok(create)));
+        let access = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path(PathBuf::from("/contest/a/main.rs"));
+        assert!(!is_main_rs_event(&Ok(access)));
+    }
+
+    #[test]
+    fn test_summarize() {
+        let lines = summarize(&[
+            result("sample_1.in", TestStatus::AC, 3),
+            result("sample_2.in", TestStatus::WA, 12),
+        ]);
+        assert_eq!(lines[0], "1 / 2 sample(s) passed");
+        assert_eq!(lines[2], "  WA   sample_2.in         12 ms");
+        assert_eq!(summarize(&[])[0], "No sample cases");
+        assert_eq!(
+            summarize(&[result("sample_1.in", TestStatus::AC, 3)])[0],
+            "All 1 sample(s) passed"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! ログイン中のアカウントを表示するモジュール
+//!
+//! 保存されているセッション(`REVEL_SESSION`)で AtCoder のページを取得し、
+//! ページに埋め込まれたユーザー名(`userScreenName`)からセッションが有効かを確認する。
+//! 確認結果とあわせて、ログインからの経過時間と有効期限までの残り時間を表示する。
+use reqwest::Client;
+use serde::Serialize;
+use std::error::Error;
+
+use super::config::{get_base_url, get_session_file};
+use super::http::client;
+use super::login::{fetch_with_session, Session, SESSION_EXPIRY};
+use super::output::{is_json, print_json};
+
+/// ログイン中のアカウント表示処理のエントリーポイント
+///
+/// セッションが存在しない場合でもログインは試行せず、状態のみを表示する。
+pub async fn execute() -> Result<(), Box<dyn Error>> {
+    let session = match Session::load(&get_session_file())? {
+        Some(session) => session,
+        None if is_json() => {
+            return print_json(&WhoamiOutput {
+                username: None,
+                valid: false,
+                session_age: None,
+                expires_in: None,
+            });
+        }
+        None => {
+            println!("Not logged in");
+            return Ok(());
+        }
+    };
+
+    let username = fetch_username(&get_base_url(), &client()?, &session).await?;
+    let age = session.age();
+    if is_json() {
+        return print_json(&WhoamiOutput {
+            valid: username.is_some(),
+            username,
+            session_age: Some(age),
+            expires_in: Some(SESSION_EXPIRY.saturating_sub(age)),
+        });
+    }
+    match username {
+        Some(username) => println!("Logged in as {}", username),
+        None => println!(
+            "Session for {} is no longer valid (run `cargo atc login`)",
+            session.username
+        ),
+    }
+    println!("Session age : {}", format_duration(age));
+    if session.is_expired() {
+        println!("Expires in  : expired");
+    } else {
+        println!("Expires in  : {}", format_duration(SESSION_EXPIRY - age));
+    }
+    Ok(())
+}
+
+/// `--json` を指定した場合に出力するログイン状態
+#[derive(Serialize)]
+struct WhoamiOutput {
+    username: Option<String>, // ログイン中のユーザー名 (セッションが無効な場合は `None`)
+    valid: bool,              // セッションが有効かどうか
+    session_age: Option<u64>, // ログインからの経過時間 (秒)
+    expires_in: Option<u64>,  // 有効期限までの残り時間 (秒)
+}
+
+/// セッションで AtCoder のトップページを取得し、ログイン中のユーザー名を返す
+///
+/// # 戻り値
+/// - `Ok(Some(String))`: セッションが有効な場合のユーザー名
+/// - `Ok(None)`: セッションが無効な場合
+/// - `Err(Box<dyn Error>)`: ページの取得に失敗した場合
+pub async fn fetch_username(
+    base_url: &str,
+    client: &Client,
+    session: &Session,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let html = fetch_with_session(client, &format!("{}/home", base_url), session).await?;
+    Ok(parse_username(&html))
+}
+
+/// ページに埋め込まれた `var userScreenName = "...";` からユーザー名を抽出する
+///
+/// 未ログインの場合は空文字列が埋め込まれるため `None` を返す。
+fn parse_username(html: &str) -> Option<String> {
+    let marker = "userScreenName = \"";
+    let start = html.find(marker)? + marker.len();
+    let end = start + html[start..].find('"')?;
+    let username = &html[start..end];
+    if username.is_empty() {
+        None
+    } else {
+        Some(username.to_string())
+    }
+}
+
+/// 秒数を `1h 23m` の形式に変換する
+fn format_duration(secs: u64) -> String {
+    format!("{}h {:02}m", secs / 3600, secs % 3600 / 60)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use mockito::Server;
+
+    #[test]
+    fn test_parse_username() {
+        assert_eq!(
+            parse_username(r#"<script>var userScreenName = "mock_user";</script>"#),
+            Some("mock_user".to_string())
+        );
+        assert_eq!(
+            parse_username(r#"<script>var userScreenName = "";</script>"#),
+            None
+        );
+        assert_eq!(parse_username("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0h 00m");
+        assert_eq!(format_duration(5400), "1h 30m");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_username() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/home")
+            .match_header("Cookie", "REVEL_SESSION=mock_session_cookie;")
+            .with_status(200)
+            .with_body(r#"<script>var userScreenName = "mock_user";</script>"#)
+            .create_async()
+            .await;
+        let session = Session {
+            username: "mock_user".to_string(),
+            csrf_token: "mock_csrf_token".to_string(),
+            session_cookie: "REVEL_SESSION=mock_session_cookie".to_string(),
+            last_login_time: 0,
+        };
+
+        let username = fetch_username(&server.url(), &Client::new(), &session)
+            .await
+            .unwrap();
+        assert_eq!(username, Some("mock_user".to_string()));
+    }
+}
@@ -0,0 +1,187 @@
+//! コンテストを Cargo ワークスペースのメンバーとして管理するモジュール
+//!
+//! `download --workspace` を指定した場合、作業ディレクトリに `[workspace]` の `Cargo.toml` を作成し(既にあれば追記し)、
+//! コンテストディレクトリをメンバーに追加する。`target/` と `Cargo.lock` をコンテスト間で共有するため、
+//! 依存クレートのビルドはワークスペース全体で 1 回で済む。
+//!
+//! 作業ディレクトリが既にワークスペースの場合は、`--workspace` を指定しなくてもメンバーに追加する。
+//!
+//! ```text
+//! .
+//! ├── Cargo.toml      # [workspace] members = ["abc388", "abc389"]
+//! ├── Cargo.lock
+//! ├── target          # 全コンテストで共有するビルドディレクトリ
+//! ├── abc388
+//! │   └── Cargo.toml
+//! └── abc389
+//!     └── Cargo.toml
+//! ```
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+use toml::Value;
+
+use super::cargo_config::configured_target_dir;
+
+/// `dir` の `Cargo.toml` が `[workspace]` を持つかどうかを返す
+pub fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<Value>(&content).ok())
+        .is_some_and(|parsed| parsed.get("workspace").is_some())
+}
+
+/// コンテストディレクトリのビルド成果物を格納するディレクトリを返す
+///
+/// 環境変数 `CARGO_TARGET_DIR` が設定されている場合はそのディレクトリ(相対パスの場合はコンテストディレクトリからの相対パス)、
+/// コンテストディレクトリまたは親ディレクトリの `.cargo/config.toml` に `build.target-dir` が設定されている場合はそのディレクトリ、
+/// 親ディレクトリがワークスペースの場合は、ワークスペースの `target/` を返す。
+pub fn get_target_dir(contest_dir: &Path) -> PathBuf {
+    let env_target_dir = std::env::var_os("CARGO_TARGET_DIR").filter(|dir| !dir.is_empty());
+    find_target_dir(contest_dir, env_target_dir.as_deref().map(Path::new))
+}
+
+/// `CARGO_TARGET_DIR` の値を `env_target_dir` として、コンテストディレクトリのビルド成果物を格納するディレクトリを返す
+fn find_target_dir(contest_dir: &Path, env_target_dir: Option<&Path>) -> PathBuf {
+    if let Some(target_dir) = env_target_dir {
+        return contest_dir.join(target_dir);
+    }
+    if let Some(target_dir) = std::iter::once(contest_dir)
+        .chain(contest_dir.parent())
+        .find_map(configured_target_dir)
+    {
+        return target_dir;
+    }
+    match contest_dir.parent() {
+        Some(parent) if is_workspace_root(parent) => parent.join("target"),
+        _ => contest_dir.join("target"),
+    }
+}
+
+/// ワークスペースの `Cargo.toml` にメンバーを追加する
+///
+/// `Cargo.toml` が存在しない場合は `[workspace]` のみの `Cargo.toml` を作成する。
+/// 既存の `Cargo.toml` は TOML として読み込んで書き直すため、コメントは保持されない。
+///
+/// # 引数
+/// - `work_dir`: ワークスペースのルートディレクトリ
+/// - `member`: 追加するメンバー (コンテストディレクトリ名)
+///
+/// # 戻り値
+/// - `Ok(true)`: メンバーを追加した場合
+/// - `Ok(false)`: 既にメンバーに含まれている場合
+///
+/// # エラーの可能性
+/// - `Cargo.toml` が `[workspace]` を持たないパッケージの場合
+/// - `Cargo.toml` の読み込み・解析・書き込みに失敗した場合
+pub fn add_workspace_member(work_dir: &Path, member: &str) -> Result<bool, Box<dyn Error>> {
+    let cargo_toml_path = work_dir.join("Cargo.toml");
+    if !cargo_toml_path.exists() {
+        fs::write(
+            &cargo_toml_path,
+            format!(
+                "[workspace]\nresolver = \"2\"\nmembers = [\n    \"{}\",\n]\n",
+                member
+            ),
+        )?;
+        return Ok(true);
+    }
+
+    let mut parsed: Value = toml::from_str(&fs::read_to_string(&cargo_toml_path)?)?;
+    let workspace = parsed
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.as_table_mut())
+        .ok_or_else(|| {
+            format!(
+                "{} はワークスペースではありません ([workspace] がありません)",
+                cargo_toml_path.display()
+            )
+        })?;
+    let members = workspace
+        .entry("members")
+        .or_insert(Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or("[workspace] の members が配列ではありません")?;
+    if members
+        .iter()
+        .any(|existing| existing.as_str() == Some(member))
+    {
+        return Ok(false);
+    }
+    members.push(Value::String(member.to_string()));
+    fs::write(&cargo_toml_path, toml::to_string(&parsed)?)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile;
+
+    #[test]
+    fn test_add_workspace_member() {
+        let work_dir = tempfile::tempdir().expect("");
+        let contest_dir = work_dir.path().join("abc388");
+        assert!(!is_workspace_root(work_dir.path()));
+        assert_eq!(
+            find_target_dir(&contest_dir, None),
+            contest_dir.join("target")
+        );
+
+        assert!(add_workspace_member(work_dir.path(), "abc388").unwrap());
+        assert!(add_workspace_member(work_dir.path(), "abc389").unwrap());
+        assert!(!add_workspace_member(work_dir.path(), "abc388").unwrap());
+
+        let parsed: Value =
+            toml::from_str(&fs::read_to_string(work_dir.path().join("Cargo.toml")).unwrap())
+                .unwrap();
+        let members: Vec<&str> = parsed["workspace"]["members"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|member| member.as_str())
+            .collect();
+        assert_eq!(members, vec!["abc388", "abc389"]);
+        assert_eq!(parsed["workspace"]["resolver"].as_str(), Some("2"));
+        assert!(is_workspace_root(work_dir.path()));
+        assert_eq!(
+            find_target_dir(&contest_dir, None),
+            work_dir.path().join("target")
+        );
+
+        // `.cargo/config.toml` の `build.target-dir` を優先する
+        fs::create_dir_all(work_dir.path().join(".cargo")).unwrap();
+        fs::write(
+            work_dir.path().join(".cargo/config.toml"),
+            "[build]\ntarget-dir = \"/tmp/atc-target\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            find_target_dir(&contest_dir, None),
+            PathBuf::from("/tmp/atc-target")
+        );
+
+        // 環境変数 `CARGO_TARGET_DIR` を最優先し、相対パスはコンテストディレクトリを基準とする
+        assert_eq!(
+            find_target_dir(&contest_dir, Some(Path::new("/tmp/env-target"))),
+            PathBuf::from("/tmp/env-target")
+        );
+        assert_eq!(
+            find_target_dir(&contest_dir, Some(Path::new("build"))),
+            contest_dir.join("build")
+        );
+    }
+
+    #[test]
+    fn test_add_workspace_member_to_package() {
+        let work_dir = tempfile::tempdir().expect("");
+        fs::write(
+            work_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"abc388\"\n",
+        )
+        .unwrap();
+        assert!(add_workspace_member(work_dir.path(), "abc388").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 mod commands;
-use std::env;
+use std::{env, path::PathBuf};
 
 use clap::{Parser, Subcommand};
 use tokio;
@@ -7,16 +7,301 @@ use tokio;
 #[derive(Parser)]
 #[command(name = "cargo-atc")]
 struct Cli {
+    #[arg(long, global = true)]
+    json: bool,
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    Login,
-    Test { problem_name: String },
-    Download { contest_name: String },
-    Submit { problem_name: String },
+    Login {
+        #[arg(long)]
+        check: bool,
+    },
+    Logout {
+        #[arg(long)]
+        all_profiles: bool,
+    },
+    Whoami,
+    Doctor,
+    Init {
+        #[arg(long)]
+        force: bool,
+    },
+    Import {
+        #[arg(required_unless_present = "oj")]
+        source_dir: Option<PathBuf>,
+        #[arg(long, conflicts_with = "source_dir")]
+        oj: Option<String>,
+    },
+    Export {
+        problem_name: String,
+    },
+    Test {
+        #[arg(required_unless_present = "all")]
+        problem_name: Option<String>,
+        #[arg(long, conflicts_with_all = ["problem_name", "case"])]
+        all: bool,
+        #[arg(long)]
+        remote: bool,
+        #[arg(long)]
+        system: bool,
+        #[arg(long)]
+        release: bool,
+        #[arg(long)]
+        case: Option<String>,
+        #[arg(long, conflicts_with = "silent")]
+        quiet: bool,
+        #[arg(long)]
+        silent: bool,
+        #[arg(long, conflicts_with = "keep_going")]
+        fail_fast: bool,
+        #[arg(long)]
+        keep_going: bool,
+        #[arg(long)]
+        timeout: Option<u64>,
+        #[arg(long, value_parser = commands::compare::COMPARE_MODES)]
+        compare: Option<String>,
+        #[arg(long, conflicts_with = "compare")]
+        exact: bool,
+        #[arg(long)]
+        force_build: bool,
+        #[arg(long, conflicts_with_all = ["all", "remote"])]
+        history: bool,
+        #[arg(long, default_value_t = 1)]
+        repeat: usize,
+        #[arg(long)]
+        full_output: bool,
+    },
+    Case {
+        #[command(subcommand)]
+        action: CaseAction,
+    },
+    Testcases {
+        problem_name: String,
+        #[arg(long)]
+        force: bool,
+    },
+    Run {
+        problem_name: String,
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    Bench {
+        problem_name: String,
+        #[arg(long)]
+        case: Option<String>,
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+    },
+    Diff {
+        problem_name: String,
+    },
+    Watch {
+        problem_name: String,
+    },
+    Expand {
+        problem_name: String,
+    },
+    Gen {
+        problem_name: String,
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    Stress {
+        problem_name: String,
+        #[arg(long, default_value_t = 1000)]
+        count: usize,
+        #[arg(long)]
+        seed: Option<u64>,
+        #[arg(long)]
+        remote: bool,
+    },
+    Download {
+        contest_name: String,
+        #[arg(long)]
+        problem: Option<String>,
+        #[arg(long)]
+        template: Option<String>,
+        #[arg(long)]
+        no_cache: bool,
+        #[arg(long)]
+        wait: bool,
+        #[arg(long)]
+        workspace: bool,
+        #[arg(long)]
+        no_git: bool,
+        #[arg(long)]
+        force: bool,
+        #[arg(long)]
+        output: Option<PathBuf>,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    Update {
+        contest_name: String,
+        #[arg(long)]
+        no_cache: bool,
+    },
+    Submit {
+        problem_name: String,
+    },
+    Add {
+        problem_name: String,
+    },
+    Archive {
+        contest_name: String,
+        #[arg(long)]
+        strip: bool,
+    },
+    Clean {
+        #[arg(long, default_value_t = 30)]
+        days: u64,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    New {
+        project_name: String,
+        #[arg(long, default_value = "a")]
+        problem: String,
+        #[arg(long, default_value_t = commands::new::DEFAULT_TIMEOUT)]
+        timeout: u128,
+    },
+    Open {
+        problem_name: String,
+        #[arg(long)]
+        submissions: bool,
+    },
+    Editorial {
+        problem_name: Option<String>,
+    },
+    Status {
+        #[arg(long)]
+        watch: bool,
+    },
+    Result {
+        submission_id: String,
+        #[arg(long)]
+        watch: bool,
+    },
+    Clar {
+        #[arg(long)]
+        all: bool,
+        #[arg(long)]
+        watch: Option<u64>,
+    },
+    Submissions {
+        #[arg(long, default_value_t = 1)]
+        page: u32,
+    },
+    MigrateEnv {
+        #[arg(long, default_value = "practice")]
+        contest: String,
+        #[arg(long)]
+        dependencies: Option<PathBuf>,
+    },
+    Lang {
+        #[arg(long, default_value = "practice")]
+        contest: String,
+        #[arg(long)]
+        filter: Option<String>,
+    },
+    Standings {
+        contest_name: String,
+        #[arg(long)]
+        watch: Option<u64>,
+    },
+    Contests {
+        #[arg(long)]
+        next: bool,
+    },
+    Perf {
+        contest_name: String,
+    },
+    Stats,
+    Rating {
+        username: Option<String>,
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+    },
+    Score {
+        #[command(subcommand)]
+        action: ScoreAction,
+    },
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    #[command(alias = "virtual")]
+    Timer {
+        #[command(subcommand)]
+        action: TimerAction,
+    },
+    #[command(external_subcommand)]
+    External(Vec<String>),
+}
+
+#[derive(Subcommand)]
+enum ScoreAction {
+    Record {
+        problem_name: String,
+        total: i64,
+        #[arg(long, default_value_t = 1)]
+        cases: usize,
+        #[arg(long)]
+        submission: bool,
+    },
+    Show {
+        problem_name: String,
+        #[arg(long)]
+        svg: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CaseAction {
+    Add {
+        problem_name: String,
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    Get { key: String },
+    Set { key: String, value: String },
+    List,
+}
+
+#[derive(Subcommand)]
+enum TemplateAction {
+    List,
+    Save {
+        name: String,
+        #[arg(long, default_value = "a")]
+        problem: String,
+    },
+    Use {
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TimerAction {
+    Start {
+        #[arg(long, default_value_t = 100)]
+        minutes: u64,
+    },
+    Status,
+    Stop,
 }
 
 #[tokio::main]
@@ -24,20 +309,286 @@ async fn main() {
     let work_dir = env::current_dir().expect("Failed to get current directory");
 
     let cli = Cli::parse();
+    commands::output::set_json(cli.json);
     match cli.command {
-        Commands::Login => {
+        Commands::Login { check: true } => {
+            if let Err(e) = commands::login::execute_check().await {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Login { check: false } => {
             if let Err(e) = commands::login::execute().await {
                 eprintln!("Error: {}", e);
             }
         }
-        Commands::Test { problem_name } => {
-            if let Err(e) = commands::test::execute(&work_dir, &problem_name) {
+        Commands::Logout { all_profiles } => {
+            if let Err(e) = commands::logout::execute(all_profiles) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Doctor => {
+            if let Err(e) = commands::doctor::execute(&work_dir).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Init { force } => {
+            if let Err(e) = commands::init::execute(&work_dir, force) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Import { source_dir, oj } => {
+            let result = match (source_dir, oj) {
+                (_, Some(problem_name)) => commands::import::execute_oj(&work_dir, &problem_name),
+                (Some(source_dir), None) => commands::import::execute(&work_dir, &source_dir),
+                (None, None) => Err("取り込み元を指定してください".into()),
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Export { problem_name } => {
+            if let Err(e) = commands::export::execute(&work_dir, &problem_name) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Whoami => {
+            if let Err(e) = commands::whoami::execute().await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Test {
+            problem_name,
+            all: _,
+            remote,
+            system,
+            release,
+            case,
+            quiet,
+            silent,
+            fail_fast,
+            keep_going,
+            timeout,
+            compare,
+            exact,
+            force_build,
+            history,
+            repeat,
+            full_output,
+        } => {
+            commands::test::set_release(release);
+            commands::test::set_full_output(full_output);
+            commands::build_cache::set_force_build(force_build);
+            let result = if history {
+                commands::history::execute(&work_dir, problem_name.as_deref().unwrap_or_default())
+            } else if remote {
+                let mut args = vec!["test"];
+                match &problem_name {
+                    Some(problem_name) => args.push(problem_name.as_str()),
+                    None => args.push("--all"),
+                }
+                if system {
+                    args.push("--system");
+                }
+                if release {
+                    args.push("--release");
+                }
+                if let Some(case) = &case {
+                    args.extend(["--case", case.as_str()]);
+                }
+                if quiet {
+                    args.push("--quiet");
+                }
+                if silent {
+                    args.push("--silent");
+                }
+                if fail_fast {
+                    args.push("--fail-fast");
+                }
+                if keep_going {
+                    args.push("--keep-going");
+                }
+                let timeout_arg = timeout.map(|timeout| timeout.to_string());
+                if let Some(timeout) = &timeout_arg {
+                    args.extend(["--timeout", timeout.as_str()]);
+                }
+                if let Some(compare) = &compare {
+                    args.extend(["--compare", compare.as_str()]);
+                }
+                if exact {
+                    args.push("--exact");
+                }
+                if force_build {
+                    args.push("--force-build");
+                }
+                let repeat_arg = repeat.to_string();
+                if repeat > 1 {
+                    args.extend(["--repeat", repeat_arg.as_str()]);
+                }
+                if full_output {
+                    args.push("--full-output");
+                }
+                commands::remote::execute(&work_dir, &args)
+            } else {
+                let verbosity = if silent {
+                    commands::test::Verbosity::Silent
+                } else if quiet {
+                    commands::test::Verbosity::Quiet
+                } else {
+                    commands::test::Verbosity::Normal
+                };
+                let options = commands::test::TestOptions {
+                    system,
+                    case: case.as_deref(),
+                    verbosity,
+                    fail_fast: commands::test::fail_fast_enabled(
+                        fail_fast,
+                        keep_going,
+                        commands::config::get_fail_fast(),
+                    ),
+                    timeout,
+                    repeat,
+                    compare: if exact {
+                        Some(commands::compare::CompareMode::Exact)
+                    } else {
+                        compare.and_then(|compare| compare.parse().ok())
+                    },
+                };
+                match &problem_name {
+                    Some(problem_name) => {
+                        commands::test::execute(&work_dir, problem_name, &options)
+                    }
+                    None => commands::test::execute_all(&work_dir, &options),
+                }
+            };
+            if let Err(e) = result {
+                // リモートのエラーメッセージはリモートの `cargo atc` が出力するため、終了コードのみを引き継ぐ
+                if e.downcast_ref::<commands::remote::RemoteExit>().is_none() {
+                    eprintln!("Error: {}", e);
+                }
+                std::process::exit(commands::test::exit_code(e.as_ref()));
+            }
+        }
+        Commands::Case { action } => {
+            let result = match action {
+                CaseAction::Add { problem_name, name } => {
+                    commands::case::execute_add(&work_dir, &problem_name, name.as_deref())
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Testcases {
+            problem_name,
+            force,
+        } => {
+            if let Err(e) = commands::testcases::execute(&work_dir, &problem_name, force).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Run {
+            problem_name,
+            input,
+        } => {
+            if let Err(e) = commands::run::execute(&work_dir, &problem_name, input.as_deref()) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Bench {
+            problem_name,
+            case,
+            runs,
+        } => {
+            if let Err(e) =
+                commands::bench::execute(&work_dir, &problem_name, case.as_deref(), runs)
+            {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Diff { problem_name } => {
+            if let Err(e) = commands::diff::execute(&work_dir, &problem_name) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Watch { problem_name } => {
+            if let Err(e) = commands::watch::execute(&work_dir, &problem_name) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Expand { problem_name } => {
+            if let Err(e) = commands::expand::execute(&work_dir, &problem_name) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Gen {
+            problem_name,
+            count,
+            seed,
+        } => {
+            if let Err(e) = commands::gen::execute(&work_dir, &problem_name, count, seed) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Stress {
+            problem_name,
+            count,
+            seed,
+            remote,
+        } => {
+            let result = if remote {
+                let count_arg = count.to_string();
+                let mut args = vec![
+                    "stress",
+                    problem_name.as_str(),
+                    "--count",
+                    count_arg.as_str(),
+                ];
+                let seed_arg = seed.map(|seed| seed.to_string());
+                if let Some(seed) = &seed_arg {
+                    args.extend(["--seed", seed.as_str()]);
+                }
+                commands::remote::execute(&work_dir, &args)
+            } else {
+                commands::stress::execute(&work_dir, &problem_name, count, seed)
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Download {
+            contest_name,
+            problem,
+            template,
+            no_cache,
+            wait,
+            workspace,
+            no_git,
+            force,
+            output,
+            dry_run,
+        } => {
+            commands::cache::set_enabled(!no_cache);
+            let options = commands::download::DownloadOptions {
+                problem,
+                template,
+                wait,
+                workspace,
+                git: !no_git && commands::config::get_git_init(),
+                force,
+                output,
+                dry_run,
+            };
+            if let Err(e) = commands::download::execute(&work_dir, &contest_name, &options).await {
                 eprintln!("Error: {}", e);
             }
         }
-        Commands::Download { contest_name } => {
-            println!("DEBUG0");
-            if let Err(e) = commands::download::execute(&work_dir, &contest_name).await {
+        Commands::Update {
+            contest_name,
+            no_cache,
+        } => {
+            commands::cache::set_enabled(!no_cache);
+            if let Err(e) = commands::update::execute(&work_dir, &contest_name).await {
                 eprintln!("Error: {}", e);
             }
         }
@@ -46,5 +597,179 @@ async fn main() {
                 eprintln!("Error: {}", e);
             }
         }
+        Commands::Add { problem_name } => {
+            if let Err(e) = commands::add::execute(&work_dir, &problem_name).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Archive {
+            contest_name,
+            strip,
+        } => {
+            if let Err(e) = commands::archive::execute(&work_dir, &contest_name, strip).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Clean { days, dry_run } => {
+            if let Err(e) = commands::clean::execute(&work_dir, days, dry_run) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::New {
+            project_name,
+            problem,
+            timeout,
+        } => {
+            if let Err(e) = commands::new::execute(&work_dir, &project_name, &problem, timeout) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Open {
+            problem_name,
+            submissions,
+        } => {
+            if let Err(e) = commands::open::execute(&work_dir, &problem_name, submissions) {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Editorial { problem_name } => {
+            if let Err(e) = commands::editorial::execute(&work_dir, problem_name.as_deref()).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Status { watch } => {
+            if let Err(e) = commands::status::execute(&work_dir, watch).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Result {
+            submission_id,
+            watch,
+        } => {
+            if let Err(e) = commands::result::execute(&work_dir, &submission_id, watch).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Clar { all, watch } => {
+            if let Err(e) = commands::clar::execute(&work_dir, all, watch).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Submissions { page } => {
+            if let Err(e) = commands::submissions::execute(&work_dir, page).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Lang { contest, filter } => {
+            if let Err(e) = commands::lang::execute(&contest, filter.as_deref()).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::MigrateEnv {
+            contest,
+            dependencies,
+        } => {
+            if let Err(e) =
+                commands::migrate::execute(&work_dir, &contest, dependencies.as_deref()).await
+            {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Standings {
+            contest_name,
+            watch,
+        } => {
+            if let Err(e) = commands::standings::execute(&contest_name, watch).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Contests { next } => {
+            if let Err(e) = commands::contests::execute(next).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Perf { contest_name } => {
+            if let Err(e) = commands::performance::execute(&contest_name).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Stats => {
+            if let Err(e) = commands::stats::execute() {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Rating { username, count } => {
+            if let Err(e) = commands::rating::execute(username.as_deref(), count).await {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Score { action } => {
+            let result = match action {
+                ScoreAction::Record {
+                    problem_name,
+                    total,
+                    cases,
+                    submission,
+                } => commands::score::execute_record(
+                    &work_dir,
+                    &problem_name,
+                    total,
+                    cases,
+                    submission,
+                ),
+                ScoreAction::Show { problem_name, svg } => {
+                    commands::score::execute_show(&work_dir, &problem_name, svg.as_deref())
+                }
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Timer { action } => {
+            let result = match action {
+                TimerAction::Start { minutes } => {
+                    commands::timer::execute_start(&work_dir, minutes)
+                }
+                TimerAction::Status => commands::timer::execute_status(&work_dir),
+                TimerAction::Stop => commands::timer::execute_stop(&work_dir),
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Config { action } => {
+            let result = match action {
+                ConfigAction::Get { key } => commands::config::execute_get(&key),
+                ConfigAction::Set { key, value } => commands::config::execute_set(&key, &value),
+                ConfigAction::List => commands::config::execute_list(),
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::Template { action } => {
+            let result = match action {
+                TemplateAction::List => commands::template::execute_list(&work_dir),
+                TemplateAction::Save { name, problem } => {
+                    commands::template::execute_save(&work_dir, &name, &problem)
+                }
+                TemplateAction::Use { name } => commands::template::execute_use(&work_dir, &name),
+            };
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+            }
+        }
+        Commands::External(args) => {
+            if let Err(e) = commands::plugin::execute(&work_dir, &args) {
+                // プラグインのエラーメッセージはプラグイン自身が出力するため、終了コードのみを引き継ぐ
+                match e.downcast_ref::<commands::plugin::PluginExit>() {
+                    Some(exit) => std::process::exit(exit.exit_code()),
+                    None => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
     }
 }